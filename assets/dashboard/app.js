@@ -101,29 +101,68 @@ function updateCategories(categories) {
     `).join('');
 }
 
-// Update recent requests table
-function updateRecent(requests) {
+// Update recent requests table (calls arrive newest first)
+function updateRecent(calls) {
     const tbody = document.getElementById('recent-body');
 
-    if (requests.length === 0) {
-        tbody.innerHTML = '<tr class="empty-row"><td colspan="5">No recent requests</td></tr>';
+    if (calls.length === 0) {
+        tbody.innerHTML = '<tr class="empty-row"><td colspan="7">No recent requests</td></tr>';
         return;
     }
 
-    // Show most recent first
-    const reversed = [...requests].reverse();
-
-    tbody.innerHTML = reversed.slice(0, 20).map(r => `
+    tbody.innerHTML = calls.map(r => `
         <tr>
             <td>${formatTime(r.timestamp)}</td>
             <td><code>${escapeHtml(r.tool)}</code></td>
             <td>${r.project ? `<code>${escapeHtml(r.project)}</code>` : '-'}</td>
+            <td>${r.access_decision ? escapeHtml(r.access_decision) : '-'}</td>
             <td><span class="status-badge ${r.success ? 'status-success' : 'status-error'}">${r.success ? 'OK' : 'Error'}</span></td>
             <td>${r.duration_ms}ms</td>
+            <td>${r.error_details ? escapeHtml(r.error_details) : '-'}</td>
         </tr>
     `).join('');
 }
 
+// Fetch recent tool calls
+async function fetchRecent() {
+    try {
+        const response = await fetch('/api/recent?limit=20');
+        const recent = await response.json();
+        updateRecent(recent.calls);
+    } catch (error) {
+        console.error('Failed to fetch recent calls:', error);
+    }
+}
+
+// Render the allowed/denied tool summary for a project
+function updateProjectAccess(summary) {
+    const result = document.getElementById('project-access-result');
+
+    result.innerHTML = `
+        <p><code>${escapeHtml(summary.project)}</code>: ${summary.allowed_count} allowed, ${summary.denied_count} denied</p>
+        ${summary.categories.map(c => `
+            <details class="access-category">
+                <summary>${escapeHtml(c.category)} (${c.allowed.length} allowed, ${c.denied.length} denied)</summary>
+                <ul>
+                    ${c.allowed.map(t => `<li><span class="status-badge status-success">allow</span> <code>${escapeHtml(t)}</code></li>`).join('')}
+                    ${c.denied.map(d => `<li><span class="status-badge status-error">deny</span> <code>${escapeHtml(d.tool)}</code> ${escapeHtml(d.reason)}</li>`).join('')}
+                </ul>
+            </details>
+        `).join('')}
+    `;
+}
+
+// Fetch the access summary for a project
+async function fetchProjectAccess(project) {
+    try {
+        const response = await fetch(`/api/projects/${encodeURIComponent(project)}/access`);
+        const summary = await response.json();
+        updateProjectAccess(summary);
+    } catch (error) {
+        console.error('Failed to fetch project access:', error);
+    }
+}
+
 // Escape HTML to prevent XSS
 function escapeHtml(text) {
     const div = document.createElement('div');
@@ -131,6 +170,19 @@ function escapeHtml(text) {
     return div.innerHTML;
 }
 
+// Show or hide the GitLab degraded banner
+function updateGitLabHealth(gitlab) {
+    const banner = document.getElementById('gitlab-banner');
+    if (!gitlab || !gitlab.degraded) {
+        banner.style.display = 'none';
+        return;
+    }
+
+    document.getElementById('gitlab-failures').textContent = gitlab.consecutive_failures;
+    document.getElementById('gitlab-error').textContent = gitlab.last_error || '';
+    banner.style.display = 'block';
+}
+
 // Fetch and update metrics
 async function fetchMetrics() {
     try {
@@ -141,7 +193,8 @@ async function fetchMetrics() {
         updateProjects(data.projects);
         updateTools(data.tools);
         updateCategories(data.categories);
-        updateRecent(data.recent_requests);
+        updateGitLabHealth(data.gitlab);
+        fetchRecent();
 
         document.getElementById('status-text').textContent = 'Connected';
         document.querySelector('.status-dot').style.backgroundColor = 'var(--accent-green)';
@@ -187,6 +240,12 @@ document.addEventListener('DOMContentLoaded', () => {
     fetchMetrics();
     checkForUpdates();
 
+    document.getElementById('project-access-form').addEventListener('submit', (event) => {
+        event.preventDefault();
+        const project = document.getElementById('project-access-input').value.trim();
+        if (project) fetchProjectAccess(project);
+    });
+
     // Refresh metrics every 2 seconds
     setInterval(fetchMetrics, 2000);
 
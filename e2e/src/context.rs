@@ -4,6 +4,21 @@
 //! - MCP client (via shared servers for stdio or HTTP transport)
 //! - GitLab container access
 //! - Test project management
+//!
+//! # Test projects
+//!
+//! Every project a context creates is owned by a [`ProjectGuard`] that
+//! deletes it when the context is dropped, so a failing assertion or an early
+//! return does not leak it. [`TestContext::cleanup`] deletes the project
+//! asynchronously instead and disarms the guard; the guard only costs a
+//! blocking delete on a helper thread when `cleanup` was not reached.
+//!
+//! [`TestContextBuilder::with_isolated_project`] creates the project in a
+//! group of its own, so nothing else shares its namespace and deleting the
+//! group removes everything the test left behind. Use it for tests that
+//! mutate repository or namespace state (branches, files, protections).
+//!
+//! [`TestContext::assert_clean`] confirms afterwards that a project is gone.
 
 use anyhow::{Context, Result};
 use uuid::Uuid;
@@ -29,6 +44,10 @@ pub struct TestContext {
     pub project_id: Option<u64>,
     /// The test project path (if created).
     pub project_path: Option<String>,
+    /// The group holding an isolated test project (if created).
+    pub group_id: Option<u64>,
+    /// Deletes the test project on drop (if created).
+    project_guard: Option<ProjectGuard>,
 }
 
 impl TestContext {
@@ -56,6 +75,8 @@ impl TestContext {
             token: servers.token().to_string(),
             project_id: None,
             project_path: None,
+            group_id: None,
+            project_guard: None,
         }))
     }
 
@@ -67,17 +88,51 @@ impl TestContext {
     /// Create a test project with a unique name.
     pub async fn create_test_project(&mut self) -> Result<()> {
         let project_name = format!("e2e-test-{}", &Uuid::new_v4().to_string()[..8]);
+        self.create_project_named(&project_name).await
+    }
 
+    /// Create a test project in a new group owned by this context alone.
+    ///
+    /// The group is deleted along with the project when the context is
+    /// dropped, even if the test panics before calling
+    /// [`TestContext::cleanup`].
+    pub async fn create_isolated_project(&mut self) -> Result<()> {
+        let suffix = Uuid::new_v4().simple().to_string();
+        let group = self
+            .gitlab
+            .create_group(&self.token, &format!("e2e-group-{}", suffix))
+            .await
+            .context("Failed to create test group")?;
+        let group_id = group["id"]
+            .as_u64()
+            .context("Create group response has no id")?;
+        self.group_id = Some(group_id);
+
+        self.create_project_named(&format!("e2e-isolated-{}", suffix))
+            .await
+    }
+
+    async fn create_project_named(&mut self, project_name: &str) -> Result<()> {
         let project = self
             .gitlab
-            .create_project(&self.token, &project_name)
+            .create_project_in(&self.token, project_name, self.group_id)
             .await
             .context("Failed to create test project")?;
 
-        self.project_id = project["id"].as_u64();
+        let project_id = project["id"]
+            .as_u64()
+            .context("Create project response has no id")?;
+        self.project_id = Some(project_id);
         self.project_path = project["path_with_namespace"]
             .as_str()
             .map(|s| s.to_string());
+        self.project_guard = Some(ProjectGuard {
+            gitlab: self.gitlab,
+            token: self.token.clone(),
+            project_id,
+            group_id: self.group_id,
+            armed: true,
+        });
 
         tracing::info!(
             "Created test project: {} (ID: {:?})",
@@ -107,12 +162,33 @@ impl TestContext {
             .context("project_id not set after create_test_project")
     }
 
+    /// Check that a project created by a test no longer exists.
+    ///
+    /// Capture the ID (and token) before the context is cleaned up or
+    /// dropped, then call this to catch leaked projects.
+    pub async fn assert_clean(
+        gitlab: &GitLabContainer,
+        token: &str,
+        project_id: u64,
+    ) -> Result<()> {
+        if gitlab.project_exists(token, project_id).await? {
+            anyhow::bail!("Test project {} still exists after cleanup", project_id);
+        }
+        Ok(())
+    }
+
     /// Cleanup test resources.
-    pub async fn cleanup(self) -> Result<()> {
-        // Delete test project if created
+    pub async fn cleanup(mut self) -> Result<()> {
+        // Delete test project if created; the guard has nothing left to do
         if let Some(project_id) = self.project_id {
             let _ = self.gitlab.delete_project(&self.token, project_id).await;
         }
+        if let Some(group_id) = self.group_id {
+            let _ = self.gitlab.delete_group(&self.token, group_id).await;
+        }
+        if let Some(guard) = self.project_guard.take() {
+            guard.disarm();
+        }
 
         // Shutdown MCP client (no-op for shared client - server stays running)
         self.client.shutdown().await?;
@@ -121,10 +197,64 @@ impl TestContext {
     }
 }
 
+/// Deletes a test project, and the group of an isolated one, when dropped.
+///
+/// `Drop` cannot await, and during a panic the test's runtime may already be
+/// shutting down, so the delete runs on a helper thread with its own runtime
+/// and a [detached](GitLabContainer::detached) HTTP client.
+pub struct ProjectGuard {
+    gitlab: &'static GitLabContainer,
+    token: String,
+    project_id: u64,
+    group_id: Option<u64>,
+    armed: bool,
+}
+
+impl ProjectGuard {
+    /// Drop the guard without deleting the project.
+    pub fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for ProjectGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        let gitlab = self.gitlab.detached();
+        let token = std::mem::take(&mut self.token);
+        let project_id = self.project_id;
+        let group_id = self.group_id;
+
+        let result = std::thread::spawn(move || -> Result<()> {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(async {
+                gitlab.delete_project(&token, project_id).await?;
+                match group_id {
+                    Some(group_id) => gitlab.delete_group(&token, group_id).await,
+                    None => Ok(()),
+                }
+            })
+        })
+        .join();
+
+        match result {
+            Ok(Ok(())) => tracing::info!("Deleted test project {} on drop", project_id),
+            Ok(Err(e)) => tracing::warn!("Failed to delete test project {}: {}", project_id, e),
+            Err(_) => tracing::warn!("Cleanup thread for test project {} panicked", project_id),
+        }
+    }
+}
+
 /// Builder for TestContext with custom options.
 pub struct TestContextBuilder {
     transport: TransportKind,
     create_project: bool,
+    isolated_project: bool,
 }
 
 impl TestContextBuilder {
@@ -133,15 +263,26 @@ impl TestContextBuilder {
         Self {
             transport,
             create_project: false,
+            isolated_project: false,
         }
     }
 
     /// Create a test project automatically.
+    ///
+    /// Prefer [`Self::with_isolated_project`] for tests that mutate it.
     pub fn with_project(mut self) -> Self {
         self.create_project = true;
         self
     }
 
+    /// Create a project in a group of its own, owned by this test alone.
+    ///
+    /// Takes precedence over [`Self::with_project`].
+    pub fn with_isolated_project(mut self) -> Self {
+        self.isolated_project = true;
+        self
+    }
+
     /// Build the test context.
     ///
     /// Returns `None` if the requested transport is not available.
@@ -151,7 +292,9 @@ impl TestContextBuilder {
             None => return Ok(None),
         };
 
-        if self.create_project {
+        if self.isolated_project {
+            ctx.create_isolated_project().await?;
+        } else if self.create_project {
             ctx.create_test_project().await?;
         }
 
@@ -5,7 +5,13 @@
 //! - Waiting for GitLab to be ready
 //! - Creating Personal Access Tokens
 //! - Managing test projects
+//!
+//! Projects are only deleted if this process created (or explicitly tracked)
+//! them, so the framework can run against a long-lived GitLab instance
+//! without touching pre-existing data.
 
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
@@ -19,6 +25,12 @@ pub const DEFAULT_ROOT_PASSWORD: &str = "testpassword123!";
 /// Default GitLab HTTP port.
 pub const DEFAULT_GITLAB_PORT: u16 = 8080;
 
+/// Default GitLab Docker image.
+pub const DEFAULT_GITLAB_IMAGE: &str = "gitlab/gitlab-ce";
+
+/// Default GitLab Docker image tag.
+pub const DEFAULT_GITLAB_TAG: &str = "latest";
+
 /// GitLab container configuration.
 #[derive(Debug, Clone)]
 pub struct GitLabConfig {
@@ -28,14 +40,21 @@ pub struct GitLabConfig {
     pub root_password: String,
     /// Container name.
     pub container_name: String,
+    /// Docker image (e.g., "gitlab/gitlab-ce").
+    pub image: String,
+    /// Docker image tag (e.g., "17.5.1-ce.0").
+    pub tag: String,
 }
 
 impl Default for GitLabConfig {
+    /// Defaults, with the image taken from `GITLAB_IMAGE`/`GITLAB_TAG` if set.
     fn default() -> Self {
         Self {
             base_url: format!("http://localhost:{}", DEFAULT_GITLAB_PORT),
             root_password: DEFAULT_ROOT_PASSWORD.to_string(),
             container_name: "tanuki-mcp-e2e-gitlab".to_string(),
+            image: std::env::var("GITLAB_IMAGE").unwrap_or_else(|_| DEFAULT_GITLAB_IMAGE.into()),
+            tag: std::env::var("GITLAB_TAG").unwrap_or_else(|_| DEFAULT_GITLAB_TAG.into()),
         }
     }
 }
@@ -58,12 +77,31 @@ impl GitLabConfig {
     pub fn api_url(&self) -> String {
         format!("{}/api/v4", self.base_url)
     }
+
+    /// Use a specific Docker image and tag.
+    pub fn with_image(mut self, image: impl Into<String>, tag: impl Into<String>) -> Self {
+        self.image = image.into();
+        self.tag = tag.into();
+        self
+    }
+
+    /// Full image reference (e.g., "gitlab/gitlab-ce:17.5.1-ce.0").
+    pub fn image_ref(&self) -> String {
+        format!("{}:{}", self.image, self.tag)
+    }
 }
 
+/// Path of the compose file defining the GitLab CE service.
+const COMPOSE_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/docker-compose.yml");
+
 /// GitLab container manager.
 pub struct GitLabContainer {
     config: GitLabConfig,
     client: Client,
+    /// IDs of projects created by this process, the only ones it may delete.
+    created_projects: Arc<Mutex<HashSet<u64>>>,
+    /// IDs of groups created by this process, the only ones it may delete.
+    created_groups: Arc<Mutex<HashSet<u64>>>,
 }
 
 impl GitLabContainer {
@@ -80,7 +118,67 @@ impl GitLabContainer {
                 .timeout(Duration::from_secs(30))
                 .build()
                 .expect("Failed to create HTTP client"),
+            created_projects: Arc::default(),
+            created_groups: Arc::default(),
+        }
+    }
+
+    /// Create a manager sharing this one's record of created projects and
+    /// groups but with its own HTTP client, for use from another runtime.
+    pub fn detached(&self) -> Self {
+        Self {
+            created_projects: Arc::clone(&self.created_projects),
+            created_groups: Arc::clone(&self.created_groups),
+            ..Self::with_config(self.config.clone())
+        }
+    }
+
+    /// Record a project created outside [`Self::create_project`] (e.g. via
+    /// an MCP tool) so that [`Self::delete_project`] may remove it.
+    pub fn track_project(&self, project_id: u64) {
+        self.projects().insert(project_id);
+    }
+
+    /// IDs of projects created or tracked by this process and not yet deleted.
+    pub fn created_projects(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = self.projects().iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn projects(&self) -> std::sync::MutexGuard<'_, HashSet<u64>> {
+        self.created_projects
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn groups(&self) -> std::sync::MutexGuard<'_, HashSet<u64>> {
+        self.created_groups
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start the GitLab CE service from the E2E compose file.
+    ///
+    /// Used when no external instance is configured via `GITLAB_URL`.
+    pub async fn start(&self) -> Result<()> {
+        if self.is_running().await {
+            return Ok(());
         }
+
+        tracing::info!("Starting GitLab container {}...", self.config.image_ref());
+        let output = self
+            .compose_up_command()
+            .output()
+            .await
+            .context("Failed to run docker compose")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to start GitLab container: {}", stderr);
+        }
+
+        Ok(())
     }
 
     /// Get the GitLab configuration.
@@ -88,6 +186,24 @@ impl GitLabContainer {
         &self.config
     }
 
+    /// Build the `docker compose up` command for the configured image.
+    pub fn compose_up_command(&self) -> Command {
+        let mut command = Command::new("docker");
+        command
+            .args([
+                "compose",
+                "-f",
+                COMPOSE_FILE,
+                "--profile",
+                "infra",
+                "up",
+                "-d",
+            ])
+            .env("GITLAB_IMAGE", &self.config.image)
+            .env("GITLAB_TAG", &self.config.tag);
+        command
+    }
+
     /// Check if the GitLab container is running.
     pub async fn is_running(&self) -> bool {
         let output = Command::new("docker")
@@ -215,17 +331,32 @@ impl GitLabContainer {
 
     /// Create a test project.
     pub async fn create_project(&self, token: &str, name: &str) -> Result<serde_json::Value> {
+        self.create_project_in(token, name, None).await
+    }
+
+    /// Create a test project in a namespace (the user's own when `None`).
+    pub async fn create_project_in(
+        &self,
+        token: &str,
+        name: &str,
+        namespace_id: Option<u64>,
+    ) -> Result<serde_json::Value> {
         let url = format!("{}/projects", self.config.api_url());
 
+        let mut body = serde_json::json!({
+            "name": name,
+            "visibility": "private",
+            "initialize_with_readme": true
+        });
+        if let Some(namespace_id) = namespace_id {
+            body["namespace_id"] = namespace_id.into();
+        }
+
         let response = self
             .client
             .post(&url)
             .header("PRIVATE-TOKEN", token)
-            .json(&serde_json::json!({
-                "name": name,
-                "visibility": "private",
-                "initialize_with_readme": true
-            }))
+            .json(&body)
             .send()
             .await
             .context("Failed to send create project request")?;
@@ -236,14 +367,28 @@ impl GitLabContainer {
             anyhow::bail!("Failed to create project: {} - {}", status, body);
         }
 
-        response
+        let project: serde_json::Value = response
             .json()
             .await
-            .context("Failed to parse project response")
+            .context("Failed to parse project response")?;
+        if let Some(id) = project["id"].as_u64() {
+            self.track_project(id);
+        }
+
+        Ok(project)
     }
 
     /// Delete a project by ID.
+    ///
+    /// Refuses to delete projects this process did not create or track.
     pub async fn delete_project(&self, token: &str, project_id: u64) -> Result<()> {
+        if !self.projects().contains(&project_id) {
+            anyhow::bail!(
+                "Refusing to delete project {}: not created by this test run",
+                project_id
+            );
+        }
+
         let url = format!("{}/projects/{}", self.config.api_url(), project_id);
 
         let response = self
@@ -260,9 +405,121 @@ impl GitLabContainer {
             anyhow::bail!("Failed to delete project: {} - {}", status, body);
         }
 
+        self.projects().remove(&project_id);
         Ok(())
     }
 
+    /// Create a private top-level group for one test.
+    pub async fn create_group(&self, token: &str, name: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/groups", self.config.api_url());
+
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", token)
+            .json(&serde_json::json!({
+                "name": name,
+                "path": name,
+                "visibility": "private"
+            }))
+            .send()
+            .await
+            .context("Failed to send create group request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Failed to create group: {} - {}", status, body);
+        }
+
+        let group: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse group response")?;
+        if let Some(id) = group["id"].as_u64() {
+            self.groups().insert(id);
+        }
+
+        Ok(group)
+    }
+
+    /// Delete a group, and every project in it, by ID.
+    ///
+    /// Refuses to delete groups this process did not create.
+    pub async fn delete_group(&self, token: &str, group_id: u64) -> Result<()> {
+        if !self.groups().contains(&group_id) {
+            anyhow::bail!(
+                "Refusing to delete group {}: not created by this test run",
+                group_id
+            );
+        }
+
+        let url = format!("{}/groups/{}", self.config.api_url(), group_id);
+
+        let response = self
+            .client
+            .delete(&url)
+            .header("PRIVATE-TOKEN", token)
+            .send()
+            .await
+            .context("Failed to send delete group request")?;
+
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Failed to delete group: {} - {}", status, body);
+        }
+
+        self.groups().remove(&group_id);
+        Ok(())
+    }
+
+    /// Delete every project and group created or tracked by this process.
+    pub async fn delete_created_projects(&self, token: &str) -> Result<()> {
+        for project_id in self.created_projects() {
+            self.delete_project(token, project_id).await?;
+        }
+        let group_ids: Vec<u64> = self.groups().iter().copied().collect();
+        for group_id in group_ids {
+            self.delete_group(token, group_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether a project still exists.
+    ///
+    /// Projects pending delayed deletion count as gone.
+    pub async fn project_exists(&self, token: &str, project_id: u64) -> Result<bool> {
+        let url = format!("{}/projects/{}", self.config.api_url(), project_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("PRIVATE-TOKEN", token)
+            .send()
+            .await
+            .context("Failed to send get project request")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Failed to get project: {} - {}", status, body);
+        }
+
+        let project: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse project response")?;
+        let pending_deletion = ["marked_for_deletion_at", "marked_for_deletion_on"]
+            .iter()
+            .any(|field| !project[field].is_null());
+
+        Ok(!pending_deletion)
+    }
+
     /// Get current user info (validates token).
     pub async fn get_current_user(&self, token: &str) -> Result<serde_json::Value> {
         let url = format!("{}/user", self.config.api_url());
@@ -439,6 +696,42 @@ mod tests {
         assert_eq!(config.api_url(), "http://localhost:8080/api/v4");
     }
 
+    #[tokio::test]
+    async fn test_delete_refuses_untracked_projects() {
+        let gitlab = GitLabContainer::with_config(GitLabConfig::from_url("http://127.0.0.1:9"));
+
+        let err = gitlab.delete_project("token", 42).await.unwrap_err();
+        assert!(err.to_string().contains("not created by this test run"));
+
+        gitlab.track_project(42);
+        assert_eq!(gitlab.detached().created_projects(), vec![42]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_refuses_untracked_groups() {
+        let gitlab = GitLabContainer::with_config(GitLabConfig::from_url("http://127.0.0.1:9"));
+
+        let err = gitlab.delete_group("token", 7).await.unwrap_err();
+        assert!(err.to_string().contains("not created by this test run"));
+    }
+
+    #[test]
+    fn test_compose_up_uses_requested_image() {
+        let config = GitLabConfig::default().with_image("gitlab/gitlab-ce", "16.11.10-ce.0");
+        assert_eq!(config.image_ref(), "gitlab/gitlab-ce:16.11.10-ce.0");
+
+        let command = GitLabContainer::with_config(config).compose_up_command();
+        let envs: Vec<_> = command.as_std().get_envs().collect();
+        assert!(envs.contains(&(
+            std::ffi::OsStr::new("GITLAB_TAG"),
+            Some(std::ffi::OsStr::new("16.11.10-ce.0"))
+        )));
+        assert!(envs.contains(&(
+            std::ffi::OsStr::new("GITLAB_IMAGE"),
+            Some(std::ffi::OsStr::new("gitlab/gitlab-ce"))
+        )));
+    }
+
     #[test]
     fn test_from_url() {
         let config = GitLabConfig::from_url("http://localhost:9090");
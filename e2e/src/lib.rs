@@ -9,6 +9,7 @@
 //! - `transport`: MCP client abstraction supporting stdio and HTTP transports
 //! - `gitlab`: GitLab CE container management and API helpers
 //! - `context`: Test context combining MCP client, GitLab access, and test resources
+//! - `snapshot`: Snapshot assertions comparing normalized JSON against stored files
 //!
 //! # Usage
 //!
@@ -31,13 +32,18 @@
 pub mod context;
 pub mod gitlab;
 pub mod shared;
+pub mod snapshot;
 pub mod transport;
 
 // Re-export main types for convenience
-pub use context::{TestContext, TestContextBuilder};
-pub use gitlab::{DEFAULT_GITLAB_PORT, DEFAULT_ROOT_PASSWORD, GitLabConfig, GitLabContainer};
+pub use context::{ProjectGuard, TestContext, TestContextBuilder};
+pub use gitlab::{
+    DEFAULT_GITLAB_IMAGE, DEFAULT_GITLAB_PORT, DEFAULT_GITLAB_TAG, DEFAULT_ROOT_PASSWORD,
+    GitLabConfig, GitLabContainer,
+};
 pub use shared::{
     SharedMcpClient, SharedPeer, SharedServers, find_binary, generate_config, get_gitlab_url,
     get_shared_servers, get_token,
 };
-pub use transport::{McpClient, TransportKind};
+pub use snapshot::assert_json_shape;
+pub use transport::{McpClient, TransportKind, parse_tool_output};
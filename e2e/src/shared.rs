@@ -7,6 +7,14 @@
 //! - HTTP transport (Streamable HTTP): Peer is Clone, tests can call concurrently
 //! - Stdio transport: Mutex-protected, tests serialize access
 //!
+//! # GitLab Instance
+//!
+//! When `GITLAB_URL` and `GITLAB_TOKEN` are both set, tests talk to that
+//! instance directly and never start a container. Otherwise the GitLab CE
+//! service from `e2e/docker-compose.yml` is started (if needed) and a token
+//! is created in it. Either way, only projects created by the run are
+//! deleted.
+//!
 //! # Runtime Considerations
 //!
 //! Each `#[tokio::test]` creates its own runtime. To ensure the shared servers
@@ -15,6 +23,7 @@
 
 use std::path::PathBuf;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use rmcp::model::{CallToolRequestParam, CallToolResult, ListToolsResult};
@@ -28,7 +37,7 @@ use tokio::runtime::Runtime;
 use tokio::sync::Mutex as TokioMutex;
 
 use crate::gitlab::{GitLabConfig, GitLabContainer};
-use crate::transport::TransportKind;
+use crate::transport::{POLL_DELAY, TransportKind, parse_tool_output, retry_until};
 
 /// Global shared servers singleton.
 static SHARED_SERVERS: OnceLock<SharedServers> = OnceLock::new();
@@ -38,6 +47,9 @@ static SHARED_SERVERS: OnceLock<SharedServers> = OnceLock::new();
 /// service tasks don't get cancelled between tests.
 static SERVICE_RUNTIME: OnceLock<Runtime> = OnceLock::new();
 
+/// How long to wait for a freshly started GitLab container (3-5 minutes typical).
+const CONTAINER_READY_TIMEOUT: Duration = Duration::from_secs(600);
+
 /// Async initialization lock to prevent concurrent initialization.
 static INIT_LOCK: TokioMutex<()> = TokioMutex::const_new(());
 
@@ -117,10 +129,17 @@ impl SharedServers {
     async fn init() -> Result<Self> {
         tracing::info!("Initializing shared MCP servers...");
 
-        let gitlab_url = get_gitlab_url()?;
-        let token = get_token()?;
-
-        let gitlab = GitLabContainer::with_config(GitLabConfig::from_url(&gitlab_url));
+        let (gitlab, token) = match (get_gitlab_url(), get_token()) {
+            (Ok(gitlab_url), Ok(token)) => {
+                tracing::info!("Using external GitLab instance at {}", gitlab_url);
+                let gitlab = GitLabContainer::with_config(GitLabConfig::from_url(&gitlab_url));
+                (gitlab, token)
+            }
+            _ => {
+                tracing::info!("GITLAB_URL/GITLAB_TOKEN not set, using the GitLab container");
+                start_container().await?
+            }
+        };
 
         // Create config directory and file (needed for stdio)
         let config_dir = TempDir::new().context("Failed to create temp directory")?;
@@ -188,6 +207,24 @@ impl SharedServers {
     }
 }
 
+/// Start the Docker GitLab CE service and create a token in it.
+async fn start_container() -> Result<(GitLabContainer, String)> {
+    let gitlab = GitLabContainer::new();
+    gitlab.start().await?;
+    gitlab
+        .wait_for_ready(CONTAINER_READY_TIMEOUT)
+        .await
+        .context("GitLab container did not become ready")?;
+
+    let token_value = format!("glpat-e2e-{}", uuid::Uuid::new_v4().simple());
+    let token = gitlab
+        .create_personal_access_token("e2e-test", &token_value)
+        .await
+        .context("Failed to create token in GitLab container")?;
+
+    Ok((gitlab, token))
+}
+
 /// Get GitLab URL from environment variable.
 pub fn get_gitlab_url() -> Result<String> {
     std::env::var("GITLAB_URL").context(
@@ -449,6 +486,38 @@ impl SharedMcpClient {
         serde_json::from_str(&text).context("Failed to parse tool result as JSON")
     }
 
+    /// Call a tool and deserialize its JSON result into `T`.
+    ///
+    /// See [`parse_tool_output`] for the error on a shape mismatch.
+    pub async fn call_tool_typed<T: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<T> {
+        let value = self.call_tool_json(name, arguments).await?;
+        parse_tool_output(name, value)
+    }
+
+    /// Call a tool until its JSON result satisfies `predicate`.
+    ///
+    /// Polls every [`POLL_DELAY`] for at most `max_attempts` calls; see
+    /// [`retry_until`].
+    pub async fn call_tool_json_retry(
+        &self,
+        name: &str,
+        arguments: Value,
+        predicate: impl Fn(&Value) -> bool,
+        max_attempts: usize,
+    ) -> Result<Value> {
+        retry_until(
+            max_attempts,
+            POLL_DELAY,
+            || self.call_tool_json(name, arguments.clone()),
+            predicate,
+        )
+        .await
+    }
+
     /// Shutdown the client (no-op for shared client).
     ///
     /// The shared server stays running for other tests.
@@ -0,0 +1,154 @@
+//! Snapshot assertions for tool output.
+//!
+//! [`assert_json_shape`] normalizes the volatile parts of a JSON response and
+//! compares the result against a stored file in `e2e/snapshots/`. This catches
+//! changes to the overall structure that field-by-field assertions miss.
+//!
+//! Normalization replaces:
+//! - numeric `id`, `iid` and `*_id` fields with `"[id]"`
+//! - non-null `*_at` fields with `"[timestamp]"`
+//! - every occurrence of a caller-supplied redaction (unique names, project
+//!   paths, the GitLab URL) inside string values with its placeholder
+//!
+//! Set `UPDATE_SNAPSHOTS=1` to write the normalized output instead of
+//! comparing, then review the resulting diff.
+
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+/// Environment variable enabling snapshot update mode.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Directory holding the stored snapshots.
+pub fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots")
+}
+
+/// Replace volatile values so the result is stable across runs.
+///
+/// `redactions` are `(value, placeholder)` pairs; longer values are replaced
+/// first so that a project path wins over a name it contains.
+pub fn normalize(value: &Value, redactions: &[(&str, &str)]) -> Value {
+    let mut redactions: Vec<_> = redactions
+        .iter()
+        .filter(|(value, _)| !value.is_empty())
+        .copied()
+        .collect();
+    redactions.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+
+    normalize_value(None, value, &redactions)
+}
+
+fn normalize_value(key: Option<&str>, value: &Value, redactions: &[(&str, &str)]) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), normalize_value(Some(k), v, redactions)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| normalize_value(None, item, redactions))
+                .collect(),
+        ),
+        Value::Number(_) if key.is_some_and(is_id_key) => Value::String("[id]".to_string()),
+        Value::String(_) if key.is_some_and(|k| k.ends_with("_at")) => {
+            Value::String("[timestamp]".to_string())
+        }
+        Value::String(s) => Value::String(
+            redactions
+                .iter()
+                .fold(s.clone(), |s, (value, placeholder)| {
+                    s.replace(value, placeholder)
+                }),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn is_id_key(key: &str) -> bool {
+    key == "id" || key == "iid" || key.ends_with("_id")
+}
+
+/// Assert that `actual` matches the snapshot `name` after normalization.
+///
+/// The snapshot lives at `e2e/snapshots/<name>.json`. With `UPDATE_SNAPSHOTS`
+/// set, the snapshot is (re)written instead of compared.
+pub fn assert_json_shape(name: &str, actual: &Value, redactions: &[(&str, &str)]) {
+    let normalized = normalize(actual, redactions);
+    let path = snapshot_dir().join(format!("{}.json", name));
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        std::fs::create_dir_all(snapshot_dir()).expect("Failed to create snapshot directory");
+        let mut content =
+            serde_json::to_string_pretty(&normalized).expect("Failed to serialize snapshot");
+        content.push('\n');
+        std::fs::write(&path, content).expect("Failed to write snapshot");
+        return;
+    }
+
+    let stored = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}; run with {}=1 to create it",
+            path.display(),
+            UPDATE_SNAPSHOTS_ENV
+        )
+    });
+    let expected: Value = serde_json::from_str(&stored)
+        .unwrap_or_else(|e| panic!("Invalid snapshot {}: {}", path.display(), e));
+
+    assert!(
+        normalized == expected,
+        "Snapshot {} does not match (run with {}=1 to update)\n--- expected\n{}\n--- actual\n{}",
+        name,
+        UPDATE_SNAPSHOTS_ENV,
+        serde_json::to_string_pretty(&expected).unwrap_or_default(),
+        serde_json::to_string_pretty(&normalized).unwrap_or_default()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_volatile_fields() {
+        let value = json!({
+            "id": 42,
+            "iid": 1,
+            "project_id": 7,
+            "title": "release-abc123",
+            "created_at": "2024-01-01T00:00:00Z",
+            "due_date": null,
+            "closed_at": null,
+            "web_url": "http://localhost:8080/root/e2e-test-1/-/milestones/1",
+            "labels": [{"id": 3, "name": "bug"}]
+        });
+
+        let normalized = normalize(
+            &value,
+            &[
+                ("http://localhost:8080", "[gitlab]"),
+                ("root/e2e-test-1", "[project]"),
+                ("release-abc123", "[title]"),
+            ],
+        );
+
+        assert_eq!(
+            normalized,
+            json!({
+                "id": "[id]",
+                "iid": "[id]",
+                "project_id": "[id]",
+                "title": "[title]",
+                "created_at": "[timestamp]",
+                "due_date": null,
+                "closed_at": null,
+                "web_url": "[gitlab]/[project]/-/milestones/1",
+                "labels": [{"id": "[id]", "name": "bug"}]
+            })
+        );
+    }
+}
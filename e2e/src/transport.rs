@@ -4,6 +4,7 @@
 //! - Stdio: Spawns tanuki-mcp as a child process
 //! - HTTP: Connects to tanuki-mcp running in HTTP mode (Streamable HTTP transport)
 
+use std::future::Future;
 use std::path::Path;
 use std::time::Duration;
 
@@ -12,10 +13,70 @@ use rmcp::model::{CallToolRequestParam, CallToolResult, ListToolsResult};
 use rmcp::service::{Peer, RoleClient, RunningService, ServiceExt};
 use rmcp::transport::child_process::TokioChildProcess;
 use rmcp::transport::streamable_http_client::StreamableHttpClientTransport;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use tokio::process::{Child, Command};
 use tokio::time::sleep;
 
+/// Maximum attempts when polling for resource readiness.
+pub const MAX_POLL_ATTEMPTS: usize = 20;
+
+/// Delay between poll attempts.
+pub const POLL_DELAY: Duration = Duration::from_secs(1);
+
+/// Deserialize a tool's JSON result into `T`.
+///
+/// On a mismatch the error names the tool, the expected type, the serde error
+/// (e.g. the missing field), and the actual output.
+pub fn parse_tool_output<T: DeserializeOwned>(name: &str, value: Value) -> Result<T> {
+    serde_json::from_value(value.clone()).map_err(|e| {
+        anyhow::anyhow!(
+            "Output of tool '{}' does not match {}: {}\nOutput: {}",
+            name,
+            std::any::type_name::<T>(),
+            e,
+            value
+        )
+    })
+}
+
+/// Repeat `call` until its result satisfies `predicate`.
+///
+/// Makes at most `max_attempts` calls, sleeping `delay` between them. A failed
+/// call counts as an unsatisfied attempt, so transient errors are retried too.
+/// When attempts run out, the error names the last result or error seen.
+pub async fn retry_until<F, Fut, P>(
+    max_attempts: usize,
+    delay: Duration,
+    mut call: F,
+    predicate: P,
+) -> Result<Value>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Value>>,
+    P: Fn(&Value) -> bool,
+{
+    let mut last = "no attempt made".to_string();
+
+    for attempt in 1..=max_attempts {
+        match call().await {
+            Ok(value) if predicate(&value) => return Ok(value),
+            Ok(value) => last = format!("last result: {}", value),
+            Err(e) => last = format!("last error: {:#}", e),
+        }
+
+        if attempt < max_attempts {
+            sleep(delay).await;
+        }
+    }
+
+    anyhow::bail!(
+        "Condition not met after {} attempts ({})",
+        max_attempts,
+        last
+    )
+}
+
 /// The transport kind for E2E tests.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransportKind {
@@ -206,6 +267,38 @@ impl McpClient {
         serde_json::from_str(&text).context("Failed to parse tool result as JSON")
     }
 
+    /// Call a tool and deserialize its JSON result into `T`.
+    ///
+    /// See [`parse_tool_output`] for the error on a shape mismatch.
+    pub async fn call_tool_typed<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<T> {
+        let value = self.call_tool_json(name, arguments).await?;
+        parse_tool_output(name, value)
+    }
+
+    /// Call a tool until its JSON result satisfies `predicate`.
+    ///
+    /// Polls every [`POLL_DELAY`] for at most `max_attempts` calls; see
+    /// [`retry_until`].
+    pub async fn call_tool_json_retry(
+        &self,
+        name: &str,
+        arguments: Value,
+        predicate: impl Fn(&Value) -> bool,
+        max_attempts: usize,
+    ) -> Result<Value> {
+        retry_until(
+            max_attempts,
+            POLL_DELAY,
+            || self.call_tool_json(name, arguments.clone()),
+            predicate,
+        )
+        .await
+    }
+
     /// Shutdown the client and cleanup resources.
     pub async fn shutdown(mut self) -> Result<()> {
         if let Some(service) = self.running_service.take() {
@@ -253,4 +346,78 @@ mod tests {
         assert_eq!(TransportKind::Stdio.to_string(), "stdio");
         assert_eq!(TransportKind::Http.to_string(), "http");
     }
+
+    #[test]
+    fn test_parse_tool_output_into_struct() {
+        #[derive(Debug, serde::Deserialize)]
+        struct CreatedIssue {
+            iid: u64,
+            title: String,
+            state: String,
+        }
+
+        let issue: CreatedIssue = parse_tool_output(
+            "create_issue",
+            serde_json::json!({"id": 9, "iid": 3, "title": "Bug", "state": "opened"}),
+        )
+        .unwrap();
+        assert_eq!(issue.iid, 3);
+        assert_eq!(issue.title, "Bug");
+        assert_eq!(issue.state, "opened");
+
+        let err = parse_tool_output::<CreatedIssue>("create_issue", serde_json::json!({"iid": 3}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("create_issue"));
+        assert!(err.contains("CreatedIssue"));
+        assert!(err.contains("missing field `title`"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_passes_on_third_attempt() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = AtomicUsize::new(0);
+        let result = retry_until(
+            5,
+            Duration::ZERO,
+            || {
+                let attempt = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    if attempt == 1 {
+                        anyhow::bail!("transient failure");
+                    }
+                    Ok(serde_json::json!({ "attempt": attempt }))
+                }
+            },
+            |value| value["attempt"] == 3,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result["attempt"], 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_gives_up_after_max_attempts() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = AtomicUsize::new(0);
+        let err = retry_until(
+            3,
+            Duration::ZERO,
+            || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok(serde_json::json!({ "status": "running" })) }
+            },
+            |value| value["status"] == "success",
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(err.to_string().contains("after 3 attempts"));
+        assert!(err.to_string().contains("running"));
+    }
 }
@@ -3,13 +3,8 @@
 #![allow(dead_code)]
 
 use serde_json::Value;
-use std::time::Duration;
 
-/// Maximum attempts when polling for resource readiness.
-pub const MAX_POLL_ATTEMPTS: usize = 20;
-
-/// Delay between poll attempts.
-pub const POLL_DELAY: Duration = Duration::from_secs(1);
+pub use tanuki_mcp_e2e::transport::MAX_POLL_ATTEMPTS;
 
 /// Initialize tracing for tests.
 pub fn init_tracing() {
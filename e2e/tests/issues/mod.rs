@@ -5,9 +5,19 @@
 use crate::common;
 
 use rstest::rstest;
+use serde::Deserialize;
 use serde_json::json;
 use tanuki_mcp_e2e::{TestContextBuilder, TransportKind};
 
+/// The fields of a `create_issue` result these tests rely on.
+#[derive(Debug, Deserialize)]
+struct CreatedIssue {
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+}
+
 /// Test listing issues in a project.
 #[rstest]
 #[case::stdio(TransportKind::Stdio)]
@@ -59,9 +69,9 @@ async fn test_create_issue(#[case] transport: TransportKind) {
     let project_path = ctx.project_path.clone().expect("No project path");
     let issue_title = common::unique_name("test-issue");
 
-    let result = ctx
+    let issue: CreatedIssue = ctx
         .client
-        .call_tool_json(
+        .call_tool_typed(
             "create_issue",
             json!({
                 "project": project_path,
@@ -73,12 +83,13 @@ async fn test_create_issue(#[case] transport: TransportKind) {
         .expect("Failed to create issue");
 
     // Verify issue was created
-    assert!(result.is_object(), "Expected object, got: {:?}", result);
-    assert!(result.get("iid").is_some(), "Missing issue IID");
+    assert!(issue.iid > 0, "Unexpected issue IID");
+    assert_eq!(issue.title, issue_title);
     assert_eq!(
-        result.get("title").and_then(|v| v.as_str()),
-        Some(issue_title.as_str())
+        issue.description.as_deref(),
+        Some("E2E test issue description")
     );
+    assert_eq!(issue.state, "opened");
 
     ctx.cleanup().await.expect("Cleanup failed");
 }
@@ -92,46 +92,15 @@ async fn create_pipeline_and_get_job(
     (pipeline_id, job_id)
 }
 
-/// Helper to wait for a job to complete (success, failed, or canceled).
-async fn wait_for_job_completion(
-    ctx: &tanuki_mcp_e2e::TestContext,
-    project_path: &str,
-    job_id: i64,
-    timeout_secs: u64,
-) -> serde_json::Value {
-    let start = std::time::Instant::now();
-    loop {
-        let job = ctx
-            .client
-            .call_tool_json(
-                "get_job",
-                json!({
-                    "project": project_path,
-                    "job_id": job_id
-                }),
-            )
-            .await
-            .expect("Failed to get job");
-
-        let status = job
-            .get("status")
-            .and_then(|s| s.as_str())
-            .unwrap_or("unknown");
-
-        // Check if job has finished (not pending, running, or created)
-        match status {
-            "success" | "failed" | "canceled" | "skipped" => return job,
-            _ => {
-                if start.elapsed().as_secs() > timeout_secs {
-                    panic!(
-                        "Timeout waiting for job {} to complete. Current status: {}",
-                        job_id, status
-                    );
-                }
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            }
-        }
-    }
+/// Maximum polls while waiting for a job to finish (one per `POLL_DELAY`).
+const JOB_COMPLETION_ATTEMPTS: usize = 60;
+
+/// Whether a job has finished (success, failed, canceled, or skipped).
+fn is_job_finished(job: &serde_json::Value) -> bool {
+    matches!(
+        job.get("status").and_then(|s| s.as_str()),
+        Some("success" | "failed" | "canceled" | "skipped")
+    )
 }
 
 /// Test listing pipeline jobs.
@@ -257,7 +226,15 @@ async fn test_get_job_log(#[case] transport: TransportKind) {
     let (_, job_id) = create_pipeline_and_get_job(&ctx, &project_path).await;
 
     // Wait for the job to complete so we have logs
-    wait_for_job_completion(&ctx, &project_path, job_id, 60).await;
+    ctx.client
+        .call_tool_json_retry(
+            "get_job",
+            json!({ "project": project_path, "job_id": job_id }),
+            is_job_finished,
+            JOB_COMPLETION_ATTEMPTS,
+        )
+        .await
+        .expect("Job did not complete");
 
     // Get job log - returns text content, not JSON
     let result = ctx
@@ -305,7 +282,15 @@ async fn test_retry_job(#[case] transport: TransportKind) {
     let (_, job_id) = create_pipeline_and_get_job(&ctx, &project_path).await;
 
     // Wait for job to complete - can only retry finished jobs
-    wait_for_job_completion(&ctx, &project_path, job_id, 60).await;
+    ctx.client
+        .call_tool_json_retry(
+            "get_job",
+            json!({ "project": project_path, "job_id": job_id }),
+            is_job_finished,
+            JOB_COMPLETION_ATTEMPTS,
+        )
+        .await
+        .expect("Job did not complete");
 
     let result = ctx
         .client
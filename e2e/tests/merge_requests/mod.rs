@@ -16,32 +16,24 @@ async fn wait_for_mr_ready(
     project_path: &str,
     mr_iid: i64,
 ) -> serde_json::Value {
-    for _ in 0..common::MAX_POLL_ATTEMPTS {
-        let mr = ctx
-            .client
-            .call_tool_json(
-                "get_merge_request",
-                json!({
-                    "project": project_path,
-                    "merge_request_iid": mr_iid
-                }),
-            )
-            .await
-            .expect("Failed to get MR");
-
-        let status = mr
-            .get("detailed_merge_status")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-
-        if status != "preparing" && status != "checking" {
-            return mr;
-        }
-
-        tokio::time::sleep(common::POLL_DELAY).await;
-    }
-
-    panic!("MR did not become ready within timeout");
+    ctx.client
+        .call_tool_json_retry(
+            "get_merge_request",
+            json!({
+                "project": project_path,
+                "merge_request_iid": mr_iid
+            }),
+            |mr| {
+                let status = mr
+                    .get("detailed_merge_status")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                status != "preparing" && status != "checking"
+            },
+            common::MAX_POLL_ATTEMPTS,
+        )
+        .await
+        .expect("MR did not become ready within timeout")
 }
 
 /// Helper to create a merge request for testing.
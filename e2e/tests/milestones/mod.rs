@@ -7,7 +7,7 @@ use crate::common;
 
 use rstest::rstest;
 use serde_json::json;
-use tanuki_mcp_e2e::{TestContextBuilder, TransportKind};
+use tanuki_mcp_e2e::{TestContextBuilder, TransportKind, assert_json_shape};
 
 /// Test listing milestones.
 #[rstest]
@@ -155,7 +155,14 @@ async fn test_create_milestone(#[case] transport: TransportKind) {
         result.get("title").and_then(|v| v.as_str()),
         Some(milestone_title.as_str())
     );
-    assert!(result.get("id").is_some(), "Expected id field");
+    assert_json_shape(
+        "create_milestone",
+        &result,
+        &[
+            (project_path.as_str(), "[project]"),
+            (milestone_title.as_str(), "[title]"),
+        ],
+    );
 
     ctx.cleanup().await.expect("Cleanup failed");
 }
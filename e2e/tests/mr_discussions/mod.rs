@@ -2,7 +2,8 @@
 //!
 //! Tests: list_mr_discussions, get_mr_discussion, create_mr_discussion,
 //!        add_mr_discussion_note, update_mr_discussion_note,
-//!        delete_mr_discussion_note, resolve_mr_discussion
+//!        delete_mr_discussion_note, resolve_mr_discussion,
+//!        unresolve_mr_discussion
 
 use crate::common;
 
@@ -443,7 +444,7 @@ async fn test_delete_mr_discussion_note(#[case] transport: TransportKind) {
     ctx.cleanup().await.expect("Cleanup failed");
 }
 
-/// Test resolving an MR discussion.
+/// Test resolving and unresolving an MR discussion.
 #[rstest]
 #[case::stdio(TransportKind::Stdio)]
 #[case::http(TransportKind::Http)]
@@ -492,14 +493,38 @@ async fn test_resolve_mr_discussion(#[case] transport: TransportKind) {
             json!({
                 "project": project_path,
                 "merge_request_iid": mr_iid,
-                "discussion_id": discussion_id,
-                "resolved": true
+                "discussion_id": discussion_id
             }),
         )
         .await
         .expect("Failed to resolve discussion");
 
-    assert!(result.is_object(), "Expected object, got: {:?}", result);
+    assert_eq!(
+        result.get("resolved").and_then(|v| v.as_bool()),
+        Some(true),
+        "Expected resolved thread, got: {:?}",
+        result
+    );
+
+    let result = ctx
+        .client
+        .call_tool_json(
+            "unresolve_mr_discussion",
+            json!({
+                "project": project_path,
+                "merge_request_iid": mr_iid,
+                "discussion_id": discussion_id
+            }),
+        )
+        .await
+        .expect("Failed to unresolve discussion");
+
+    assert_eq!(
+        result.get("resolved").and_then(|v| v.as_bool()),
+        Some(false),
+        "Expected unresolved thread, got: {:?}",
+        result
+    );
 
     ctx.cleanup().await.expect("Cleanup failed");
 }
@@ -5,6 +5,7 @@
 
 use crate::common;
 
+use anyhow::Context;
 use rstest::rstest;
 use serde_json::json;
 use tanuki_mcp_e2e::{TestContext, TestContextBuilder, TransportKind};
@@ -107,6 +108,7 @@ async fn test_create_project(#[case] transport: TransportKind) {
 
     // Cleanup - delete the created project
     if let Some(id) = project_id {
+        ctx.gitlab.track_project(id);
         let _ = ctx.gitlab.delete_project(&ctx.token, id).await;
     }
 
@@ -245,6 +247,7 @@ async fn test_fork_project(#[case] transport: TransportKind) {
 
     // Cleanup - delete the fork
     if let Some(id) = fork_id {
+        ctx.gitlab.track_project(id);
         let _ = ctx.gitlab.delete_project(&ctx.token, id).await;
     }
 
@@ -321,3 +324,41 @@ async fn test_list_projects_pagination(#[case] transport: TransportKind) {
 
     ctx.cleanup().await.expect("Cleanup failed");
 }
+
+/// Stand-in for a test body that fails before reaching `cleanup`.
+async fn return_before_cleanup(ctx: TestContext) -> anyhow::Result<()> {
+    let project_path = ctx.project_path.clone().context("No project path")?;
+    ctx.client
+        .call_tool_json("get_project", json!({ "project": project_path }))
+        .await?;
+
+    Err(anyhow::anyhow!("Returning early without cleanup"))
+}
+
+/// Test that a project is deleted when the test returns before `cleanup`.
+#[rstest]
+#[case::stdio(TransportKind::Stdio)]
+#[case::http(TransportKind::Http)]
+#[tokio::test]
+async fn test_project_deleted_on_early_return(#[case] transport: TransportKind) {
+    common::init_tracing();
+
+    let Some(ctx) = TestContextBuilder::new(transport)
+        .with_project()
+        .build()
+        .await
+        .expect("Failed to create context")
+    else {
+        return;
+    };
+
+    let gitlab = ctx.gitlab;
+    let token = ctx.token.clone();
+    let project_id = ctx.project_id.expect("No project id");
+
+    assert!(return_before_cleanup(ctx).await.is_err());
+
+    TestContext::assert_clean(gitlab, &token, project_id)
+        .await
+        .expect("Project leaked after early return");
+}
@@ -376,3 +376,67 @@ async fn test_get_repository_tree_with_path(#[case] transport: TransportKind) {
 
     ctx.cleanup().await.expect("Cleanup failed");
 }
+
+/// Test that an isolated project only contains this test's changes.
+#[rstest]
+#[case::stdio(TransportKind::Stdio)]
+#[case::http(TransportKind::Http)]
+#[tokio::test]
+async fn test_create_file_in_isolated_project(#[case] transport: TransportKind) {
+    common::init_tracing();
+
+    let Some(ctx) = TestContextBuilder::new(transport)
+        .with_isolated_project()
+        .build()
+        .await
+        .expect("Failed to create context")
+    else {
+        return;
+    };
+
+    let project_path = ctx.project_path.clone().expect("No project path");
+    let file_path = "isolated.txt";
+    assert!(ctx.group_id.is_some(), "Expected a group for the project");
+    assert!(
+        project_path.starts_with("e2e-group-"),
+        "Expected the project in its own group, got: {}",
+        project_path
+    );
+
+    let _ = ctx
+        .client
+        .call_tool_json(
+            "create_or_update_file",
+            json!({
+                "project": project_path,
+                "file_path": file_path,
+                "content": "Only this test writes here",
+                "branch": "main",
+                "commit_message": "Add file in isolated project"
+            }),
+        )
+        .await
+        .expect("Failed to create file");
+
+    let result = ctx
+        .client
+        .call_tool_json("get_repository_tree", json!({ "project": project_path }))
+        .await
+        .expect("Failed to get repository tree");
+
+    // The fixed file name is safe: nothing else touches this project
+    let names: Vec<&str> = common::as_array(&result)
+        .iter()
+        .filter_map(|entry| entry.get("name").and_then(|v| v.as_str()))
+        .collect();
+    assert_eq!(
+        names.len(),
+        2,
+        "Expected README and {}, got: {:?}",
+        file_path,
+        names
+    );
+    assert!(names.contains(&file_path));
+
+    ctx.cleanup().await.expect("Cleanup failed");
+}
@@ -0,0 +1,142 @@
+//! Project path allowlist
+//!
+//! Restricts the server to projects matching `gitlab.allowed_projects`,
+//! independently of the per-tool access rules.
+
+use crate::error::{ConfigError, GitLabResult};
+use crate::gitlab::GitLabClient;
+use regex::Regex;
+
+/// Compiled `gitlab.allowed_projects` globs
+///
+/// In a glob, `*` matches within one path segment, `**` matches across
+/// segments, and `?` matches a single character other than `/`. Matching is
+/// case-insensitive, like GitLab project paths.
+#[derive(Debug)]
+pub struct ProjectAllowlist {
+    patterns: Vec<Regex>,
+}
+
+impl ProjectAllowlist {
+    /// Compile a list of globs (an empty list allows every project)
+    pub fn new(globs: &[String]) -> Result<Self, ConfigError> {
+        let patterns = globs
+            .iter()
+            .map(|glob| {
+                let glob = glob.trim().trim_matches('/');
+                if glob.is_empty() {
+                    return Err(ConfigError::Invalid {
+                        message: "gitlab.allowed_projects entries must not be empty".to_string(),
+                    });
+                }
+                Regex::new(&glob_to_regex(glob)).map_err(|e| ConfigError::InvalidPattern {
+                    pattern: glob.to_string(),
+                    reason: e.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { patterns })
+    }
+
+    /// Whether no restriction is configured
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether a project path is allowed
+    pub fn allows(&self, project: &str) -> bool {
+        let project = project.trim_matches('/');
+        self.is_empty() || self.patterns.iter().any(|p| p.is_match(project))
+    }
+
+    /// Path of a project given by path or numeric ID, if it is not allowed
+    ///
+    /// Numeric IDs are looked up first, since the globs match paths.
+    pub async fn outside(
+        &self,
+        gitlab: &GitLabClient,
+        project: &str,
+    ) -> GitLabResult<Option<String>> {
+        let project = project.trim();
+        let path = if !project.is_empty() && project.bytes().all(|b| b.is_ascii_digit()) {
+            gitlab.project_path(project).await?
+        } else {
+            project.to_string()
+        };
+        Ok((!self.allows(&path)).then_some(path))
+    }
+}
+
+/// Translate a path glob into an anchored, case-insensitive regex
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("(?i)^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(globs: &[&str]) -> ProjectAllowlist {
+        let globs: Vec<String> = globs.iter().map(|g| g.to_string()).collect();
+        ProjectAllowlist::new(&globs).unwrap()
+    }
+
+    #[test]
+    fn test_empty_allows_everything() {
+        let allowlist = allowlist(&[]);
+        assert!(allowlist.is_empty());
+        assert!(allowlist.allows("any/project"));
+    }
+
+    #[test]
+    fn test_exact_path() {
+        let allowlist = allowlist(&["b/c"]);
+        assert!(allowlist.allows("b/c"));
+        assert!(allowlist.allows("B/C"));
+        assert!(!allowlist.allows("b/cd"));
+        assert!(!allowlist.allows("b/c/d"));
+    }
+
+    #[test]
+    fn test_single_segment_wildcard() {
+        let allowlist = allowlist(&["a/*"]);
+        assert!(allowlist.allows("a/app"));
+        assert!(!allowlist.allows("a/sub/app"));
+        assert!(!allowlist.allows("ab/app"));
+    }
+
+    #[test]
+    fn test_recursive_wildcard() {
+        let allowlist = allowlist(&["a/**"]);
+        assert!(allowlist.allows("a/app"));
+        assert!(allowlist.allows("a/sub/app"));
+        assert!(!allowlist.allows("b/app"));
+    }
+
+    #[test]
+    fn test_regex_characters_are_literal() {
+        let allowlist = allowlist(&["a/my.app"]);
+        assert!(allowlist.allows("a/my.app"));
+        assert!(!allowlist.allows("a/myxapp"));
+    }
+
+    #[test]
+    fn test_empty_entry_rejected() {
+        assert!(ProjectAllowlist::new(&[" ".to_string()]).is_err());
+    }
+}
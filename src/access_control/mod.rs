@@ -36,10 +36,12 @@
 //! all = "read"                    # Production is read-only
 //! ```
 
+pub mod allowlist;
 pub mod patterns;
 pub mod resolver;
 pub mod types;
 
+pub use allowlist::ProjectAllowlist;
 pub use patterns::PatternMatcher;
 pub use resolver::{AccessDecision, AccessResolver};
 pub use types::{AccessControlled, OperationType, ToolCategory};
@@ -36,6 +36,8 @@ pub struct AccessResolver {
     actions: HashMap<String, ActionPermission>,
     /// Project-specific configurations
     projects: HashMap<String, ProjectConfig>,
+    /// Resolve numeric project IDs to paths before checking project rules
+    resolve_numeric_ids: bool,
 }
 
 /// Compiled category configuration
@@ -105,6 +107,7 @@ impl AccessResolver {
             categories,
             actions: config.actions.clone(),
             projects,
+            resolve_numeric_ids: config.resolve_numeric_ids,
         })
     }
 
@@ -383,6 +386,14 @@ impl AccessResolver {
         false
     }
 
+    /// Whether numeric project IDs must be resolved to paths before checking
+    ///
+    /// Only true when enabled and per-project rules exist, since the rules are
+    /// keyed by path.
+    pub fn resolves_numeric_ids(&self) -> bool {
+        self.resolve_numeric_ids && !self.projects.is_empty()
+    }
+
     /// Create a permissive resolver that allows everything (for testing)
     pub fn allow_all() -> Self {
         Self {
@@ -392,6 +403,7 @@ impl AccessResolver {
             categories: HashMap::new(),
             actions: HashMap::new(),
             projects: HashMap::new(),
+            resolve_numeric_ids: false,
         }
     }
 
@@ -404,6 +416,7 @@ impl AccessResolver {
             categories: HashMap::new(),
             actions: HashMap::new(),
             projects: HashMap::new(),
+            resolve_numeric_ids: false,
         }
     }
 }
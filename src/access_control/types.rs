@@ -25,10 +25,16 @@ pub enum ToolCategory {
     Pipelines,
     Milestones,
     Releases,
+    Environments,
+    Runners,
+    DeployKeys,
+    AccessTokens,
     Users,
     Groups,
     Tags,
     Search,
+    Instance,
+    Raw,
 }
 
 impl ToolCategory {
@@ -51,10 +57,16 @@ impl ToolCategory {
             ToolCategory::Pipelines => "pipelines",
             ToolCategory::Milestones => "milestones",
             ToolCategory::Releases => "releases",
+            ToolCategory::Environments => "environments",
+            ToolCategory::Runners => "runners",
+            ToolCategory::DeployKeys => "deploy_keys",
+            ToolCategory::AccessTokens => "access_tokens",
             ToolCategory::Users => "users",
             ToolCategory::Groups => "groups",
             ToolCategory::Tags => "tags",
             ToolCategory::Search => "search",
+            ToolCategory::Instance => "instance",
+            ToolCategory::Raw => "raw",
         }
     }
 
@@ -77,10 +89,16 @@ impl ToolCategory {
             "pipelines" => Some(ToolCategory::Pipelines),
             "milestones" => Some(ToolCategory::Milestones),
             "releases" => Some(ToolCategory::Releases),
+            "environments" => Some(ToolCategory::Environments),
+            "runners" => Some(ToolCategory::Runners),
+            "deploy_keys" => Some(ToolCategory::DeployKeys),
+            "access_tokens" => Some(ToolCategory::AccessTokens),
             "users" => Some(ToolCategory::Users),
             "groups" => Some(ToolCategory::Groups),
             "tags" => Some(ToolCategory::Tags),
             "search" => Some(ToolCategory::Search),
+            "instance" => Some(ToolCategory::Instance),
+            "raw" => Some(ToolCategory::Raw),
             _ => None,
         }
     }
@@ -104,10 +122,16 @@ impl ToolCategory {
             ToolCategory::Pipelines,
             ToolCategory::Milestones,
             ToolCategory::Releases,
+            ToolCategory::Environments,
+            ToolCategory::Runners,
+            ToolCategory::DeployKeys,
+            ToolCategory::AccessTokens,
             ToolCategory::Users,
             ToolCategory::Groups,
             ToolCategory::Tags,
             ToolCategory::Search,
+            ToolCategory::Instance,
+            ToolCategory::Raw,
         ]
     }
 }
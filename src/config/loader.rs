@@ -12,6 +12,7 @@
 //! All existing config files are merged together, with later sources
 //! overriding earlier ones.
 
+use crate::access_control::ProjectAllowlist;
 use crate::config::types::AppConfig;
 use crate::error::ConfigError;
 use config::{Config, Environment, File, FileFormat};
@@ -38,7 +39,7 @@ pub fn load_config_from_str(toml_str: &str) -> Result<AppConfig, ConfigError> {
         .map_err(|e| ConfigError::Load(e.to_string()))?;
 
     // Skip token validation for testing
-    validate_config_relaxed(&app_config)?;
+    validate_common(&app_config)?;
 
     Ok(app_config)
 }
@@ -123,8 +124,11 @@ pub fn load_config(config_path: Option<&str>) -> Result<AppConfig, ConfigError>
     Ok(app_config)
 }
 
-/// Validate configuration values (relaxed - for testing without token)
-fn validate_config_relaxed(config: &AppConfig) -> Result<(), ConfigError> {
+/// Validate configuration values other than the token
+///
+/// Shared by [`load_config`] and [`load_config_from_str`], which skips the
+/// token requirement for testing.
+fn validate_common(config: &AppConfig) -> Result<(), ConfigError> {
     // Validate GitLab URL
     if config.gitlab.url.is_empty() {
         return Err(ConfigError::Missing {
@@ -141,13 +145,22 @@ fn validate_config_relaxed(config: &AppConfig) -> Result<(), ConfigError> {
         });
     }
 
-    // Validate timeout
-    if config.gitlab.timeout_secs == 0 {
+    // Validate timeouts
+    validate_timeouts(config)?;
+
+    // Validate outbound concurrency limit
+    if config.gitlab.max_concurrent_requests == Some(0) {
         return Err(ConfigError::Invalid {
-            message: "gitlab.timeout_secs must be greater than 0".to_string(),
+            message: "gitlab.max_concurrent_requests must be greater than 0".to_string(),
         });
     }
 
+    // Validate per-instance overrides
+    validate_instances(config)?;
+
+    // Validate cache warming
+    validate_warm_projects(config)?;
+
     // Validate port
     if config.server.port == 0 {
         return Err(ConfigError::Invalid {
@@ -155,14 +168,131 @@ fn validate_config_relaxed(config: &AppConfig) -> Result<(), ConfigError> {
         });
     }
 
-    // Validate regex patterns
+    // Validate request size limit
+    if config.server.max_request_bytes == 0 {
+        return Err(ConfigError::Invalid {
+            message: "server.max_request_bytes must be greater than 0".to_string(),
+        });
+    }
+
+    // Validate JSON-RPC limits
+    if config.server.http.max_batch_len == 0 || config.server.http.max_message_bytes == 0 {
+        return Err(ConfigError::Invalid {
+            message:
+                "server.http.max_batch_len and server.http.max_message_bytes must be greater than 0"
+                    .to_string(),
+        });
+    }
+
+    // Validate list size cap
+    if config.server.max_items_per_list == Some(0) {
+        return Err(ConfigError::Invalid {
+            message: "server.max_items_per_list must be greater than 0".to_string(),
+        });
+    }
+
+    // Validate tool name prefix
+    if !config
+        .server
+        .tool_prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(ConfigError::Invalid {
+            message: "server.tool_prefix may only contain letters, digits, '_' and '-'".to_string(),
+        });
+    }
+
+    // Validate polling of `wait: true` tools
+    if config.server.poll_max_attempts == 0 || config.server.poll_interval_secs == 0 {
+        return Err(ConfigError::Invalid {
+            message:
+                "server.poll_max_attempts and server.poll_interval_secs must be greater than 0"
+                    .to_string(),
+        });
+    }
+
+    // Validate regex patterns and project globs
     validate_all_patterns(config)?;
 
+    // Validate rate limits
+    config.ratelimit.limits()?;
+
+    Ok(())
+}
+
+/// Validate that no GitLab timeout is zero
+fn validate_timeouts(config: &AppConfig) -> Result<(), ConfigError> {
+    let timeouts = [
+        ("timeout_secs", Some(config.gitlab.timeout_secs)),
+        ("connect_timeout_secs", config.gitlab.connect_timeout_secs),
+        ("request_timeout_secs", config.gitlab.request_timeout_secs),
+    ];
+    for (name, value) in timeouts {
+        if value == Some(0) {
+            return Err(ConfigError::Invalid {
+                message: format!("gitlab.{} must be greater than 0", name),
+            });
+        }
+    }
+
     Ok(())
 }
 
-/// Validate all regex patterns in config
+/// Validate the named instances and the selection among them
+fn validate_instances(config: &AppConfig) -> Result<(), ConfigError> {
+    if let Some(name) = &config.gitlab.instance
+        && !config.gitlab.instances.contains_key(name)
+    {
+        return Err(ConfigError::Invalid {
+            message: format!(
+                "gitlab.instance names an unknown instance: {} (define it under [gitlab.instances.{}])",
+                name, name
+            ),
+        });
+    }
+
+    for (name, instance) in &config.gitlab.instances {
+        if !instance.url.starts_with("http://") && !instance.url.starts_with("https://") {
+            return Err(ConfigError::Invalid {
+                message: format!(
+                    "gitlab.instances.{}.url must start with http:// or https://, got: {}",
+                    name, instance.url
+                ),
+            });
+        }
+        for (field, value) in [
+            ("timeout_secs", instance.timeout_secs),
+            ("connect_timeout_secs", instance.connect_timeout_secs),
+        ] {
+            if value == Some(0) {
+                return Err(ConfigError::Invalid {
+                    message: format!("gitlab.instances.{}.{} must be greater than 0", name, field),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that warmed projects have a cache to warm
+fn validate_warm_projects(config: &AppConfig) -> Result<(), ConfigError> {
+    if !config.gitlab.warm_projects.is_empty() && config.gitlab.cache_ttl_secs == 0 {
+        return Err(ConfigError::Invalid {
+            message: "gitlab.warm_projects requires gitlab.cache_ttl_secs to be greater than 0"
+                .to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate all regex patterns and project globs in config
 fn validate_all_patterns(config: &AppConfig) -> Result<(), ConfigError> {
+    ProjectAllowlist::new(&config.gitlab.allowed_projects)?;
+    validate_patterns(&config.tools.enabled, "tools.enabled")?;
+    validate_patterns(&config.tools.disabled, "tools.disabled")?;
     validate_patterns(&config.access_control.deny, "access_control.deny")?;
     validate_patterns(&config.access_control.allow, "access_control.allow")?;
 
@@ -208,23 +338,9 @@ fn validate_all_patterns(config: &AppConfig) -> Result<(), ConfigError> {
     Ok(())
 }
 
-/// Validate configuration values
+/// Validate configuration values, requiring a token
 fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
-    // Validate GitLab URL
-    if config.gitlab.url.is_empty() {
-        return Err(ConfigError::Missing {
-            field: "gitlab.url".to_string(),
-        });
-    }
-
-    if !config.gitlab.url.starts_with("http://") && !config.gitlab.url.starts_with("https://") {
-        return Err(ConfigError::Invalid {
-            message: format!(
-                "gitlab.url must start with http:// or https://, got: {}",
-                config.gitlab.url
-            ),
-        });
-    }
+    validate_common(config)?;
 
     // Token is required unless we add OAuth support later
     if config.gitlab.token.is_none() {
@@ -235,23 +351,6 @@ fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
         });
     }
 
-    // Validate timeout
-    if config.gitlab.timeout_secs == 0 {
-        return Err(ConfigError::Invalid {
-            message: "gitlab.timeout_secs must be greater than 0".to_string(),
-        });
-    }
-
-    // Validate port
-    if config.server.port == 0 {
-        return Err(ConfigError::Invalid {
-            message: "server.port must be greater than 0".to_string(),
-        });
-    }
-
-    // Validate regex patterns in access control
-    validate_all_patterns(config)?;
-
     Ok(())
 }
 
@@ -3,7 +3,9 @@
 //! Handles loading and validating configuration from TOML files and environment variables.
 
 pub mod loader;
+pub mod starter;
 pub mod types;
 
 pub use loader::{load_config, load_config_from_str};
+pub use starter::{STARTER_CONFIG, write_starter_config};
 pub use types::*;
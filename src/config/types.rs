@@ -3,12 +3,17 @@
 //! This module defines the configuration structure that can be loaded from
 //! TOML files and/or environment variables.
 
+use crate::access_control::ToolCategory;
+use crate::error::ConfigError;
 use crate::update::UpdateConfig;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
 
 /// Root configuration structure
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AppConfig {
     /// GitLab connection settings
@@ -28,10 +33,137 @@ pub struct AppConfig {
 
     /// Update configuration
     pub updates: UpdateConfig,
+
+    /// Tool availability configuration
+    pub tools: ToolsConfig,
+
+    /// Per-category rate limits
+    pub ratelimit: RateLimitConfig,
+}
+
+impl AppConfig {
+    /// Placeholder written in place of secrets by [`AppConfig::to_redacted_toml`]
+    pub const REDACTED: &'static str = "[REDACTED]";
+
+    /// Render the configuration as TOML with secrets replaced by [`Self::REDACTED`]
+    pub fn to_redacted_toml(&self) -> Result<String, ConfigError> {
+        let mut config = self.clone();
+        if config.gitlab.token.is_some() {
+            config.gitlab.token = Some(Self::REDACTED.to_string());
+        }
+        if config.server.http.webhook_secret.is_some() {
+            config.server.http.webhook_secret = Some(Self::REDACTED.to_string());
+        }
+
+        toml::to_string_pretty(&config).map_err(|e| ConfigError::Invalid {
+            message: format!("cannot be rendered as TOML: {}", e),
+        })
+    }
+}
+
+/// Tool availability configuration
+///
+/// Controls which optional tools are registered at all, independently of
+/// access control.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    /// Enable the raw `gitlab_request` escape-hatch tool (default: false)
+    pub enable_raw_request: bool,
+
+    /// Tool name patterns (regex) to keep; when non-empty, all other tools are disabled
+    pub enabled: Vec<String>,
+
+    /// Tool name patterns (regex) to disable, applied after `enabled`
+    pub disabled: Vec<String>,
+}
+
+/// Rate limiting configuration
+///
+/// Limits are token buckets keyed by tool category; categories not listed are
+/// unlimited.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Category name to rate, e.g. `pipelines = "10/s"`
+    pub categories: HashMap<String, String>,
+}
+
+impl RateLimitConfig {
+    /// Parse the configured rates, keyed by tool category
+    pub fn limits(&self) -> Result<HashMap<ToolCategory, RateLimit>, ConfigError> {
+        self.categories
+            .iter()
+            .map(|(name, limit)| {
+                let category =
+                    ToolCategory::try_parse(name).ok_or_else(|| ConfigError::Invalid {
+                        message: format!("Unknown category in ratelimit.categories: {}", name),
+                    })?;
+                let limit = limit
+                    .parse::<RateLimit>()
+                    .map_err(|message| ConfigError::Invalid {
+                        message: format!("ratelimit.categories.{}: {}", name, message),
+                    })?;
+                Ok((category, limit))
+            })
+            .collect()
+    }
+}
+
+/// A call rate such as `10/s`, `100/m`, or `1000/h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Calls allowed per period (also the burst size)
+    pub calls: u32,
+    /// Length of the period
+    pub period: Duration,
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (calls, unit) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected '<calls>/<s|m|h>', got '{}'", s))?;
+
+        let calls: u32 = calls
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid call count in '{}'", s))?;
+        if calls == 0 {
+            return Err(format!("call count must be greater than 0 in '{}'", s));
+        }
+
+        let period = match unit.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            other => {
+                return Err(format!(
+                    "unknown period '{}' in '{}' (expected s, m, or h)",
+                    other, s
+                ));
+            }
+        };
+
+        Ok(Self { calls, period })
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.period.as_secs() {
+            60 => "m",
+            3600 => "h",
+            _ => "s",
+        };
+        write!(f, "{}/{}", self.calls, unit)
+    }
 }
 
 /// Dashboard configuration (TOML format)
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DashboardConfigToml {
     /// Enable the dashboard server
@@ -42,6 +174,9 @@ pub struct DashboardConfigToml {
 
     /// Dashboard port
     pub port: u16,
+
+    /// Number of recent tool calls kept for the dashboard timeline
+    pub recent_requests: usize,
 }
 
 impl Default for DashboardConfigToml {
@@ -50,12 +185,13 @@ impl Default for DashboardConfigToml {
             enabled: true,
             host: "127.0.0.1".into(),
             port: 19892,
+            recent_requests: 100,
         }
     }
 }
 
 /// GitLab connection configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct GitLabConfig {
     /// GitLab instance URL (e.g., `https://gitlab.com`)
@@ -68,18 +204,80 @@ pub struct GitLabConfig {
     /// API version (default: "v4")
     pub api_version: String,
 
-    /// Request timeout in seconds
+    /// Timeout in seconds, the default for both connect and request timeouts
     pub timeout_secs: u64,
 
+    /// Timeout for establishing a connection (default: `timeout_secs`)
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+
+    /// Timeout for a whole request (default: `timeout_secs`)
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
+
     /// Maximum retries for failed requests
     pub max_retries: u32,
 
+    /// Maximum number of GitLab requests in flight at once (default: unlimited)
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+
     /// Whether to verify SSL certificates
     pub verify_ssl: bool,
 
-    /// Custom User-Agent header (default: `tanuki-mcp/{version}`)
+    /// Follow GitLab's same-origin 301/302 redirects on project endpoints,
+    /// e.g. after a project was renamed (default: false)
+    #[serde(default)]
+    pub follow_redirects: bool,
+
+    /// Custom User-Agent header (default: `tanuki-mcp/{version}`), suffixed
+    /// with the transport mode
     #[serde(default)]
     pub user_agent: Option<String>,
+
+    /// Project used by tools when their `project` argument is omitted
+    #[serde(default)]
+    pub default_project: Option<String>,
+
+    /// Name of the `instances` entry to connect to (default: none, use `url`)
+    #[serde(default)]
+    pub instance: Option<String>,
+
+    /// Named GitLab instances with their own URL and connection overrides
+    #[serde(default)]
+    pub instances: HashMap<String, GitLabInstanceConfig>,
+
+    /// Seconds to cache project metadata, labels, and milestones (0 disables)
+    #[serde(default)]
+    pub cache_ttl_secs: u64,
+
+    /// Projects whose metadata, labels, and milestones are cached at startup
+    #[serde(default)]
+    pub warm_projects: Vec<String>,
+
+    /// Globs of project paths tools may act on (empty allows every project)
+    #[serde(default)]
+    pub allowed_projects: Vec<String>,
+}
+
+/// One named GitLab instance, selected with `gitlab.instance`
+///
+/// Lets a shared configuration switch between instances, giving a slow
+/// self-hosted one longer timeouts without affecting the others.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GitLabInstanceConfig {
+    /// Instance URL (replaces `gitlab.url` when selected)
+    pub url: String,
+
+    /// Connect timeout in seconds (overrides `gitlab.connect_timeout_secs`)
+    pub connect_timeout_secs: Option<u64>,
+
+    /// Request timeout in seconds (overrides `gitlab.request_timeout_secs`)
+    pub timeout_secs: Option<u64>,
+
+    /// Maximum retries for failed requests (overrides `gitlab.max_retries`)
+    pub max_retries: Option<u32>,
 }
 
 impl Default for GitLabConfig {
@@ -89,9 +287,19 @@ impl Default for GitLabConfig {
             token: None,
             api_version: "v4".into(),
             timeout_secs: 30,
+            connect_timeout_secs: None,
+            request_timeout_secs: None,
             max_retries: 3,
+            max_concurrent_requests: None,
             verify_ssl: true,
+            follow_redirects: false,
             user_agent: None,
+            default_project: None,
+            instance: None,
+            instances: HashMap::new(),
+            cache_ttl_secs: 0,
+            warm_projects: Vec::new(),
+            allowed_projects: Vec::new(),
         }
     }
 }
@@ -101,14 +309,62 @@ impl GitLabConfig {
     pub fn api_url(&self) -> String {
         format!(
             "{}/api/{}",
-            self.url.trim_end_matches('/'),
+            self.base_url().trim_end_matches('/'),
             self.api_version
         )
     }
+
+    /// URL of the selected instance, or `url` when none is selected
+    pub fn base_url(&self) -> &str {
+        self.instance().map_or(&self.url, |instance| &instance.url)
+    }
+
+    /// Get the instance selected by `instance`, if any
+    pub fn instance(&self) -> Option<&GitLabInstanceConfig> {
+        self.instance
+            .as_deref()
+            .and_then(|name| self.instances.get(name))
+    }
+
+    /// Request timeout for the selected instance
+    pub fn effective_timeout_secs(&self) -> u64 {
+        self.instance()
+            .and_then(|instance| instance.timeout_secs)
+            .or(self.request_timeout_secs)
+            .unwrap_or(self.timeout_secs)
+    }
+
+    /// Connect timeout for the selected instance
+    pub fn effective_connect_timeout_secs(&self) -> u64 {
+        self.instance()
+            .and_then(|instance| instance.connect_timeout_secs)
+            .or(self.connect_timeout_secs)
+            .unwrap_or(self.timeout_secs)
+    }
+
+    /// Maximum retries for the selected instance
+    pub fn effective_max_retries(&self) -> u32 {
+        self.instance()
+            .and_then(|instance| instance.max_retries)
+            .unwrap_or(self.max_retries)
+    }
+
+    /// User-Agent sent to GitLab: `user_agent` or `tanuki-mcp/{version}`,
+    /// followed by the transport mode when one is given
+    pub fn user_agent_header(&self, transport: Option<TransportMode>) -> String {
+        let base = self
+            .user_agent
+            .clone()
+            .unwrap_or_else(|| format!("tanuki-mcp/{}", env!("CARGO_PKG_VERSION")));
+        match transport {
+            Some(transport) => format!("{} ({})", base, transport),
+            None => base,
+        }
+    }
 }
 
 /// Server/transport configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ServerConfig {
     /// Transport mode
@@ -128,6 +384,39 @@ pub struct ServerConfig {
 
     /// CORS mode for HTTP transport
     pub cors: CorsMode,
+
+    /// Output format for tool results
+    pub output_format: OutputFormat,
+
+    /// HTTP transport settings
+    pub http: HttpServerConfig,
+
+    /// Preview write/delete/execute tools instead of calling GitLab
+    pub dry_run: bool,
+
+    /// Seconds to remember create results by idempotency key (0 disables)
+    pub idempotency_ttl_secs: u64,
+
+    /// Wrap list results as `{items, total, next_page}` using GitLab's pagination headers
+    pub include_pagination_meta: bool,
+
+    /// Largest accepted tool call, measured as the serialized JSON arguments
+    pub max_request_bytes: usize,
+
+    /// Truncate list results to at most this many items (unset disables)
+    pub max_items_per_list: Option<usize>,
+
+    /// Return recoverable tool failures as regular results carrying an `error` field
+    pub soft_errors: bool,
+
+    /// Prefix added to every advertised tool name (empty keeps the plain names)
+    pub tool_prefix: String,
+
+    /// Re-fetches made by tools called with `wait: true` before giving up
+    pub poll_max_attempts: u32,
+
+    /// Seconds between those re-fetches
+    pub poll_interval_secs: u64,
 }
 
 impl Default for ServerConfig {
@@ -139,12 +428,51 @@ impl Default for ServerConfig {
             name: "tanuki-mcp".into(),
             version: env!("CARGO_PKG_VERSION").into(),
             cors: CorsMode::default(),
+            output_format: OutputFormat::default(),
+            http: HttpServerConfig::default(),
+            dry_run: false,
+            idempotency_ttl_secs: 600,
+            include_pagination_meta: false,
+            max_request_bytes: 1024 * 1024,
+            max_items_per_list: None,
+            soft_errors: false,
+            tool_prefix: String::new(),
+            poll_max_attempts: 30,
+            poll_interval_secs: 2,
+        }
+    }
+}
+
+/// HTTP transport settings
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HttpServerConfig {
+    /// Interval in seconds between SSE keep-alive comments (0 disables)
+    pub sse_keepalive_secs: u64,
+    /// Maximum number of MCP requests handled concurrently (0 disables the limit)
+    pub max_concurrent_requests: usize,
+    /// Secret token enabling `POST /webhook` for cache invalidation (unset disables it)
+    pub webhook_secret: Option<String>,
+    /// Maximum number of messages in a JSON-RPC batch
+    pub max_batch_len: usize,
+    /// Maximum size of a single JSON-RPC message in bytes
+    pub max_message_bytes: usize,
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            sse_keepalive_secs: 30,
+            max_concurrent_requests: 64,
+            webhook_secret: None,
+            max_batch_len: 32,
+            max_message_bytes: 4 * 1024 * 1024,
         }
     }
 }
 
 /// Transport mode selection
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TransportMode {
     /// Standard input/output (default, for Claude Code)
@@ -154,8 +482,24 @@ pub enum TransportMode {
     Http,
 }
 
+impl TransportMode {
+    /// Configuration name of the transport
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransportMode::Stdio => "stdio",
+            TransportMode::Http => "http",
+        }
+    }
+}
+
+impl std::fmt::Display for TransportMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// CORS configuration mode for HTTP transport
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CorsMode {
     /// Allow all origins, methods, and headers (default)
@@ -165,6 +509,19 @@ pub enum CorsMode {
     Disabled,
 }
 
+/// Output format for JSON tool results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Indented JSON (default)
+    #[default]
+    Pretty,
+    /// Single-line JSON, for programmatic callers
+    Compact,
+    /// Markdown tables and lists, for human-facing chat
+    Markdown,
+}
+
 /// Access control configuration
 ///
 /// The access control system uses a hierarchical override model:
@@ -175,7 +532,7 @@ pub enum CorsMode {
 ///
 /// At each level, `deny` patterns are checked first, then `allow` patterns.
 /// `allow` patterns can override `deny` patterns at the same level.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AccessControlConfig {
     /// Base access level for all tools
@@ -200,6 +557,10 @@ pub struct AccessControlConfig {
     /// Per-project access overrides
     #[serde(default)]
     pub projects: HashMap<String, ProjectAccessConfig>,
+
+    /// Look up the path of numeric project IDs so per-project rules apply
+    #[serde(default)]
+    pub resolve_numeric_ids: bool,
 }
 
 impl Default for AccessControlConfig {
@@ -211,12 +572,13 @@ impl Default for AccessControlConfig {
             categories: HashMap::new(),
             actions: HashMap::new(),
             projects: HashMap::new(),
+            resolve_numeric_ids: false,
         }
     }
 }
 
 /// Base access level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AccessLevel {
     /// No access decision at this level (fall through to next level)
@@ -231,7 +593,7 @@ pub enum AccessLevel {
 }
 
 /// Category-level access configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct CategoryAccessConfig {
     /// Access level for this category
@@ -257,7 +619,7 @@ impl Default for CategoryAccessConfig {
 }
 
 /// Individual action permission
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ActionPermission {
     /// Explicitly allow this action
@@ -269,7 +631,7 @@ pub enum ActionPermission {
 /// Project-specific access configuration
 ///
 /// Inherits from global config but can override any setting
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ProjectAccessConfig {
     /// Override base access level for this project
@@ -294,7 +656,7 @@ pub struct ProjectAccessConfig {
 }
 
 /// Logging configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct LoggingConfig {
     /// Log level (trace, debug, info, warn, error)
@@ -314,7 +676,7 @@ impl Default for LoggingConfig {
 }
 
 /// Log output format
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     /// Human-readable output
@@ -4,10 +4,11 @@
 //! and request statistics.
 
 use crate::access_control::ToolCategory;
+use crate::gitlab::{GitLabHealth, HealthStatus};
 use serde::Serialize;
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{Duration, Instant, SystemTime};
 
 /// Dashboard metrics collector
@@ -27,6 +28,8 @@ pub struct DashboardMetrics {
     data: RwLock<MetricsData>,
     /// Maximum recent requests to keep
     max_recent_requests: usize,
+    /// Availability of the GitLab instance, when tracked
+    gitlab_health: Option<Arc<GitLabHealth>>,
 }
 
 /// Internal tool statistics
@@ -133,6 +136,9 @@ pub struct MetricsSnapshot {
     pub projects: Vec<ProjectStats>,
     pub categories: Vec<CategoryStats>,
     pub recent_requests: Vec<RequestRecord>,
+    /// GitLab availability (absent when not tracked)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<HealthStatus>,
 }
 
 impl DashboardMetrics {
@@ -155,9 +161,16 @@ impl DashboardMetrics {
                 recent_requests: VecDeque::with_capacity(max_recent_requests),
             }),
             max_recent_requests,
+            gitlab_health: None,
         }
     }
 
+    /// Report the health of a GitLab client alongside the usage metrics
+    pub fn with_gitlab_health(mut self, health: Arc<GitLabHealth>) -> Self {
+        self.gitlab_health = Some(health);
+        self
+    }
+
     // Helper methods for safe lock access with poison recovery
     // These recover from poisoned locks by logging a warning and continuing with the data
 
@@ -386,9 +399,40 @@ impl DashboardMetrics {
             projects,
             categories,
             recent_requests,
+            gitlab: self.gitlab_status(),
         }
     }
 
+    /// Current GitLab availability, if tracked
+    pub fn gitlab_status(&self) -> Option<HealthStatus> {
+        self.gitlab_health.as_ref().map(|health| health.status())
+    }
+
+    /// Whether the tracked GitLab instance is degraded
+    pub fn gitlab_degraded(&self) -> bool {
+        self.gitlab_health
+            .as_ref()
+            .is_some_and(|health| health.is_degraded())
+    }
+
+    /// Get the most recent tool calls, newest first
+    ///
+    /// Returns at most `limit` records (or the whole buffer when `None`).
+    pub fn recent_calls(&self, limit: Option<usize>) -> Vec<RequestRecord> {
+        let data = self.read_data();
+        data.recent_requests
+            .iter()
+            .rev()
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the maximum number of recent calls kept
+    pub fn recent_capacity(&self) -> usize {
+        self.max_recent_requests
+    }
+
     /// Get uptime duration
     pub fn uptime(&self) -> Duration {
         self.start_time.elapsed()
@@ -482,6 +526,30 @@ mod tests {
         assert_eq!(snapshot.recent_requests[2].tool, "tool_4");
     }
 
+    #[test]
+    fn test_recent_calls_newest_first() {
+        let metrics = DashboardMetrics::with_capacity(3);
+
+        for i in 0..5 {
+            metrics.record_call(
+                &format!("tool_{}", i),
+                ToolCategory::Issues,
+                None,
+                Duration::from_millis(10),
+                true,
+            );
+        }
+
+        let recent = metrics.recent_calls(None);
+        assert_eq!(recent.len(), 3);
+        assert_eq!(recent[0].tool, "tool_4");
+        assert_eq!(recent[2].tool, "tool_2");
+
+        let recent = metrics.recent_calls(Some(1));
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].tool, "tool_4");
+    }
+
     #[test]
     fn test_category_stats() {
         let metrics = DashboardMetrics::new();
@@ -6,5 +6,7 @@
 pub mod metrics;
 pub mod server;
 
-pub use metrics::{DashboardMetrics, ProjectStats, ToolStats};
-pub use server::{DEFAULT_DASHBOARD_PORT, DashboardConfig, run_dashboard};
+pub use metrics::{DashboardMetrics, ProjectStats, RequestRecord, ToolStats};
+pub use server::{
+    DEFAULT_DASHBOARD_PORT, DashboardConfig, DashboardState, dashboard_router, run_dashboard,
+};
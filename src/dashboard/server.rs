@@ -2,18 +2,20 @@
 //!
 //! Serves the dashboard web interface and API endpoints.
 
+use crate::access_control::{AccessDecision, AccessResolver, OperationType, ToolCategory};
 use crate::config::AppConfig;
-use crate::dashboard::metrics::{DashboardMetrics, MetricsSnapshot};
+use crate::dashboard::metrics::{DashboardMetrics, MetricsSnapshot, RequestRecord};
+use crate::tools::{ToolRegistry, definitions};
 use crate::update::UpdateManager;
 use crate::util::find_available_port;
 use axum::{
     Json, Router,
-    extract::State,
+    extract::{Path, Query, State},
     http::{StatusCode, header},
     response::{Html, IntoResponse, Response},
     routing::get,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -49,6 +51,17 @@ impl DashboardConfig {
             enabled: true,
         })
     }
+
+    /// Whether the dashboard's address overlaps `addr`
+    ///
+    /// The ports match and the hosts are the same or one side listens on
+    /// all interfaces.
+    pub fn conflicts_with(&self, addr: SocketAddr) -> bool {
+        self.bind.port() == addr.port()
+            && (self.bind.ip() == addr.ip()
+                || self.bind.ip().is_unspecified()
+                || addr.ip().is_unspecified())
+    }
 }
 
 /// Shared state for dashboard handlers
@@ -56,6 +69,26 @@ impl DashboardConfig {
 pub struct DashboardState {
     pub metrics: Arc<DashboardMetrics>,
     pub config: Arc<AppConfig>,
+    pub access: Arc<AccessResolver>,
+    pub registry: Arc<ToolRegistry>,
+}
+
+impl DashboardState {
+    /// Create dashboard state, registering the tools enabled by the configuration
+    pub fn new(
+        metrics: Arc<DashboardMetrics>,
+        config: Arc<AppConfig>,
+        access: Arc<AccessResolver>,
+    ) -> Self {
+        let mut registry = ToolRegistry::new();
+        definitions::register_enabled_tools(&mut registry, &config.tools);
+        Self {
+            metrics,
+            config,
+            access,
+            registry: Arc::new(registry),
+        }
+    }
 }
 
 /// Configuration info for the API
@@ -77,6 +110,83 @@ struct UpdateStatus {
     update_available: bool,
 }
 
+/// Query parameters for the recent calls API
+#[derive(Deserialize)]
+struct RecentQuery {
+    /// Maximum number of calls to return
+    limit: Option<usize>,
+}
+
+/// Recent tool calls for the API
+#[derive(Serialize)]
+struct RecentCalls {
+    capacity: usize,
+    calls: Vec<RequestRecord>,
+}
+
+/// Denied tool with the reason reported by the access resolver
+#[derive(Serialize)]
+struct DeniedTool {
+    tool: String,
+    reason: String,
+}
+
+/// Access summary for one category within a project
+#[derive(Serialize)]
+struct CategoryAccess {
+    category: String,
+    allowed: Vec<String>,
+    denied: Vec<DeniedTool>,
+}
+
+/// Access summary for a project
+#[derive(Serialize)]
+struct ProjectAccessSummary {
+    project: String,
+    allowed_count: usize,
+    denied_count: usize,
+    categories: Vec<CategoryAccess>,
+}
+
+/// Registered tool metadata for the API
+#[derive(Serialize)]
+struct ToolEntry {
+    name: &'static str,
+    description: &'static str,
+    category: ToolCategory,
+    operation: OperationType,
+}
+
+/// Number of registered tools in a category
+#[derive(Serialize)]
+struct CategoryCount {
+    category: ToolCategory,
+    count: usize,
+}
+
+/// Registered tools for the API
+#[derive(Serialize)]
+struct ToolsInfo {
+    total: usize,
+    categories: Vec<CategoryCount>,
+    tools: Vec<ToolEntry>,
+}
+
+/// Build the dashboard router
+pub fn dashboard_router(state: DashboardState) -> Router {
+    Router::new()
+        .route("/", get(dashboard_html))
+        .route("/api/metrics", get(api_metrics))
+        .route("/api/recent", get(api_recent))
+        .route("/api/tools", get(api_tools))
+        .route("/api/projects/{path}/access", get(api_project_access))
+        .route("/api/config", get(api_config))
+        .route("/api/update", get(api_update))
+        .route("/assets/style.css", get(serve_css))
+        .route("/assets/app.js", get(serve_js))
+        .with_state(state)
+}
+
 /// Run the dashboard server
 ///
 /// Port discovery is used to find an available port if the configured port is taken.
@@ -84,7 +194,7 @@ pub async fn run_dashboard(
     config: DashboardConfig,
     metrics: Arc<DashboardMetrics>,
     app_config: Arc<AppConfig>,
-    tool_count: usize,
+    access: Arc<AccessResolver>,
 ) -> anyhow::Result<()> {
     if !config.enabled {
         info!("Dashboard is disabled");
@@ -98,20 +208,8 @@ pub async fn run_dashboard(
 
     let bind_addr = SocketAddr::new(config.bind.ip(), actual_port);
 
-    let state = DashboardState {
-        metrics,
-        config: app_config,
-    };
-
-    // Build router
-    let app = Router::new()
-        .route("/", get(dashboard_html))
-        .route("/api/metrics", get(api_metrics))
-        .route("/api/config", get(move |s| api_config(s, tool_count)))
-        .route("/api/update", get(api_update))
-        .route("/assets/style.css", get(serve_css))
-        .route("/assets/app.js", get(serve_js))
-        .with_state(state);
+    let state = DashboardState::new(metrics, app_config, access);
+    let app = dashboard_router(state);
 
     let listener = TcpListener::bind(bind_addr).await?;
     info!("Dashboard server running at http://{}", bind_addr);
@@ -131,13 +229,107 @@ async fn api_metrics(State(state): State<DashboardState>) -> Json<MetricsSnapsho
     Json(state.metrics.snapshot())
 }
 
+/// API endpoint for recent tool calls (newest first)
+async fn api_recent(
+    State(state): State<DashboardState>,
+    Query(query): Query<RecentQuery>,
+) -> Json<RecentCalls> {
+    Json(RecentCalls {
+        capacity: state.metrics.recent_capacity(),
+        calls: state.metrics.recent_calls(query.limit),
+    })
+}
+
+/// API endpoint listing registered tools and per-category counts
+async fn api_tools(State(state): State<DashboardState>) -> Json<ToolsInfo> {
+    let mut tools: Vec<ToolEntry> = state
+        .registry
+        .tools()
+        .map(|tool| ToolEntry {
+            name: tool.name,
+            description: tool.description,
+            category: tool.category,
+            operation: tool.operation,
+        })
+        .collect();
+    tools.sort_unstable_by_key(|tool| tool.name);
+
+    let categories = ToolCategory::all()
+        .iter()
+        .map(|&category| CategoryCount {
+            category,
+            count: state.registry.tools_in_category(category).len(),
+        })
+        .filter(|entry| entry.count > 0)
+        .collect();
+
+    Json(ToolsInfo {
+        total: tools.len(),
+        categories,
+        tools,
+    })
+}
+
+/// API endpoint summarizing which tools are allowed for a project
+///
+/// The project path must be URL-encoded (e.g., `group%2Fproject`).
+async fn api_project_access(
+    State(state): State<DashboardState>,
+    Path(project): Path<String>,
+) -> Json<ProjectAccessSummary> {
+    let mut allowed_count = 0;
+    let mut denied_count = 0;
+
+    let categories = ToolCategory::all()
+        .iter()
+        .filter_map(|&category| {
+            let mut tools = state.registry.tools_in_category(category);
+            if tools.is_empty() {
+                return None;
+            }
+            tools.sort_unstable_by_key(|t| t.name);
+
+            let mut allowed = Vec::new();
+            let mut denied = Vec::new();
+            for tool in tools {
+                match state
+                    .access
+                    .check(tool.name, category, tool.operation, Some(&project))
+                {
+                    AccessDecision::Allowed => allowed.push(tool.name.to_string()),
+                    AccessDecision::Denied(reason) => denied.push(DeniedTool {
+                        tool: tool.name.to_string(),
+                        reason: reason.into_owned(),
+                    }),
+                }
+            }
+            allowed_count += allowed.len();
+            denied_count += denied.len();
+
+            Some(CategoryAccess {
+                category: category.to_string(),
+                allowed,
+                denied,
+            })
+        })
+        .collect();
+
+    Json(ProjectAccessSummary {
+        project,
+        allowed_count,
+        denied_count,
+        categories,
+    })
+}
+
 /// API endpoint for configuration
-async fn api_config(State(state): State<DashboardState>, tool_count: usize) -> Json<ConfigInfo> {
+async fn api_config(State(state): State<DashboardState>) -> Json<ConfigInfo> {
+    let tool_count = state.registry.len();
     let config = &state.config;
     Json(ConfigInfo {
         server_name: config.server.name.clone(),
         server_version: config.server.version.clone(),
-        gitlab_url: config.gitlab.url.clone(),
+        gitlab_url: config.gitlab.base_url().to_string(),
         transport_mode: format!("{:?}", config.server.transport),
         access_level: format!("{:?}", config.access_control.all),
         tool_count,
@@ -0,0 +1,225 @@
+//! Token scope diagnostics
+//!
+//! Backs the `tanuki-mcp doctor` subcommand: for every category the access
+//! control configuration allows, performs one cheap read against GitLab and
+//! reports whether the token can actually use it.
+
+use crate::access_control::{AccessResolver, ToolCategory};
+use crate::error::GitLabError;
+use crate::gitlab::GitLabClient;
+use crate::tools::ToolRegistry;
+use std::fmt;
+
+/// Outcome of probing a single category
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// The read succeeded
+    Ok,
+    /// GitLab rejected the token (HTTP 401 or 403)
+    Denied(String),
+    /// Any other failure (network, 404, server error, ...)
+    Error(String),
+}
+
+impl CheckStatus {
+    /// Classify the result of a probe request
+    pub fn from_result<T>(result: &Result<T, GitLabError>) -> Self {
+        match result {
+            Ok(_) => CheckStatus::Ok,
+            Err(e @ (GitLabError::Unauthorized | GitLabError::Forbidden { .. })) => {
+                CheckStatus::Denied(e.to_string())
+            }
+            Err(e) => CheckStatus::Error(e.to_string()),
+        }
+    }
+
+    /// Short label used in the report table
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Denied(_) => "DENIED",
+            CheckStatus::Error(_) => "ERROR",
+        }
+    }
+
+    /// Failure detail, if any
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            CheckStatus::Ok => None,
+            CheckStatus::Denied(detail) | CheckStatus::Error(detail) => Some(detail),
+        }
+    }
+}
+
+/// Result of probing one category
+#[derive(Debug, Clone)]
+pub struct CategoryCheck {
+    pub category: ToolCategory,
+    pub endpoint: String,
+    pub status: CheckStatus,
+}
+
+/// Aggregated results of a doctor run
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<CategoryCheck>,
+}
+
+impl DoctorReport {
+    /// Number of categories whose probe succeeded
+    pub fn ok_count(&self) -> usize {
+        self.count(|status| matches!(status, CheckStatus::Ok))
+    }
+
+    /// Number of categories GitLab denied
+    pub fn denied_count(&self) -> usize {
+        self.count(|status| matches!(status, CheckStatus::Denied(_)))
+    }
+
+    /// Number of categories that failed for other reasons
+    pub fn error_count(&self) -> usize {
+        self.count(|status| matches!(status, CheckStatus::Error(_)))
+    }
+
+    /// Whether any allowed category was denied by GitLab
+    pub fn has_denied(&self) -> bool {
+        self.denied_count() > 0
+    }
+
+    fn count(&self, predicate: impl Fn(&CheckStatus) -> bool) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| predicate(&check.status))
+            .count()
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let category_width = self
+            .checks
+            .iter()
+            .map(|check| check.category.as_str().len())
+            .chain(["CATEGORY".len()])
+            .max()
+            .unwrap_or_default();
+        let endpoint_width = self
+            .checks
+            .iter()
+            .map(|check| check.endpoint.len())
+            .chain(["ENDPOINT".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:<category_width$}  {:<endpoint_width$}  STATUS",
+            "CATEGORY", "ENDPOINT"
+        )?;
+        for check in &self.checks {
+            write!(
+                f,
+                "{:<category_width$}  {:<endpoint_width$}  {}",
+                check.category.as_str(),
+                check.endpoint,
+                check.status.label()
+            )?;
+            if let Some(detail) = check.status.detail() {
+                write!(f, " ({})", detail)?;
+            }
+            writeln!(f)?;
+        }
+
+        write!(
+            f,
+            "\n{} ok, {} denied, {} error",
+            self.ok_count(),
+            self.denied_count(),
+            self.error_count()
+        )
+    }
+}
+
+/// Categories with at least one registered tool that the configuration allows
+///
+/// Mirrors the tool listing: a category counts as allowed unless every one of
+/// its tools is globally denied.
+pub fn allowed_categories(registry: &ToolRegistry, access: &AccessResolver) -> Vec<ToolCategory> {
+    ToolCategory::all()
+        .iter()
+        .copied()
+        .filter(|&category| {
+            registry
+                .tools_in_category(category)
+                .iter()
+                .any(|tool| !access.is_globally_denied(tool.name, category, tool.operation))
+        })
+        .collect()
+}
+
+/// Cheap read endpoint used to probe a category
+///
+/// Project-scoped categories probe the given project when one is available and
+/// otherwise fall back to listing the token's projects.
+pub fn probe_endpoint(category: ToolCategory, project: Option<&str>) -> String {
+    let project = project.map(GitLabClient::encode_project);
+    let scoped = |path: &str| match &project {
+        Some(id) => format!("/projects/{}/{}", id, path),
+        None => "/projects?membership=true&per_page=1".to_string(),
+    };
+
+    match category {
+        ToolCategory::Issues | ToolCategory::IssueLinks | ToolCategory::IssueNotes => {
+            match &project {
+                Some(id) => format!("/projects/{}/issues?per_page=1", id),
+                None => "/issues?per_page=1".to_string(),
+            }
+        }
+        ToolCategory::MergeRequests | ToolCategory::MrDiscussions | ToolCategory::MrDrafts => {
+            match &project {
+                Some(id) => format!("/projects/{}/merge_requests?per_page=1", id),
+                None => "/merge_requests?per_page=1".to_string(),
+            }
+        }
+        ToolCategory::Repository => scoped("repository/tree?per_page=1"),
+        ToolCategory::Branches => scoped("repository/branches?per_page=1"),
+        ToolCategory::Commits => scoped("repository/commits?per_page=1"),
+        ToolCategory::Tags => scoped("repository/tags?per_page=1"),
+        ToolCategory::Labels => scoped("labels?per_page=1"),
+        ToolCategory::Wiki => scoped("wikis"),
+        ToolCategory::Pipelines => scoped("pipelines?per_page=1"),
+        ToolCategory::Milestones => scoped("milestones?per_page=1"),
+        ToolCategory::Releases => scoped("releases?per_page=1"),
+        ToolCategory::Environments => scoped("environments?per_page=1"),
+        ToolCategory::Runners => scoped("runners?per_page=1"),
+        ToolCategory::DeployKeys => scoped("deploy_keys?per_page=1"),
+        ToolCategory::AccessTokens => scoped("access_tokens?per_page=1"),
+        ToolCategory::Projects => "/projects?membership=true&per_page=1".to_string(),
+        ToolCategory::Namespaces => "/namespaces?per_page=1".to_string(),
+        ToolCategory::Users => "/user".to_string(),
+        ToolCategory::Groups => "/groups?per_page=1".to_string(),
+        ToolCategory::Search => "/search?scope=projects&search=tanuki&per_page=1".to_string(),
+        ToolCategory::Instance | ToolCategory::Raw => "/version".to_string(),
+    }
+}
+
+/// Probe each category once and collect the results
+pub async fn run_checks(
+    gitlab: &GitLabClient,
+    categories: &[ToolCategory],
+    project: Option<&str>,
+) -> DoctorReport {
+    let mut checks = Vec::with_capacity(categories.len());
+
+    for &category in categories {
+        let endpoint = probe_endpoint(category, project);
+        let result = gitlab.get_json(&endpoint).await;
+        checks.push(CategoryCheck {
+            category,
+            status: CheckStatus::from_result(&result),
+            endpoint,
+        });
+    }
+
+    DoctorReport { checks }
+}
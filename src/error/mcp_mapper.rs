@@ -45,6 +45,16 @@ pub fn map_tool_error(error: &ToolError) -> McpError {
             })),
         },
 
+        ToolError::Disabled(name) => McpError {
+            code: ErrorCode::METHOD_NOT_FOUND,
+            message: Cow::Owned(format!("Tool '{}' is disabled", name)),
+            data: Some(json!({
+                "tool": name,
+                "error_type": "ToolDisabled",
+                "hint": "The tool is turned off by the server's [tools] configuration"
+            })),
+        },
+
         ToolError::InvalidArguments(msg) => McpError {
             code: ErrorCode::INVALID_PARAMS,
             message: Cow::Owned(msg.clone()),
@@ -78,6 +88,27 @@ pub fn map_tool_error(error: &ToolError) -> McpError {
             })),
         },
 
+        ToolError::RateLimited { category, limit } => McpError {
+            code: ErrorCode::INTERNAL_ERROR,
+            message: Cow::Owned(error.to_string()),
+            data: Some(json!({
+                "error_type": "rate_limited",
+                "category": category,
+                "limit": limit,
+                "hint": "Limited by the server's [ratelimit] configuration, not by GitLab"
+            })),
+        },
+
+        ToolError::UnsupportedEdition { feature } => McpError {
+            code: ErrorCode::INTERNAL_ERROR,
+            message: Cow::Owned(error.to_string()),
+            data: Some(json!({
+                "error_type": "unsupported_edition",
+                "feature": feature,
+                "hint": "Use get_gitlab_metadata to check the instance's version and edition"
+            })),
+        },
+
         ToolError::GitLab(gitlab_err) => map_gitlab_error(gitlab_err),
 
         ToolError::AccessDenied(access_err) => map_access_denied_error(access_err),
@@ -135,6 +166,15 @@ pub fn map_gitlab_error(error: &GitLabError) -> McpError {
             })),
         },
 
+        GitLabError::TooLarge { max_bytes } => McpError {
+            code: ErrorCode::INTERNAL_ERROR,
+            message: Cow::Owned(error.to_string()),
+            data: Some(json!({
+                "error_type": "TooLarge",
+                "max_bytes": max_bytes
+            })),
+        },
+
         GitLabError::Api { status, message } => McpError {
             code: ErrorCode::INTERNAL_ERROR,
             message: Cow::Owned(format!("GitLab API error (HTTP {}): {}", status, message)),
@@ -144,6 +184,16 @@ pub fn map_gitlab_error(error: &GitLabError) -> McpError {
             })),
         },
 
+        GitLabError::Validation { fields } => McpError {
+            code: ErrorCode::INTERNAL_ERROR,
+            message: Cow::Owned(error.to_string()),
+            data: Some(json!({
+                "error_type": "Validation",
+                "validation": fields,
+                "hint": "Fix the listed fields and try again"
+            })),
+        },
+
         GitLabError::Request(e) => McpError {
             code: ErrorCode::INTERNAL_ERROR,
             message: Cow::Owned(format!("HTTP request failed: {}", e)),
@@ -249,6 +299,20 @@ mod tests {
         assert!(mcp_error.message.contains("project"));
     }
 
+    #[test]
+    fn test_map_rate_limited() {
+        let error = ToolError::RateLimited {
+            category: "pipelines".into(),
+            limit: "10/s".into(),
+        };
+        let mcp_error = map_tool_error(&error);
+
+        assert!(mcp_error.message.contains("pipelines"));
+        let data = mcp_error.data.unwrap();
+        assert_eq!(data["error_type"], "rate_limited");
+        assert_eq!(data["limit"], "10/s");
+    }
+
     #[test]
     fn test_map_gitlab_unauthorized() {
         let error = GitLabError::Unauthorized;
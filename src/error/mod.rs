@@ -6,6 +6,8 @@
 
 pub mod mcp_mapper;
 
+use serde_json::Value;
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Top-level application error
@@ -75,11 +77,26 @@ pub enum GitLabError {
 
     #[error("Request timeout after {timeout_secs} seconds")]
     Timeout { timeout_secs: u64 },
+
+    #[error("Response exceeds the size limit of {max_bytes} bytes")]
+    TooLarge { max_bytes: usize },
+
+    #[error("GitLab rejected the request: {}", format_field_errors(fields))]
+    Validation {
+        fields: BTreeMap<String, Vec<String>>,
+    },
 }
 
 impl GitLabError {
     /// Create an appropriate error from an HTTP status code and response body
     pub fn from_response(status: u16, body: &str) -> Self {
+        // Validation failures list the rejected fields
+        if status == 422
+            && let Some(fields) = parse_field_errors(body)
+        {
+            return GitLabError::Validation { fields };
+        }
+
         match status {
             401 => GitLabError::Unauthorized,
             403 => GitLabError::Forbidden {
@@ -102,6 +119,58 @@ impl GitLabError {
             },
         }
     }
+
+    /// Stable machine-readable code for logs
+    pub fn code(&self) -> &'static str {
+        match self {
+            GitLabError::Request(_) => "gitlab_request",
+            GitLabError::Api { .. } => "gitlab_api",
+            GitLabError::RateLimited { .. } => "gitlab_rate_limited",
+            GitLabError::NotFound { .. } => "gitlab_not_found",
+            GitLabError::Unauthorized => "gitlab_unauthorized",
+            GitLabError::Forbidden { .. } => "gitlab_forbidden",
+            GitLabError::InvalidResponse(_) => "gitlab_invalid_response",
+            GitLabError::Timeout { .. } => "gitlab_timeout",
+            GitLabError::TooLarge { .. } => "gitlab_too_large",
+            GitLabError::Validation { .. } => "gitlab_validation",
+        }
+    }
+}
+
+/// Field errors from a `{"message": {"field": ["error", ...]}}` body
+fn parse_field_errors(body: &str) -> Option<BTreeMap<String, Vec<String>>> {
+    let body: Value = serde_json::from_str(body).ok()?;
+    let fields = body.get("message")?.as_object()?;
+    if fields.is_empty() {
+        return None;
+    }
+
+    let messages = |value: &Value| match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect(),
+        Value::String(s) => vec![s.clone()],
+        other => vec![other.to_string()],
+    };
+    Some(
+        fields
+            .iter()
+            .map(|(field, value)| (field.clone(), messages(value)))
+            .collect(),
+    )
+}
+
+/// Render field errors as `field: message, message; field: message`
+fn format_field_errors(fields: &BTreeMap<String, Vec<String>>) -> String {
+    fields
+        .iter()
+        .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Access control errors
@@ -162,6 +231,17 @@ impl AccessDeniedError {
         }
     }
 
+    /// Create an error indicating the project is outside `gitlab.allowed_projects`
+    pub fn project_not_allowed(tool: impl Into<String>, project: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            reason: format!(
+                "forbidden: project '{}' is outside the server's allowed projects",
+                project.into()
+            ),
+        }
+    }
+
     /// Create an error indicating the tool is completely unavailable
     pub fn globally_unavailable(tool: impl Into<String>) -> Self {
         Self {
@@ -192,8 +272,59 @@ pub enum ToolError {
     #[error("Tool not found: {0}")]
     NotFound(String),
 
+    #[error("Tool disabled: {0} is turned off in the [tools] configuration")]
+    Disabled(String),
+
     #[error("Access denied: {0}")]
     AccessDenied(#[from] AccessDeniedError),
+
+    #[error("Rate limited: {category} tools are limited to {limit}, try again shortly")]
+    RateLimited { category: String, limit: String },
+
+    #[error(
+        "Unsupported on this GitLab edition: the {feature} API is only available in GitLab EE, and this instance runs CE"
+    )]
+    UnsupportedEdition { feature: String },
+}
+
+impl ToolError {
+    /// Stable machine-readable code for logs
+    pub fn code(&self) -> &'static str {
+        match self {
+            ToolError::InvalidArguments(_) => "invalid_arguments",
+            ToolError::MissingArgument(_) => "missing_argument",
+            ToolError::ExecutionFailed(_) => "execution_failed",
+            ToolError::GitLab(e) => e.code(),
+            ToolError::Serialization(_) => "serialization",
+            ToolError::NotFound(_) => "tool_not_found",
+            ToolError::Disabled(_) => "tool_disabled",
+            ToolError::AccessDenied(_) => "access_denied",
+            ToolError::RateLimited { .. } => "rate_limited",
+            ToolError::UnsupportedEdition { .. } => "unsupported_edition",
+        }
+    }
+
+    /// Whether an agent can reasonably react to the failure and carry on
+    ///
+    /// Failures of the GitLab call itself (missing resources, API errors,
+    /// timeouts, rate limits) are recoverable. Invalid arguments, unknown or
+    /// disabled tools, access denials, including GitLab rejecting the token,
+    /// and features missing from the GitLab edition are not.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            ToolError::GitLab(GitLabError::Unauthorized | GitLabError::Forbidden { .. }) => false,
+            ToolError::GitLab(_)
+            | ToolError::ExecutionFailed(_)
+            | ToolError::RateLimited { .. } => true,
+            ToolError::InvalidArguments(_)
+            | ToolError::MissingArgument(_)
+            | ToolError::Serialization(_)
+            | ToolError::NotFound(_)
+            | ToolError::Disabled(_)
+            | ToolError::AccessDenied(_)
+            | ToolError::UnsupportedEdition { .. } => false,
+        }
+    }
 }
 
 /// Transport layer errors
@@ -270,6 +401,27 @@ mod tests {
         assert!(matches!(api_err, GitLabError::Api { status: 500, .. }));
     }
 
+    #[test]
+    fn test_gitlab_error_validation_fields() {
+        let err = GitLabError::from_response(
+            422,
+            r#"{"message": {"title": ["can't be blank", "is too short"], "base": "invalid"}}"#,
+        );
+        let GitLabError::Validation { fields } = &err else {
+            panic!("expected a validation error, got {:?}", err);
+        };
+        assert_eq!(fields["title"], vec!["can't be blank", "is too short"]);
+        assert_eq!(fields["base"], vec!["invalid"]);
+        assert_eq!(
+            err.to_string(),
+            "GitLab rejected the request: base: invalid; title: can't be blank, is too short"
+        );
+
+        // A plain message stays an API error
+        let err = GitLabError::from_response(422, r#"{"message": "Branch cannot be merged"}"#);
+        assert!(matches!(err, GitLabError::Api { status: 422, .. }));
+    }
+
     #[test]
     fn test_access_denied_constructors() {
         let err = AccessDeniedError::read_only("create_issue");
@@ -0,0 +1,210 @@
+//! Short-lived cache of GitLab project metadata
+//!
+//! Project details, labels and milestones change rarely but are read by many
+//! tool calls. When `gitlab.cache_ttl_secs` is set, GET responses for exactly
+//! those endpoints are kept for that long. Any write the client sends to a
+//! project drops that project's entries, so the server's own changes are
+//! visible immediately. A project can be addressed by ID or by path; the
+//! cached project details tie both forms together so a write through either
+//! drops the entries of both.
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A cached response body
+#[derive(Debug)]
+struct CachedResponse {
+    stored_at: Instant,
+    value: Value,
+}
+
+/// TTL cache of project metadata responses, keyed by endpoint
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    /// Create a cache keeping responses for `ttl` (zero disables caching)
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether responses are cached at all
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Whether an endpoint is project metadata, labels, or milestones
+    pub fn is_cacheable(endpoint: &str) -> bool {
+        let Some(rest) = endpoint.strip_prefix("/projects/") else {
+            return false;
+        };
+        let rest = &rest[project_segment(rest).len()..];
+        let path = rest.split('?').next().unwrap_or_default();
+
+        matches!(path, "" | "/labels" | "/milestones")
+    }
+
+    /// Get a fresh cached response
+    pub fn get(&self, endpoint: &str) -> Option<Value> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(endpoint) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => Some(entry.value.clone()),
+            Some(_) => {
+                entries.remove(endpoint);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a response
+    pub fn insert(&self, endpoint: &str, value: Value) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                endpoint.to_string(),
+                CachedResponse {
+                    stored_at: Instant::now(),
+                    value,
+                },
+            );
+    }
+
+    /// Drop every entry of the project an endpoint belongs to
+    pub fn invalidate_endpoint(&self, endpoint: &str) {
+        if let Some(rest) = endpoint.strip_prefix("/projects/") {
+            self.invalidate_project(project_segment(rest));
+        }
+    }
+
+    /// Drop every entry of a project, given as its URL-encoded path or ID
+    ///
+    /// Entries stored under the project's other form are dropped too when the
+    /// project details are cached under either form.
+    pub fn invalidate_project(&self, project: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut keys = HashSet::from([project.to_string()]);
+        for (endpoint, entry) in entries.iter() {
+            let Some(rest) = endpoint.strip_prefix("/projects/") else {
+                continue;
+            };
+            let segment = project_segment(rest);
+            let is_details = rest.split('?').next() == Some(segment);
+            let aliases = Self::project_keys(&entry.value);
+            if is_details && (segment == project || aliases.iter().any(|key| key == project)) {
+                keys.insert(segment.to_string());
+                keys.extend(aliases);
+            }
+        }
+
+        entries.retain(|endpoint, _| {
+            endpoint
+                .strip_prefix("/projects/")
+                .is_none_or(|rest| !keys.contains(project_segment(rest)))
+        });
+    }
+
+    /// Cache keys of a project object: its ID and its encoded path
+    pub fn project_keys(project: &Value) -> Vec<String> {
+        let id = project
+            .get("id")
+            .and_then(Value::as_u64)
+            .map(|id| id.to_string());
+        let path = project
+            .get("path_with_namespace")
+            .and_then(Value::as_str)
+            .map(|path| urlencoding::encode(path).into_owned());
+
+        id.into_iter().chain(path).collect()
+    }
+
+    /// Number of cached responses, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The project ID or encoded path at the start of `/projects/`-relative path
+fn project_segment(rest: &str) -> &str {
+    rest.split(['/', '?']).next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cacheable_endpoints() {
+        assert!(ResponseCache::is_cacheable("/projects/group%2Fapp"));
+        assert!(ResponseCache::is_cacheable("/projects/42?statistics=true"));
+        assert!(ResponseCache::is_cacheable("/projects/group%2Fapp/labels"));
+        assert!(ResponseCache::is_cacheable(
+            "/projects/group%2Fapp/milestones?state=active"
+        ));
+        assert!(!ResponseCache::is_cacheable("/projects/group%2Fapp/issues"));
+        assert!(!ResponseCache::is_cacheable(
+            "/projects/group%2Fapp/labels/1"
+        ));
+        assert!(!ResponseCache::is_cacheable("/projects?membership=true"));
+        assert!(!ResponseCache::is_cacheable("/groups/group"));
+    }
+
+    #[test]
+    fn test_invalidate_endpoint_drops_only_that_project() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("/projects/a%2Fb", json!({"id": 1}));
+        cache.insert("/projects/a%2Fb/labels", json!([]));
+        cache.insert("/projects/c%2Fd", json!({"id": 2}));
+
+        cache.invalidate_endpoint("/projects/a%2Fb/labels/3");
+
+        assert!(cache.get("/projects/a%2Fb").is_none());
+        assert!(cache.get("/projects/a%2Fb/labels").is_none());
+        assert_eq!(cache.get("/projects/c%2Fd"), Some(json!({"id": 2})));
+    }
+
+    #[test]
+    fn test_invalidate_endpoint_drops_both_project_forms() {
+        let project = json!({"id": 42, "path_with_namespace": "group/app"});
+
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("/projects/group%2Fapp", project.clone());
+        cache.insert("/projects/group%2Fapp/labels", json!([]));
+        cache.insert("/projects/42/milestones", json!([]));
+        cache.invalidate_endpoint("/projects/42/labels");
+        assert!(cache.is_empty());
+
+        cache.insert("/projects/42?statistics=true", project);
+        cache.insert("/projects/group%2Fapp/labels", json!([]));
+        cache.invalidate_endpoint("/projects/group%2Fapp/issues");
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_cache_stores_nothing() {
+        let cache = ResponseCache::new(Duration::ZERO);
+        cache.insert("/projects/a%2Fb", json!({"id": 1}));
+
+        assert!(!cache.is_enabled());
+        assert!(cache.is_empty());
+    }
+}
@@ -3,37 +3,142 @@
 //! Provides a typed HTTP client for interacting with the GitLab REST API.
 
 use crate::auth::BoxedAuthProvider;
-use crate::config::GitLabConfig;
+use crate::config::{GitLabConfig, TransportMode};
 use crate::error::{GitLabError, GitLabResult};
-use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use crate::gitlab::cache::ResponseCache;
+use crate::gitlab::health::GitLabHealth;
+use crate::gitlab::types::{ByteRange, InstanceMetadata, Pagination};
+use crate::util::MAX_PER_PAGE;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, redirect};
 use serde::{Serialize, de::DeserializeOwned};
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
-use tracing::{debug, instrument, warn};
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
+use tracing::{debug, info, instrument, warn};
+
+/// Header carrying the request correlation ID on outgoing GitLab calls
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Maximum number of redirects followed for one request
+const MAX_REDIRECTS: usize = 10;
+
+/// How often a degraded GitLab is probed for recovery
+pub const HEALTH_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Timeout for a single health probe
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a failed instance metadata fetch is reported before retrying
+const METADATA_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+tokio::task_local! {
+    /// Correlation ID of the tool call being executed on the current task
+    static REQUEST_ID: String;
+
+    /// Pagination headers of the last successful response on the current task
+    static PAGINATION: RefCell<Option<Pagination>>;
+}
+
+/// Run a future with a correlation ID attached to every GitLab call it makes
+///
+/// Requests issued from tasks spawned by the future do not inherit the ID.
+pub async fn with_request_id<F: Future>(request_id: impl Into<String>, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id.into(), future).await
+}
+
+/// Run a future and return the pagination metadata of the last GitLab
+/// response it received
+pub async fn with_pagination<F: Future>(future: F) -> (F::Output, Option<Pagination>) {
+    PAGINATION
+        .scope(RefCell::new(None), async move {
+            let output = future.await;
+            (output, PAGINATION.with(RefCell::take))
+        })
+        .await
+}
+
+/// Most pages [`GitLabClient::get_all_pages`] fetches before stopping
+pub const MAX_ALL_PAGES: u32 = 50;
+
+/// Items collected by [`GitLabClient::get_all_pages`]
+#[derive(Debug, Clone, Default)]
+pub struct AllPages {
+    /// Items of every page fetched, in order
+    pub items: Vec<serde_json::Value>,
+    /// First page not fetched because [`MAX_ALL_PAGES`] was reached
+    pub next_page: Option<u32>,
+}
+
+impl AllPages {
+    /// Whether pages were left unfetched
+    pub fn is_truncated(&self) -> bool {
+        self.next_page.is_some()
+    }
+}
+
+/// What is known about the instance's version and edition
+#[derive(Debug, Default)]
+enum MetadataState {
+    #[default]
+    Unknown,
+    Known(InstanceMetadata),
+    Failed {
+        at: Instant,
+        message: String,
+    },
+}
 
 /// GitLab API client
 pub struct GitLabClient {
     http: Client,
     base_url: String,
     auth: Arc<RwLock<BoxedAuthProvider>>,
+    timeout: Duration,
+    connect_timeout: Duration,
     max_retries: u32,
+    cache: Arc<ResponseCache>,
+    read_cache: bool,
+    caller: Option<String>,
+    concurrency: Option<Arc<Semaphore>>,
+    metadata: Arc<Mutex<MetadataState>>,
+    health: Arc<GitLabHealth>,
 }
 
 impl GitLabClient {
     /// Create a new GitLab client from configuration
+    ///
+    /// The URL, timeouts, and retries come from the `gitlab.instances` entry
+    /// selected by `gitlab.instance` when there is one.
     pub fn new(config: &GitLabConfig, auth: BoxedAuthProvider) -> GitLabResult<Self> {
+        Self::build(config, auth, config.user_agent_header(None))
+    }
+
+    /// Create a new GitLab client whose User-Agent names the transport mode
+    pub fn for_transport(
+        config: &GitLabConfig,
+        auth: BoxedAuthProvider,
+        transport: TransportMode,
+    ) -> GitLabResult<Self> {
+        Self::build(config, auth, config.user_agent_header(Some(transport)))
+    }
+
+    fn build(
+        config: &GitLabConfig,
+        auth: BoxedAuthProvider,
+        user_agent: String,
+    ) -> GitLabResult<Self> {
+        let timeout = Duration::from_secs(config.effective_timeout_secs());
+        let connect_timeout = Duration::from_secs(config.effective_connect_timeout_secs());
         let http = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
+            .timeout(timeout)
+            .connect_timeout(connect_timeout)
             .pool_max_idle_per_host(10)
             .pool_idle_timeout(Duration::from_secs(90))
             .danger_accept_invalid_certs(!config.verify_ssl)
-            .user_agent(
-                config
-                    .user_agent
-                    .clone()
-                    .unwrap_or_else(|| format!("tanuki-mcp/{}", env!("CARGO_PKG_VERSION"))),
-            )
+            .redirect(redirect_policy(config.follow_redirects))
+            .user_agent(user_agent)
             .build()
             .map_err(GitLabError::Request)?;
 
@@ -41,10 +146,205 @@ impl GitLabClient {
             http,
             base_url: config.api_url(),
             auth: Arc::new(RwLock::new(auth)),
-            max_retries: config.max_retries,
+            timeout,
+            connect_timeout,
+            max_retries: config.effective_max_retries(),
+            cache: Arc::new(ResponseCache::new(Duration::from_secs(
+                config.cache_ttl_secs,
+            ))),
+            read_cache: true,
+            caller: None,
+            concurrency: config
+                .max_concurrent_requests
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            metadata: Default::default(),
+            health: Default::default(),
         })
     }
 
+    /// A client that sends requests with other credentials
+    ///
+    /// Shares the connection pool, concurrency limit, and response cache with
+    /// `self`. Reads, including project path lookups, bypass the cache, so
+    /// nothing read with one token is served to a caller using another,
+    /// while writes still invalidate the shared cache. `caller` is an opaque
+    /// identity of the credentials, such as a hash of the token, used to keep
+    /// per-caller state apart.
+    pub fn with_auth(&self, auth: BoxedAuthProvider, caller: impl Into<String>) -> Self {
+        Self {
+            http: self.http.clone(),
+            base_url: self.base_url.clone(),
+            auth: Arc::new(RwLock::new(auth)),
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            max_retries: self.max_retries,
+            cache: self.cache.clone(),
+            read_cache: false,
+            caller: Some(caller.into()),
+            concurrency: self.concurrency.clone(),
+            metadata: self.metadata.clone(),
+            health: self.health.clone(),
+        }
+    }
+
+    /// Request timeout used by this client
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Connect timeout used by this client
+    pub fn connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// Maximum retries for failed requests
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Availability of the GitLab instance, updated by every request
+    ///
+    /// Shared by clients created with [`with_auth`](Self::with_auth).
+    pub fn health(&self) -> &Arc<GitLabHealth> {
+        &self.health
+    }
+
+    /// Check whether GitLab answers with one uncached `/version` request
+    ///
+    /// Sent without retries and with a short timeout; the outcome updates
+    /// [`health`](Self::health) like any other request.
+    pub async fn probe_health(&self) -> GitLabResult<()> {
+        let request = self
+            .http
+            .get(self.url("/version"))
+            .timeout(HEALTH_PROBE_TIMEOUT.min(self.timeout));
+        let request = self.authenticate(request).await?;
+
+        let result = match request.send().await {
+            Ok(response) => self.handle_response(response).await.map(drop),
+            Err(e) => Err(GitLabError::Request(e)),
+        };
+        self.health.record(&result);
+        result
+    }
+
+    /// Probe GitLab every `interval` while it is degraded
+    ///
+    /// Otherwise a degraded instance only recovers when a tool call happens
+    /// to succeed, leaving `/readyz` failing on an idle server. The task ends
+    /// once the client is dropped.
+    pub fn spawn_health_probe(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let client = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(client) = client.upgrade() else {
+                    break;
+                };
+                if client.health.is_degraded()
+                    && let Err(e) = client.probe_health().await
+                {
+                    debug!(error = %e, "GitLab health probe failed");
+                }
+            }
+        })
+    }
+
+    /// Identity of the credentials of a client created with
+    /// [`with_auth`](Self::with_auth) (`None` for the configured token)
+    pub fn caller(&self) -> Option<&str> {
+        self.caller.as_deref()
+    }
+
+    /// Cache of project metadata responses
+    pub fn cache(&self) -> &ResponseCache {
+        &self.cache
+    }
+
+    /// Full path of a project given by numeric ID
+    ///
+    /// The lookup goes through the response cache, so a path is reused for
+    /// `gitlab.cache_ttl_secs` and dropped early on writes and webhooks, and a
+    /// renamed or transferred project resolves to its new path.
+    pub async fn project_path(&self, id: &str) -> GitLabResult<String> {
+        let project: serde_json::Value = self.get(&format!("/projects/{}", id)).await?;
+        let path = project
+            .get("path_with_namespace")
+            .and_then(|p| p.as_str())
+            .ok_or_else(|| {
+                GitLabError::InvalidResponse(format!("Project {} has no path_with_namespace", id))
+            })?
+            .to_string();
+        Ok(path)
+    }
+
+    /// Version and edition of the GitLab instance
+    ///
+    /// Fetched from `/metadata` once and remembered for the lifetime of the
+    /// client. Instances older than GitLab 15.2 lack that endpoint, so their
+    /// edition is derived from the `-ee` suffix of `/version`. A failed fetch
+    /// is reported again without contacting GitLab for
+    /// [`METADATA_RETRY_AFTER`].
+    pub async fn metadata(&self) -> GitLabResult<InstanceMetadata> {
+        match &*self.metadata.lock().unwrap_or_else(|e| e.into_inner()) {
+            MetadataState::Known(metadata) => return Ok(metadata.clone()),
+            MetadataState::Failed { at, message } if at.elapsed() < METADATA_RETRY_AFTER => {
+                return Err(GitLabError::Api {
+                    status: 503,
+                    message: message.clone(),
+                });
+            }
+            _ => {}
+        }
+
+        let result = self.fetch_metadata().await;
+        *self.metadata.lock().unwrap_or_else(|e| e.into_inner()) = match &result {
+            Ok(metadata) => MetadataState::Known(metadata.clone()),
+            Err(e) => MetadataState::Failed {
+                at: Instant::now(),
+                message: format!("GitLab version and edition unavailable: {}", e),
+            },
+        };
+        result
+    }
+
+    /// Fetch the version and edition, falling back to `/version`
+    async fn fetch_metadata(&self) -> GitLabResult<InstanceMetadata> {
+        match self.get::<InstanceMetadata>("/metadata").await {
+            Ok(metadata) => Ok(metadata),
+            Err(GitLabError::NotFound { .. }) => {
+                let mut metadata: InstanceMetadata = self.get("/version").await?;
+                metadata.enterprise = metadata.version.contains("-ee");
+                Ok(metadata)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetch a project's metadata, labels, and milestones into the cache
+    pub async fn warm_project(&self, project: &str) -> GitLabResult<()> {
+        let project = Self::encode_project(project);
+        for endpoint in [
+            format!("/projects/{}", project),
+            format!("/projects/{}/labels", project),
+            format!("/projects/{}/milestones", project),
+        ] {
+            self.get_json(&endpoint).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Warm the cache for each project, logging failures instead of failing
+    pub async fn warm_projects(&self, projects: &[String]) {
+        for project in projects {
+            match self.warm_project(project).await {
+                Ok(()) => info!(project = %project, "Warmed project cache"),
+                Err(e) => warn!(project = %project, error = %e, "Failed to warm project cache"),
+            }
+        }
+    }
+
     /// Build a URL for an API endpoint
     fn url(&self, path: &str) -> String {
         format!("{}{}", self.base_url, path)
@@ -62,7 +362,18 @@ impl GitLabClient {
     }
 
     /// Execute a request with retries
-    async fn execute(&self, request: RequestBuilder) -> GitLabResult<Response> {
+    ///
+    /// The returned permit holds the request's `max_concurrent_requests` slot;
+    /// keep it until the body has been read, so large downloads count against
+    /// the limit too.
+    async fn execute(
+        &self,
+        request: RequestBuilder,
+    ) -> GitLabResult<(Response, Option<OwnedSemaphorePermit>)> {
+        let request = match REQUEST_ID.try_with(Clone::clone) {
+            Ok(request_id) => request.header(REQUEST_ID_HEADER, request_id),
+            Err(_) => request,
+        };
         let mut last_error = None;
 
         for attempt in 0..=self.max_retries {
@@ -78,9 +389,23 @@ impl GitLabClient {
                 .try_clone()
                 .ok_or_else(|| GitLabError::InvalidResponse("Cannot clone request".to_string()))?;
 
+            // Hold a slot only while the request is in flight, not during backoff
+            let permit = match &self.concurrency {
+                Some(semaphore) => Some(
+                    semaphore
+                        .clone()
+                        .acquire_owned()
+                        .await
+                        .map_err(|e| GitLabError::InvalidResponse(e.to_string()))?,
+                ),
+                None => None,
+            };
+
             match req.send().await {
                 Ok(response) => {
-                    return self.handle_response(response).await;
+                    let result = self.handle_response(response).await;
+                    self.health.record(&result);
+                    return result.map(|response| (response, permit));
                 }
                 Err(e) => {
                     warn!("Request failed: {}", e);
@@ -94,7 +419,10 @@ impl GitLabClient {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| GitLabError::InvalidResponse("Unknown error".to_string())))
+        let error =
+            last_error.unwrap_or_else(|| GitLabError::InvalidResponse("Unknown error".to_string()));
+        self.health.record_failure(&error);
+        Err(error)
     }
 
     /// Handle API response
@@ -102,6 +430,9 @@ impl GitLabClient {
         let status = response.status();
 
         if status.is_success() {
+            let _ = PAGINATION.try_with(|pagination| {
+                pagination.replace(Pagination::from_headers(response.headers()));
+            });
             return Ok(response);
         }
 
@@ -123,7 +454,7 @@ impl GitLabClient {
         &self,
         request: RequestBuilder,
     ) -> GitLabResult<T> {
-        let response = self.execute(request).await?;
+        let (response, _permit) = self.execute(request).await?;
         response
             .json()
             .await
@@ -131,8 +462,39 @@ impl GitLabClient {
     }
 
     /// Make a GET request
+    ///
+    /// Project metadata, labels, and milestones are served from the cache
+    /// when it is enabled, except while pagination metadata is being collected.
     #[instrument(skip(self), fields(endpoint = %endpoint))]
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> GitLabResult<T> {
+        let cacheable = self.read_cache
+            && self.cache.is_enabled()
+            && ResponseCache::is_cacheable(endpoint)
+            && PAGINATION.try_with(|_| ()).is_err();
+        if !cacheable {
+            return self.get_uncached(endpoint).await;
+        }
+
+        let value = match self.cache.get(endpoint) {
+            Some(value) => {
+                debug!("Serving response from cache");
+                value
+            }
+            None => {
+                let value: serde_json::Value = self.get_uncached(endpoint).await?;
+                self.cache.insert(endpoint, value.clone());
+                value
+            }
+        };
+        serde_json::from_value(value)
+            .map_err(|e| GitLabError::InvalidResponse(format!("Failed to parse response: {}", e)))
+    }
+
+    /// Make a GET request that bypasses the response cache
+    ///
+    /// For polling a resource whose state is expected to change, such as a
+    /// project while its import runs.
+    pub async fn get_uncached<T: DeserializeOwned>(&self, endpoint: &str) -> GitLabResult<T> {
         let url = self.url(endpoint);
         let request = self.http.get(&url);
         let request = self.authenticate(request).await?;
@@ -144,6 +506,43 @@ impl GitLabClient {
         self.get(endpoint).await
     }
 
+    /// GET every page of a list endpoint and concatenate the items
+    ///
+    /// Follows `X-Next-Page` with the largest page size, stopping after
+    /// [`MAX_ALL_PAGES`] pages; the result then carries the next page so a
+    /// partial list is never mistaken for a complete one. `endpoint` must not
+    /// set `page` or `per_page`.
+    pub async fn get_all_pages(&self, endpoint: &str) -> GitLabResult<AllPages> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut all = AllPages::default();
+        let mut page = 1;
+
+        for fetched in 1..=MAX_ALL_PAGES {
+            let url = format!(
+                "{}{}per_page={}&page={}",
+                endpoint, separator, MAX_PER_PAGE, page
+            );
+            let (result, pagination) =
+                with_pagination(self.get::<Vec<serde_json::Value>>(&url)).await;
+            all.items.extend(result?);
+
+            match pagination.and_then(|p| p.next_page) {
+                Some(next) if fetched < MAX_ALL_PAGES => page = next,
+                Some(next) => {
+                    warn!(
+                        endpoint,
+                        pages = fetched,
+                        "Stopped following pages at the limit"
+                    );
+                    all.next_page = Some(next);
+                }
+                None => break,
+            }
+        }
+
+        Ok(all)
+    }
+
     /// Make a GET request returning raw text (not JSON)
     #[instrument(skip(self), fields(endpoint = %endpoint))]
     pub async fn get_text(&self, endpoint: &str) -> GitLabResult<String> {
@@ -151,7 +550,7 @@ impl GitLabClient {
         let request = self.http.get(&url);
         let request = self.authenticate(request).await?;
 
-        let response = self.execute(request).await?;
+        let (response, _permit) = self.execute(request).await?;
         let text = response.text().await.map_err(|e| {
             GitLabError::InvalidResponse(format!("Failed to read response text: {}", e))
         })?;
@@ -159,6 +558,59 @@ impl GitLabClient {
         Ok(text)
     }
 
+    /// Make a GET request returning raw bytes, up to `max_bytes`
+    ///
+    /// Fails with `GitLabError::TooLarge` if the body is larger than
+    /// `max_bytes`, without reading more than the limit.
+    #[instrument(skip(self), fields(endpoint = %endpoint))]
+    pub async fn get_bytes(&self, endpoint: &str, max_bytes: usize) -> GitLabResult<Vec<u8>> {
+        let url = self.url(endpoint);
+        let request = self.http.get(&url);
+        let request = self.authenticate(request).await?;
+
+        let (response, _permit) = self.execute(request).await?;
+        read_limited(response, max_bytes).await
+    }
+
+    /// Make a GET request for part of a resource, up to `max_bytes`
+    ///
+    /// `range` is a `Range` header value such as `bytes=-1024` (the last 1024
+    /// bytes) or `bytes=0-99`. When GitLab ignores the range and sends the
+    /// whole resource, the result covers all of it.
+    #[instrument(skip(self), fields(endpoint = %endpoint))]
+    pub async fn get_range(
+        &self,
+        endpoint: &str,
+        range: &str,
+        max_bytes: usize,
+    ) -> GitLabResult<ByteRange> {
+        let url = self.url(endpoint);
+        let request = self.http.get(&url).header(reqwest::header::RANGE, range);
+        let request = self.authenticate(request).await?;
+
+        let (response, _permit) = self.execute(request).await?;
+        let content_range = if response.status() == StatusCode::PARTIAL_CONTENT {
+            let header = response
+                .headers()
+                .get(reqwest::header::CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            Some(parse_content_range(header).ok_or_else(|| {
+                GitLabError::InvalidResponse(format!("Invalid Content-Range: '{}'", header))
+            })?)
+        } else {
+            None
+        };
+
+        let bytes = read_limited(response, max_bytes).await?;
+        let (offset, total) = content_range.unwrap_or((0, bytes.len() as u64));
+        Ok(ByteRange {
+            bytes,
+            offset,
+            total,
+        })
+    }
+
     /// Make a POST request
     #[instrument(skip(self, body), fields(endpoint = %endpoint))]
     pub async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
@@ -167,6 +619,7 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<T> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.post(&url).json(body);
         let request = self.authenticate(request).await?;
         self.execute_and_parse(request).await
@@ -188,6 +641,7 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<()> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.post(&url).json(body);
         let request = self.authenticate(request).await?;
 
@@ -195,6 +649,50 @@ impl GitLabClient {
         Ok(())
     }
 
+    /// Upload a file as `multipart/form-data` in a POST request
+    ///
+    /// The file is sent as a single form field named `field`.
+    #[instrument(skip(self, content), fields(endpoint = %endpoint, size = content.len()))]
+    pub async fn post_multipart_file<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        field: &str,
+        filename: &str,
+        content: &[u8],
+    ) -> GitLabResult<T> {
+        let boundary = format!("tanuki-mcp-{:032x}", rand::random::<u128>());
+        let filename: String = filename
+            .chars()
+            .filter(|c| !c.is_control())
+            .map(|c| if c == '"' { '\'' } else { c })
+            .collect();
+
+        let mut body = Vec::with_capacity(content.len() + 256);
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                 Content-Type: application/octet-stream\r\n\r\n",
+                boundary, field, filename
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(content);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
+        let request = self
+            .http
+            .post(&url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .body(body);
+        let request = self.authenticate(request).await?;
+        self.execute_and_parse(request).await
+    }
+
     /// Make a PUT request
     #[instrument(skip(self, body), fields(endpoint = %endpoint))]
     pub async fn put<T: DeserializeOwned, B: Serialize + ?Sized>(
@@ -203,6 +701,7 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<T> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.put(&url).json(body);
         let request = self.authenticate(request).await?;
         self.execute_and_parse(request).await
@@ -224,6 +723,7 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<()> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.put(&url).json(body);
         let request = self.authenticate(request).await?;
 
@@ -235,6 +735,7 @@ impl GitLabClient {
     #[instrument(skip(self), fields(endpoint = %endpoint))]
     pub async fn delete(&self, endpoint: &str) -> GitLabResult<()> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.delete(&url);
         let request = self.authenticate(request).await?;
 
@@ -249,6 +750,7 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<()> {
         let url = self.url(endpoint);
+        self.cache.invalidate_endpoint(endpoint);
         let request = self.http.delete(&url).json(body);
         let request = self.authenticate(request).await?;
 
@@ -263,10 +765,13 @@ impl GitLabClient {
         endpoint: &str,
     ) -> GitLabResult<T> {
         let url = self.url(endpoint);
+        if method != Method::GET {
+            self.cache.invalidate_endpoint(endpoint);
+        }
         let request = self.http.request(method, &url);
         let request = self.authenticate(request).await?;
 
-        let response = self.execute(request).await?;
+        let (response, _permit) = self.execute(request).await?;
         let data = response.json().await.map_err(|e| {
             GitLabError::InvalidResponse(format!("Failed to parse response: {}", e))
         })?;
@@ -282,10 +787,13 @@ impl GitLabClient {
         body: &B,
     ) -> GitLabResult<T> {
         let url = self.url(endpoint);
+        if method != Method::GET {
+            self.cache.invalidate_endpoint(endpoint);
+        }
         let request = self.http.request(method, &url).json(body);
         let request = self.authenticate(request).await?;
 
-        let response = self.execute(request).await?;
+        let (response, _permit) = self.execute(request).await?;
         let data = response.json().await.map_err(|e| {
             GitLabError::InvalidResponse(format!("Failed to parse response: {}", e))
         })?;
@@ -293,12 +801,110 @@ impl GitLabClient {
         Ok(data)
     }
 
+    /// Make a request with custom method and optional JSON body, returning raw JSON
+    ///
+    /// Empty response bodies (e.g., HTTP 204 No Content) are returned as `Value::Null`.
+    pub async fn request_json(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&serde_json::Value>,
+    ) -> GitLabResult<serde_json::Value> {
+        let url = self.url(endpoint);
+        if method != Method::GET {
+            self.cache.invalidate_endpoint(endpoint);
+        }
+        let mut request = self.http.request(method, &url);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let request = self.authenticate(request).await?;
+
+        let (response, _permit) = self.execute(request).await?;
+        let text = response.text().await.map_err(|e| {
+            GitLabError::InvalidResponse(format!("Failed to read response text: {}", e))
+        })?;
+
+        if text.trim().is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+
+        serde_json::from_str(&text)
+            .map_err(|e| GitLabError::InvalidResponse(format!("Failed to parse response: {}", e)))
+    }
+
     /// URL-encode a project path for use in API endpoints
     pub fn encode_project(project: &str) -> String {
         urlencoding::encode(project).to_string()
     }
 }
 
+/// Read a response body, failing as soon as it exceeds `max_bytes`
+async fn read_limited(mut response: Response, max_bytes: usize) -> GitLabResult<Vec<u8>> {
+    let too_large = || GitLabError::TooLarge { max_bytes };
+
+    if response
+        .content_length()
+        .is_some_and(|len| len > max_bytes as u64)
+    {
+        return Err(too_large());
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| GitLabError::InvalidResponse(format!("Failed to read response body: {}", e)))?
+    {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+/// Parse a `Content-Range: bytes <first>-<last>/<total>` value
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, _) = range.split_once('-')?;
+    Some((first.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// Redirect handling for GitLab requests
+///
+/// A same-origin redirect of a project endpoint means the project was renamed
+/// or moved. Those are only followed when enabled, and each one is logged with
+/// the new location. Other redirects, such as artifact downloads served from
+/// object storage, are always followed.
+fn redirect_policy(follow_project_redirects: bool) -> redirect::Policy {
+    redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() > MAX_REDIRECTS {
+            return attempt.error("too many redirects");
+        }
+
+        let Some(from) = attempt.previous().last() else {
+            return attempt.follow();
+        };
+        let moved_project =
+            from.origin() == attempt.url().origin() && from.path().contains("/projects/");
+        if !moved_project {
+            return attempt.follow();
+        }
+        if !follow_project_redirects {
+            return attempt.stop();
+        }
+
+        warn!(
+            from = from.path(),
+            to = attempt.url().path(),
+            "Followed GitLab redirect; the project has moved, update its path"
+        );
+        attempt.follow()
+    })
+}
+
 /// Check if an error is retryable
 fn is_retryable(error: &GitLabError) -> bool {
     match error {
@@ -0,0 +1,159 @@
+//! GitLab availability tracking
+//!
+//! A small circuit breaker fed by every request the client sends. Failures
+//! that point at GitLab itself (connection errors, timeouts, 5xx responses)
+//! count towards marking the instance degraded; any answer from a working
+//! GitLab, including a 4xx, closes the breaker again.
+
+use crate::error::GitLabError;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Consecutive outage failures after which GitLab is reported as degraded
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Health of the GitLab instance as seen by the client
+#[derive(Debug)]
+pub struct GitLabHealth {
+    threshold: u32,
+    consecutive_failures: AtomicU32,
+    last_error: Mutex<Option<String>>,
+}
+
+/// Point-in-time view of [`GitLabHealth`]
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthStatus {
+    /// Whether GitLab is considered unhealthy
+    pub degraded: bool,
+    /// Outage failures since the last successful call
+    pub consecutive_failures: u32,
+    /// Most recent outage failure, kept while degraded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl GitLabHealth {
+    /// Track health with the default failure threshold
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_FAILURE_THRESHOLD)
+    }
+
+    /// Track health, reporting degraded after `threshold` consecutive failures
+    pub fn with_threshold(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            consecutive_failures: AtomicU32::new(0),
+            last_error: Mutex::new(None),
+        }
+    }
+
+    /// Record the outcome of a request
+    ///
+    /// Errors that GitLab answered deliberately (not found, forbidden,
+    /// validation, rate limiting) count as successes.
+    pub fn record<T>(&self, result: &Result<T, GitLabError>) {
+        match result {
+            Err(error) if is_outage(error) => self.record_failure(error),
+            _ => self.record_success(),
+        }
+    }
+
+    /// Record a successful call, closing the breaker
+    pub fn record_success(&self) {
+        if self.consecutive_failures.swap(0, Ordering::Relaxed) >= self.threshold {
+            tracing::info!("GitLab API recovered");
+        }
+        *self.lock_last_error() = None;
+    }
+
+    /// Record a failure caused by GitLab being unreachable or erroring
+    pub fn record_failure(&self, error: &GitLabError) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures == self.threshold {
+            tracing::warn!(failures, error = %error, "GitLab API degraded");
+        }
+        *self.lock_last_error() = Some(error.to_string());
+    }
+
+    /// Whether GitLab is currently considered unhealthy
+    pub fn is_degraded(&self) -> bool {
+        self.consecutive_failures.load(Ordering::Relaxed) >= self.threshold
+    }
+
+    /// Snapshot the current state
+    pub fn status(&self) -> HealthStatus {
+        let consecutive_failures = self.consecutive_failures.load(Ordering::Relaxed);
+        let degraded = consecutive_failures >= self.threshold;
+        HealthStatus {
+            degraded,
+            consecutive_failures,
+            last_error: if degraded {
+                self.lock_last_error().clone()
+            } else {
+                None
+            },
+        }
+    }
+
+    fn lock_last_error(&self) -> std::sync::MutexGuard<'_, Option<String>> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for GitLabHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether an error means GitLab itself is unavailable
+fn is_outage(error: &GitLabError) -> bool {
+    match error {
+        GitLabError::Request(_) | GitLabError::Timeout { .. } => true,
+        GitLabError::Api { status, .. } => *status >= 500,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> GitLabError {
+        GitLabError::Api {
+            status: 502,
+            message: "Bad Gateway".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_degrades_after_threshold() {
+        let health = GitLabHealth::with_threshold(3);
+        for _ in 0..2 {
+            health.record::<()>(&Err(server_error()));
+        }
+        assert!(!health.is_degraded());
+
+        health.record::<()>(&Err(server_error()));
+        assert!(health.is_degraded());
+        let status = health.status();
+        assert_eq!(status.consecutive_failures, 3);
+        assert!(status.last_error.unwrap().contains("502"));
+    }
+
+    #[test]
+    fn test_client_errors_count_as_success() {
+        let health = GitLabHealth::with_threshold(1);
+        health.record::<()>(&Err(server_error()));
+        assert!(health.is_degraded());
+
+        health.record::<()>(&Err(GitLabError::NotFound {
+            resource: "project".to_string(),
+        }));
+        assert!(!health.is_degraded());
+        assert!(health.status().last_error.is_none());
+    }
+}
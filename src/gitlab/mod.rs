@@ -2,8 +2,15 @@
 //!
 //! Provides a typed client for interacting with the GitLab REST API.
 
+pub mod cache;
 pub mod client;
+pub mod health;
 pub mod types;
 
-pub use client::GitLabClient;
+pub use cache::ResponseCache;
+pub use client::{
+    AllPages, GitLabClient, HEALTH_PROBE_INTERVAL, REQUEST_ID_HEADER, with_pagination,
+    with_request_id,
+};
+pub use health::{GitLabHealth, HealthStatus};
 pub use types::*;
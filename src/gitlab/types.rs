@@ -2,6 +2,7 @@
 //!
 //! Common types used across multiple GitLab API endpoints.
 
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 
 /// GitLab user (author, assignee, etc.)
@@ -18,6 +19,23 @@ pub struct User {
     pub web_url: Option<String>,
 }
 
+/// Version and edition of the GitLab instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceMetadata {
+    pub version: String,
+    #[serde(default)]
+    pub revision: String,
+    #[serde(default)]
+    pub enterprise: bool,
+}
+
+impl InstanceMetadata {
+    /// "EE" for Enterprise Edition, "CE" for Community Edition
+    pub fn edition(&self) -> &'static str {
+        if self.enterprise { "EE" } else { "CE" }
+    }
+}
+
 /// GitLab project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -292,6 +310,39 @@ pub struct Label {
     pub priority: Option<u32>,
 }
 
+/// GitLab resource label event (label added to or removed from an issue/MR)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceLabelEvent {
+    pub id: u64,
+    #[serde(default)]
+    pub user: Option<User>,
+    pub created_at: String,
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    #[serde(default)]
+    pub resource_id: Option<u64>,
+    /// The label, or `None` if it has since been deleted
+    #[serde(default)]
+    pub label: Option<Label>,
+    /// "add" or "remove"
+    pub action: String,
+}
+
+/// GitLab resource state event (issue/MR closed, reopened, merged, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceStateEvent {
+    pub id: u64,
+    #[serde(default)]
+    pub user: Option<User>,
+    pub created_at: String,
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    #[serde(default)]
+    pub resource_id: Option<u64>,
+    /// New state: "opened", "closed", "reopened", "merged", ...
+    pub state: String,
+}
+
 /// GitLab note (comment)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Note {
@@ -373,9 +424,39 @@ pub struct ReleaseLink {
     pub name: String,
     pub url: String,
     #[serde(default)]
+    pub direct_asset_url: Option<String>,
+    #[serde(default)]
     pub link_type: Option<String>,
 }
 
+/// GitLab project badge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBadge {
+    pub id: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub link_url: String,
+    pub image_url: String,
+    #[serde(default)]
+    pub rendered_link_url: Option<String>,
+    #[serde(default)]
+    pub rendered_image_url: Option<String>,
+    /// "project" or "group" (group badges are inherited)
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+/// Body of a ranged GET response and where it sits in the whole resource
+#[derive(Debug, Clone, Default)]
+pub struct ByteRange {
+    /// The bytes received
+    pub bytes: Vec<u8>,
+    /// Offset of the first received byte within the resource
+    pub offset: u64,
+    /// Size of the whole resource
+    pub total: u64,
+}
+
 /// Pagination information from GitLab response headers
 #[derive(Debug, Clone, Default)]
 pub struct Pagination {
@@ -386,3 +467,26 @@ pub struct Pagination {
     pub next_page: Option<u32>,
     pub prev_page: Option<u32>,
 }
+
+impl Pagination {
+    /// Read the `X-Total`/`X-Next-Page`/... headers, if the response has any
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        if !headers.contains_key("x-total") && !headers.contains_key("x-next-page") {
+            return None;
+        }
+        let number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        };
+        Some(Self {
+            page: number("x-page"),
+            per_page: number("x-per-page"),
+            total: number("x-total"),
+            total_pages: number("x-total-pages"),
+            next_page: number("x-next-page"),
+            prev_page: number("x-prev-page"),
+        })
+    }
+}
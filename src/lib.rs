@@ -42,6 +42,7 @@ pub mod access_control;
 pub mod auth;
 pub mod config;
 pub mod dashboard;
+pub mod doctor;
 pub mod error;
 pub mod gitlab;
 pub mod server;
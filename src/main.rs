@@ -2,19 +2,25 @@
 //!
 //! A Model Context Protocol server for GitLab with fine-grained access control.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tanuki_mcp::{
     access_control::AccessResolver,
     auth::create_auth_provider,
-    config::{AppConfig, TransportMode, load_config},
+    config::{AppConfig, ToolsConfig, TransportMode, load_config, write_starter_config},
     dashboard::{DEFAULT_DASHBOARD_PORT, DashboardConfig, DashboardMetrics, run_dashboard},
-    gitlab::GitLabClient,
-    server::GitLabMcpHandler,
-    transport::{DEFAULT_HTTP_PORT, HttpConfig, run_http_blocking, run_stdio},
+    doctor,
+    gitlab::{GitLabClient, HEALTH_PROBE_INTERVAL},
+    server::{GitLabMcpHandler, RateLimiter},
+    tools::{IdempotencyCache, ToolRegistry, definitions},
+    transport::{
+        DEFAULT_HTTP_PORT, HttpConfig, WebhookConfig, check_http_port, run_http_blocking, run_stdio,
+    },
     update::{UpdateChecker, UpdateManager},
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 /// GitLab MCP Server - Fine-grained access control for GitLab via MCP
@@ -41,9 +47,9 @@ struct Args {
     #[arg(long, env = "TANUKI_MCP_HTTP_HOST", default_value = "127.0.0.1")]
     http_host: String,
 
-    /// HTTP server port (for http transport)
-    #[arg(long, env = "TANUKI_MCP_HTTP_PORT", default_value_t = DEFAULT_HTTP_PORT)]
-    http_port: u16,
+    /// HTTP server port (for http transport) [default: 20289]
+    #[arg(long, env = "TANUKI_MCP_HTTP_PORT")]
+    http_port: Option<u16>,
 
     /// Disable the dashboard
     #[arg(long, env = "TANUKI_MCP_NO_DASHBOARD")]
@@ -73,14 +79,43 @@ enum Commands {
 
     /// Show version information
     Version,
+
+    /// Check that the token can read every category the config allows
+    Doctor {
+        /// Project to probe project-scoped categories against
+        /// (defaults to `gitlab.default_project`)
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+
+    /// Print the effective configuration (file, environment, and defaults)
+    /// as TOML with secrets redacted
+    PrintConfig,
+
+    /// Print the tool catalog (names, descriptions, input schemas, categories,
+    /// and operations) without starting a server
+    ListTools {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ListToolsFormat::Json)]
+        format: ListToolsFormat,
+    },
+
+    /// Write a commented starter configuration with safe defaults
+    InitConfig {
+        /// Where to write the configuration
+        #[arg(short, long, default_value = "tanuki-mcp.toml")]
+        output: PathBuf,
+
+        /// Overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
 }
 
-fn create_handler(
-    config: &AppConfig,
-    gitlab: Arc<GitLabClient>,
-    access: Arc<AccessResolver>,
-) -> GitLabMcpHandler {
-    GitLabMcpHandler::new_with_shared(config, gitlab, access)
+/// Output formats of the list-tools command
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ListToolsFormat {
+    Json,
 }
 
 fn create_handler_with_metrics(
@@ -88,8 +123,10 @@ fn create_handler_with_metrics(
     gitlab: Arc<GitLabClient>,
     access: Arc<AccessResolver>,
     metrics: Arc<DashboardMetrics>,
-) -> GitLabMcpHandler {
-    GitLabMcpHandler::new_with_metrics(config, gitlab, access, metrics)
+) -> anyhow::Result<GitLabMcpHandler> {
+    Ok(GitLabMcpHandler::new_with_metrics(
+        config, gitlab, access, metrics,
+    )?)
 }
 
 /// Handle the update command
@@ -137,6 +174,77 @@ fn handle_update_command(check_only: bool, skip_confirm: bool) -> anyhow::Result
     Ok(())
 }
 
+/// Handle the doctor command
+///
+/// Fails, so the process exits with status 1, if GitLab denies any category
+/// the configuration allows.
+async fn handle_doctor_command(
+    config: &AppConfig,
+    gitlab: &GitLabClient,
+    access: &AccessResolver,
+    project: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut registry = ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &config.tools);
+
+    let categories = doctor::allowed_categories(&registry, access);
+    let project = project.or(config.gitlab.default_project.as_deref());
+
+    println!(
+        "Checking {} categories against {}...",
+        categories.len(),
+        config.gitlab.base_url()
+    );
+    if project.is_none() {
+        println!("No project given; project-scoped categories probe the project list.");
+    }
+    println!();
+
+    let report = doctor::run_checks(gitlab, &categories, project).await;
+    println!("{}", report);
+
+    if report.has_denied() {
+        anyhow::bail!(
+            "GitLab denied {} of the categories the configuration allows",
+            report.denied_count()
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle the print-config command
+fn handle_print_config_command(config_path: Option<&str>) -> anyhow::Result<()> {
+    let config = load_config(config_path)?;
+    print!("{}", config.to_redacted_toml()?);
+    Ok(())
+}
+
+/// Handle the list-tools command
+///
+/// Lists the tools enabled by the default configuration; no GitLab
+/// connection or token is needed.
+fn handle_list_tools_command(format: ListToolsFormat) -> anyhow::Result<()> {
+    let mut registry = ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &ToolsConfig::default());
+
+    match format {
+        ListToolsFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&registry.catalog())?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the init-config command
+fn handle_init_config_command(output: &Path, force: bool) -> anyhow::Result<()> {
+    write_starter_config(output, force)?;
+    println!("Wrote starter configuration to {}", output.display());
+    println!("Set TANUKI_MCP__GITLAB_TOKEN (or GITLAB_TOKEN) before starting the server.");
+    Ok(())
+}
+
 /// Handle the version command
 fn handle_version_command() {
     println!("tanuki-mcp v{}", env!("CARGO_PKG_VERSION"));
@@ -160,12 +268,29 @@ async fn main() -> anyhow::Result<()> {
                 handle_version_command();
                 return Ok(());
             }
+            Commands::PrintConfig => {
+                return handle_print_config_command(args.config.as_deref());
+            }
+            Commands::ListTools { format } => {
+                return handle_list_tools_command(*format);
+            }
+            Commands::InitConfig { output, force } => {
+                return handle_init_config_command(output, *force);
+            }
+            Commands::Doctor { .. } => {}
         }
     }
 
-    // Initialize logging
-    let filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&args.log_level));
+    // Initialize logging. rmcp logs every response at debug level, and tool
+    // results may carry secrets such as newly created access tokens, so its
+    // service logs stay at info unless RUST_LOG asks for more.
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(&args.log_level).add_directive(
+            "rmcp::service=info"
+                .parse()
+                .expect("static log directive is valid"),
+        )
+    });
 
     tracing_subscriber::registry()
         .with(fmt::layer().with_writer(std::io::stderr))
@@ -181,6 +306,10 @@ async fn main() -> anyhow::Result<()> {
     let config = load_config(args.config.as_deref())
         .inspect_err(|e| error!(error = %e, "Failed to load configuration"))?;
 
+    if config.server.dry_run {
+        info!("Dry-run mode enabled: write, delete, and execute tools will not call GitLab");
+    }
+
     // Check for updates in background
     let update_checker = UpdateChecker::new(&config.updates);
     update_checker.check_in_background();
@@ -189,9 +318,20 @@ async fn main() -> anyhow::Result<()> {
     let auth = create_auth_provider(&config.gitlab)
         .inspect_err(|e| error!(error = %e, "Failed to create auth provider"))?;
 
+    // Determine transport mode
+    let transport = args
+        .transport
+        .as_deref()
+        .map(|t| match t {
+            "stdio" => TransportMode::Stdio,
+            "http" => TransportMode::Http,
+            _ => config.server.transport,
+        })
+        .unwrap_or(config.server.transport);
+
     // Create GitLab client
     let gitlab = Arc::new(
-        GitLabClient::new(&config.gitlab, auth)
+        GitLabClient::for_transport(&config.gitlab, auth, transport)
             .inspect_err(|e| error!(error = %e, "Failed to create GitLab client"))?,
     );
 
@@ -201,30 +341,84 @@ async fn main() -> anyhow::Result<()> {
             .inspect_err(|e| error!(error = %e, "Failed to create access resolver"))?,
     );
 
-    // Create shared metrics collector
-    let metrics = Arc::new(DashboardMetrics::new());
+    if let Some(Commands::Doctor { project }) = &args.command {
+        return handle_doctor_command(&config, &gitlab, &access, project.as_deref()).await;
+    }
 
-    // Determine if dashboard is enabled
+    // Catch conflicting transport and port settings before starting anything
+    let http_port = args.http_port.unwrap_or(DEFAULT_HTTP_PORT);
     let dashboard_enabled = !args.no_dashboard && config.dashboard.enabled;
+    let dashboard_config = DashboardConfig::new(&args.dashboard_host, args.dashboard_port)
+        .unwrap_or_else(|_| {
+            DashboardConfig::new(&config.dashboard.host, config.dashboard.port).unwrap_or_default()
+        });
+    match transport {
+        TransportMode::Stdio if args.http_port.is_some() => {
+            warn!(
+                port = http_port,
+                "An HTTP port is set but the transport is stdio; the port is ignored"
+            );
+        }
+        TransportMode::Stdio => {}
+        TransportMode::Http => {
+            let http_config = HttpConfig::from_host_port(&args.http_host, http_port)?;
+            // The dashboard starts first and would take the port from the transport
+            if dashboard_enabled && dashboard_config.conflicts_with(http_config.bind) {
+                error!(
+                    dashboard = %dashboard_config.bind,
+                    http = %http_config.bind,
+                    "Dashboard and HTTP transport share an address"
+                );
+                anyhow::bail!(
+                    "dashboard address {} conflicts with HTTP transport address {}; choose another port with --dashboard-port or --http-port",
+                    dashboard_config.bind,
+                    http_config.bind
+                );
+            }
+            check_http_port(&http_config)
+                .await
+                .inspect_err(|e| error!(error = %e, "Cannot start HTTP transport"))?;
+        }
+    }
+
+    // Notice when a degraded GitLab comes back even if no tool calls arrive
+    gitlab.spawn_health_probe(HEALTH_PROBE_INTERVAL);
+
+    // Fetch frequently used project metadata without holding up the transport
+    if !config.gitlab.warm_projects.is_empty() {
+        let gitlab = gitlab.clone();
+        let projects = config.gitlab.warm_projects.clone();
+        tokio::spawn(async move { gitlab.warm_projects(&projects).await });
+    }
+
+    // Remember the GitLab edition so EE-only tools can fail fast on CE
+    let metadata_client = gitlab.clone();
+    tokio::spawn(async move {
+        match metadata_client.metadata().await {
+            Ok(metadata) => info!(
+                version = %metadata.version,
+                edition = metadata.edition(),
+                "Connected to GitLab"
+            ),
+            Err(e) => warn!(error = %e, "Failed to fetch GitLab version and edition"),
+        }
+    });
+
+    // Create shared metrics collector
+    let metrics = Arc::new(
+        DashboardMetrics::with_capacity(config.dashboard.recent_requests)
+            .with_gitlab_health(gitlab.health().clone()),
+    );
 
     // Start dashboard if enabled (in background)
     let dashboard_handle = if dashboard_enabled {
-        let dashboard_config = DashboardConfig::new(&args.dashboard_host, args.dashboard_port)
-            .unwrap_or_else(|_| {
-                DashboardConfig::new(&config.dashboard.host, config.dashboard.port)
-                    .unwrap_or_default()
-            });
-
         let metrics_clone = metrics.clone();
         let app_config = Arc::new(config.clone());
-
-        // Get tool count by creating a temporary handler
-        let temp_handler = create_handler(&config, gitlab.clone(), access.clone());
-        let tool_count = temp_handler.tool_count();
+        let access_clone = access.clone();
 
         Some(tokio::spawn(async move {
             if let Err(e) =
-                run_dashboard(dashboard_config, metrics_clone, app_config, tool_count).await
+                run_dashboard(dashboard_config, metrics_clone, app_config, access_clone).await
             {
                 error!(error = %e, "Dashboard server error");
             }
@@ -234,42 +428,37 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    // Determine transport mode
-    let transport = args
-        .transport
-        .as_deref()
-        .map(|t| match t {
-            "stdio" => TransportMode::Stdio,
-            "http" => TransportMode::Http,
-            _ => config.server.transport,
-        })
-        .unwrap_or(config.server.transport);
-
     // Run the appropriate transport
     match transport {
         TransportMode::Stdio => {
-            let handler = create_handler_with_metrics(&config, gitlab, access, metrics);
+            let handler = create_handler_with_metrics(&config, gitlab, access, metrics)?;
             run_stdio(handler).await?;
         }
         TransportMode::Http => {
-            let mut http_config = HttpConfig::from_host_port(&args.http_host, args.http_port)?;
+            let mut http_config = HttpConfig::from_host_port(&args.http_host, http_port)?
+                .with_sse_keep_alive_secs(config.server.http.sse_keepalive_secs)
+                .with_max_concurrent_requests(config.server.http.max_concurrent_requests)
+                .with_message_limits(
+                    config.server.http.max_batch_len,
+                    config.server.http.max_message_bytes,
+                )
+                .with_gitlab_health(gitlab.health().clone());
             http_config.cors = config.server.cors;
+            if let Some(secret) = &config.server.http.webhook_secret {
+                http_config =
+                    http_config.with_webhook(WebhookConfig::new(secret.as_str(), gitlab.clone()));
+            }
+
+            // Sessions share one handler, and with it the registry and caches
+            let idempotency = Arc::new(IdempotencyCache::new(Duration::from_secs(
+                config.server.idempotency_ttl_secs,
+            )));
+            let rate_limiter = Arc::new(RateLimiter::new(config.ratelimit.limits()?));
+            let handler = create_handler_with_metrics(&config, gitlab, access, metrics)?
+                .with_idempotency_cache(idempotency)
+                .with_rate_limiter(rate_limiter);
 
-            // Clone the shared resources for the factory closure
-            let config = Arc::new(config);
-
-            run_http_blocking(
-                move || {
-                    create_handler_with_metrics(
-                        &config,
-                        gitlab.clone(),
-                        access.clone(),
-                        metrics.clone(),
-                    )
-                },
-                http_config,
-            )
-            .await?;
+            run_http_blocking(move || handler.clone(), http_config).await?;
         }
     }
 
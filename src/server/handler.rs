@@ -2,12 +2,22 @@
 //!
 //! Implements the MCP protocol handler for GitLab tools.
 
-use crate::access_control::AccessResolver;
-use crate::config::AppConfig;
+use crate::access_control::{AccessResolver, ProjectAllowlist, ToolCategory};
+use crate::auth::PatProvider;
+use crate::config::{AppConfig, OutputFormat};
 use crate::dashboard::DashboardMetrics;
-use crate::gitlab::GitLabClient;
-use crate::tools::{ContentBlock, ToolContext, ToolOutput, ToolRegistry, definitions};
+use crate::error::mcp_mapper::map_tool_error;
+use crate::error::{AccessDeniedError, AuthError, ConfigError, GitLabError, ToolError};
+use crate::gitlab::client::MAX_ALL_PAGES;
+use crate::gitlab::{GitLabClient, REQUEST_ID_HEADER};
+use crate::server::RateLimiter;
+use crate::tools::{
+    ContentBlock, IdempotencyCache, PollConfig, ToolContext, ToolOutput, ToolRegistry, definitions,
+};
+use crate::util::redact_arguments;
+use axum::http::request::Parts;
 use base64::Engine;
+use futures::stream::{self, BoxStream, StreamExt};
 use rmcp::ErrorData as McpError;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::{
@@ -22,8 +32,13 @@ use rmcp::service::{RequestContext, RoleServer};
 use serde_json::{Map, Value};
 use std::borrow::Cow;
 use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
 use std::sync::{Arc, OnceLock};
-use tracing::{debug, error, info, instrument};
+use std::time::Duration;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Header with which HTTP callers send their own GitLab token
+pub const GITLAB_TOKEN_HEADER: &str = "X-GitLab-Token";
 
 /// GitLab MCP server handler
 #[derive(Clone)]
@@ -40,20 +55,79 @@ pub struct GitLabMcpHandler {
     access: Arc<AccessResolver>,
     /// Dashboard metrics (optional)
     metrics: Option<Arc<DashboardMetrics>>,
+    /// Output format for tool results
+    output_format: OutputFormat,
+    /// Preview mutating tools instead of calling GitLab
+    dry_run: bool,
+    /// Project used when a tool's `project` argument is omitted
+    default_project: Option<String>,
+    /// Wrap list results with GitLab pagination metadata
+    pagination_meta: bool,
+    /// Largest accepted serialized tool arguments
+    max_request_bytes: usize,
+    /// Largest number of items returned by a list tool
+    max_items_per_list: Option<usize>,
+    /// Report recoverable failures as regular results with an `error` field
+    soft_errors: bool,
+    /// Prefix added to advertised tool names and stripped on dispatch
+    tool_prefix: String,
+    /// Polling used by tools called with `wait: true`
+    poll: PollConfig,
+    /// Results of create calls made with an idempotency key
+    idempotency: Option<Arc<IdempotencyCache>>,
+    /// Per-category rate limits (None when unlimited)
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Projects tools may act on (None when unrestricted)
+    allowed_projects: Option<Arc<ProjectAllowlist>>,
     /// Cached tool list (lazy-initialized, shared across clones)
     cached_tools: Arc<OnceLock<Vec<Tool>>>,
 }
 
 impl GitLabMcpHandler {
-    /// Create a new tool registry with all tools registered
-    fn create_registry() -> Arc<ToolRegistry> {
+    /// Create a new tool registry with all enabled tools registered
+    fn create_registry(config: &AppConfig) -> Arc<ToolRegistry> {
         let mut registry = ToolRegistry::new();
-        definitions::register_all_tools(&mut registry);
+        definitions::register_enabled_tools(&mut registry, &config.tools);
         Arc::new(registry)
     }
 
+    /// Create the idempotency cache, unless disabled in configuration
+    fn create_idempotency_cache(config: &AppConfig) -> Option<Arc<IdempotencyCache>> {
+        let ttl = config.server.idempotency_ttl_secs;
+        (ttl > 0).then(|| Arc::new(IdempotencyCache::new(Duration::from_secs(ttl))))
+    }
+
+    /// Polling configured for tools called with `wait: true`
+    fn poll_config(config: &AppConfig) -> PollConfig {
+        PollConfig {
+            max_attempts: config.server.poll_max_attempts,
+            delay: Duration::from_secs(config.server.poll_interval_secs),
+        }
+    }
+
+    /// Create the rate limiter, unless no category is limited
+    ///
+    /// Loaded configurations are already validated; one built in code with
+    /// an invalid limit is rejected here the same way.
+    fn create_rate_limiter(config: &AppConfig) -> Result<Option<Arc<RateLimiter>>, ConfigError> {
+        let limits = config.ratelimit.limits()?;
+        Ok((!limits.is_empty()).then(|| Arc::new(RateLimiter::new(limits))))
+    }
+
+    /// Compile the project allowlist, unless every project is allowed
+    ///
+    /// An invalid allowlist is an error rather than no restriction at all.
+    fn create_allowlist(config: &AppConfig) -> Result<Option<Arc<ProjectAllowlist>>, ConfigError> {
+        let allowlist = ProjectAllowlist::new(&config.gitlab.allowed_projects)?;
+        Ok((!allowlist.is_empty()).then(|| Arc::new(allowlist)))
+    }
+
     /// Create a new handler from configuration
-    pub fn new(config: &AppConfig, gitlab: GitLabClient, access: AccessResolver) -> Self {
+    pub fn new(
+        config: &AppConfig,
+        gitlab: GitLabClient,
+        access: AccessResolver,
+    ) -> Result<Self, ConfigError> {
         Self::new_with_shared(config, Arc::new(gitlab), Arc::new(access))
     }
 
@@ -66,19 +140,33 @@ impl GitLabMcpHandler {
         config: &AppConfig,
         gitlab: Arc<GitLabClient>,
         access: Arc<AccessResolver>,
-    ) -> Self {
-        let registry = Self::create_registry();
+    ) -> Result<Self, ConfigError> {
+        let allowed_projects = Self::create_allowlist(config)?;
+        let rate_limiter = Self::create_rate_limiter(config)?;
+        let registry = Self::create_registry(config);
         info!(tools = registry.len(), "Initialized GitLab MCP handler");
 
-        Self {
+        Ok(Self {
             name: config.server.name.clone(),
             version: config.server.version.clone(),
             registry,
             gitlab,
             access,
             metrics: None,
+            output_format: config.server.output_format,
+            dry_run: config.server.dry_run,
+            default_project: config.gitlab.default_project.clone(),
+            pagination_meta: config.server.include_pagination_meta,
+            max_request_bytes: config.server.max_request_bytes,
+            max_items_per_list: config.server.max_items_per_list,
+            soft_errors: config.server.soft_errors,
+            tool_prefix: config.server.tool_prefix.clone(),
+            poll: Self::poll_config(config),
+            idempotency: Self::create_idempotency_cache(config),
+            rate_limiter,
+            allowed_projects,
             cached_tools: Arc::new(OnceLock::new()),
-        }
+        })
     }
 
     /// Create a new handler with shared resources and metrics
@@ -87,22 +175,59 @@ impl GitLabMcpHandler {
         gitlab: Arc<GitLabClient>,
         access: Arc<AccessResolver>,
         metrics: Arc<DashboardMetrics>,
-    ) -> Self {
-        let registry = Self::create_registry();
+    ) -> Result<Self, ConfigError> {
+        let allowed_projects = Self::create_allowlist(config)?;
+        let rate_limiter = Self::create_rate_limiter(config)?;
+        let registry = Self::create_registry(config);
         info!(
             tools = registry.len(),
             "Initialized GitLab MCP handler with metrics"
         );
 
-        Self {
+        Ok(Self {
             name: config.server.name.clone(),
             version: config.server.version.clone(),
             registry,
             gitlab,
             access,
             metrics: Some(metrics),
+            output_format: config.server.output_format,
+            dry_run: config.server.dry_run,
+            default_project: config.gitlab.default_project.clone(),
+            pagination_meta: config.server.include_pagination_meta,
+            max_request_bytes: config.server.max_request_bytes,
+            max_items_per_list: config.server.max_items_per_list,
+            soft_errors: config.server.soft_errors,
+            tool_prefix: config.server.tool_prefix.clone(),
+            poll: Self::poll_config(config),
+            idempotency: Self::create_idempotency_cache(config),
+            rate_limiter,
+            allowed_projects,
             cached_tools: Arc::new(OnceLock::new()),
+        })
+    }
+
+    /// Share an idempotency cache with other handlers
+    ///
+    /// Handlers created per HTTP session should share one cache so retries
+    /// arriving on a new session are still deduplicated. Has no effect if
+    /// idempotency is disabled in configuration.
+    pub fn with_idempotency_cache(mut self, cache: Arc<IdempotencyCache>) -> Self {
+        if self.idempotency.is_some() {
+            self.idempotency = Some(cache);
+        }
+        self
+    }
+
+    /// Share a rate limiter with other handlers
+    ///
+    /// Handlers created per HTTP session must share one limiter for limits to
+    /// apply across sessions. Has no effect if no rate limits are configured.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        if self.rate_limiter.is_some() {
+            self.rate_limiter = Some(limiter);
         }
+        self
     }
 
     /// Get the number of registered tools
@@ -110,19 +235,66 @@ impl GitLabMcpHandler {
         self.registry.len()
     }
 
-    /// Create tool context for a request
-    fn create_context(&self, request_id: &str) -> ToolContext {
-        match &self.metrics {
-            Some(metrics) => ToolContext::with_metrics(
-                self.gitlab.clone(),
-                self.access.clone(),
-                request_id,
-                metrics.clone(),
-            ),
-            None => ToolContext::new(self.gitlab.clone(), self.access.clone(), request_id),
+    /// GitLab client for a request
+    ///
+    /// Over HTTP, a caller's own token in the `X-GitLab-Token` header replaces
+    /// the configured one for that request. Access control applies either way.
+    pub(crate) fn gitlab_for(&self, parts: Option<&Parts>) -> Result<Arc<GitLabClient>, AuthError> {
+        let Some(token) = parts.and_then(|parts| parts.headers.get(GITLAB_TOKEN_HEADER)) else {
+            return Ok(self.gitlab.clone());
+        };
+        let token = token.to_str().map_err(|_| AuthError::InvalidToken)?;
+        let auth = PatProvider::new(token.trim())?;
+        Ok(Arc::new(
+            self.gitlab
+                .with_auth(Box::new(auth), caller_id(token.trim())),
+        ))
+    }
+
+    /// Refuse a project outside `gitlab.allowed_projects`
+    ///
+    /// Resources and prompts read GitLab directly rather than through a tool,
+    /// so they are checked here.
+    async fn check_allowed_project(
+        &self,
+        gitlab: &GitLabClient,
+        source: &str,
+        project: &str,
+    ) -> Result<(), McpError> {
+        let Some(allowlist) = &self.allowed_projects else {
+            return Ok(());
+        };
+        match allowlist.outside(gitlab, project).await {
+            Ok(None) => Ok(()),
+            Ok(Some(path)) => {
+                warn!(source, project = %path, "Project is outside gitlab.allowed_projects");
+                Err(map_tool_error(&ToolError::AccessDenied(
+                    AccessDeniedError::project_not_allowed(source, path),
+                )))
+            }
+            Err(e) => Err(internal_error(format!("Failed to resolve project: {}", e))),
         }
     }
 
+    /// Create tool context for a request
+    fn create_context(&self, request_id: &str, gitlab: Arc<GitLabClient>) -> ToolContext {
+        let ctx = match &self.metrics {
+            Some(metrics) => {
+                ToolContext::with_metrics(gitlab, self.access.clone(), request_id, metrics.clone())
+            }
+            None => ToolContext::new(gitlab, self.access.clone(), request_id),
+        };
+        ctx.with_output_format(self.output_format)
+            .with_dry_run(self.dry_run)
+            .with_default_project(self.default_project.clone())
+            .with_idempotency(self.idempotency.clone())
+            .with_pagination_meta(self.pagination_meta)
+            .with_max_request_bytes(Some(self.max_request_bytes))
+            .with_max_items_per_list(self.max_items_per_list)
+            .with_allowed_projects(self.allowed_projects.clone())
+            .with_poll_config(self.poll)
+    }
+
     /// Convert internal tool output to MCP result
     fn to_mcp_result(&self, output: ToolOutput) -> CallToolResult {
         let content = output
@@ -174,7 +346,14 @@ impl GitLabMcpHandler {
                             input_schema.insert("properties".to_string(), props.clone());
                         }
                         if let Some(required) = schema_value.get("required") {
-                            input_schema.insert("required".to_string(), required.clone());
+                            let mut required = required.clone();
+                            // `project` is optional when a default project is configured
+                            if self.default_project.is_some()
+                                && let Some(names) = required.as_array_mut()
+                            {
+                                names.retain(|name| name != "project");
+                            }
+                            input_schema.insert("required".to_string(), required);
                         }
 
                         // Check if this tool is globally denied (denied everywhere)
@@ -192,7 +371,7 @@ impl GitLabMcpHandler {
                         };
 
                         Tool {
-                            name: Cow::Owned(tool.name.to_string()),
+                            name: Cow::Owned(format!("{}{}", self.tool_prefix, tool.name)),
                             description: Some(Cow::Owned(description)),
                             input_schema: Arc::new(input_schema),
                             annotations: None,
@@ -207,35 +386,78 @@ impl GitLabMcpHandler {
             .clone()
     }
 
-    /// Get tool names for completion, filtered by prefix
+    /// Map an advertised tool name back to its registry name
+    ///
+    /// Returns None if the name lacks the configured prefix, so unprefixed
+    /// names are reported as unknown tools rather than silently accepted.
+    fn strip_tool_prefix<'a>(&self, name: &'a str) -> Option<&'a str> {
+        name.strip_prefix(self.tool_prefix.as_str())
+    }
+
+    /// Get advertised tool names for completion, filtered by prefix
+    ///
+    /// Names carry the configured tool prefix, matching `tools/list`.
     fn get_tool_completions(&self, prefix: &str) -> Vec<String> {
         self.registry
             .tools()
-            .filter(|tool| tool.name.starts_with(prefix))
-            .map(|tool| tool.name.to_string())
+            .map(|tool| format!("{}{}", self.tool_prefix, tool.name))
+            .filter(|name| name.starts_with(prefix))
             .collect()
     }
 
     /// Execute a tool call
+    ///
+    /// `request_id` correlates the call with GitLab requests and logs; one is
+    /// generated when absent.
     async fn execute_tool(
         &self,
         name: &str,
         arguments: Option<Map<String, Value>>,
+        request_id: Option<String>,
+        gitlab: Arc<GitLabClient>,
     ) -> CallToolResult {
-        // Generate a request ID for tracing
-        let request_id = format!("{:x}", rand::random::<u64>());
-        let ctx = self.create_context(&request_id);
+        // Generate a request ID for tracing if the caller did not supply one
+        let request_id = request_id.unwrap_or_else(|| format!("{:x}", rand::random::<u64>()));
+        let ctx = self.create_context(&request_id, gitlab);
 
         // Get arguments or empty object - convert Map to Value
         let args = arguments
             .map(Value::Object)
             .unwrap_or_else(|| serde_json::json!({}));
 
+        // Enforce per-category rate limits before doing any work
+        let rate_limit = match (&self.rate_limiter, self.registry.get(name)) {
+            (Some(limiter), Some(tool)) => limiter
+                .check(tool.category)
+                .inspect_err(|e| record_rate_limited(&ctx, name, tool.category, &args, e)),
+            _ => Ok(()),
+        };
+
         // Execute the tool
-        let result = self.registry.execute(name, &ctx, args).await;
+        let result = match rate_limit {
+            Ok(()) => self.registry.execute(name, &ctx, args).await,
+            Err(e) => Err(e),
+        };
 
         match result {
             Ok(output) => self.to_mcp_result(output),
+            Err(e) if self.soft_errors && e.is_recoverable() => {
+                warn!(error = %e, "Tool execution failed, reporting as soft error");
+                let mut error = serde_json::json!({
+                    "error": {"code": e.code(), "message": e.to_string()}
+                });
+                if let ToolError::GitLab(GitLabError::Validation { fields }) = &e {
+                    error["error"]["validation"] = serde_json::json!(fields);
+                }
+                CallToolResult {
+                    content: vec![Content::text(
+                        serde_json::to_string_pretty(&error).unwrap_or_default(),
+                    )],
+                    is_error: Some(false),
+                    meta: None,
+                    structured_content: None,
+                }
+            }
             Err(e) => {
                 error!(error = %e, "Tool execution failed");
                 CallToolResult {
@@ -248,9 +470,116 @@ impl GitLabMcpHandler {
         }
     }
 
+    /// Run a tool page by page, yielding each page's items as it arrives
+    ///
+    /// Backs NDJSON streaming over HTTP. Tools taking a `page` argument are
+    /// called again for each next page GitLab reports, up to
+    /// [`MAX_ALL_PAGES`] pages. Each page counts against the rate limit, and
+    /// the stream stops once `server.max_items_per_list` items have been
+    /// yielded in total. A result that is not a list is yielded as a single
+    /// item. The stream ends after the first error.
+    pub fn stream_tool(
+        &self,
+        name: &str,
+        arguments: Option<Map<String, Value>>,
+        request_id: Option<String>,
+        gitlab: Arc<GitLabClient>,
+    ) -> BoxStream<'static, Result<Vec<Value>, ToolError>> {
+        let Some(name) = self.strip_tool_prefix(name).map(str::to_string) else {
+            return stream::iter([Err(ToolError::NotFound(name.to_string()))]).boxed();
+        };
+        let tool = self.registry.get(&name);
+        let rate_limit = self
+            .rate_limiter
+            .clone()
+            .zip(tool.map(|tool| tool.category));
+        let paged = tool.is_some_and(|tool| {
+            tool.input_schema
+                .get("properties")
+                .is_some_and(|properties| properties.get("page").is_some())
+        });
+
+        let request_id = request_id.unwrap_or_else(|| format!("{:x}", rand::random::<u64>()));
+        let ctx = Arc::new(
+            self.create_context(&request_id, gitlab)
+                .with_pagination_meta(true),
+        );
+        let registry = self.registry.clone();
+        let args = arguments.unwrap_or_default();
+        let first_page = args.get("page").and_then(Value::as_u64).unwrap_or(1);
+        let max_items = ctx.max_items_per_list;
+
+        stream::try_unfold(Some((first_page, 1, 0)), move |state| {
+            let (registry, ctx, name, mut args, rate_limit) = (
+                registry.clone(),
+                ctx.clone(),
+                name.clone(),
+                args.clone(),
+                rate_limit.clone(),
+            );
+            async move {
+                let Some((page, fetched, yielded)) = state else {
+                    return Ok(None);
+                };
+                if paged {
+                    args.insert("page".to_string(), page.into());
+                }
+                let args = Value::Object(args);
+                if let Some((limiter, category)) = &rate_limit {
+                    limiter
+                        .check(*category)
+                        .inspect_err(|e| record_rate_limited(&ctx, &name, *category, &args, e))?;
+                }
+
+                let output = registry.execute(&name, &ctx, args).await?;
+                let text = || {
+                    output
+                        .content
+                        .iter()
+                        .filter_map(|block| match block {
+                            ContentBlock::Text { text } => Some(text.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                if output.is_error {
+                    return Err(ToolError::ExecutionFailed(text()));
+                }
+                let (mut items, next_page) = match output.data.clone() {
+                    Some(Value::Object(mut page))
+                        if page.get("items").is_some_and(Value::is_array) =>
+                    {
+                        let next_page = page.get("next_page").and_then(Value::as_u64);
+                        match page.remove("items") {
+                            Some(Value::Array(items)) => (items, next_page),
+                            _ => (Vec::new(), next_page),
+                        }
+                    }
+                    Some(Value::Array(items)) => (items, None),
+                    Some(value) => (vec![value], None),
+                    None => (vec![Value::String(text())], None),
+                };
+
+                if let Some(max_items) = max_items {
+                    items.truncate(max_items.saturating_sub(yielded));
+                }
+                let yielded = yielded + items.len();
+
+                let next = next_page
+                    .filter(|_| paged && fetched < MAX_ALL_PAGES)
+                    .filter(|_| max_items.is_none_or(|max_items| yielded < max_items))
+                    .map(|next_page| (next_page, fetched + 1, yielded));
+                Ok(Some((items, next)))
+            }
+        })
+        .boxed()
+    }
+
     /// Build the analyze_issue prompt
     async fn build_analyze_issue_prompt(
         &self,
+        gitlab: &GitLabClient,
         arguments: Option<Map<String, Value>>,
     ) -> Result<GetPromptResult, McpError> {
         let args = arguments.ok_or_else(|| missing_argument("arguments required"))?;
@@ -259,6 +588,8 @@ impl GitLabMcpHandler {
             .get("project")
             .and_then(|v| v.as_str())
             .ok_or_else(|| missing_argument("project"))?;
+        self.check_allowed_project(gitlab, "analyze_issue", project)
+            .await?;
 
         let issue_iid = args
             .get("issue_iid")
@@ -273,16 +604,14 @@ impl GitLabMcpHandler {
         let encoded_project = GitLabClient::encode_project(project);
         let issue_endpoint = format!("/projects/{}/issues/{}", encoded_project, issue_iid);
 
-        let issue: Value = self
-            .gitlab
+        let issue: Value = gitlab
             .get(&issue_endpoint)
             .await
             .map_err(|e| internal_error(format!("Failed to fetch issue: {}", e)))?;
 
         // Fetch issue discussions
         let discussions_endpoint = format!("{}/discussions", issue_endpoint);
-        let discussions: Value = self
-            .gitlab
+        let discussions: Value = gitlab
             .get(&discussions_endpoint)
             .await
             .unwrap_or_else(|_| serde_json::json!([]));
@@ -372,6 +701,7 @@ impl GitLabMcpHandler {
     /// Build the review_merge_request prompt
     async fn build_review_mr_prompt(
         &self,
+        gitlab: &GitLabClient,
         arguments: Option<Map<String, Value>>,
     ) -> Result<GetPromptResult, McpError> {
         let args = arguments.ok_or_else(|| missing_argument("arguments required"))?;
@@ -380,6 +710,8 @@ impl GitLabMcpHandler {
             .get("project")
             .and_then(|v| v.as_str())
             .ok_or_else(|| missing_argument("project"))?;
+        self.check_allowed_project(gitlab, "review_merge_request", project)
+            .await?;
 
         let mr_iid = args
             .get("mr_iid")
@@ -394,24 +726,21 @@ impl GitLabMcpHandler {
         let encoded_project = GitLabClient::encode_project(project);
         let mr_endpoint = format!("/projects/{}/merge_requests/{}", encoded_project, mr_iid);
 
-        let mr: Value = self
-            .gitlab
+        let mr: Value = gitlab
             .get(&mr_endpoint)
             .await
             .map_err(|e| internal_error(format!("Failed to fetch merge request: {}", e)))?;
 
         // Fetch MR changes (diff)
         let changes_endpoint = format!("{}/changes", mr_endpoint);
-        let changes: Value = self
-            .gitlab
+        let changes: Value = gitlab
             .get(&changes_endpoint)
             .await
             .unwrap_or_else(|_| serde_json::json!({"changes": []}));
 
         // Fetch MR discussions
         let discussions_endpoint = format!("{}/discussions", mr_endpoint);
-        let discussions: Value = self
-            .gitlab
+        let discussions: Value = gitlab
             .get(&discussions_endpoint)
             .await
             .unwrap_or_else(|_| serde_json::json!([]));
@@ -614,14 +943,54 @@ impl ServerHandler for GitLabMcpHandler {
         }
     }
 
-    #[instrument(skip(self, _context), fields(tool = %request.name))]
+    #[instrument(skip(self, context), fields(tool = %request.name))]
     fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
-        debug!(?request.arguments, "Calling tool");
-        async move { Ok(self.execute_tool(&request.name, request.arguments).await) }
+        debug!(
+            arguments = ?request.arguments.as_ref().map(redact_arguments),
+            "Calling tool"
+        );
+        // Reuse the HTTP request's correlation ID when running over HTTP
+        let parts = context.extensions.get::<Parts>();
+        let request_id = parts
+            .and_then(|parts| parts.headers.get(REQUEST_ID_HEADER))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let gitlab = self.gitlab_for(parts);
+        async move {
+            let gitlab = match gitlab {
+                Ok(gitlab) => gitlab,
+                Err(e) => {
+                    warn!(error = %e, "Rejecting tool call with invalid GitLab token header");
+                    return Ok(CallToolResult {
+                        content: vec![Content::text(format!(
+                            "Error: invalid {} header: {}",
+                            GITLAB_TOKEN_HEADER, e
+                        ))],
+                        is_error: Some(true),
+                        meta: None,
+                        structured_content: None,
+                    });
+                }
+            };
+            let Some(name) = self.strip_tool_prefix(&request.name) else {
+                return Ok(CallToolResult {
+                    content: vec![Content::text(format!(
+                        "Error: {}",
+                        ToolError::NotFound(request.name.to_string())
+                    ))],
+                    is_error: Some(true),
+                    meta: None,
+                    structured_content: None,
+                });
+            };
+            Ok(self
+                .execute_tool(name, request.arguments, request_id, gitlab)
+                .await)
+        }
     }
 
     #[instrument(skip(self, _context))]
@@ -687,16 +1056,22 @@ impl ServerHandler for GitLabMcpHandler {
     /// - project: URL-encoded project path (e.g., `group%2Fsubgroup%2Fproject`)
     /// - path: File path within repository
     /// - ref: Optional git reference (branch, tag, commit) - defaults to HEAD
-    #[instrument(skip(self, _context))]
+    #[instrument(skip(self, context))]
     fn read_resource(
         &self,
         request: ReadResourceRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ReadResourceResult, McpError>> + Send + '_ {
         debug!(uri = %request.uri, "Reading resource");
+        let gitlab = self.gitlab_for(context.extensions.get::<Parts>());
         async move {
+            let gitlab =
+                gitlab.map_err(|e| internal_error(format!("Authentication error: {}", e)))?;
+
             // Parse gitlab://project/path?ref=branch URI
             let (project, file_path, ref_name) = parse_gitlab_uri(&request.uri)?;
+            self.check_allowed_project(&gitlab, "read_resource", &project)
+                .await?;
 
             // Build GitLab API endpoint
             let encoded_project = GitLabClient::encode_project(&project);
@@ -710,8 +1085,7 @@ impl ServerHandler for GitLabMcpHandler {
             );
 
             // Fetch file content
-            let result: serde_json::Value = self
-                .gitlab
+            let result: serde_json::Value = gitlab
                 .get(&endpoint)
                 .await
                 .map_err(|e| internal_error(format!("GitLab API error: {}", e)))?;
@@ -807,23 +1181,43 @@ impl ServerHandler for GitLabMcpHandler {
     /// Get a specific prompt by name
     ///
     /// Builds workflow prompts that fetch relevant GitLab data.
-    #[instrument(skip(self, _context))]
+    #[instrument(skip(self, context))]
     fn get_prompt(
         &self,
         request: GetPromptRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<GetPromptResult, McpError>> + Send + '_ {
         debug!(name = %request.name, "Getting prompt");
+        let gitlab = self.gitlab_for(context.extensions.get::<Parts>());
         async move {
+            let gitlab =
+                gitlab.map_err(|e| internal_error(format!("Authentication error: {}", e)))?;
             match request.name.as_str() {
-                "analyze_issue" => self.build_analyze_issue_prompt(request.arguments).await,
-                "review_merge_request" => self.build_review_mr_prompt(request.arguments).await,
+                "analyze_issue" => {
+                    self.build_analyze_issue_prompt(&gitlab, request.arguments)
+                        .await
+                }
+                "review_merge_request" => {
+                    self.build_review_mr_prompt(&gitlab, request.arguments)
+                        .await
+                }
                 _ => Err(method_not_found(&request.name)),
             }
         }
     }
 }
 
+/// Opaque identity of a caller's token, for keeping per-caller state apart
+///
+/// The hash is keyed per process, so it reveals nothing about the token.
+fn caller_id(token: &str) -> String {
+    static KEYS: OnceLock<RandomState> = OnceLock::new();
+    format!(
+        "{:016x}",
+        KEYS.get_or_init(RandomState::new).hash_one(token)
+    )
+}
+
 /// Parse a gitlab:// URI into (project, path, ref_name)
 ///
 /// URI format: `gitlab://{project}/{path}?ref={branch}`
@@ -871,6 +1265,28 @@ fn parse_gitlab_uri(uri: &str) -> Result<(String, String, Option<String>), McpEr
     Ok((project, file_path, ref_name))
 }
 
+/// Record a call refused by the rate limiter, which never reaches the registry
+fn record_rate_limited(
+    ctx: &ToolContext,
+    name: &str,
+    category: ToolCategory,
+    args: &Value,
+    error: &ToolError,
+) {
+    if let Some(ref metrics) = ctx.metrics {
+        metrics.record_call_with_audit(
+            name,
+            category,
+            args.get("project").and_then(Value::as_str),
+            Duration::ZERO,
+            false,
+            Some(&ctx.request_id),
+            None,
+            Some(&error.to_string()),
+        );
+    }
+}
+
 /// Create an internal error McpError
 fn internal_error(message: impl Into<Cow<'static, str>>) -> McpError {
     McpError {
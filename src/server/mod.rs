@@ -3,5 +3,7 @@
 //! MCP server implementation for GitLab tools.
 
 pub mod handler;
+pub mod ratelimit;
 
-pub use handler::GitLabMcpHandler;
+pub use handler::{GITLAB_TOKEN_HEADER, GitLabMcpHandler};
+pub use ratelimit::RateLimiter;
@@ -0,0 +1,86 @@
+//! Per-category rate limiting
+//!
+//! Token buckets keyed by tool category, configured under `[ratelimit.categories]`.
+//! Calls over the limit fail with [`ToolError::RateLimited`] before GitLab is
+//! contacted.
+
+use crate::access_control::ToolCategory;
+use crate::config::RateLimit;
+use crate::error::ToolError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Token bucket for one category
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.calls),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Refill for the time elapsed and take one token if available
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        let rate = f64::from(self.limit.calls) / self.limit.period.as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(f64::from(self.limit.calls));
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Rate limiter shared by all tool calls of a server
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: HashMap<ToolCategory, Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter from the rates parsed by
+    /// [`RateLimitConfig::limits`](crate::config::RateLimitConfig::limits)
+    pub fn new(limits: HashMap<ToolCategory, RateLimit>) -> Self {
+        let buckets = limits
+            .into_iter()
+            .map(|(category, limit)| (category, Mutex::new(Bucket::new(limit))))
+            .collect();
+        Self { buckets }
+    }
+
+    /// Whether no category is limited
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Record a call in a category, failing if its limit is exhausted
+    pub fn check(&self, category: ToolCategory) -> Result<(), ToolError> {
+        let Some(bucket) = self.buckets.get(&category) else {
+            return Ok(());
+        };
+
+        let mut bucket = bucket.lock().unwrap_or_else(|e| e.into_inner());
+        if bucket.try_take() {
+            Ok(())
+        } else {
+            Err(ToolError::RateLimited {
+                category: category.to_string(),
+                limit: bucket.limit.to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Project access token tools
+//!
+//! Tools for rotating project access tokens. GitLab returns a token's value
+//! only in the response that creates it; listings carry metadata only.
+
+use crate::error::ToolError;
+use crate::gitlab::GitLabClient;
+use crate::tools::definitions::groups::MemberAccessLevel;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
+use async_trait::async_trait;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// List project access tokens
+#[gitlab_tool(
+    name = "list_project_access_tokens",
+    description = "List the access tokens of a project with their scopes, role, and expiry. Token values are never included",
+    category = "access_tokens",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListProjectAccessTokens {
+    /// Project path or ID
+    pub project: String,
+    /// Filter by state: active or inactive
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Number of tokens per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListProjectAccessTokens {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .optional("state", self.state.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/projects/{}/access_tokens{}", project, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Create a project access token
+#[gitlab_tool(
+    name = "create_project_access_tokens",
+    description = "Create a project access token. The token value is in the response only once; GitLab cannot show it again",
+    category = "access_tokens",
+    operation = "write",
+    project_field = "project",
+    returns_secret
+)]
+pub struct CreateProjectAccessTokens {
+    /// Project path or ID
+    pub project: String,
+    /// Token name
+    pub name: String,
+    /// Scopes such as api, read_api, read_repository, write_repository, read_registry
+    pub scopes: Vec<String>,
+    /// Expiration date (YYYY-MM-DD)
+    pub expires_at: String,
+    /// Role of the token's bot user (defaults to maintainer)
+    #[serde(default)]
+    pub access_level: Option<MemberAccessLevel>,
+}
+
+#[async_trait]
+impl ToolExecutor for CreateProjectAccessTokens {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        if self.scopes.is_empty() {
+            return Err(ToolError::InvalidArguments(
+                "At least one scope is required".to_string(),
+            ));
+        }
+
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/access_tokens", project);
+
+        let mut body = serde_json::json!({
+            "name": self.name,
+            "scopes": self.scopes,
+            "expires_at": self.expires_at,
+        });
+        if let Some(access_level) = self.access_level {
+            body["access_level"] = serde_json::json!(access_level.as_u32());
+        }
+
+        let mut result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+        if result.get("token").is_some() {
+            result["notice"] =
+                serde_json::json!("Store this token now: GitLab will not show its value again");
+        }
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Revoke a project access token
+#[gitlab_tool(
+    name = "revoke_project_access_token",
+    description = "Revoke a project access token so it can no longer be used",
+    category = "access_tokens",
+    operation = "delete",
+    project_field = "project"
+)]
+pub struct RevokeProjectAccessToken {
+    /// Project path or ID
+    pub project: String,
+    /// Access token ID
+    pub token_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for RevokeProjectAccessToken {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/access_tokens/{}", project, self.token_id);
+
+        ctx.gitlab.delete(&endpoint).await?;
+
+        Ok(ToolOutput::text(format!(
+            "Access token {} revoked for project '{}'",
+            self.token_id, self.project
+        )))
+    }
+}
@@ -37,8 +37,7 @@ impl ToolExecutor for ListBranches {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
             .optional_encoded("search", self.search.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/repository/branches{}", project, query);
@@ -5,9 +5,11 @@
 use crate::error::ToolError;
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
-use crate::util::QueryBuilder;
+use crate::util::{QueryBuilder, default_max_diff_length, is_iso8601, truncate_diff};
 use async_trait::async_trait;
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tanuki_mcp_macros::gitlab_tool;
 
 /// List commits in a repository
@@ -32,6 +34,9 @@ pub struct ListCommits {
     /// Only commits before this date (ISO 8601 format)
     #[serde(default)]
     pub until: Option<String>,
+    /// Only commits by this author (name or email)
+    #[serde(default)]
+    pub author: Option<String>,
     /// Include commit stats
     #[serde(default)]
     pub with_stats: bool,
@@ -46,15 +51,26 @@ pub struct ListCommits {
 #[async_trait]
 impl ToolExecutor for ListCommits {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        for (name, value) in [("since", &self.since), ("until", &self.until)] {
+            if let Some(value) = value
+                && !is_iso8601(value)
+            {
+                return Err(ToolError::InvalidArguments(format!(
+                    "{} must be an ISO 8601 date (e.g., 2024-01-31 or 2024-01-31T12:00:00Z), got '{}'",
+                    name, value
+                )));
+            }
+        }
+
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
             .optional_encoded("ref_name", self.ref_name.as_ref())
             .optional_encoded("path", self.path.as_ref())
             .optional_encoded("since", self.since.as_ref())
             .optional_encoded("until", self.until.as_ref())
+            .optional_encoded("author", self.author.as_ref())
             .optional("with_stats", self.with_stats.then_some("true"))
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/repository/commits{}", project, query);
@@ -75,9 +91,9 @@ pub struct GetCommit {
     pub project: String,
     /// Commit SHA
     pub sha: String,
-    /// Include commit stats
-    #[serde(default)]
-    pub stats: bool,
+    /// Include addition/deletion counts in `stats`
+    #[serde(default, alias = "stats")]
+    pub with_stats: bool,
 }
 
 #[async_trait]
@@ -85,7 +101,7 @@ impl ToolExecutor for GetCommit {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
-            .optional("stats", self.stats.then_some("true"))
+            .optional("stats", self.with_stats.then_some("true"))
             .build();
         let endpoint = format!(
             "/projects/{}/repository/commits/{}{}",
@@ -99,7 +115,7 @@ impl ToolExecutor for GetCommit {
 /// Get commit diff
 #[gitlab_tool(
     name = "get_commit_diff",
-    description = "Get the diff of a specific commit",
+    description = "Get the per-file diffs of a specific commit. Large diffs are truncated per file",
     category = "commits",
     operation = "read"
 )]
@@ -108,17 +124,53 @@ pub struct GetCommitDiff {
     pub project: String,
     /// Commit SHA
     pub sha: String,
+    /// Maximum length of each file diff before truncation (default: 10000)
+    #[serde(default = "default_max_diff_length")]
+    pub max_diff_length: usize,
+    /// Number of files per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
 }
 
 #[async_trait]
 impl ToolExecutor for GetCommitDiff {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let endpoint = format!("/projects/{}/repository/commits/{}/diff", project, self.sha);
-
-        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let query = QueryBuilder::new()
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!(
+            "/projects/{}/repository/commits/{}/diff{}",
+            project, self.sha, query
+        );
 
-        ToolOutput::json_value(result)
+        let files: Vec<serde_json::Value> = ctx.gitlab.get(&endpoint).await?;
+
+        let files: Vec<serde_json::Value> = files
+            .iter()
+            .map(|file| {
+                let diff = file.get("diff").and_then(|d| d.as_str()).unwrap_or("");
+                let (diff, truncated) = truncate_diff(diff, self.max_diff_length);
+                serde_json::json!({
+                    "old_path": file.get("old_path"),
+                    "new_path": file.get("new_path"),
+                    "new_file": file.get("new_file"),
+                    "renamed_file": file.get("renamed_file"),
+                    "deleted_file": file.get("deleted_file"),
+                    "diff": diff,
+                    "truncated": truncated,
+                })
+            })
+            .collect();
+
+        ToolOutput::json_value(serde_json::json!({
+            "sha": self.sha,
+            "files_count": files.len(),
+            "files": files,
+        }))
     }
 }
 
@@ -288,3 +340,119 @@ impl ToolExecutor for RevertCommit {
         ToolOutput::json_value(result)
     }
 }
+
+/// Kind of change a commit action makes to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitActionKind {
+    /// Create a new file
+    Create,
+    /// Replace the content of an existing file
+    Update,
+    /// Delete a file
+    Delete,
+    /// Move (rename) a file, optionally changing its content
+    Move,
+}
+
+/// A single file change within a commit
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommitAction {
+    /// Action to perform: create, update, delete, or move
+    pub action: CommitActionKind,
+    /// Path of the file
+    pub file_path: String,
+    /// Original path of the file (required for move)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+    /// File content (required for create and update)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Content encoding: text (default) or base64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    /// Last known commit ID of the file, to guard against concurrent changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_id: Option<String>,
+}
+
+impl CommitAction {
+    /// Check that the fields required by the action are present
+    pub(crate) fn validate(&self) -> Result<(), ToolError> {
+        let missing = match self.action {
+            CommitActionKind::Create | CommitActionKind::Update if self.content.is_none() => {
+                "content"
+            }
+            CommitActionKind::Move if self.previous_path.is_none() => "previous_path",
+            _ => return Ok(()),
+        };
+        Err(ToolError::InvalidArguments(format!(
+            "{} is required for the {:?} action on '{}'",
+            missing, self.action, self.file_path
+        )))
+    }
+}
+
+/// Create a commit with multiple file actions
+#[gitlab_tool(
+    name = "create_commit",
+    description = "Create a single commit that creates, updates, deletes, or moves several files at once",
+    category = "commits",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct CreateCommit {
+    /// Project path or ID
+    pub project: String,
+    /// Branch to commit to
+    pub branch: String,
+    /// Commit message
+    pub commit_message: String,
+    /// File actions to include in the commit
+    pub actions: Vec<CommitAction>,
+    /// Create `branch` from this ref if it does not exist (optional)
+    #[serde(default)]
+    pub start_branch: Option<String>,
+    /// Author email (optional)
+    #[serde(default)]
+    pub author_email: Option<String>,
+    /// Author name (optional)
+    #[serde(default)]
+    pub author_name: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for CreateCommit {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        if self.actions.is_empty() {
+            return Err(ToolError::InvalidArguments(
+                "At least one action is required".to_string(),
+            ));
+        }
+        for action in &self.actions {
+            action.validate()?;
+        }
+
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/repository/commits", project);
+
+        let mut body = serde_json::json!({
+            "branch": self.branch,
+            "commit_message": self.commit_message,
+            "actions": self.actions,
+        });
+
+        if let Some(ref start_branch) = self.start_branch {
+            body["start_branch"] = serde_json::Value::String(start_branch.clone());
+        }
+        if let Some(ref email) = self.author_email {
+            body["author_email"] = serde_json::Value::String(email.clone());
+        }
+        if let Some(ref name) = self.author_name {
+            body["author_name"] = serde_json::Value::String(name.clone());
+        }
+
+        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json_value(result)
+    }
+}
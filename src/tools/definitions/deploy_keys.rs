@@ -0,0 +1,160 @@
+//! Deploy key tools
+//!
+//! Tools for managing the SSH deploy keys that give CI/CD systems and
+//! deployment hosts access to a project's repository. Key material is only
+//! ever sent to GitLab: it is redacted from debug output and stripped from
+//! responses, which identify keys by fingerprint instead.
+
+use crate::error::ToolError;
+use crate::gitlab::GitLabClient;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::{QueryBuilder, SecretString};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// Remove the public key from a deploy key, or from each one in a list
+fn strip_key_material(mut value: Value) -> Value {
+    let keys = match &mut value {
+        Value::Array(keys) => keys.iter_mut().collect(),
+        key => vec![key],
+    };
+    for key in keys.into_iter().filter_map(Value::as_object_mut) {
+        key.remove("key");
+    }
+    value
+}
+
+/// List deploy keys
+#[gitlab_tool(
+    name = "list_deploy_keys",
+    description = "List the deploy keys of a project with their title, fingerprint, push access, and expiry. Key material is not returned",
+    category = "deploy_keys",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListDeployKeys {
+    /// Project path or ID
+    pub project: String,
+    /// Number of keys per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListDeployKeys {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/projects/{}/deploy_keys{}", project, query);
+        let result: Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(strip_key_material(result))
+    }
+}
+
+/// Add a deploy key
+#[gitlab_tool(
+    name = "add_deploy_key",
+    description = "Add an SSH deploy key to a project, read-only unless can_push is set",
+    category = "deploy_keys",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct AddDeployKey {
+    /// Project path or ID
+    pub project: String,
+    /// Title shown for the key in GitLab
+    pub title: String,
+    /// Public SSH key (e.g. "ssh-ed25519 AAAA... deploy@ci")
+    #[schemars(with = "String")]
+    pub key: SecretString,
+    /// Allow the key to push to the repository
+    #[serde(default)]
+    pub can_push: bool,
+    /// Expiration date in ISO 8601 format (e.g. "2025-12-31T00:00:00Z")
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for AddDeployKey {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/deploy_keys", project);
+
+        let mut body = serde_json::json!({
+            "title": self.title,
+            "key": self.key.expose_secret(),
+            "can_push": self.can_push,
+        });
+        if let Some(ref expires_at) = self.expires_at {
+            body["expires_at"] = serde_json::json!(expires_at);
+        }
+
+        let result: Value = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json_value(strip_key_material(result))
+    }
+}
+
+/// Enable a deploy key
+#[gitlab_tool(
+    name = "enable_deploy_key",
+    description = "Enable an existing deploy key, for example one already used by another project, for this project",
+    category = "deploy_keys",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct EnableDeployKey {
+    /// Project path or ID
+    pub project: String,
+    /// Deploy key ID
+    pub key_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for EnableDeployKey {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/deploy_keys/{}/enable", project, self.key_id);
+
+        let result: Value = ctx.gitlab.post(&endpoint, &serde_json::json!({})).await?;
+        ToolOutput::json_value(strip_key_material(result))
+    }
+}
+
+/// Delete a deploy key
+#[gitlab_tool(
+    name = "delete_deploy_key",
+    description = "Remove a deploy key from a project. The key is deleted from GitLab if no other project uses it",
+    category = "deploy_keys",
+    operation = "delete",
+    project_field = "project"
+)]
+pub struct DeleteDeployKey {
+    /// Project path or ID
+    pub project: String,
+    /// Deploy key ID
+    pub key_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for DeleteDeployKey {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/deploy_keys/{}", project, self.key_id);
+
+        ctx.gitlab.delete(&endpoint).await?;
+
+        Ok(ToolOutput::text(format!(
+            "Deploy key {} removed from project '{}'",
+            self.key_id, self.project
+        )))
+    }
+}
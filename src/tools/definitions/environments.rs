@@ -0,0 +1,124 @@
+//! Environment tools
+//!
+//! Tools for listing, stopping, and deleting project environments such as
+//! review apps.
+
+use crate::error::ToolError;
+use crate::gitlab::GitLabClient;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
+use async_trait::async_trait;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// List environments
+#[gitlab_tool(
+    name = "list_environments",
+    description = "List environments of a project, optionally filtered by name or state",
+    category = "environments",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListEnvironments {
+    /// Project path or ID
+    pub project: String,
+    /// Return the environment with exactly this name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Return environments whose name starts with this text (e.g., "review/")
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Filter by state: available, stopping, or stopped
+    #[serde(default)]
+    pub states: Option<String>,
+    /// Number of environments per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListEnvironments {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .optional_encoded("name", self.name.as_ref())
+            .optional_encoded("search", self.search.as_ref())
+            .optional("states", self.states.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/projects/{}/environments{}", project, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Stop an environment
+#[gitlab_tool(
+    name = "stop_environment",
+    description = "Stop an environment, running its on_stop job (e.g., tearing down a review app). Returns the environment with its new state",
+    category = "environments",
+    operation = "execute",
+    project_field = "project"
+)]
+pub struct StopEnvironment {
+    /// Project path or ID
+    pub project: String,
+    /// Environment ID
+    pub environment_id: u64,
+    /// Stop the environment without running its on_stop job
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[async_trait]
+impl ToolExecutor for StopEnvironment {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/environments/{}/stop",
+            project, self.environment_id
+        );
+
+        let mut body = serde_json::json!({});
+        if self.force {
+            body["force"] = serde_json::Value::Bool(true);
+        }
+
+        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Delete an environment
+#[gitlab_tool(
+    name = "delete_environment",
+    description = "Delete a stopped environment",
+    category = "environments",
+    operation = "delete",
+    project_field = "project"
+)]
+pub struct DeleteEnvironment {
+    /// Project path or ID
+    pub project: String,
+    /// Environment ID (the environment must be stopped first)
+    pub environment_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for DeleteEnvironment {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/environments/{}", project, self.environment_id);
+
+        ctx.gitlab.delete(&endpoint).await?;
+
+        Ok(ToolOutput::text(format!(
+            "Environment {} deleted successfully",
+            self.environment_id
+        )))
+    }
+}
@@ -7,7 +7,8 @@ use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
 use crate::util::QueryBuilder;
 use async_trait::async_trait;
-
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tanuki_mcp_macros::gitlab_tool;
 
 /// List groups
@@ -54,8 +55,7 @@ impl ToolExecutor for ListGroups {
             .optional("statistics", self.statistics.then_some("true"))
             .optional("order_by", self.order_by.as_ref())
             .optional("sort", self.sort.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/groups{}", query);
@@ -124,8 +124,7 @@ impl ToolExecutor for ListGroupMembers {
         let group = GitLabClient::encode_project(&self.group);
         let query_str = QueryBuilder::new()
             .optional_encoded("query", self.query.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/groups/{}/members{}", group, query_str);
@@ -134,6 +133,161 @@ impl ToolExecutor for ListGroupMembers {
     }
 }
 
+/// Role of a group or project member
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberAccessLevel {
+    /// Minimal access (5)
+    MinimalAccess,
+    /// Guest (10)
+    Guest,
+    /// Planner (15)
+    Planner,
+    /// Reporter (20)
+    Reporter,
+    /// Developer (30)
+    Developer,
+    /// Maintainer (40)
+    Maintainer,
+    /// Owner (50)
+    Owner,
+}
+
+impl MemberAccessLevel {
+    /// The numeric access level GitLab's API expects
+    pub fn as_u32(self) -> u32 {
+        match self {
+            MemberAccessLevel::MinimalAccess => 5,
+            MemberAccessLevel::Guest => 10,
+            MemberAccessLevel::Planner => 15,
+            MemberAccessLevel::Reporter => 20,
+            MemberAccessLevel::Developer => 30,
+            MemberAccessLevel::Maintainer => 40,
+            MemberAccessLevel::Owner => 50,
+        }
+    }
+}
+
+/// Add a member to a group
+#[gitlab_tool(
+    name = "add_group_member",
+    description = "Add a user to a group with the given role",
+    category = "groups",
+    operation = "write"
+)]
+pub struct AddGroupMember {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// ID of the user to add
+    pub user_id: u64,
+    /// Role: minimal_access, guest, planner, reporter, developer, maintainer, or owner
+    pub access_level: MemberAccessLevel,
+    /// Date the membership expires (YYYY-MM-DD)
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for AddGroupMember {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/members", group);
+
+        let mut body = serde_json::json!({
+            "user_id": self.user_id,
+            "access_level": self.access_level.as_u32(),
+        });
+        if let Some(ref expires_at) = self.expires_at {
+            body["expires_at"] = serde_json::Value::String(expires_at.clone());
+        }
+
+        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Change a group member's role or expiry
+#[gitlab_tool(
+    name = "edit_group_member",
+    description = "Change the role or membership expiry of a group member",
+    category = "groups",
+    operation = "write"
+)]
+pub struct EditGroupMember {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// ID of the member's user
+    pub user_id: u64,
+    /// New role: minimal_access, guest, planner, reporter, developer, maintainer, or owner
+    #[serde(default)]
+    pub access_level: Option<MemberAccessLevel>,
+    /// New expiry date (YYYY-MM-DD)
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for EditGroupMember {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/members/{}", group, self.user_id);
+
+        let mut body = serde_json::json!({});
+        if let Some(level) = self.access_level {
+            body["access_level"] = serde_json::Value::Number(level.as_u32().into());
+        }
+        if let Some(ref expires_at) = self.expires_at {
+            body["expires_at"] = serde_json::Value::String(expires_at.clone());
+        }
+        if body.as_object().is_some_and(|fields| fields.is_empty()) {
+            return Err(ToolError::InvalidArguments(
+                "At least one setting to change is required".to_string(),
+            ));
+        }
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Remove a member from a group
+#[gitlab_tool(
+    name = "remove_group_member",
+    description = "Remove a user from a group",
+    category = "groups",
+    operation = "delete"
+)]
+pub struct RemoveGroupMember {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// ID of the member's user
+    pub user_id: u64,
+    /// Keep the user's direct memberships in subgroups and projects
+    #[serde(default)]
+    pub skip_subresources: bool,
+}
+
+#[async_trait]
+impl ToolExecutor for RemoveGroupMember {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let query = QueryBuilder::new()
+            .optional(
+                "skip_subresources",
+                self.skip_subresources.then_some("true"),
+            )
+            .build();
+        let endpoint = format!("/groups/{}/members/{}{}", group, self.user_id, query);
+
+        ctx.gitlab.delete(&endpoint).await?;
+
+        Ok(ToolOutput::text(format!(
+            "User {} removed from group '{}'",
+            self.user_id, self.group
+        )))
+    }
+}
+
 /// List group projects
 #[gitlab_tool(
     name = "list_group_projects",
@@ -176,8 +330,7 @@ impl ToolExecutor for ListGroupProjects {
             .optional("archived", self.archived)
             .optional("visibility", self.visibility.as_ref())
             .optional_encoded("search", self.search.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/groups/{}/projects{}", group, query);
@@ -217,8 +370,7 @@ impl ToolExecutor for ListSubgroups {
         let query = QueryBuilder::new()
             .optional("statistics", self.statistics.then_some("true"))
             .optional_encoded("search", self.search.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/groups/{}/subgroups{}", group, query);
@@ -226,3 +378,49 @@ impl ToolExecutor for ListSubgroups {
         ToolOutput::json_value(result)
     }
 }
+
+/// List group epics
+#[gitlab_tool(
+    name = "list_group_epics",
+    description = "List epics in a group (GitLab EE Premium or Ultimate only)",
+    category = "groups",
+    operation = "read"
+)]
+pub struct ListGroupEpics {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Filter by state: opened, closed, or all
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Search in title and description
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Filter by labels (comma-separated)
+    #[serde(default)]
+    pub labels: Option<String>,
+    /// Number of epics per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListGroupEpics {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        ctx.require_enterprise("epics").await?;
+
+        let group = GitLabClient::encode_project(&self.group);
+        let query = QueryBuilder::new()
+            .optional("state", self.state.as_ref())
+            .optional_encoded("search", self.search.as_ref())
+            .optional_encoded("labels", self.labels.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/groups/{}/epics{}", group, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
@@ -0,0 +1,31 @@
+//! Instance tools
+//!
+//! Tools describing the GitLab instance itself.
+
+use crate::error::ToolError;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use async_trait::async_trait;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// Get GitLab version and edition
+#[gitlab_tool(
+    name = "get_gitlab_metadata",
+    description = "Get the GitLab version, revision, and edition (CE or EE). Check the edition before using Premium/Ultimate features such as epics",
+    category = "instance",
+    operation = "read"
+)]
+pub struct GetGitlabMetadata {}
+
+#[async_trait]
+impl ToolExecutor for GetGitlabMetadata {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let metadata = ctx.gitlab.metadata().await?;
+        ToolOutput::json_value(serde_json::json!({
+            "version": metadata.version,
+            "revision": metadata.revision,
+            "edition": metadata.edition(),
+            "enterprise": metadata.enterprise,
+        }))
+    }
+}
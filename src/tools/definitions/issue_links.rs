@@ -1,10 +1,13 @@
 //! Issue links tools
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tanuki_mcp_macros::gitlab_tool;
 
 use crate::error::ToolError;
 use crate::tools::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 // ============================================================================
@@ -13,8 +16,8 @@ use async_trait::async_trait;
 
 #[gitlab_tool(
     name = "list_issue_links",
-    description = "List all linked issues for a given issue",
-    category = "issues",
+    description = "List all linked issues for a given issue with the link_type of each",
+    category = "issue_links",
     operation = "read"
 )]
 pub struct ListIssueLinks {
@@ -22,13 +25,25 @@ pub struct ListIssueLinks {
     pub project: String,
     /// Issue IID
     pub issue_iid: u64,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Results per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
 }
 
 #[async_trait]
 impl ToolExecutor for ListIssueLinks {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = urlencoding::encode(&self.project);
-        let endpoint = format!("/projects/{}/issues/{}/links", project, self.issue_iid);
+        let query = QueryBuilder::new()
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!(
+            "/projects/{}/issues/{}/links{}",
+            project, self.issue_iid, query
+        );
 
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
         ToolOutput::json_value(result)
@@ -39,10 +54,22 @@ impl ToolExecutor for ListIssueLinks {
 // Create Issue Link
 // ============================================================================
 
+/// Relationship of the source issue to the target issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueLinkType {
+    /// The issues are related
+    RelatesTo,
+    /// The source issue blocks the target issue
+    Blocks,
+    /// The source issue is blocked by the target issue
+    IsBlockedBy,
+}
+
 #[gitlab_tool(
     name = "create_issue_link",
     description = "Create a link between two issues",
-    category = "issues",
+    category = "issue_links",
     operation = "write"
 )]
 pub struct CreateIssueLink {
@@ -54,9 +81,9 @@ pub struct CreateIssueLink {
     pub target_project: String,
     /// Target issue IID
     pub target_issue_iid: u64,
-    /// Link type: relates_to, blocks, is_blocked_by
+    /// Link type: relates_to (default), blocks, or is_blocked_by
     #[serde(default)]
-    pub link_type: Option<String>,
+    pub link_type: Option<IssueLinkType>,
 }
 
 #[async_trait]
@@ -70,7 +97,7 @@ impl ToolExecutor for CreateIssueLink {
             "target_issue_iid": self.target_issue_iid,
         });
 
-        if let Some(ref link_type) = self.link_type {
+        if let Some(link_type) = self.link_type {
             body["link_type"] = json!(link_type);
         }
 
@@ -86,7 +113,7 @@ impl ToolExecutor for CreateIssueLink {
 #[gitlab_tool(
     name = "delete_issue_link",
     description = "Remove a link between two issues",
-    category = "issues",
+    category = "issue_links",
     operation = "delete"
 )]
 pub struct DeleteIssueLink {
@@ -5,6 +5,7 @@ use tanuki_mcp_macros::gitlab_tool;
 
 use crate::error::ToolError;
 use crate::tools::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 // ============================================================================
@@ -40,26 +41,15 @@ pub struct ListIssueNotes {
 impl ToolExecutor for ListIssueNotes {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = urlencoding::encode(&self.project);
-        let mut endpoint = format!("/projects/{}/issues/{}/notes", project, self.issue_iid);
-
-        let mut params = Vec::new();
-        if let Some(ref sort) = self.sort {
-            params.push(format!("sort={}", sort));
-        }
-        if let Some(ref order_by) = self.order_by {
-            params.push(format!("order_by={}", order_by));
-        }
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page));
-        }
-
-        if !params.is_empty() {
-            endpoint.push('?');
-            endpoint.push_str(&params.join("&"));
-        }
+        let query = QueryBuilder::new()
+            .optional("sort", self.sort.as_ref())
+            .optional("order_by", self.order_by.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!(
+            "/projects/{}/issues/{}/notes{}",
+            project, self.issue_iid, query
+        );
 
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
         ToolOutput::json_value(result)
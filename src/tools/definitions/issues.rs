@@ -3,9 +3,9 @@
 //! Tools for managing GitLab issues.
 
 use crate::error::ToolError;
-use crate::gitlab::GitLabClient;
+use crate::gitlab::{GitLabClient, ResourceLabelEvent, ResourceStateEvent};
 use crate::tools::{ToolContext, ToolExecutor, ToolOutput};
-use crate::util::QueryBuilder;
+use crate::util::{MAX_PER_PAGE, QueryBuilder, add_scoped_labels};
 use async_trait::async_trait;
 use serde::Serialize;
 use tanuki_mcp_macros::gitlab_tool;
@@ -56,6 +56,10 @@ pub struct ListIssues {
     #[serde(default)]
     pub search: Option<String>,
 
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
     /// Page number (default: 1)
     #[serde(default = "default_page")]
     pub page: u32,
@@ -71,17 +75,97 @@ impl ToolExecutor for ListIssues {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
             .param("page", self.page)
-            .param("per_page", self.per_page.min(100))
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
             .optional("state", self.state.as_ref())
             .optional_encoded("labels", self.labels.as_ref())
             .optional_encoded("milestone", self.milestone.as_ref())
             .optional("assignee_id", self.assignee_id)
             .optional("author_id", self.author_id)
             .optional_encoded("search", self.search.as_ref())
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
             .build();
 
         let endpoint = format!("/projects/{}/issues{}", project, query);
-        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        add_scoped_labels(&mut response);
+        ToolOutput::json_value(response)
+    }
+}
+
+// ============================================================================
+// list_group_issues
+// ============================================================================
+
+/// List issues across all projects of a GitLab group with optional filtering by state, labels, milestone, assignee, or search terms.
+///
+/// Not project-scoped: access is checked against the global and category rules only.
+#[gitlab_tool(name = "list_group_issues", category = "issues", operation = "read")]
+pub struct ListGroupIssues {
+    /// Group ID or URL-encoded path (e.g., "my-group/subgroup")
+    pub group: String,
+
+    /// Filter by state: opened, closed, or all
+    #[serde(default)]
+    pub state: Option<String>,
+
+    /// Filter by labels (comma-separated)
+    #[serde(default)]
+    pub labels: Option<String>,
+
+    /// Filter by milestone title
+    #[serde(default)]
+    pub milestone: Option<String>,
+
+    /// Filter by assignee ID
+    #[serde(default)]
+    pub assignee_id: Option<u64>,
+
+    /// Filter by author ID
+    #[serde(default)]
+    pub author_id: Option<u64>,
+
+    /// Search in title and description
+    #[serde(default)]
+    pub search: Option<String>,
+
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (default: 20, max: 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListGroupIssues {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .optional("state", self.state.as_ref())
+            .optional_encoded("labels", self.labels.as_ref())
+            .optional_encoded("milestone", self.milestone.as_ref())
+            .optional("assignee_id", self.assignee_id)
+            .optional("author_id", self.author_id)
+            .optional_encoded("search", self.search.as_ref())
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
+            .build();
+
+        let endpoint = format!("/groups/{}/issues{}", group, query);
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        add_scoped_labels(&mut response);
         ToolOutput::json_value(response)
     }
 }
@@ -103,19 +187,66 @@ pub struct GetIssue {
 
     /// Issue IID (internal ID within the project)
     pub issue_iid: u64,
+
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
+    /// Also return the description rendered to HTML as `description_html`
+    #[serde(default)]
+    pub render_markdown: bool,
 }
 
 #[async_trait]
 impl ToolExecutor for GetIssue {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let query = QueryBuilder::new()
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
+            .build();
+        let endpoint = format!("/projects/{}/issues/{}{}", project, self.issue_iid, query);
 
-        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        add_scoped_labels(&mut response);
+        if self.render_markdown {
+            add_rendered_description(ctx, &self.project, &mut response).await?;
+        }
         ToolOutput::json_value(response)
     }
 }
 
+/// Render an issue or merge request description with GitLab's Markdown API
+///
+/// Adds the HTML as `description_html`. References such as `#12` are
+/// resolved within `project`.
+pub(crate) async fn add_rendered_description(
+    ctx: &ToolContext,
+    project: &str,
+    response: &mut serde_json::Value,
+) -> Result<(), ToolError> {
+    let Some(description) = response.get("description").and_then(|d| d.as_str()) else {
+        return Ok(());
+    };
+
+    // The Markdown API wants the project's full path, not its ID
+    let project = if project.chars().all(|c| c.is_ascii_digit()) {
+        ctx.gitlab.project_path(project).await?
+    } else {
+        project.to_string()
+    };
+    let body = serde_json::json!({
+        "text": description,
+        "gfm": true,
+        "project": project,
+    });
+    let rendered: serde_json::Value = ctx.gitlab.post("/markdown", &body).await?;
+    response["description_html"] = rendered["html"].clone();
+    Ok(())
+}
+
 // ============================================================================
 // create_issue
 // ============================================================================
@@ -246,6 +377,10 @@ pub struct UpdateIssue {
     /// Update confidentiality
     #[serde(default)]
     pub confidential: Option<bool>,
+
+    /// Iteration ID to assign the issue to (GitLab EE only)
+    #[serde(default)]
+    pub iteration_id: Option<u64>,
 }
 
 #[async_trait]
@@ -272,6 +407,12 @@ impl ToolExecutor for UpdateIssue {
             due_date: Option<&'a str>,
             #[serde(skip_serializing_if = "Option::is_none")]
             confidential: Option<bool>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            iteration_id: Option<u64>,
+        }
+
+        if self.iteration_id.is_some() {
+            ctx.require_enterprise("iterations").await?;
         }
 
         let body = UpdateIssueRequest {
@@ -283,6 +424,7 @@ impl ToolExecutor for UpdateIssue {
             assignee_ids: self.assignee_ids.as_deref(),
             due_date: self.due_date.as_deref(),
             confidential: self.confidential,
+            iteration_id: self.iteration_id,
         };
 
         let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
@@ -290,6 +432,203 @@ impl ToolExecutor for UpdateIssue {
     }
 }
 
+// ============================================================================
+// set_issue_confidential
+// ============================================================================
+
+/// Mark an issue as confidential or make it public again
+#[gitlab_tool(
+    name = "set_issue_confidential",
+    category = "issues",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetIssueConfidential {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+
+    /// Whether the issue is visible only to project members with at least Planner/Reporter access
+    pub confidential: bool,
+}
+
+#[async_trait]
+impl ToolExecutor for SetIssueConfidential {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let body = serde_json::json!({ "confidential": self.confidential });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        issue_flags(&response)
+    }
+}
+
+// ============================================================================
+// set_issue_discussion_locked
+// ============================================================================
+
+/// Lock or unlock the discussion of an issue (only project members can comment while locked)
+#[gitlab_tool(
+    name = "set_issue_discussion_locked",
+    category = "issues",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetIssueDiscussionLocked {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+
+    /// Whether the discussion is locked
+    pub discussion_locked: bool,
+}
+
+#[async_trait]
+impl ToolExecutor for SetIssueDiscussionLocked {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let body = serde_json::json!({ "discussion_locked": self.discussion_locked });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        issue_flags(&response)
+    }
+}
+
+/// Summarize the moderation flags of an updated issue
+fn issue_flags(issue: &serde_json::Value) -> Result<ToolOutput, ToolError> {
+    ToolOutput::json(serde_json::json!({
+        "iid": issue.get("iid"),
+        "web_url": issue.get("web_url"),
+        "confidential": issue.get("confidential").and_then(|v| v.as_bool()).unwrap_or(false),
+        "discussion_locked": issue.get("discussion_locked").and_then(|v| v.as_bool()).unwrap_or(false),
+    }))
+}
+
+// ============================================================================
+// set_issue_weight
+// ============================================================================
+
+/// Set the weight of an issue
+#[gitlab_tool(
+    name = "set_issue_weight",
+    description = "Set the weight of an issue and return its weight and iteration. Weights require GitLab EE; CE accepts the request but ignores the weight",
+    category = "issues",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetIssueWeight {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+
+    /// Issue weight (0 or greater)
+    pub weight: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for SetIssueWeight {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let body = serde_json::json!({ "weight": self.weight });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+        let mut result = serde_json::json!({
+            "iid": response.get("iid"),
+            "web_url": response.get("web_url"),
+            "weight": response.get("weight"),
+            "iteration": response.get("iteration"),
+        });
+        // CE drops the weight silently, so the issue comes back without one
+        if response.get("weight").is_none_or(|w| w.is_null()) {
+            result["notice"] =
+                serde_json::json!("GitLab ignored the weight: issue weights require GitLab EE");
+        }
+        ToolOutput::json_value(result)
+    }
+}
+
+// ============================================================================
+// close_issue
+// ============================================================================
+
+/// Close an issue
+#[gitlab_tool(
+    name = "close_issue",
+    category = "issues",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct CloseIssue {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for CloseIssue {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let body = serde_json::json!({ "state_event": "close" });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        issue_state(&response)
+    }
+}
+
+// ============================================================================
+// reopen_issue
+// ============================================================================
+
+/// Reopen a closed issue
+#[gitlab_tool(
+    name = "reopen_issue",
+    category = "issues",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct ReopenIssue {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for ReopenIssue {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/issues/{}", project, self.issue_iid);
+        let body = serde_json::json!({ "state_event": "reopen" });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        issue_state(&response)
+    }
+}
+
+/// Summarize the state of an issue after closing or reopening it
+fn issue_state(issue: &serde_json::Value) -> Result<ToolOutput, ToolError> {
+    ToolOutput::json(serde_json::json!({
+        "iid": issue.get("iid"),
+        "web_url": issue.get("web_url"),
+        "state": issue.get("state"),
+        "closed_at": issue.get("closed_at"),
+    }))
+}
+
 // ============================================================================
 // delete_issue
 // ============================================================================
@@ -322,3 +661,93 @@ impl ToolExecutor for DeleteIssue {
         }))
     }
 }
+
+// ============================================================================
+// list_issue_label_events
+// ============================================================================
+
+/// List the label history of an issue (labels added and removed, by whom and when)
+#[gitlab_tool(
+    name = "list_issue_label_events",
+    category = "issues",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListIssueLabelEvents {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (default: 20, max: 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListIssueLabelEvents {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/issues/{}/resource_label_events{}",
+            project, self.issue_iid, query
+        );
+        let events: Vec<ResourceLabelEvent> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(events)
+    }
+}
+
+// ============================================================================
+// list_issue_state_events
+// ============================================================================
+
+/// List the state history of an issue (closed, reopened, by whom and when)
+#[gitlab_tool(
+    name = "list_issue_state_events",
+    category = "issues",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListIssueStateEvents {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Issue IID
+    pub issue_iid: u64,
+
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (default: 20, max: 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListIssueStateEvents {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/issues/{}/resource_state_events{}",
+            project, self.issue_iid, query
+        );
+        let events: Vec<ResourceStateEvent> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(events)
+    }
+}
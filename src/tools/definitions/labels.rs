@@ -5,14 +5,55 @@
 use crate::error::ToolError;
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 use tanuki_mcp_macros::gitlab_tool;
 
+/// CSS color names GitLab accepts for label colors, separated by whitespace
+const CSS_COLOR_NAMES: &str = "\
+    aliceblue antiquewhite aqua aquamarine azure beige bisque black blanchedalmond blue \
+    blueviolet brown burlywood cadetblue chartreuse chocolate coral cornflowerblue \
+    cornsilk crimson cyan darkblue darkcyan darkgoldenrod darkgray darkgreen darkgrey \
+    darkkhaki darkmagenta darkolivegreen darkorange darkorchid darkred darksalmon \
+    darkseagreen darkslateblue darkslategray darkslategrey darkturquoise darkviolet \
+    deeppink deepskyblue dimgray dimgrey dodgerblue firebrick floralwhite forestgreen \
+    fuchsia gainsboro ghostwhite gold goldenrod gray green greenyellow grey honeydew \
+    hotpink indianred indigo ivory khaki lavender lavenderblush lawngreen lemonchiffon \
+    lightblue lightcoral lightcyan lightgoldenrodyellow lightgray lightgreen lightgrey \
+    lightpink lightsalmon lightseagreen lightskyblue lightslategray lightslategrey \
+    lightsteelblue lightyellow lime limegreen linen magenta maroon mediumaquamarine \
+    mediumblue mediumorchid mediumpurple mediumseagreen mediumslateblue \
+    mediumspringgreen mediumturquoise mediumvioletred midnightblue mintcream mistyrose \
+    moccasin navajowhite navy oldlace olive olivedrab orange orangered orchid \
+    palegoldenrod palegreen paleturquoise palevioletred papayawhip peachpuff peru pink \
+    plum powderblue purple rebeccapurple red rosybrown royalblue saddlebrown salmon \
+    sandybrown seagreen seashell sienna silver skyblue slateblue slategray slategrey \
+    snow springgreen steelblue tan teal thistle tomato turquoise violet wheat white \
+     whitesmoke yellow yellowgreen";
+
+/// Check that a label color is a hex code (`#RGB` or `#RRGGBB`) or a CSS color name
+fn validate_color(color: &str) -> Result<(), ToolError> {
+    let is_hex = color.strip_prefix('#').is_some_and(|digits| {
+        matches!(digits.len(), 3 | 6) && digits.bytes().all(|b| b.is_ascii_hexdigit())
+    });
+    let is_named = CSS_COLOR_NAMES
+        .split_whitespace()
+        .any(|name| name.eq_ignore_ascii_case(color));
+    if is_hex || is_named {
+        return Ok(());
+    }
+
+    Err(ToolError::InvalidArguments(format!(
+        "Invalid label color '{}': use a hex code such as \"#FF0000\" or a CSS color name such as \"red\"",
+        color
+    )))
+}
+
 /// List project labels
 #[gitlab_tool(
     name = "list_labels",
-    description = "List labels in a project",
+    description = "List labels in a project, optionally with ancestor group labels and issue and merge request counts",
     category = "labels",
     operation = "read"
 )]
@@ -22,6 +63,9 @@ pub struct ListLabels {
     /// Include labels from ancestor groups
     #[serde(default)]
     pub include_ancestor_groups: bool,
+    /// Include open issue, closed issue, and open merge request counts
+    #[serde(default)]
+    pub with_counts: bool,
     /// Search for labels matching this string
     #[serde(default)]
     pub search: Option<String>,
@@ -37,26 +81,15 @@ pub struct ListLabels {
 impl ToolExecutor for ListLabels {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let mut params = Vec::new();
-
-        if self.include_ancestor_groups {
-            params.push("include_ancestor_groups=true".to_string());
-        }
-        if let Some(ref search) = self.search {
-            params.push(format!("search={}", urlencoding::encode(search)));
-        }
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page.min(100)));
-        }
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
-
-        let query = if params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", params.join("&"))
-        };
+        let query = QueryBuilder::new()
+            .optional(
+                "include_ancestor_groups",
+                self.include_ancestor_groups.then_some("true"),
+            )
+            .optional("with_counts", self.with_counts.then_some("true"))
+            .optional_encoded("search", self.search.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
 
         let endpoint = format!("/projects/{}/labels{}", project, query);
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
@@ -95,7 +128,7 @@ impl ToolExecutor for GetLabel {
 /// Create a new label
 #[gitlab_tool(
     name = "create_label",
-    description = "Create a new label in a project",
+    description = "Create a new label in a project with a color, description, and optional priority",
     category = "labels",
     operation = "write"
 )]
@@ -104,7 +137,7 @@ pub struct CreateLabel {
     pub project: String,
     /// Label name
     pub name: String,
-    /// Label color (hex code with #, e.g., "#FF0000")
+    /// Label color: hex code with # (e.g., "#FF0000") or CSS color name (e.g., "red")
     pub color: String,
     /// Label description
     #[serde(default)]
@@ -117,6 +150,8 @@ pub struct CreateLabel {
 #[async_trait]
 impl ToolExecutor for CreateLabel {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        validate_color(&self.color)?;
+
         let project = GitLabClient::encode_project(&self.project);
         let endpoint = format!("/projects/{}/labels", project);
 
@@ -153,7 +188,7 @@ pub struct UpdateLabel {
     /// New label name
     #[serde(default)]
     pub new_name: Option<String>,
-    /// New label color (hex code with #)
+    /// New label color: hex code with # or CSS color name
     #[serde(default)]
     pub color: Option<String>,
     /// New description
@@ -167,6 +202,10 @@ pub struct UpdateLabel {
 #[async_trait]
 impl ToolExecutor for UpdateLabel {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        if let Some(ref color) = self.color {
+            validate_color(color)?;
+        }
+
         let project = GitLabClient::encode_project(&self.project);
         let label_id = urlencoding::encode(&self.label_id);
         let endpoint = format!("/projects/{}/labels/{}", project, label_id);
@@ -221,3 +260,31 @@ impl ToolExecutor for DeleteLabel {
         )))
     }
 }
+
+/// Promote a project label to a group label
+#[gitlab_tool(
+    name = "promote_project_label",
+    description = "Promote a project label to a group label, merging it with same-named labels in the group's other projects",
+    category = "labels",
+    operation = "execute",
+    project_field = "project"
+)]
+pub struct PromoteProjectLabel {
+    /// Project path or ID
+    pub project: String,
+    /// Label ID or name
+    pub label_id: String,
+}
+
+#[async_trait]
+impl ToolExecutor for PromoteProjectLabel {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let label_id = urlencoding::encode(&self.label_id);
+        let endpoint = format!("/projects/{}/labels/{}/promote", project, label_id);
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &serde_json::json!({})).await?;
+
+        ToolOutput::json_value(result)
+    }
+}
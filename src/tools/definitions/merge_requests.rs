@@ -2,12 +2,18 @@
 //!
 //! Tools for managing GitLab merge requests.
 
-use crate::error::ToolError;
-use crate::gitlab::GitLabClient;
+use crate::error::{GitLabError, ToolError};
+use crate::gitlab::{GitLabClient, ResourceLabelEvent, ResourceStateEvent};
+use crate::tools::definitions::commits::CommitAction;
+use crate::tools::definitions::issues::add_rendered_description;
 use crate::tools::{ToolContext, ToolExecutor, ToolOutput};
-use crate::util::QueryBuilder;
+use crate::util::{
+    MAX_PER_PAGE, QueryBuilder, add_scoped_labels, default_max_diff_length, truncate_diff,
+};
 use async_trait::async_trait;
-use serde::Serialize;
+use reqwest::Method;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tanuki_mcp_macros::gitlab_tool;
 
 fn default_page() -> u32 {
@@ -64,6 +70,19 @@ pub struct ListMergeRequests {
     #[serde(default)]
     pub search: Option<String>,
 
+    /// Ask GitLab to recheck the merge status of the returned merge requests
+    #[serde(default)]
+    pub with_merge_status_recheck: bool,
+
+    /// Only return merge requests that do (true) or do not (false) conflict
+    /// with their target branch. Applied to the fetched page.
+    #[serde(default)]
+    pub has_conflicts: Option<bool>,
+
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
     /// Page number
     #[serde(default = "default_page")]
     pub page: u32,
@@ -79,7 +98,7 @@ impl ToolExecutor for ListMergeRequests {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
             .param("page", self.page)
-            .param("per_page", self.per_page.min(100))
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
             .optional("state", self.state.as_ref())
             .optional_encoded("source_branch", self.source_branch.as_ref())
             .optional_encoded("target_branch", self.target_branch.as_ref())
@@ -88,10 +107,121 @@ impl ToolExecutor for ListMergeRequests {
             .optional("author_id", self.author_id)
             .optional("assignee_id", self.assignee_id)
             .optional_encoded("search", self.search.as_ref())
+            .optional(
+                "with_merge_status_recheck",
+                self.with_merge_status_recheck.then_some("true"),
+            )
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
             .build();
 
         let endpoint = format!("/projects/{}/merge_requests{}", project, query);
-        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        if let Some(has_conflicts) = self.has_conflicts
+            && let Some(merge_requests) = response.as_array_mut()
+        {
+            merge_requests.retain(|mr| is_conflicting(mr) == has_conflicts);
+        }
+        add_scoped_labels(&mut response);
+
+        ToolOutput::json_value(response)
+    }
+}
+
+/// Whether a merge request conflicts with its target branch
+fn is_conflicting(mr: &serde_json::Value) -> bool {
+    mr.get("has_conflicts").and_then(|c| c.as_bool()) == Some(true)
+        || mr.get("detailed_merge_status").and_then(|s| s.as_str()) == Some("conflict")
+}
+
+// ============================================================================
+// list_group_merge_requests
+// ============================================================================
+
+/// List merge requests across all projects of a GitLab group with optional filtering.
+///
+/// Not project-scoped: access is checked against the global and category rules only.
+#[gitlab_tool(
+    name = "list_group_merge_requests",
+    category = "merge_requests",
+    operation = "read"
+)]
+pub struct ListGroupMergeRequests {
+    /// Group ID or URL-encoded path
+    pub group: String,
+
+    /// Filter by state: opened, closed, merged, or all
+    #[serde(default)]
+    pub state: Option<String>,
+
+    /// Filter by source branch
+    #[serde(default)]
+    pub source_branch: Option<String>,
+
+    /// Filter by target branch
+    #[serde(default)]
+    pub target_branch: Option<String>,
+
+    /// Filter by labels (comma-separated)
+    #[serde(default)]
+    pub labels: Option<String>,
+
+    /// Filter by milestone title
+    #[serde(default)]
+    pub milestone: Option<String>,
+
+    /// Filter by author ID
+    #[serde(default)]
+    pub author_id: Option<u64>,
+
+    /// Filter by assignee ID
+    #[serde(default)]
+    pub assignee_id: Option<u64>,
+
+    /// Search in title and description
+    #[serde(default)]
+    pub search: Option<String>,
+
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
+    /// Page number
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (max 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListGroupMergeRequests {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .optional("state", self.state.as_ref())
+            .optional_encoded("source_branch", self.source_branch.as_ref())
+            .optional_encoded("target_branch", self.target_branch.as_ref())
+            .optional_encoded("labels", self.labels.as_ref())
+            .optional_encoded("milestone", self.milestone.as_ref())
+            .optional("author_id", self.author_id)
+            .optional("assignee_id", self.assignee_id)
+            .optional_encoded("search", self.search.as_ref())
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
+            .build();
+
+        let endpoint = format!("/groups/{}/merge_requests{}", group, query);
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        add_scoped_labels(&mut response);
         ToolOutput::json_value(response)
     }
 }
@@ -102,6 +232,9 @@ impl ToolExecutor for ListMergeRequests {
 
 /// Get detailed information about a specific merge request.
 ///
+/// The response includes `merge_status`, `detailed_merge_status`, `has_conflicts`,
+/// and the `head_pipeline` summary, so merge readiness can be checked before merging.
+///
 /// Note: Avoid setting `include_changes` to true as it can return enormous amounts of data.
 /// Use `get_merge_request_diffs` for reviewing changes - it supports pagination.
 #[gitlab_tool(
@@ -127,6 +260,14 @@ pub struct GetMergeRequest {
     /// Use `get_merge_request_diffs` instead if you need to review changes, as it supports pagination.
     #[serde(default)]
     pub include_changes: bool,
+
+    /// Return labels as objects with color and description instead of names
+    #[serde(default)]
+    pub with_labels_details: bool,
+
+    /// Also return the description rendered to HTML as `description_html`
+    #[serde(default)]
+    pub render_markdown: bool,
 }
 
 #[async_trait]
@@ -134,20 +275,31 @@ impl ToolExecutor for GetMergeRequest {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
 
+        let query = QueryBuilder::new()
+            .optional(
+                "with_labels_details",
+                self.with_labels_details.then_some("true"),
+            )
+            .build();
+
         // Choose endpoint based on what data is requested
         let endpoint = if self.include_changes {
             format!(
-                "/projects/{}/merge_requests/{}/changes",
-                project, self.merge_request_iid
+                "/projects/{}/merge_requests/{}/changes{}",
+                project, self.merge_request_iid, query
             )
         } else {
             format!(
-                "/projects/{}/merge_requests/{}",
-                project, self.merge_request_iid
+                "/projects/{}/merge_requests/{}{}",
+                project, self.merge_request_iid, query
             )
         };
 
-        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let mut response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        add_scoped_labels(&mut response);
+        if self.render_markdown {
+            add_rendered_description(ctx, &self.project, &mut response).await?;
+        }
         ToolOutput::json_value(response)
     }
 }
@@ -156,6 +308,26 @@ impl ToolExecutor for GetMergeRequest {
 // create_merge_request
 // ============================================================================
 
+/// Labels given either as a comma-separated string or as an array
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LabelList {
+    /// Comma-separated label names
+    Text(String),
+    /// Label names
+    List(Vec<String>),
+}
+
+impl LabelList {
+    /// Labels in the comma-separated form GitLab expects
+    pub fn to_comma_separated(&self) -> String {
+        match self {
+            LabelList::Text(labels) => labels.clone(),
+            LabelList::List(labels) => labels.join(","),
+        }
+    }
+}
+
 /// Create a new merge request in a GitLab project
 #[gitlab_tool(
     name = "create_merge_request",
@@ -188,9 +360,9 @@ pub struct CreateMergeRequest {
     #[serde(default)]
     pub reviewer_ids: Option<Vec<u64>>,
 
-    /// Labels (comma-separated)
+    /// Labels, comma-separated or as an array
     #[serde(default)]
-    pub labels: Option<String>,
+    pub labels: Option<LabelList>,
 
     /// Milestone ID
     #[serde(default)]
@@ -231,7 +403,7 @@ impl ToolExecutor for CreateMergeRequest {
             #[serde(skip_serializing_if = "Option::is_none")]
             reviewer_ids: Option<&'a [u64]>,
             #[serde(skip_serializing_if = "Option::is_none")]
-            labels: Option<&'a str>,
+            labels: Option<String>,
             #[serde(skip_serializing_if = "Option::is_none")]
             milestone_id: Option<u64>,
             #[serde(skip_serializing_if = "Option::is_none")]
@@ -251,7 +423,7 @@ impl ToolExecutor for CreateMergeRequest {
             description: self.description.as_deref(),
             assignee_ids: self.assignee_ids.as_deref(),
             reviewer_ids: self.reviewer_ids.as_deref(),
-            labels: self.labels.as_deref(),
+            labels: self.labels.as_ref().map(LabelList::to_comma_separated),
             milestone_id: self.milestone_id,
             draft: self.draft,
             allow_collaboration: self.allow_collaboration,
@@ -381,6 +553,158 @@ impl ToolExecutor for UpdateMergeRequest {
     }
 }
 
+// ============================================================================
+// set_merge_request_reviewers
+// ============================================================================
+
+/// Replace the reviewers of a merge request
+#[gitlab_tool(
+    name = "set_merge_request_reviewers",
+    category = "merge_requests",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetMergeRequestReviewers {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// User IDs of the reviewers (empty to remove all reviewers)
+    pub user_ids: Vec<u64>,
+}
+
+#[async_trait]
+impl ToolExecutor for SetMergeRequestReviewers {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}",
+            project, self.merge_request_iid
+        );
+        let body = serde_json::json!({ "reviewer_ids": self.user_ids });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        ToolOutput::json_value(response)
+    }
+}
+
+// ============================================================================
+// set_merge_request_assignees
+// ============================================================================
+
+/// Replace the assignees of a merge request
+#[gitlab_tool(
+    name = "set_merge_request_assignees",
+    category = "merge_requests",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetMergeRequestAssignees {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// User IDs of the assignees (empty to remove all assignees)
+    pub user_ids: Vec<u64>,
+}
+
+#[async_trait]
+impl ToolExecutor for SetMergeRequestAssignees {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}",
+            project, self.merge_request_iid
+        );
+        let body = serde_json::json!({ "assignee_ids": self.user_ids });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        ToolOutput::json_value(response)
+    }
+}
+
+// ============================================================================
+// close_merge_request
+// ============================================================================
+
+/// Close a merge request without merging it
+#[gitlab_tool(
+    name = "close_merge_request",
+    category = "merge_requests",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct CloseMergeRequest {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for CloseMergeRequest {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}",
+            project, self.merge_request_iid
+        );
+        let body = serde_json::json!({ "state_event": "close" });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        merge_request_state(&response)
+    }
+}
+
+// ============================================================================
+// reopen_merge_request
+// ============================================================================
+
+/// Reopen a closed merge request
+#[gitlab_tool(
+    name = "reopen_merge_request",
+    category = "merge_requests",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct ReopenMergeRequest {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for ReopenMergeRequest {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}",
+            project, self.merge_request_iid
+        );
+        let body = serde_json::json!({ "state_event": "reopen" });
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        merge_request_state(&response)
+    }
+}
+
+/// Summarize the state of a merge request after closing or reopening it
+fn merge_request_state(mr: &serde_json::Value) -> Result<ToolOutput, ToolError> {
+    ToolOutput::json(serde_json::json!({
+        "iid": mr.get("iid"),
+        "web_url": mr.get("web_url"),
+        "state": mr.get("state"),
+        "closed_at": mr.get("closed_at"),
+    }))
+}
+
 // ============================================================================
 // merge_merge_request
 // ============================================================================
@@ -457,12 +781,56 @@ impl ToolExecutor for MergeMergeRequest {
             merge_when_pipeline_succeeds: self.merge_when_pipeline_succeeds,
             sha: self.sha.as_deref(),
         };
+        let body = serde_json::to_value(&body)?;
+
+        // GitLab may answer 202 Accepted with an empty body while the merge is
+        // queued, so use the raw request path that tolerates empty responses.
+        let response = ctx
+            .gitlab
+            .request_json(Method::PUT, &endpoint, Some(&body))
+            .await
+            .map_err(|e| self.merge_error(e))?;
+
+        if response.is_null() {
+            return ToolOutput::json_value(serde_json::json!({
+                "status": "accepted",
+                "merge_request_iid": self.merge_request_iid,
+                "merge_when_pipeline_succeeds": self.merge_when_pipeline_succeeds.unwrap_or(false),
+                "message": "GitLab accepted the merge request; it will be merged asynchronously",
+            }));
+        }
 
-        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
         ToolOutput::json_value(response)
     }
 }
 
+impl MergeMergeRequest {
+    /// Translate merge-specific HTTP errors into actionable messages
+    fn merge_error(&self, error: GitLabError) -> ToolError {
+        let GitLabError::Api { status, message } = &error else {
+            return error.into();
+        };
+
+        let reason = match status {
+            405 if self.merge_when_pipeline_succeeds == Some(true) => {
+                "merge when pipeline succeeds could not be set; the pipeline may have already failed, or the merge request is a draft, closed, or blocked"
+            }
+            405 => {
+                "the merge request is not mergeable yet; it may be a draft, closed, blocked by unresolved discussions or approvals, or waiting for a pipeline (set merge_when_pipeline_succeeds to merge once CI passes)"
+            }
+            406 => "the source branch cannot be merged, likely due to conflicts",
+            409 => "the provided sha does not match the HEAD of the source branch",
+            422 => "the merge request cannot be merged in its current state",
+            _ => return error.into(),
+        };
+
+        ToolError::ExecutionFailed(format!(
+            "Cannot merge merge request !{} (HTTP {}): {}. GitLab said: {}",
+            self.merge_request_iid, status, reason, message
+        ))
+    }
+}
+
 // ============================================================================
 // get_merge_request_diffs
 // ============================================================================
@@ -500,7 +868,7 @@ impl ToolExecutor for GetMergeRequestDiffs {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
             .param("page", self.page)
-            .param("per_page", self.per_page.min(100))
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
             .optional("unidiff", self.unidiff.filter(|&b| b).map(|_| "true"))
             .build();
 
@@ -512,3 +880,530 @@ impl ToolExecutor for GetMergeRequestDiffs {
         ToolOutput::json_value(response)
     }
 }
+
+// ============================================================================
+// get_merge_request_changes
+// ============================================================================
+
+/// Get the per-file changes of a merge request, including old/new paths and diffs.
+///
+/// Large diffs are truncated per file (see `max_diff_length`). Use `file_path`
+/// to fetch the diff of a single file.
+#[gitlab_tool(
+    name = "get_merge_request_changes",
+    category = "merge_requests",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetMergeRequestChanges {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Only return the change for this file (matches old or new path)
+    #[serde(default)]
+    pub file_path: Option<String>,
+
+    /// Maximum length of each file diff before truncation (default: 10000)
+    #[serde(default = "default_max_diff_length")]
+    pub max_diff_length: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetMergeRequestChanges {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/changes",
+            project, self.merge_request_iid
+        );
+
+        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        let changes: Vec<serde_json::Value> = response
+            .get("changes")
+            .and_then(|c| c.as_array())
+            .map(|arr| arr.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|change| match &self.file_path {
+                Some(file_path) => {
+                    change.get("old_path").and_then(|p| p.as_str()) == Some(file_path)
+                        || change.get("new_path").and_then(|p| p.as_str()) == Some(file_path)
+                }
+                None => true,
+            })
+            .map(|change| {
+                let diff = change.get("diff").and_then(|d| d.as_str()).unwrap_or("");
+                let (diff, truncated) = truncate_diff(diff, self.max_diff_length);
+                serde_json::json!({
+                    "old_path": change.get("old_path"),
+                    "new_path": change.get("new_path"),
+                    "new_file": change.get("new_file"),
+                    "renamed_file": change.get("renamed_file"),
+                    "deleted_file": change.get("deleted_file"),
+                    "diff": diff,
+                    "truncated": truncated,
+                })
+            })
+            .collect();
+
+        if let Some(file_path) = &self.file_path
+            && changes.is_empty()
+        {
+            return Err(ToolError::InvalidArguments(format!(
+                "File '{}' is not changed in merge request !{}",
+                file_path, self.merge_request_iid
+            )));
+        }
+
+        ToolOutput::json(serde_json::json!({
+            "merge_request_iid": self.merge_request_iid,
+            "title": response.get("title"),
+            "source_branch": response.get("source_branch"),
+            "target_branch": response.get("target_branch"),
+            "changes_count": changes.len(),
+            "overflow": response.get("overflow").and_then(|o| o.as_bool()).unwrap_or(false),
+            "changes": changes,
+        }))
+    }
+}
+
+// ============================================================================
+// get_merge_request_diff_patch
+// ============================================================================
+
+/// Default maximum length of a merge request patch before it is truncated
+const DEFAULT_MAX_PATCH_LENGTH: usize = 100_000;
+
+/// Largest patch read from GitLab before truncation (16 MiB)
+const MAX_PATCH_SIZE: usize = 16 * 1024 * 1024;
+
+fn default_max_patch_length() -> usize {
+    DEFAULT_MAX_PATCH_LENGTH
+}
+
+/// Get the changes of a merge request as a single unified diff (patch) text. Requires GitLab 17.9 or later.
+///
+/// Uses the `raw_diffs` endpoint, which older instances lack. Large patches
+/// are truncated (see `max_length`); use `get_merge_request_changes` with
+/// `file_path` to read individual files in full.
+#[gitlab_tool(
+    name = "get_merge_request_diff_patch",
+    category = "merge_requests",
+    operation = "read",
+    output = "text",
+    project_field = "project"
+)]
+pub struct GetMergeRequestDiffPatch {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Maximum length of the patch before truncation (default: 100000, at most 16 MiB)
+    #[serde(default = "default_max_patch_length")]
+    pub max_length: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetMergeRequestDiffPatch {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/raw_diffs",
+            project, self.merge_request_iid
+        );
+
+        // The raw diffs endpoint returns plain text, not JSON
+        let max_length = self.max_length.min(MAX_PATCH_SIZE);
+        let bytes = ctx.gitlab.get_bytes(&endpoint, MAX_PATCH_SIZE).await?;
+        let patch = String::from_utf8_lossy(&bytes);
+        let (patch, truncated) = truncate_diff(&patch, max_length);
+        if truncated {
+            return Ok(ToolOutput::text(format!(
+                "{}\n\nPatch truncated to {} bytes; use get_merge_request_changes with file_path to read single files.",
+                patch, max_length
+            )));
+        }
+
+        Ok(ToolOutput::text(patch))
+    }
+}
+
+// ============================================================================
+// rebase_merge_request
+// ============================================================================
+
+/// Rebase a merge request's source branch onto its target branch.
+///
+/// The rebase runs asynchronously in GitLab; this tool does not wait for it to
+/// finish and only reports whether a rebase is in progress. Poll
+/// `get_merge_request` (`rebase_in_progress`, `merge_error`) to check the outcome.
+#[gitlab_tool(
+    name = "rebase_merge_request",
+    category = "merge_requests",
+    operation = "execute",
+    project_field = "project"
+)]
+pub struct RebaseMergeRequest {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Skip triggering a CI pipeline for the rebased commits
+    #[serde(default)]
+    pub skip_ci: Option<bool>,
+
+    /// Wait until the rebase has finished (default: false)
+    #[serde(default)]
+    pub wait: bool,
+}
+
+#[async_trait]
+impl ToolExecutor for RebaseMergeRequest {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/rebase",
+            project, self.merge_request_iid
+        );
+
+        #[derive(Serialize)]
+        struct RebaseMRRequest {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            skip_ci: Option<bool>,
+        }
+
+        let body = RebaseMRRequest {
+            skip_ci: self.skip_ci,
+        };
+
+        let response: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        let rebase_in_progress = response
+            .get("rebase_in_progress")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if !self.wait || !rebase_in_progress {
+            return ToolOutput::json(serde_json::json!({
+                "merge_request_iid": self.merge_request_iid,
+                "rebase_in_progress": rebase_in_progress,
+            }));
+        }
+
+        let mr_endpoint = format!(
+            "/projects/{}/merge_requests/{}?include_rebase_in_progress=true",
+            project, self.merge_request_iid
+        );
+        let mr = ctx
+            .poll_until(
+                "Rebase",
+                || ctx.gitlab.get(&mr_endpoint),
+                |mr| mr.get("rebase_in_progress").and_then(|v| v.as_bool()) != Some(true),
+            )
+            .await?;
+
+        // GitLab reports a failed rebase through merge_error
+        if let Some(error) = mr.get("merge_error").and_then(|e| e.as_str()) {
+            return Err(ToolError::ExecutionFailed(format!(
+                "Rebase failed: {}",
+                error
+            )));
+        }
+
+        ToolOutput::json(serde_json::json!({
+            "merge_request_iid": self.merge_request_iid,
+            "rebase_in_progress": false,
+        }))
+    }
+}
+
+// ============================================================================
+// list_merge_request_pipelines
+// ============================================================================
+
+/// List the pipelines run for a merge request, most recent first
+#[gitlab_tool(
+    name = "list_merge_request_pipelines",
+    category = "merge_requests",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListMergeRequestPipelines {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Page number
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (max 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListMergeRequestPipelines {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/pipelines{}",
+            project, self.merge_request_iid, query
+        );
+        let response: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(response)
+    }
+}
+
+// ============================================================================
+// list_merge_request_label_events
+// ============================================================================
+
+/// List the label history of a merge request (labels added and removed, by whom and when)
+#[gitlab_tool(
+    name = "list_merge_request_label_events",
+    category = "merge_requests",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListMergeRequestLabelEvents {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Page number
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (max 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListMergeRequestLabelEvents {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/resource_label_events{}",
+            project, self.merge_request_iid, query
+        );
+        let events: Vec<ResourceLabelEvent> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(events)
+    }
+}
+
+// ============================================================================
+// list_merge_request_state_events
+// ============================================================================
+
+/// List the state history of a merge request (closed, reopened, merged, by whom and when)
+#[gitlab_tool(
+    name = "list_merge_request_state_events",
+    category = "merge_requests",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListMergeRequestStateEvents {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Merge request IID
+    pub merge_request_iid: u64,
+
+    /// Page number
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    /// Items per page (max 100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[async_trait]
+impl ToolExecutor for ListMergeRequestStateEvents {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("page", self.page)
+            .param("per_page", self.per_page.min(MAX_PER_PAGE))
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/resource_state_events{}",
+            project, self.merge_request_iid, query
+        );
+        let events: Vec<ResourceStateEvent> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(events)
+    }
+}
+
+// ============================================================================
+// propose_change
+// ============================================================================
+
+/// Create a branch, commit file changes to it, and open a merge request
+#[gitlab_tool(
+    name = "propose_change",
+    description = "Propose a change in one step: create a branch from the base branch, commit the file actions to it, and open a merge request back into the base branch. The branch is deleted again if the commit or merge request cannot be created.",
+    category = "merge_requests",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct ProposeChange {
+    /// Project ID or URL-encoded path
+    pub project: String,
+
+    /// Branch to start from and to merge back into
+    pub base_branch: String,
+
+    /// Name of the new branch
+    pub branch: String,
+
+    /// File actions to commit
+    pub actions: Vec<CommitAction>,
+
+    /// Commit message (defaults to the MR title)
+    #[serde(default)]
+    pub commit_message: Option<String>,
+
+    /// MR title
+    pub title: String,
+
+    /// MR description (Markdown)
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Create the MR as draft
+    #[serde(default)]
+    pub draft: Option<bool>,
+
+    /// Delete the branch after merge
+    #[serde(default)]
+    pub remove_source_branch: Option<bool>,
+}
+
+impl ProposeChange {
+    /// Delete the new branch after a later step failed, keeping that step's error
+    async fn roll_back(&self, ctx: &ToolContext, step: &str, error: ToolError) -> ToolError {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/repository/branches/{}",
+            project,
+            urlencoding::encode(&self.branch)
+        );
+
+        match ctx.gitlab.delete(&endpoint).await {
+            Ok(()) => ToolError::ExecutionFailed(format!(
+                "Failed to {}: {}. Branch '{}' was deleted again",
+                step, error, self.branch
+            )),
+            Err(e) => {
+                tracing::warn!(branch = %self.branch, error = %e, "Failed to roll back branch");
+                ToolError::ExecutionFailed(format!(
+                    "Failed to {}: {}. Branch '{}' could not be deleted: {}",
+                    step, error, self.branch, e
+                ))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ProposeChange {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        if self.actions.is_empty() {
+            return Err(ToolError::InvalidArguments(
+                "At least one action is required".to_string(),
+            ));
+        }
+        for action in &self.actions {
+            action.validate()?;
+        }
+
+        let project = GitLabClient::encode_project(&self.project);
+
+        let branch: serde_json::Value = ctx
+            .gitlab
+            .post(
+                &format!("/projects/{}/repository/branches", project),
+                &serde_json::json!({"branch": self.branch, "ref": self.base_branch}),
+            )
+            .await?;
+
+        let commit_body = serde_json::json!({
+            "branch": self.branch,
+            "commit_message": self.commit_message.as_deref().unwrap_or(&self.title),
+            "actions": self.actions,
+        });
+        let commit: serde_json::Value = match ctx
+            .gitlab
+            .post(
+                &format!("/projects/{}/repository/commits", project),
+                &commit_body,
+            )
+            .await
+        {
+            Ok(commit) => commit,
+            Err(e) => return Err(self.roll_back(ctx, "commit the changes", e.into()).await),
+        };
+
+        let mut mr_body = serde_json::json!({
+            "source_branch": self.branch,
+            "target_branch": self.base_branch,
+            "title": self.title,
+        });
+        if let Some(ref description) = self.description {
+            mr_body["description"] = serde_json::Value::String(description.clone());
+        }
+        if let Some(draft) = self.draft {
+            mr_body["draft"] = serde_json::Value::Bool(draft);
+        }
+        if let Some(remove_source_branch) = self.remove_source_branch {
+            mr_body["remove_source_branch"] = serde_json::Value::Bool(remove_source_branch);
+        }
+        let merge_request: serde_json::Value = match ctx
+            .gitlab
+            .post(&format!("/projects/{}/merge_requests", project), &mr_body)
+            .await
+        {
+            Ok(merge_request) => merge_request,
+            Err(e) => {
+                return Err(self
+                    .roll_back(ctx, "create the merge request", e.into())
+                    .await);
+            }
+        };
+
+        ToolOutput::json(serde_json::json!({
+            "branch": branch.get("name"),
+            "commit": {
+                "id": commit.get("id"),
+                "short_id": commit.get("short_id"),
+                "title": commit.get("title"),
+            },
+            "merge_request": merge_request,
+        }))
+    }
+}
@@ -1,6 +1,6 @@
 //! Milestone tools
 //!
-//! Tools for managing project milestones.
+//! Tools for managing project and group milestones.
 
 use crate::error::ToolError;
 use crate::gitlab::GitLabClient;
@@ -48,8 +48,7 @@ impl ToolExecutor for ListMilestones {
                 "include_parent_milestones",
                 self.include_parent_milestones.then_some("true"),
             )
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/milestones{}", project, query);
@@ -246,8 +245,7 @@ impl ToolExecutor for GetMilestoneIssues {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!(
@@ -284,8 +282,7 @@ impl ToolExecutor for GetMilestoneMergeRequests {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!(
@@ -296,3 +293,213 @@ impl ToolExecutor for GetMilestoneMergeRequests {
         ToolOutput::json_value(result)
     }
 }
+
+/// List group milestones
+#[gitlab_tool(
+    name = "list_group_milestones",
+    description = "List milestones of a group (shared by all projects in the group)",
+    category = "milestones",
+    operation = "read"
+)]
+pub struct ListGroupMilestones {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Filter by state: active, closed, or all
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Search by title
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Include milestones from parent groups
+    #[serde(default)]
+    pub include_parent_milestones: bool,
+    /// Number of milestones per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListGroupMilestones {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let query = QueryBuilder::new()
+            .optional("state", self.state.as_ref())
+            .optional_encoded("search", self.search.as_ref())
+            .optional(
+                "include_parent_milestones",
+                self.include_parent_milestones.then_some("true"),
+            )
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/groups/{}/milestones{}", group, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Get a specific group milestone
+#[gitlab_tool(
+    name = "get_group_milestone",
+    description = "Get details of a specific group milestone",
+    category = "milestones",
+    operation = "read"
+)]
+pub struct GetGroupMilestone {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Milestone ID
+    pub milestone_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for GetGroupMilestone {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/milestones/{}", group, self.milestone_id);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Create a new group milestone
+#[gitlab_tool(
+    name = "create_group_milestone",
+    description = "Create a new milestone in a group",
+    category = "milestones",
+    operation = "write"
+)]
+pub struct CreateGroupMilestone {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Milestone title
+    pub title: String,
+    /// Milestone description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Due date (YYYY-MM-DD format)
+    #[serde(default)]
+    pub due_date: Option<String>,
+    /// Start date (YYYY-MM-DD format)
+    #[serde(default)]
+    pub start_date: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for CreateGroupMilestone {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/milestones", group);
+
+        let mut body = serde_json::json!({
+            "title": self.title,
+        });
+
+        if let Some(ref description) = self.description {
+            body["description"] = serde_json::Value::String(description.clone());
+        }
+        if let Some(ref due_date) = self.due_date {
+            body["due_date"] = serde_json::Value::String(due_date.clone());
+        }
+        if let Some(ref start_date) = self.start_date {
+            body["start_date"] = serde_json::Value::String(start_date.clone());
+        }
+
+        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Update a group milestone
+#[gitlab_tool(
+    name = "update_group_milestone",
+    description = "Update an existing group milestone",
+    category = "milestones",
+    operation = "write"
+)]
+pub struct UpdateGroupMilestone {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Milestone ID
+    pub milestone_id: u64,
+    /// New title
+    #[serde(default)]
+    pub title: Option<String>,
+    /// New description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// New due date (YYYY-MM-DD format)
+    #[serde(default)]
+    pub due_date: Option<String>,
+    /// New start date (YYYY-MM-DD format)
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// New state: close or activate
+    #[serde(default)]
+    pub state_event: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for UpdateGroupMilestone {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/milestones/{}", group, self.milestone_id);
+
+        let mut body = serde_json::json!({});
+
+        if let Some(ref title) = self.title {
+            body["title"] = serde_json::Value::String(title.clone());
+        }
+        if let Some(ref description) = self.description {
+            body["description"] = serde_json::Value::String(description.clone());
+        }
+        if let Some(ref due_date) = self.due_date {
+            body["due_date"] = serde_json::Value::String(due_date.clone());
+        }
+        if let Some(ref start_date) = self.start_date {
+            body["start_date"] = serde_json::Value::String(start_date.clone());
+        }
+        if let Some(ref state_event) = self.state_event {
+            body["state_event"] = serde_json::Value::String(state_event.clone());
+        }
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Delete a group milestone
+#[gitlab_tool(
+    name = "delete_group_milestone",
+    description = "Delete a milestone from a group",
+    category = "milestones",
+    operation = "delete"
+)]
+pub struct DeleteGroupMilestone {
+    /// Group ID or URL-encoded path
+    pub group: String,
+    /// Milestone ID
+    pub milestone_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for DeleteGroupMilestone {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let group = GitLabClient::encode_project(&self.group);
+        let endpoint = format!("/groups/{}/milestones/{}", group, self.milestone_id);
+
+        ctx.gitlab.delete(&endpoint).await?;
+
+        Ok(ToolOutput::text(format!(
+            "Group milestone {} deleted successfully",
+            self.milestone_id
+        )))
+    }
+}
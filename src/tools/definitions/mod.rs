@@ -2,9 +2,13 @@
 //!
 //! This module contains all GitLab MCP tool implementations.
 
+pub mod access_tokens;
 pub mod branches;
 pub mod commits;
+pub mod deploy_keys;
+pub mod environments;
 pub mod groups;
+pub mod instance;
 pub mod issue_links;
 pub mod issue_notes;
 pub mod issues;
@@ -16,17 +20,52 @@ pub mod mr_drafts;
 pub mod namespaces;
 pub mod pipelines;
 pub mod projects;
+pub mod raw;
 pub mod releases;
 pub mod repository;
+pub mod runners;
 pub mod search;
 pub mod tags;
 pub mod users;
 pub mod wiki;
 
-use crate::tools::ToolRegistry;
+use crate::access_control::PatternMatcher;
+use crate::config::ToolsConfig;
+use crate::tools::{ToolInfo, ToolRegistry};
+use tracing::warn;
 
 /// Register all tools with the registry
 pub fn register_all_tools(registry: &mut ToolRegistry) {
     // Auto-register all tools using #[gitlab_tool] macro
     registry.register_all_auto();
 }
+
+/// Register all tools that are enabled by the given configuration
+///
+/// Optional tools (e.g., the raw `gitlab_request` escape hatch) are only
+/// registered when explicitly enabled. Tools turned off by the `enabled` /
+/// `disabled` patterns are removed and reported as disabled when called.
+pub fn register_enabled_tools(registry: &mut ToolRegistry, config: &ToolsConfig) {
+    register_all_tools(registry);
+
+    if !config.enable_raw_request {
+        registry.unregister(raw::GitLabRequest::name());
+    }
+
+    // Patterns are validated when the configuration is loaded
+    let enabled = PatternMatcher::new(&config.enabled).unwrap_or_else(|e| {
+        warn!(error = %e, "Ignoring invalid tools.enabled patterns");
+        PatternMatcher::empty()
+    });
+    let disabled = PatternMatcher::new(&config.disabled).unwrap_or_else(|e| {
+        warn!(error = %e, "Ignoring invalid tools.disabled patterns");
+        PatternMatcher::empty()
+    });
+
+    let names: Vec<String> = registry.tool_names().map(str::to_string).collect();
+    for name in names {
+        if (!enabled.is_empty() && !enabled.matches(&name)) || disabled.matches(&name) {
+            registry.disable(&name);
+        }
+    }
+}
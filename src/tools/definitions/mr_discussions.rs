@@ -5,6 +5,7 @@
 use crate::error::ToolError;
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 use schemars::JsonSchema;
@@ -29,37 +30,61 @@ pub struct ListMrDiscussions {
     /// Page number
     #[serde(default)]
     pub page: Option<u32>,
+    /// Fetch every page instead of one (page and per_page are ignored). If
+    /// the page limit is hit, the result is an object with `items`,
+    /// `truncated: true`, and the `next_page` to continue from
+    #[serde(default)]
+    pub all_pages: bool,
+    /// Only return threads that still have unresolved notes
+    #[serde(default)]
+    pub only_unresolved: bool,
 }
 
 #[async_trait]
 impl ToolExecutor for ListMrDiscussions {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let mut params = Vec::new();
-
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page.min(100)));
-        }
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
+        let base = format!(
+            "/projects/{}/merge_requests/{}/discussions",
+            project, self.merge_request_iid
+        );
 
-        let query = if params.is_empty() {
-            String::new()
+        let (discussions, next_page) = if self.all_pages {
+            let all = ctx.gitlab.get_all_pages(&base).await?;
+            (all.items, all.next_page)
         } else {
-            format!("?{}", params.join("&"))
+            let query = QueryBuilder::new()
+                .pagination(self.page, self.per_page)
+                .build();
+            (ctx.gitlab.get(&format!("{}{}", base, query)).await?, None)
         };
 
-        let endpoint = format!(
-            "/projects/{}/merge_requests/{}/discussions{}",
-            project, self.merge_request_iid, query
-        );
-        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        let discussions: Vec<serde_json::Value> = if self.only_unresolved {
+            discussions.into_iter().filter(is_unresolved).collect()
+        } else {
+            discussions
+        };
 
-        ToolOutput::json_value(result)
+        match next_page {
+            Some(next_page) => ToolOutput::json_value(serde_json::json!({
+                "items": discussions,
+                "truncated": true,
+                "next_page": next_page,
+            })),
+            None => ToolOutput::json_value(serde_json::Value::Array(discussions)),
+        }
     }
 }
 
+/// Whether a discussion has a resolvable note that is not resolved yet
+fn is_unresolved(discussion: &serde_json::Value) -> bool {
+    discussion["notes"].as_array().is_some_and(|notes| {
+        notes.iter().any(|note| {
+            note["resolvable"].as_bool() == Some(true) && note["resolved"].as_bool() != Some(true)
+        })
+    })
+}
+
 /// Get a specific discussion
 #[gitlab_tool(
     name = "get_mr_discussion",
@@ -268,12 +293,54 @@ impl ToolExecutor for DeleteMrDiscussionNote {
     }
 }
 
+/// Set a discussion thread's resolved state and report the state GitLab returns
+///
+/// A thread counts as resolved when every resolvable note in it is.
+async fn set_discussion_resolved(
+    ctx: &ToolContext,
+    project: &str,
+    merge_request_iid: u64,
+    discussion_id: &str,
+    resolved: bool,
+) -> Result<ToolOutput, ToolError> {
+    let project = GitLabClient::encode_project(project);
+    let endpoint = format!(
+        "/projects/{}/merge_requests/{}/discussions/{}?resolved={}",
+        project,
+        merge_request_iid,
+        urlencoding::encode(discussion_id),
+        resolved
+    );
+
+    let discussion: serde_json::Value = ctx.gitlab.put(&endpoint, &serde_json::json!({})).await?;
+
+    let notes = discussion
+        .get("notes")
+        .and_then(|notes| notes.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let resolvable: Vec<_> = notes
+        .iter()
+        .filter(|note| note.get("resolvable").and_then(|v| v.as_bool()) == Some(true))
+        .collect();
+    let is_resolved = !resolvable.is_empty()
+        && resolvable
+            .iter()
+            .all(|note| note.get("resolved").and_then(|v| v.as_bool()) == Some(true));
+
+    ToolOutput::json_value(serde_json::json!({
+        "discussion_id": discussion.get("id").cloned().unwrap_or_else(|| discussion_id.into()),
+        "resolvable": !resolvable.is_empty(),
+        "resolved": is_resolved,
+    }))
+}
+
 /// Resolve a discussion thread
 #[gitlab_tool(
     name = "resolve_mr_discussion",
-    description = "Resolve or unresolve a discussion thread",
+    description = "Mark a merge request discussion thread as resolved",
     category = "mr_discussions",
-    operation = "write"
+    operation = "execute"
 )]
 pub struct ResolveMrDiscussion {
     /// Project path or ID
@@ -282,24 +349,52 @@ pub struct ResolveMrDiscussion {
     pub merge_request_iid: u64,
     /// Discussion ID
     pub discussion_id: String,
-    /// Resolve (true) or unresolve (false)
-    pub resolved: bool,
+    /// Deprecated: use unresolve_mr_discussion instead. `false` still reopens
+    /// the thread for clients written against the earlier signature
+    #[serde(default)]
+    pub resolved: Option<bool>,
 }
 
 #[async_trait]
 impl ToolExecutor for ResolveMrDiscussion {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
-        let project = GitLabClient::encode_project(&self.project);
-        let endpoint = format!(
-            "/projects/{}/merge_requests/{}/discussions/{}",
-            project, self.merge_request_iid, self.discussion_id
-        );
+        set_discussion_resolved(
+            ctx,
+            &self.project,
+            self.merge_request_iid,
+            &self.discussion_id,
+            self.resolved.unwrap_or(true),
+        )
+        .await
+    }
+}
 
-        let body = serde_json::json!({
-            "resolved": self.resolved,
-        });
+/// Unresolve a discussion thread
+#[gitlab_tool(
+    name = "unresolve_mr_discussion",
+    description = "Reopen a resolved merge request discussion thread",
+    category = "mr_discussions",
+    operation = "execute"
+)]
+pub struct UnresolveMrDiscussion {
+    /// Project path or ID
+    pub project: String,
+    /// Merge request IID
+    pub merge_request_iid: u64,
+    /// Discussion ID
+    pub discussion_id: String,
+}
 
-        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
-        ToolOutput::json_value(result)
+#[async_trait]
+impl ToolExecutor for UnresolveMrDiscussion {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        set_discussion_resolved(
+            ctx,
+            &self.project,
+            self.merge_request_iid,
+            &self.discussion_id,
+            false,
+        )
+        .await
     }
 }
@@ -5,6 +5,7 @@ use tanuki_mcp_macros::gitlab_tool;
 
 use crate::error::ToolError;
 use crate::tools::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 // ============================================================================
@@ -34,24 +35,14 @@ pub struct ListMrDraftNotes {
 impl ToolExecutor for ListMrDraftNotes {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = urlencoding::encode(&self.project);
-        let mut endpoint = format!(
-            "/projects/{}/merge_requests/{}/draft_notes",
-            project, self.merge_request_iid
+        let query = QueryBuilder::new()
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/draft_notes{}",
+            project, self.merge_request_iid, query
         );
 
-        let mut params = Vec::new();
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page));
-        }
-
-        if !params.is_empty() {
-            endpoint.push('?');
-            endpoint.push_str(&params.join("&"));
-        }
-
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
         ToolOutput::json_value(result)
     }
@@ -4,6 +4,7 @@
 
 use crate::error::ToolError;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 use tanuki_mcp_macros::gitlab_tool;
@@ -33,26 +34,11 @@ pub struct ListNamespaces {
 #[async_trait]
 impl ToolExecutor for ListNamespaces {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
-        let mut params = Vec::new();
-
-        if let Some(ref search) = self.search {
-            params.push(format!("search={}", urlencoding::encode(search)));
-        }
-        if self.owned_only {
-            params.push("owned_only=true".to_string());
-        }
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page.min(100)));
-        }
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
-
-        let query = if params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", params.join("&"))
-        };
+        let query = QueryBuilder::new()
+            .optional_encoded("search", self.search.as_ref())
+            .optional("owned_only", self.owned_only.then_some("true"))
+            .pagination(self.page, self.per_page)
+            .build();
 
         let endpoint = format!("/namespaces{}", query);
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
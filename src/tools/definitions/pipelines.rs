@@ -2,11 +2,12 @@
 //!
 //! Tools for managing CI/CD pipelines and jobs.
 
-use crate::error::ToolError;
+use crate::error::{GitLabError, ToolError};
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
-use crate::util::QueryBuilder;
+use crate::util::{QueryBuilder, ZIP_TAIL_LEN, find_central_directory, parse_central_directory};
 use async_trait::async_trait;
+use base64::Engine;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -55,8 +56,7 @@ impl ToolExecutor for ListPipelines {
             .optional("sha", self.sha.as_ref())
             .optional_encoded("username", self.username.as_ref())
             .optional("sort", self.sort.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/pipelines{}", project, query);
@@ -245,6 +245,9 @@ pub struct ListPipelineJobs {
     /// Number of jobs per page (max 100)
     #[serde(default)]
     pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
 }
 
 #[async_trait]
@@ -253,7 +256,7 @@ impl ToolExecutor for ListPipelineJobs {
         let project = GitLabClient::encode_project(&self.project);
         let mut query = QueryBuilder::new()
             .optional("include_retried", self.include_retried.then_some("true"))
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
+            .pagination(self.page, self.per_page)
             .build();
 
         // Handle scope[] array parameters manually since QueryBuilder doesn't support arrays
@@ -273,6 +276,52 @@ impl ToolExecutor for ListPipelineJobs {
     }
 }
 
+/// List trigger jobs (bridges) in a pipeline
+#[gitlab_tool(
+    name = "list_pipeline_bridges",
+    description = "List the trigger jobs (bridges) of a pipeline; each bridge's downstream_pipeline identifies the child or multi-project pipeline it started",
+    category = "pipelines",
+    operation = "read"
+)]
+pub struct ListPipelineBridges {
+    /// Project path or ID
+    pub project: String,
+    /// Pipeline ID
+    pub pipeline_id: u64,
+    /// Filter by scope: created, pending, running, failed, success, canceled, skipped, manual
+    #[serde(default)]
+    pub scope: Option<Vec<String>>,
+    /// Number of bridges per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListPipelineBridges {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = self
+            .scope
+            .iter()
+            .flatten()
+            .fold(QueryBuilder::new(), |query, scope| {
+                query.param("scope[]", scope)
+            })
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!(
+            "/projects/{}/pipelines/{}/bridges{}",
+            project, self.pipeline_id, query
+        );
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
 /// Get a specific job
 #[gitlab_tool(
     name = "get_job",
@@ -304,7 +353,8 @@ impl ToolExecutor for GetJob {
     name = "get_job_log",
     description = "Get the log (trace) output of a job",
     category = "pipelines",
-    operation = "read"
+    operation = "read",
+    output = "text"
 )]
 pub struct GetJobLog {
     /// Project path or ID
@@ -441,3 +491,342 @@ impl ToolExecutor for GetPipelineVariables {
         ToolOutput::json_value(result)
     }
 }
+
+/// Default maximum number of failed tests listed in a test report
+const DEFAULT_MAX_FAILED_TESTS: usize = 50;
+
+fn default_max_failed_tests() -> usize {
+    DEFAULT_MAX_FAILED_TESTS
+}
+
+/// Get the test report of a pipeline
+#[gitlab_tool(
+    name = "get_pipeline_test_report",
+    description = "Get a pipeline's test report: per-suite counts plus the names and messages of failed tests",
+    category = "pipelines",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetPipelineTestReport {
+    /// Project path or ID
+    pub project: String,
+    /// Pipeline ID
+    pub pipeline_id: u64,
+    /// Maximum number of failed tests to list across all suites (default: 50)
+    #[serde(default = "default_max_failed_tests")]
+    pub max_failed_tests: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetPipelineTestReport {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!(
+            "/projects/{}/pipelines/{}/test_report",
+            project, self.pipeline_id
+        );
+
+        let report: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        let mut remaining = self.max_failed_tests;
+        let mut truncated = false;
+        let suites: Vec<serde_json::Value> = report["test_suites"]
+            .as_array()
+            .map(|suites| suites.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|suite| {
+                let failed: Vec<&serde_json::Value> = suite["test_cases"]
+                    .as_array()
+                    .map(|cases| cases.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|case| matches!(case["status"].as_str(), Some("failed" | "error")))
+                    .collect();
+
+                let shown = failed.len().min(remaining);
+                remaining -= shown;
+                truncated |= shown < failed.len();
+
+                let failed_tests: Vec<serde_json::Value> = failed[..shown]
+                    .iter()
+                    .map(|case| {
+                        serde_json::json!({
+                            "name": case["name"],
+                            "classname": case["classname"],
+                            "status": case["status"],
+                            "message": case["system_output"],
+                        })
+                    })
+                    .collect();
+
+                serde_json::json!({
+                    "name": suite["name"],
+                    "total_count": suite["total_count"],
+                    "success_count": suite["success_count"],
+                    "failed_count": suite["failed_count"],
+                    "skipped_count": suite["skipped_count"],
+                    "error_count": suite["error_count"],
+                    "failed_tests": failed_tests,
+                })
+            })
+            .collect();
+
+        ToolOutput::json_value(serde_json::json!({
+            "pipeline_id": self.pipeline_id,
+            "total_time": report["total_time"],
+            "total_count": report["total_count"],
+            "success_count": report["success_count"],
+            "failed_count": report["failed_count"],
+            "skipped_count": report["skipped_count"],
+            "error_count": report["error_count"],
+            "failed_tests_truncated": truncated,
+            "test_suites": suites,
+        }))
+    }
+}
+
+/// Default maximum size of downloaded job artifacts (1 MiB)
+const DEFAULT_MAX_ARTIFACT_SIZE: usize = 1024 * 1024;
+
+/// Largest job artifact download a caller may ask for (16 MiB)
+const MAX_ARTIFACT_SIZE: usize = 16 * 1024 * 1024;
+
+fn default_max_artifact_size() -> usize {
+    DEFAULT_MAX_ARTIFACT_SIZE
+}
+
+/// Download the artifacts archive of a job
+#[gitlab_tool(
+    name = "get_job_artifacts",
+    description = "Download a job's artifacts archive (zip) as a base64-encoded blob, subject to a size limit",
+    category = "pipelines",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetJobArtifacts {
+    /// Project path or ID
+    pub project: String,
+    /// Job ID
+    pub job_id: u64,
+    /// Maximum archive size in bytes (default: 1 MiB, at most 16 MiB)
+    #[serde(default = "default_max_artifact_size")]
+    pub max_size_bytes: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetJobArtifacts {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/jobs/{}/artifacts", project, self.job_id);
+
+        let max_size = self.max_size_bytes.min(MAX_ARTIFACT_SIZE);
+        let bytes = ctx.gitlab.get_bytes(&endpoint, max_size).await?;
+
+        ToolOutput::json_value(serde_json::json!({
+            "job_id": self.job_id,
+            "size": bytes.len(),
+            "encoding": "base64",
+            "content": base64::engine::general_purpose::STANDARD.encode(&bytes),
+        }))
+    }
+}
+
+/// Largest central directory read when listing job artifacts (16 MiB)
+const MAX_ARTIFACT_INDEX_SIZE: usize = 16 * 1024 * 1024;
+
+/// List the files in a job's artifacts archive
+#[gitlab_tool(
+    name = "list_job_artifacts",
+    description = "List the files in a job's artifacts archive with their sizes, without downloading their contents. Use get_job_artifact_file to fetch a single file",
+    category = "pipelines",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListJobArtifacts {
+    /// Project path or ID
+    pub project: String,
+    /// Job ID
+    pub job_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for ListJobArtifacts {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/jobs/{}/artifacts", project, self.job_id);
+
+        // The zip index sits at the end of the archive; fetch only that part
+        let tail = ctx
+            .gitlab
+            .get_range(&endpoint, &format!("bytes=-{}", ZIP_TAIL_LEN), ZIP_TAIL_LEN)
+            .await?;
+        let directory =
+            find_central_directory(&tail.bytes).map_err(GitLabError::InvalidResponse)?;
+
+        // The offsets come from the archive itself; never trust them past its end
+        let end = directory
+            .end()
+            .filter(|&end| end <= tail.total)
+            .ok_or_else(|| {
+                GitLabError::InvalidResponse(format!(
+                    "Corrupt zip archive: central directory at offset {} with size {} lies outside the {}-byte archive",
+                    directory.offset, directory.size, tail.total
+                ))
+            })?;
+
+        // Small archives' index is already in the tail; otherwise fetch it
+        let in_tail = directory
+            .offset
+            .checked_sub(tail.offset)
+            .zip(end.checked_sub(tail.offset))
+            .and_then(|(start, end)| Some(usize::try_from(start).ok()?..usize::try_from(end).ok()?))
+            .filter(|range| range.end <= tail.bytes.len());
+        let entries = match in_tail {
+            Some(range) => parse_central_directory(&tail.bytes[range]),
+            None => {
+                let range = format!(
+                    "bytes={}-{}",
+                    directory.offset,
+                    end.saturating_sub(1).max(directory.offset)
+                );
+                let index = ctx
+                    .gitlab
+                    .get_range(&endpoint, &range, MAX_ARTIFACT_INDEX_SIZE)
+                    .await?;
+                parse_central_directory(&index.bytes)
+            }
+        }
+        .map_err(GitLabError::InvalidResponse)?;
+
+        let files: Vec<_> = entries
+            .iter()
+            .filter(|entry| !entry.is_dir())
+            .map(|entry| {
+                serde_json::json!({
+                    "path": entry.path,
+                    "size": entry.size,
+                    "compressed_size": entry.compressed_size,
+                })
+            })
+            .collect();
+
+        ToolOutput::json_value(serde_json::json!({
+            "job_id": self.job_id,
+            "archive_size": tail.total,
+            "file_count": files.len(),
+            "files": files,
+        }))
+    }
+}
+
+/// Download a single file from a job's artifacts
+#[gitlab_tool(
+    name = "get_job_artifact_file",
+    description = "Download a single file from a job's artifacts. Text files are returned as text, binary files as base64",
+    category = "pipelines",
+    operation = "read",
+    output = "text",
+    project_field = "project"
+)]
+pub struct GetJobArtifactFile {
+    /// Project path or ID
+    pub project: String,
+    /// Job ID
+    pub job_id: u64,
+    /// Path of the file inside the artifacts archive (e.g., "coverage/report.xml")
+    pub artifact_path: String,
+    /// Maximum file size in bytes (default: 1 MiB, at most 16 MiB)
+    #[serde(default = "default_max_artifact_size")]
+    pub max_size_bytes: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetJobArtifactFile {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let artifact_path = self
+            .artifact_path
+            .trim_start_matches('/')
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        let endpoint = format!(
+            "/projects/{}/jobs/{}/artifacts/{}",
+            project, self.job_id, artifact_path
+        );
+
+        let max_size = self.max_size_bytes.min(MAX_ARTIFACT_SIZE);
+        let bytes = ctx.gitlab.get_bytes(&endpoint, max_size).await?;
+
+        // Return text files as-is; anything with invalid UTF-8 or NUL bytes is binary
+        if let Ok(text) = std::str::from_utf8(&bytes)
+            && !text.contains('\0')
+        {
+            return Ok(ToolOutput::text(text));
+        }
+
+        ToolOutput::json_value(serde_json::json!({
+            "path": self.artifact_path,
+            "size": bytes.len(),
+            "encoding": "base64",
+            "content": base64::engine::general_purpose::STANDARD.encode(&bytes),
+        }))
+    }
+}
+
+/// Validate CI/CD configuration with GitLab's CI lint
+#[gitlab_tool(
+    name = "lint_ci_yaml",
+    description = "Validate .gitlab-ci.yml content with GitLab's CI lint. With a project, the configuration is linted in that project's context (includes, variables); without one, it is validated standalone. Returns valid, errors, and warnings.",
+    category = "pipelines",
+    operation = "read"
+)]
+pub struct LintCiYaml {
+    /// Project path or ID to lint in (omit for standalone validation)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Content of the CI/CD configuration (YAML)
+    pub content: String,
+    /// Include the jobs the configuration would create (project lint only)
+    #[serde(default)]
+    pub include_jobs: Option<bool>,
+}
+
+#[async_trait]
+impl ToolExecutor for LintCiYaml {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let endpoint = match &self.project {
+            Some(project) => format!(
+                "/projects/{}/ci/lint",
+                GitLabClient::encode_project(project)
+            ),
+            None => "/ci/lint".to_string(),
+        };
+
+        let mut body = serde_json::json!({ "content": self.content });
+        if let Some(include_jobs) = self.include_jobs {
+            body["include_jobs"] = serde_json::Value::Bool(include_jobs);
+        }
+
+        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+
+        // The project endpoint reports `valid`; the standalone one reports `status`
+        let valid = result
+            .get("valid")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| result.get("status").and_then(|s| s.as_str()) == Some("valid"));
+
+        let mut output = serde_json::json!({
+            "valid": valid,
+            "errors": result.get("errors").cloned().unwrap_or_else(|| serde_json::json!([])),
+            "warnings": result.get("warnings").cloned().unwrap_or_else(|| serde_json::json!([])),
+        });
+        if let Some(jobs) = result.get("jobs") {
+            output["jobs"] = jobs.clone();
+        }
+
+        ToolOutput::json_value(output)
+    }
+}
@@ -3,10 +3,12 @@
 //! Tools for managing GitLab projects.
 
 use crate::error::ToolError;
-use crate::gitlab::GitLabClient;
+use crate::gitlab::{GitLabClient, ProjectBadge};
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
 use crate::util::QueryBuilder;
 use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use tanuki_mcp_macros::gitlab_tool;
 
@@ -62,8 +64,7 @@ impl ToolExecutor for ListProjects {
             .optional("statistics", self.statistics.then_some("true"))
             .optional("order_by", self.order_by.as_ref())
             .optional("sort", self.sort.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects{}", query);
@@ -167,10 +168,68 @@ impl ToolExecutor for CreateProject {
     }
 }
 
+/// Project visibility level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Only members can see the project
+    Private,
+    /// Any signed-in user can see the project
+    Internal,
+    /// Anyone can see the project
+    Public,
+}
+
+/// Settings changed by `edit_project`, also accepted by `update_project`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectSettings {
+    /// Visibility: private, internal, public
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    /// New description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// New default branch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    /// Enable or disable issues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issues_enabled: Option<bool>,
+    /// Enable or disable merge requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_requests_enabled: Option<bool>,
+    /// Enable or disable the wiki
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiki_enabled: Option<bool>,
+}
+
+/// PUT the provided settings, merged with `extra` fields, to a project
+async fn put_project_settings(
+    ctx: &ToolContext,
+    project: &str,
+    settings: &ProjectSettings,
+    extra: serde_json::Map<String, serde_json::Value>,
+) -> Result<ToolOutput, ToolError> {
+    let mut body = extra;
+    if let serde_json::Value::Object(fields) = serde_json::to_value(settings)? {
+        body.extend(fields);
+    }
+    if body.is_empty() {
+        return Err(ToolError::InvalidArguments(
+            "At least one setting to change is required".to_string(),
+        ));
+    }
+
+    let endpoint = format!("/projects/{}", GitLabClient::encode_project(project));
+    let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+    ToolOutput::json_value(result)
+}
+
 /// Update a project
 #[gitlab_tool(
     name = "update_project",
-    description = "Update project settings",
+    description = "Rename, move, or archive a project. Also accepts every edit_project setting. Only provided settings are changed.",
     category = "projects",
     operation = "write"
 )]
@@ -183,50 +242,52 @@ pub struct UpdateProject {
     /// New project path
     #[serde(default)]
     pub path: Option<String>,
-    /// New description
-    #[serde(default)]
-    pub description: Option<String>,
-    /// New visibility: private, internal, public
-    #[serde(default)]
-    pub visibility: Option<String>,
-    /// New default branch
-    #[serde(default)]
-    pub default_branch: Option<String>,
     /// Archive the project
     #[serde(default)]
     pub archived: Option<bool>,
+    /// Visibility, description, default branch, and features
+    #[serde(flatten)]
+    pub settings: ProjectSettings,
 }
 
 #[async_trait]
 impl ToolExecutor for UpdateProject {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
-        let project = GitLabClient::encode_project(&self.project);
-        let endpoint = format!("/projects/{}", project);
-
-        let mut body = serde_json::json!({});
-
+        let mut extra = serde_json::Map::new();
         if let Some(ref name) = self.name {
-            body["name"] = serde_json::Value::String(name.clone());
+            extra.insert("name".to_string(), name.clone().into());
         }
         if let Some(ref path) = self.path {
-            body["path"] = serde_json::Value::String(path.clone());
-        }
-        if let Some(ref description) = self.description {
-            body["description"] = serde_json::Value::String(description.clone());
-        }
-        if let Some(ref visibility) = self.visibility {
-            body["visibility"] = serde_json::Value::String(visibility.clone());
-        }
-        if let Some(ref default_branch) = self.default_branch {
-            body["default_branch"] = serde_json::Value::String(default_branch.clone());
+            extra.insert("path".to_string(), path.clone().into());
         }
         if let Some(archived) = self.archived {
-            body["archived"] = serde_json::Value::Bool(archived);
+            extra.insert("archived".to_string(), archived.into());
         }
 
-        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+        put_project_settings(ctx, &self.project, &self.settings, extra).await
+    }
+}
 
-        ToolOutput::json_value(result)
+/// Edit project visibility and feature settings
+#[gitlab_tool(
+    name = "edit_project",
+    description = "Change a project's visibility, description, default branch, or enable/disable issues, merge requests, and wiki. Only provided settings are changed. Use update_project to rename, move, or archive.",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct EditProject {
+    /// Project path or ID
+    pub project: String,
+    /// Settings to change
+    #[serde(flatten)]
+    pub settings: ProjectSettings,
+}
+
+#[async_trait]
+impl ToolExecutor for EditProject {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        put_project_settings(ctx, &self.project, &self.settings, serde_json::Map::new()).await
     }
 }
 
@@ -276,6 +337,9 @@ pub struct ForkProject {
     /// Path for the forked project
     #[serde(default)]
     pub path: Option<String>,
+    /// Wait until GitLab has finished copying the repository (default: false)
+    #[serde(default)]
+    pub wait: bool,
 }
 
 #[async_trait]
@@ -296,12 +360,45 @@ impl ToolExecutor for ForkProject {
             body["path"] = serde_json::Value::String(path.clone());
         }
 
-        let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+        let mut result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+
+        if self.wait && !is_import_done(&result) {
+            let fork = result.get("id").and_then(|id| id.as_u64()).ok_or_else(|| {
+                ToolError::ExecutionFailed("Fork response has no project ID".into())
+            })?;
+            let fork_endpoint = format!("/projects/{}", fork);
+            result = ctx
+                .poll_until(
+                    "Fork",
+                    || ctx.gitlab.get_uncached(&fork_endpoint),
+                    is_import_done,
+                )
+                .await?;
+        }
+
+        if result.get("import_status").and_then(|s| s.as_str()) == Some("failed") {
+            let reason = result
+                .get("import_error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown error");
+            return Err(ToolError::ExecutionFailed(format!(
+                "Fork failed: {}",
+                reason
+            )));
+        }
 
         ToolOutput::json_value(result)
     }
 }
 
+/// Whether a project's repository import (e.g. a fork) has finished or failed
+fn is_import_done(project: &serde_json::Value) -> bool {
+    !matches!(
+        project.get("import_status").and_then(|s| s.as_str()),
+        Some("scheduled" | "started")
+    )
+}
+
 /// List project members
 #[gitlab_tool(
     name = "list_project_members",
@@ -329,8 +426,7 @@ impl ToolExecutor for ListProjectMembers {
         let project = GitLabClient::encode_project(&self.project);
         let query_str = QueryBuilder::new()
             .optional_encoded("query", self.query.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/members{}", project, query_str);
@@ -338,3 +434,503 @@ impl ToolExecutor for ListProjectMembers {
         ToolOutput::json_value(result)
     }
 }
+
+/// Get project language breakdown
+#[gitlab_tool(
+    name = "get_project_languages",
+    description = "Get the programming language breakdown of a project as percentages (e.g., {\"Rust\": 80.2})",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetProjectLanguages {
+    /// Project path or ID
+    pub project: String,
+}
+
+#[async_trait]
+impl ToolExecutor for GetProjectLanguages {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/languages", project);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Get project statistics
+#[gitlab_tool(
+    name = "get_project_statistics",
+    description = "Get size and activity statistics of a project (commit count, repository/storage size, artifacts size, etc.)",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetProjectStatistics {
+    /// Project path or ID
+    pub project: String,
+}
+
+#[async_trait]
+impl ToolExecutor for GetProjectStatistics {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}?statistics=true", project);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        ToolOutput::json(serde_json::json!({
+            "id": result.get("id"),
+            "path_with_namespace": result.get("path_with_namespace"),
+            "statistics": result.get("statistics"),
+        }))
+    }
+}
+
+/// Project fields that make up its CI/CD settings
+const CI_SETTINGS_FIELDS: &[&str] = &[
+    "auto_devops_enabled",
+    "ci_config_path",
+    "build_timeout",
+    "shared_runners_enabled",
+];
+
+/// Pick the CI/CD settings out of a project response
+fn ci_settings(project: &serde_json::Value) -> serde_json::Value {
+    let mut settings = serde_json::json!({
+        "id": project.get("id"),
+        "path_with_namespace": project.get("path_with_namespace"),
+    });
+    for field in CI_SETTINGS_FIELDS {
+        settings[*field] = project.get(*field).cloned().unwrap_or_default();
+    }
+    settings
+}
+
+/// Get project CI/CD settings
+#[gitlab_tool(
+    name = "get_project_ci_settings",
+    description = "Get the CI/CD settings of a project: Auto DevOps, CI config path, job timeout, and shared runners",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetProjectCiSettings {
+    /// Project path or ID
+    pub project: String,
+}
+
+#[async_trait]
+impl ToolExecutor for GetProjectCiSettings {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        ToolOutput::json(ci_settings(&result))
+    }
+}
+
+/// Update project CI/CD settings
+#[gitlab_tool(
+    name = "update_project_ci_settings",
+    description = "Change the CI/CD settings of a project: Auto DevOps, CI config path, job timeout, or shared runners. Only provided settings are changed.",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct UpdateProjectCiSettings {
+    /// Project path or ID
+    pub project: String,
+    /// Enable or disable Auto DevOps
+    #[serde(default)]
+    pub auto_devops_enabled: Option<bool>,
+    /// Path to the CI configuration file (empty for the default .gitlab-ci.yml)
+    #[serde(default)]
+    pub ci_config_path: Option<String>,
+    /// Job timeout in seconds (at least 600)
+    #[serde(default)]
+    pub build_timeout: Option<u32>,
+    /// Enable or disable shared runners
+    #[serde(default)]
+    pub shared_runners_enabled: Option<bool>,
+}
+
+#[async_trait]
+impl ToolExecutor for UpdateProjectCiSettings {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+
+        let mut body = serde_json::json!({});
+
+        if let Some(auto_devops_enabled) = self.auto_devops_enabled {
+            body["auto_devops_enabled"] = serde_json::Value::Bool(auto_devops_enabled);
+        }
+        if let Some(ref ci_config_path) = self.ci_config_path {
+            body["ci_config_path"] = serde_json::Value::String(ci_config_path.clone());
+        }
+        if let Some(build_timeout) = self.build_timeout {
+            body["build_timeout"] = serde_json::Value::from(build_timeout);
+        }
+        if let Some(shared_runners_enabled) = self.shared_runners_enabled {
+            body["shared_runners_enabled"] = serde_json::Value::Bool(shared_runners_enabled);
+        }
+
+        if body.as_object().is_some_and(|fields| fields.is_empty()) {
+            return Err(ToolError::InvalidArguments(
+                "At least one setting to change is required".to_string(),
+            ));
+        }
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+        ToolOutput::json(ci_settings(&result))
+    }
+}
+
+/// How merge requests are merged into the target branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeMethod {
+    /// Always create a merge commit
+    Merge,
+    /// Create a merge commit, but only for fast-forwardable branches
+    RebaseMerge,
+    /// Fast-forward only, no merge commits
+    Ff,
+}
+
+/// When merge request commits are squashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SquashOption {
+    /// Never squash
+    Never,
+    /// Always squash
+    Always,
+    /// Squash by default, authors may opt out
+    DefaultOn,
+    /// Do not squash by default, authors may opt in
+    DefaultOff,
+}
+
+/// Project fields that make up its merge request settings
+const MERGE_REQUEST_SETTINGS_FIELDS: &[&str] = &[
+    "merge_method",
+    "squash_option",
+    "only_allow_merge_if_pipeline_succeeds",
+    "remove_source_branch_after_merge",
+];
+
+/// Pick the merge request settings out of a project response
+fn merge_request_settings(project: &serde_json::Value) -> serde_json::Value {
+    let mut settings = serde_json::json!({
+        "id": project.get("id"),
+        "path_with_namespace": project.get("path_with_namespace"),
+    });
+    for field in MERGE_REQUEST_SETTINGS_FIELDS {
+        settings[*field] = project.get(*field).cloned().unwrap_or_default();
+    }
+    settings
+}
+
+/// Get project merge request settings
+#[gitlab_tool(
+    name = "get_merge_request_settings",
+    description = "Get how a project merges merge requests: merge method, squash option, whether a passing pipeline is required, and whether source branches are deleted",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetMergeRequestSettings {
+    /// Project path or ID
+    pub project: String,
+}
+
+#[async_trait]
+impl ToolExecutor for GetMergeRequestSettings {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        ToolOutput::json(merge_request_settings(&result))
+    }
+}
+
+/// Update project merge request settings
+#[gitlab_tool(
+    name = "update_merge_request_settings",
+    description = "Change how a project merges merge requests: merge method, squash option, whether a passing pipeline is required, or whether source branches are deleted. Only provided settings are changed.",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct UpdateMergeRequestSettings {
+    /// Project path or ID
+    pub project: String,
+    /// Merge method: merge, rebase_merge, or ff (fast-forward only)
+    #[serde(default)]
+    pub merge_method: Option<MergeMethod>,
+    /// Squash option: never, always, default_on, or default_off
+    #[serde(default)]
+    pub squash_option: Option<SquashOption>,
+    /// Only allow merging when the pipeline succeeds
+    #[serde(default)]
+    pub only_allow_merge_if_pipeline_succeeds: Option<bool>,
+    /// Delete the source branch after merging by default
+    #[serde(default)]
+    pub remove_source_branch_after_merge: Option<bool>,
+}
+
+#[async_trait]
+impl ToolExecutor for UpdateMergeRequestSettings {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+
+        let mut body = serde_json::json!({});
+
+        if let Some(merge_method) = self.merge_method {
+            body["merge_method"] = serde_json::to_value(merge_method)?;
+        }
+        if let Some(squash_option) = self.squash_option {
+            body["squash_option"] = serde_json::to_value(squash_option)?;
+        }
+        if let Some(required) = self.only_allow_merge_if_pipeline_succeeds {
+            body["only_allow_merge_if_pipeline_succeeds"] = serde_json::Value::Bool(required);
+        }
+        if let Some(remove) = self.remove_source_branch_after_merge {
+            body["remove_source_branch_after_merge"] = serde_json::Value::Bool(remove);
+        }
+
+        if body.as_object().is_some_and(|fields| fields.is_empty()) {
+            return Err(ToolError::InvalidArguments(
+                "At least one setting to change is required".to_string(),
+            ));
+        }
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+        ToolOutput::json(merge_request_settings(&result))
+    }
+}
+
+/// Pick the topics out of a project response
+fn project_topics(project: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "id": project.get("id"),
+        "path_with_namespace": project.get("path_with_namespace"),
+        "topics": project.get("topics").cloned().unwrap_or_else(|| serde_json::json!([])),
+    })
+}
+
+/// Get project topics
+#[gitlab_tool(
+    name = "get_project_topics",
+    description = "Get the topics (tags used for discovery) of a project",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct GetProjectTopics {
+    /// Project path or ID
+    pub project: String,
+}
+
+#[async_trait]
+impl ToolExecutor for GetProjectTopics {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+
+        ToolOutput::json(project_topics(&result))
+    }
+}
+
+/// Set project topics
+#[gitlab_tool(
+    name = "set_project_topics",
+    description = "Replace the topics of a project with the given list (an empty list removes all topics). Returns the resulting topics",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct SetProjectTopics {
+    /// Project path or ID
+    pub project: String,
+    /// The complete list of topics
+    pub topics: Vec<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for SetProjectTopics {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}", project);
+        let body = serde_json::json!({ "topics": self.topics });
+
+        let result: serde_json::Value = ctx.gitlab.put(&endpoint, &body).await?;
+
+        ToolOutput::json(project_topics(&result))
+    }
+}
+
+/// List project badges
+#[gitlab_tool(
+    name = "list_project_badges",
+    description = "List the badges of a project, including badges inherited from its group",
+    category = "projects",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListProjectBadges {
+    /// Project path or ID
+    pub project: String,
+    /// Only badges with this name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Results per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListProjectBadges {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .optional_encoded("name", self.name.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!("/projects/{}/badges{}", project, query);
+
+        let badges: Vec<ProjectBadge> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(badges)
+    }
+}
+
+/// Add a badge to a project
+#[gitlab_tool(
+    name = "add_project_badge",
+    description = "Add a badge to a project. URLs may use placeholders such as %{project_path}, %{default_branch}, and %{commit_sha}.",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct AddProjectBadge {
+    /// Project path or ID
+    pub project: String,
+    /// URL the badge links to
+    pub link_url: String,
+    /// URL of the badge image
+    pub image_url: String,
+    /// Badge name
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for AddProjectBadge {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/badges", project);
+
+        let mut body = serde_json::json!({
+            "link_url": self.link_url,
+            "image_url": self.image_url,
+        });
+
+        if let Some(ref name) = self.name {
+            body["name"] = serde_json::Value::String(name.clone());
+        }
+
+        let badge: ProjectBadge = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json(badge)
+    }
+}
+
+/// Edit a project badge
+#[gitlab_tool(
+    name = "edit_project_badge",
+    description = "Update the link, image, or name of a project badge",
+    category = "projects",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct EditProjectBadge {
+    /// Project path or ID
+    pub project: String,
+    /// Badge ID
+    pub badge_id: u64,
+    /// New link URL
+    #[serde(default)]
+    pub link_url: Option<String>,
+    /// New image URL
+    #[serde(default)]
+    pub image_url: Option<String>,
+    /// New badge name
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for EditProjectBadge {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/badges/{}", project, self.badge_id);
+
+        let mut body = serde_json::json!({});
+
+        if let Some(ref link_url) = self.link_url {
+            body["link_url"] = serde_json::Value::String(link_url.clone());
+        }
+        if let Some(ref image_url) = self.image_url {
+            body["image_url"] = serde_json::Value::String(image_url.clone());
+        }
+        if let Some(ref name) = self.name {
+            body["name"] = serde_json::Value::String(name.clone());
+        }
+
+        let badge: ProjectBadge = ctx.gitlab.put(&endpoint, &body).await?;
+        ToolOutput::json(badge)
+    }
+}
+
+/// Delete a project badge
+#[gitlab_tool(
+    name = "delete_project_badge",
+    description = "Delete a badge from a project (inherited group badges cannot be deleted here)",
+    category = "projects",
+    operation = "delete",
+    project_field = "project"
+)]
+pub struct DeleteProjectBadge {
+    /// Project path or ID
+    pub project: String,
+    /// Badge ID
+    pub badge_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for DeleteProjectBadge {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let endpoint = format!("/projects/{}/badges/{}", project, self.badge_id);
+
+        ctx.gitlab.delete(&endpoint).await?;
+        Ok(ToolOutput::text(format!(
+            "Badge {} deleted from project '{}'",
+            self.badge_id, self.project
+        )))
+    }
+}
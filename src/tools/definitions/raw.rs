@@ -0,0 +1,105 @@
+//! Raw request tools
+//!
+//! Escape hatch for calling GitLab REST endpoints that have no dedicated tool.
+//! Disabled unless `tools.enable_raw_request = true` is set in the configuration.
+
+use crate::error::ToolError;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
+use async_trait::async_trait;
+use reqwest::Method;
+use std::collections::BTreeMap;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// Perform a raw GitLab API request
+#[gitlab_tool(
+    name = "gitlab_request",
+    description = "Perform a raw request against the GitLab REST API (path relative to /api/v4) and return the JSON response. Use only when no dedicated tool exists.",
+    category = "raw",
+    operation = "execute"
+)]
+pub struct GitLabRequest {
+    /// HTTP method: GET, POST, PUT, PATCH, or DELETE
+    pub method: String,
+    /// API path relative to /api/v4 (e.g., "/projects/123/badges")
+    pub path: String,
+    /// Query parameters
+    #[serde(default)]
+    pub query: Option<BTreeMap<String, String>>,
+    /// JSON request body (for POST, PUT, PATCH)
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Number of items per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+impl GitLabRequest {
+    /// Parse and validate the HTTP method
+    fn parse_method(&self) -> Result<Method, ToolError> {
+        match self.method.to_ascii_uppercase().as_str() {
+            "GET" => Ok(Method::GET),
+            "POST" => Ok(Method::POST),
+            "PUT" => Ok(Method::PUT),
+            "PATCH" => Ok(Method::PATCH),
+            "DELETE" => Ok(Method::DELETE),
+            other => Err(ToolError::InvalidArguments(format!(
+                "Unsupported HTTP method '{}'. Use GET, POST, PUT, PATCH, or DELETE",
+                other
+            ))),
+        }
+    }
+
+    /// Validate the path and build the endpoint including the query string
+    fn build_endpoint(&self) -> Result<String, ToolError> {
+        let path = self.path.trim();
+        let invalid = || {
+            ToolError::InvalidArguments(format!(
+                "Invalid path '{}': must be an absolute API path like '/projects/123'",
+                self.path
+            ))
+        };
+        // The URL parser collapses encoded dot segments such as `%2e%2e`, so
+        // validate the decoded form to keep requests under /api/v4
+        let decoded = urlencoding::decode(path).map_err(|_| invalid())?;
+        if !path.starts_with('/')
+            || decoded.contains("://")
+            || decoded
+                .split(['/', '\\'])
+                .any(|segment| segment == "." || segment == "..")
+        {
+            return Err(invalid());
+        }
+        if path.contains('?') {
+            return Err(ToolError::InvalidArguments(
+                "Pass query parameters via 'query', not in 'path'".to_string(),
+            ));
+        }
+
+        let mut query = QueryBuilder::new();
+        for (key, value) in self.query.iter().flatten() {
+            query = query.param(&urlencoding::encode(key), value);
+        }
+        let query = query.pagination(self.page, self.per_page).build();
+
+        Ok(format!("{}{}", path, query))
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for GitLabRequest {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let method = self.parse_method()?;
+        let endpoint = self.build_endpoint()?;
+
+        let result = ctx
+            .gitlab
+            .request_json(method, &endpoint, self.body.as_ref())
+            .await?;
+        ToolOutput::json_value(result)
+    }
+}
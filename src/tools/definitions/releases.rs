@@ -3,8 +3,9 @@
 //! Tools for managing project releases.
 
 use crate::error::ToolError;
-use crate::gitlab::GitLabClient;
+use crate::gitlab::{GitLabClient, ReleaseLink};
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
 
 use schemars::JsonSchema;
@@ -39,26 +40,11 @@ pub struct ListReleases {
 impl ToolExecutor for ListReleases {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let mut params = Vec::new();
-
-        if let Some(ref order_by) = self.order_by {
-            params.push(format!("order_by={}", order_by));
-        }
-        if let Some(ref sort) = self.sort {
-            params.push(format!("sort={}", sort));
-        }
-        if let Some(per_page) = self.per_page {
-            params.push(format!("per_page={}", per_page.min(100)));
-        }
-        if let Some(page) = self.page {
-            params.push(format!("page={}", page));
-        }
-
-        let query = if params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", params.join("&"))
-        };
+        let query = QueryBuilder::new()
+            .optional("order_by", self.order_by.as_ref())
+            .optional("sort", self.sort.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
 
         let endpoint = format!("/projects/{}/releases{}", project, query);
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
@@ -309,3 +295,185 @@ impl ToolExecutor for CollectReleaseEvidence {
         ToolOutput::json_value(result)
     }
 }
+
+/// List asset links of a release
+#[gitlab_tool(
+    name = "list_release_links",
+    description = "List the asset links attached to a release",
+    category = "releases",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListReleaseLinks {
+    /// Project path or ID
+    pub project: String,
+    /// Release tag name
+    pub tag_name: String,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Results per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListReleaseLinks {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let tag_name = urlencoding::encode(&self.tag_name);
+        let query = QueryBuilder::new()
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!(
+            "/projects/{}/releases/{}/assets/links{}",
+            project, tag_name, query
+        );
+
+        let links: Vec<ReleaseLink> = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json(links)
+    }
+}
+
+/// Create an asset link for a release
+#[gitlab_tool(
+    name = "create_release_link",
+    description = "Attach an asset link to a release",
+    category = "releases",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct CreateReleaseLink {
+    /// Project path or ID
+    pub project: String,
+    /// Release tag name
+    pub tag_name: String,
+    /// Link name
+    pub name: String,
+    /// Link URL
+    pub url: String,
+    /// Link type: other, runbook, image, package
+    #[serde(default)]
+    pub link_type: Option<String>,
+    /// Direct asset path (optional)
+    #[serde(default)]
+    pub direct_asset_path: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for CreateReleaseLink {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let tag_name = urlencoding::encode(&self.tag_name);
+        let endpoint = format!("/projects/{}/releases/{}/assets/links", project, tag_name);
+
+        let mut body = serde_json::json!({
+            "name": self.name,
+            "url": self.url,
+        });
+
+        if let Some(ref link_type) = self.link_type {
+            body["link_type"] = serde_json::Value::String(link_type.clone());
+        }
+        if let Some(ref direct_asset_path) = self.direct_asset_path {
+            body["direct_asset_path"] = serde_json::Value::String(direct_asset_path.clone());
+        }
+
+        let link: ReleaseLink = ctx.gitlab.post(&endpoint, &body).await?;
+        ToolOutput::json(link)
+    }
+}
+
+/// Update an asset link of a release
+#[gitlab_tool(
+    name = "update_release_link",
+    description = "Update an asset link attached to a release",
+    category = "releases",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct UpdateReleaseLink {
+    /// Project path or ID
+    pub project: String,
+    /// Release tag name
+    pub tag_name: String,
+    /// Link ID
+    pub link_id: u64,
+    /// New link name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// New link URL
+    #[serde(default)]
+    pub url: Option<String>,
+    /// New link type: other, runbook, image, package
+    #[serde(default)]
+    pub link_type: Option<String>,
+    /// New direct asset path
+    #[serde(default)]
+    pub direct_asset_path: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for UpdateReleaseLink {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let tag_name = urlencoding::encode(&self.tag_name);
+        let endpoint = format!(
+            "/projects/{}/releases/{}/assets/links/{}",
+            project, tag_name, self.link_id
+        );
+
+        let mut body = serde_json::json!({});
+
+        if let Some(ref name) = self.name {
+            body["name"] = serde_json::Value::String(name.clone());
+        }
+        if let Some(ref url) = self.url {
+            body["url"] = serde_json::Value::String(url.clone());
+        }
+        if let Some(ref link_type) = self.link_type {
+            body["link_type"] = serde_json::Value::String(link_type.clone());
+        }
+        if let Some(ref direct_asset_path) = self.direct_asset_path {
+            body["direct_asset_path"] = serde_json::Value::String(direct_asset_path.clone());
+        }
+
+        let link: ReleaseLink = ctx.gitlab.put(&endpoint, &body).await?;
+        ToolOutput::json(link)
+    }
+}
+
+/// Delete an asset link of a release
+#[gitlab_tool(
+    name = "delete_release_link",
+    description = "Delete an asset link from a release",
+    category = "releases",
+    operation = "delete",
+    project_field = "project"
+)]
+pub struct DeleteReleaseLink {
+    /// Project path or ID
+    pub project: String,
+    /// Release tag name
+    pub tag_name: String,
+    /// Link ID
+    pub link_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for DeleteReleaseLink {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let tag_name = urlencoding::encode(&self.tag_name);
+        let endpoint = format!(
+            "/projects/{}/releases/{}/assets/links/{}",
+            project, tag_name, self.link_id
+        );
+
+        ctx.gitlab.delete(&endpoint).await?;
+        Ok(ToolOutput::text(format!(
+            "Link {} deleted from release '{}'",
+            self.link_id, self.tag_name
+        )))
+    }
+}
@@ -2,13 +2,14 @@
 //!
 //! Tools for interacting with GitLab repository files, trees, and content.
 
-use crate::error::ToolError;
+use crate::error::{GitLabError, ToolError};
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
-use crate::util::QueryBuilder;
+use crate::util::{QueryBuilder, default_max_diff_length, truncate_diff};
 use async_trait::async_trait;
 
 use base64::Engine;
+use similar::TextDiff;
 use tanuki_mcp_macros::gitlab_tool;
 
 /// Get repository file contents
@@ -61,6 +62,69 @@ impl ToolExecutor for GetRepositoryFile {
     }
 }
 
+/// Default maximum size of raw file downloads (1 MiB)
+const DEFAULT_MAX_FILE_SIZE: usize = 1024 * 1024;
+
+/// Largest raw file download a caller may ask for (16 MiB)
+const MAX_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+fn default_max_file_size() -> usize {
+    DEFAULT_MAX_FILE_SIZE
+}
+
+/// Get a repository file's raw content
+#[gitlab_tool(
+    name = "get_file_raw",
+    description = "Get the raw content of a file at a specific ref. Text files are returned as plain text without base64 or metadata; binary files as base64",
+    category = "repository",
+    operation = "read",
+    output = "text"
+)]
+pub struct GetFileRaw {
+    /// Project path or ID (e.g., "group/project" or "123")
+    pub project: String,
+    /// Path to the file in the repository
+    pub file_path: String,
+    /// Branch, tag, or commit SHA (default: default branch)
+    #[serde(default)]
+    pub ref_name: Option<String>,
+    /// Maximum file size in bytes (default: 1 MiB, at most 16 MiB)
+    #[serde(default = "default_max_file_size")]
+    pub max_size_bytes: usize,
+}
+
+#[async_trait]
+impl ToolExecutor for GetFileRaw {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let file_path = urlencoding::encode(&self.file_path);
+        let query = QueryBuilder::new()
+            .param("ref", self.ref_name.as_deref().unwrap_or("HEAD"))
+            .build();
+        let endpoint = format!(
+            "/projects/{}/repository/files/{}/raw{}",
+            project, file_path, query
+        );
+
+        let max_size = self.max_size_bytes.min(MAX_FILE_SIZE);
+        let bytes = ctx.gitlab.get_bytes(&endpoint, max_size).await?;
+
+        // Return text files as-is; anything with invalid UTF-8 or NUL bytes is binary
+        if let Ok(text) = std::str::from_utf8(&bytes)
+            && !text.contains('\0')
+        {
+            return Ok(ToolOutput::text(text));
+        }
+
+        ToolOutput::json_value(serde_json::json!({
+            "file_path": self.file_path,
+            "size": bytes.len(),
+            "encoding": "base64",
+            "content": base64::engine::general_purpose::STANDARD.encode(&bytes),
+        }))
+    }
+}
+
 /// Get repository tree (file listing)
 #[gitlab_tool(
     name = "get_repository_tree",
@@ -96,8 +160,7 @@ impl ToolExecutor for GetRepositoryTree {
             .optional_encoded("path", self.path.as_ref())
             .optional_encoded("ref", self.ref_name.as_ref())
             .optional("recursive", self.recursive.then_some("true"))
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/repository/tree{}", project, query);
@@ -253,8 +316,7 @@ impl ToolExecutor for SearchRepository {
             .param("scope", "blobs")
             .param("search", urlencoding::encode(&self.search))
             .optional_encoded("ref", self.ref_name.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/search{}", project, query);
@@ -266,7 +328,7 @@ impl ToolExecutor for SearchRepository {
 /// Get file blame information
 #[gitlab_tool(
     name = "get_file_blame",
-    description = "Get blame information for a file showing line-by-line commit history",
+    description = "Get blame information for a file showing line-by-line commit history. Pass start_line and end_line to blame only that region of a large file",
     category = "repository",
     operation = "read"
 )]
@@ -278,21 +340,44 @@ pub struct GetFileBlame {
     /// Branch, tag, or commit SHA (default: default branch)
     #[serde(default)]
     pub ref_name: Option<String>,
+    /// First line to blame (1-based, requires end_line)
+    #[serde(default)]
+    pub start_line: Option<u32>,
+    /// Last line to blame, inclusive (requires start_line)
+    #[serde(default)]
+    pub end_line: Option<u32>,
 }
 
 #[async_trait]
 impl ToolExecutor for GetFileBlame {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let range = match (self.start_line, self.end_line) {
+            (None, None) => None,
+            (Some(start), Some(end)) if start >= 1 && end >= start => Some((start, end)),
+            (Some(_), Some(_)) => {
+                return Err(ToolError::InvalidArguments(
+                    "start_line must be at least 1 and not after end_line".to_string(),
+                ));
+            }
+            _ => {
+                return Err(ToolError::InvalidArguments(
+                    "start_line and end_line must be given together".to_string(),
+                ));
+            }
+        };
+
         let project = GitLabClient::encode_project(&self.project);
         let file_path = urlencoding::encode(&self.file_path);
+        let query = QueryBuilder::new()
+            .param("ref", self.ref_name.as_deref().unwrap_or("HEAD"))
+            .optional("range[start]", range.map(|(start, _)| start))
+            .optional("range[end]", range.map(|(_, end)| end))
+            .build();
 
-        let mut endpoint = format!("/projects/{}/repository/files/{}/blame", project, file_path);
-
-        if let Some(ref ref_name) = self.ref_name {
-            endpoint.push_str(&format!("?ref={}", urlencoding::encode(ref_name)));
-        } else {
-            endpoint.push_str("?ref=HEAD");
-        }
+        let endpoint = format!(
+            "/projects/{}/repository/files/{}/blame{}",
+            project, file_path, query
+        );
 
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
 
@@ -334,3 +419,149 @@ impl ToolExecutor for CompareRefs {
         ToolOutput::json_value(result)
     }
 }
+
+/// List repository contributors
+#[gitlab_tool(
+    name = "list_contributors",
+    description = "List repository contributors with their commit counts, additions, and deletions",
+    category = "repository",
+    operation = "read"
+)]
+pub struct ListContributors {
+    /// Project path or ID
+    pub project: String,
+    /// Order by: name, email, or commits (default: commits)
+    #[serde(default = "default_contributor_order")]
+    pub order_by: String,
+    /// Sort direction: asc or desc (default: desc)
+    #[serde(default = "default_contributor_sort")]
+    pub sort: String,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Items per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+fn default_contributor_order() -> String {
+    "commits".to_string()
+}
+
+fn default_contributor_sort() -> String {
+    "desc".to_string()
+}
+
+#[async_trait]
+impl ToolExecutor for ListContributors {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let query = QueryBuilder::new()
+            .param("order_by", &self.order_by)
+            .param("sort", &self.sort)
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/projects/{}/repository/contributors{}", project, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Compare one file between two refs
+#[gitlab_tool(
+    name = "diff_file_between_refs",
+    description = "Get a unified diff of a single file between two branches, tags, or commits, without fetching the whole comparison",
+    category = "repository",
+    operation = "read"
+)]
+pub struct DiffFileBetweenRefs {
+    /// Project path or ID
+    pub project: String,
+    /// Path to the file in the repository
+    pub file_path: String,
+    /// Base branch/tag/commit
+    pub from: String,
+    /// Changed branch/tag/commit
+    pub to: String,
+    /// Maximum length of the diff before truncation (default: 10000)
+    #[serde(default = "default_max_diff_length")]
+    pub max_diff_length: usize,
+}
+
+/// Largest file version diff_file_between_refs reads (1 MiB)
+const MAX_DIFF_FILE_SIZE: usize = 1024 * 1024;
+
+impl DiffFileBetweenRefs {
+    /// Raw file content at a ref, or `None` if the file does not exist there
+    ///
+    /// Fails without buffering the whole file when it is larger than
+    /// [`MAX_DIFF_FILE_SIZE`].
+    async fn content_at(
+        &self,
+        ctx: &ToolContext,
+        ref_name: &str,
+    ) -> Result<Option<String>, ToolError> {
+        let endpoint = format!(
+            "/projects/{}/repository/files/{}/raw?ref={}",
+            GitLabClient::encode_project(&self.project),
+            urlencoding::encode(&self.file_path),
+            urlencoding::encode(ref_name)
+        );
+
+        match ctx.gitlab.get_bytes(&endpoint, MAX_DIFF_FILE_SIZE).await {
+            Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+            Err(GitLabError::NotFound { .. }) => Ok(None),
+            Err(GitLabError::TooLarge { max_bytes }) => Err(ToolError::InvalidArguments(format!(
+                "File '{}' at '{}' is too large to diff (over {} bytes)",
+                self.file_path, ref_name, max_bytes
+            ))),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for DiffFileBetweenRefs {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let (old, new) = tokio::try_join!(
+            self.content_at(ctx, &self.from),
+            self.content_at(ctx, &self.to)
+        )?;
+
+        if old.is_none() && new.is_none() {
+            return Err(ToolError::InvalidArguments(format!(
+                "File '{}' exists at neither '{}' nor '{}'",
+                self.file_path, self.from, self.to
+            )));
+        }
+
+        let old_header = match old {
+            Some(_) => format!("a/{}", self.file_path),
+            None => "/dev/null".to_string(),
+        };
+        let new_header = match new {
+            Some(_) => format!("b/{}", self.file_path),
+            None => "/dev/null".to_string(),
+        };
+        let old_text = old.as_deref().unwrap_or_default();
+        let new_text = new.as_deref().unwrap_or_default();
+
+        let diff = TextDiff::from_lines(old_text, new_text)
+            .unified_diff()
+            .header(&old_header, &new_header)
+            .to_string();
+        let (diff, truncated) = truncate_diff(&diff, self.max_diff_length);
+
+        ToolOutput::json(serde_json::json!({
+            "file_path": self.file_path,
+            "from": self.from,
+            "to": self.to,
+            "from_size": old.as_ref().map(String::len),
+            "to_size": new.as_ref().map(String::len),
+            "identical": old == new,
+            "diff": diff,
+            "truncated": truncated,
+        }))
+    }
+}
@@ -0,0 +1,79 @@
+//! Runner tools
+//!
+//! Tools for inspecting the CI/CD runners available to a project, to find
+//! out why jobs are stuck waiting for one.
+
+use crate::error::ToolError;
+use crate::gitlab::GitLabClient;
+use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
+use async_trait::async_trait;
+
+use tanuki_mcp_macros::gitlab_tool;
+
+/// List project runners
+#[gitlab_tool(
+    name = "list_project_runners",
+    description = "List the runners available to a project, including shared and group runners, with their status and online state",
+    category = "runners",
+    operation = "read",
+    project_field = "project"
+)]
+pub struct ListProjectRunners {
+    /// Project path or ID
+    pub project: String,
+    /// Filter by runner type: instance_type, group_type, or project_type
+    #[serde(default)]
+    pub runner_type: Option<String>,
+    /// Filter by status: online, offline, stale, or never_contacted
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Only return runners with all of these tags
+    #[serde(default)]
+    pub tag_list: Option<Vec<String>>,
+    /// Number of runners per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListProjectRunners {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let project = GitLabClient::encode_project(&self.project);
+        let tags = self.tag_list.as_ref().map(|tags| tags.join(","));
+        let query = QueryBuilder::new()
+            .optional("type", self.runner_type.as_ref())
+            .optional("status", self.status.as_ref())
+            .optional_encoded("tag_list", tags.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/projects/{}/runners{}", project, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// Get a runner
+#[gitlab_tool(
+    name = "get_runner",
+    description = "Get details of a runner: status, online state, tags, whether it runs untagged jobs, and when it was last contacted",
+    category = "runners",
+    operation = "read"
+)]
+pub struct GetRunner {
+    /// Runner ID
+    pub runner_id: u64,
+}
+
+#[async_trait]
+impl ToolExecutor for GetRunner {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let endpoint = format!("/runners/{}", self.runner_id);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
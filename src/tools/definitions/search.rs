@@ -44,8 +44,7 @@ impl ToolExecutor for SearchGlobal {
             .param("search", urlencoding::encode(&self.search))
             .optional_encoded("state", self.state.as_ref())
             .optional("confidential", self.confidential)
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/search{}", query);
@@ -96,8 +95,7 @@ impl ToolExecutor for SearchProject {
             .optional_encoded("ref", self.ref_name.as_ref())
             .optional_encoded("state", self.state.as_ref())
             .optional("confidential", self.confidential)
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/search{}", project, query);
@@ -143,8 +141,7 @@ impl ToolExecutor for SearchGroup {
             .param("search", urlencoding::encode(&self.search))
             .optional_encoded("state", self.state.as_ref())
             .optional("confidential", self.confidential)
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/groups/{}/search{}", group, query);
@@ -2,7 +2,8 @@
 //!
 //! Tools for managing repository tags and protected tags.
 
-use crate::error::ToolError;
+use crate::access_control::{AccessDecision, OperationType, ToolCategory};
+use crate::error::{AccessDeniedError, ToolError};
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
 use crate::util::QueryBuilder;
@@ -45,8 +46,7 @@ impl ToolExecutor for ListTags {
             .optional_encoded("order_by", self.order_by.as_ref())
             .optional_encoded("sort", self.sort.as_ref())
             .optional_encoded("search", self.search.as_ref())
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/repository/tags{}", project, query);
@@ -85,7 +85,7 @@ impl ToolExecutor for GetTag {
 /// Create a new tag
 #[gitlab_tool(
     name = "create_tag",
-    description = "Create a new tag pointing to a ref (branch, tag, or commit)",
+    description = "Create a new tag pointing to a ref (branch, tag, or commit). Provide a message for an annotated tag and a release_description to also create a release for the tag",
     category = "tags",
     operation = "write"
 )]
@@ -99,14 +99,56 @@ pub struct CreateTag {
     /// Optional message for annotated tag
     #[serde(default)]
     pub message: Option<String>,
+    /// Optional release notes; when set, a release is created for the tag
+    #[serde(default)]
+    pub release_description: Option<String>,
 }
 
 #[async_trait]
 impl ToolExecutor for CreateTag {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        // Creating a release needs releases write access in addition to tags,
+        // checked against the same resolved path as the tags check
+        if self.release_description.is_some() {
+            let path = ctx.project_path_for_access(&self.project).await?;
+            if let AccessDecision::Denied(reason) = ctx.access.check(
+                "create_release",
+                ToolCategory::Releases,
+                OperationType::Write,
+                Some(&path),
+            ) {
+                return Err(ToolError::AccessDenied(AccessDeniedError::new(
+                    "create_tag",
+                    format!("cannot create release: {}", reason),
+                )));
+            }
+        }
+
         let project = GitLabClient::encode_project(&self.project);
-        let endpoint = format!("/projects/{}/repository/tags", project);
 
+        // The release API creates the tag from `ref` in the same call, so a
+        // failed release never leaves a stray tag behind
+        if let Some(ref description) = self.release_description {
+            let endpoint = format!("/projects/{}/releases", project);
+            let mut body = serde_json::json!({
+                "tag_name": self.tag_name,
+                "ref": self.ref_name,
+                "description": description,
+            });
+            if let Some(ref message) = self.message {
+                body["tag_message"] = serde_json::Value::String(message.clone());
+            }
+
+            let release: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
+            return ToolOutput::json_value(serde_json::json!({
+                "name": self.tag_name,
+                "message": self.message,
+                "commit": release["commit"],
+                "release": release,
+            }));
+        }
+
+        let endpoint = format!("/projects/{}/repository/tags", project);
         let mut body = serde_json::json!({
             "tag_name": self.tag_name,
             "ref": self.ref_name,
@@ -117,7 +159,6 @@ impl ToolExecutor for CreateTag {
         }
 
         let result: serde_json::Value = ctx.gitlab.post(&endpoint, &body).await?;
-
         ToolOutput::json_value(result)
     }
 }
@@ -175,8 +216,7 @@ impl ToolExecutor for ListProtectedTags {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
         let query = QueryBuilder::new()
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/projects/{}/protected_tags{}", project, query);
@@ -2,7 +2,7 @@
 //!
 //! Tools for managing users and current user information.
 
-use crate::error::ToolError;
+use crate::error::{GitLabError, ToolError};
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
 use crate::util::QueryBuilder;
 use async_trait::async_trait;
@@ -62,8 +62,7 @@ impl ToolExecutor for ListUsers {
             .optional_encoded("username", self.username.as_ref())
             .optional("active", self.active)
             .optional("blocked", self.blocked)
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/users{}", query);
@@ -116,8 +115,7 @@ pub struct GetUserActivities {
 impl ToolExecutor for GetUserActivities {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let query = QueryBuilder::new()
-            .optional("per_page", self.per_page.map(|p| p.min(100)))
-            .optional("page", self.page)
+            .pagination(self.page, self.per_page)
             .build();
 
         let endpoint = format!("/users/{}/events{}", self.user_id, query);
@@ -125,3 +123,90 @@ impl ToolExecutor for GetUserActivities {
         ToolOutput::json_value(result)
     }
 }
+
+/// List a user's projects
+#[gitlab_tool(
+    name = "list_user_projects",
+    description = "List projects owned by a user that the caller can see",
+    category = "users",
+    operation = "read"
+)]
+pub struct ListUserProjects {
+    /// User ID
+    pub user_id: u64,
+    /// Search by project name
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Filter by visibility: public, internal, private
+    #[serde(default)]
+    pub visibility: Option<String>,
+    /// Filter by archived status
+    #[serde(default)]
+    pub archived: Option<bool>,
+    /// Number of projects per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListUserProjects {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let query = QueryBuilder::new()
+            .optional_encoded("search", self.search.as_ref())
+            .optional("visibility", self.visibility.as_ref())
+            .optional("archived", self.archived)
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/users/{}/projects{}", self.user_id, query);
+        let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
+        ToolOutput::json_value(result)
+    }
+}
+
+/// List a user's memberships
+#[gitlab_tool(
+    name = "list_user_memberships",
+    description = "List the projects and groups a user is a member of, with access levels (admin only)",
+    category = "users",
+    operation = "read"
+)]
+pub struct ListUserMemberships {
+    /// User ID
+    pub user_id: u64,
+    /// Filter by membership type: Project or Namespace
+    #[serde(default)]
+    pub membership_type: Option<String>,
+    /// Number of memberships per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[async_trait]
+impl ToolExecutor for ListUserMemberships {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let query = QueryBuilder::new()
+            .optional("type", self.membership_type.as_ref())
+            .pagination(self.page, self.per_page)
+            .build();
+
+        let endpoint = format!("/users/{}/memberships{}", self.user_id, query);
+        let result: serde_json::Value =
+            ctx.gitlab
+                .get(&endpoint)
+                .await
+                .map_err(|error| match error {
+                    GitLabError::Forbidden { .. } => GitLabError::Forbidden {
+                        action: "listing user memberships (requires an administrator token)".into(),
+                    },
+                    other => other,
+                })?;
+        ToolOutput::json_value(result)
+    }
+}
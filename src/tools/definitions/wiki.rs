@@ -5,7 +5,9 @@
 use crate::error::ToolError;
 use crate::gitlab::GitLabClient;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::util::QueryBuilder;
 use async_trait::async_trait;
+use base64::Engine;
 
 use tanuki_mcp_macros::gitlab_tool;
 
@@ -22,17 +24,23 @@ pub struct ListWikiPages {
     /// Include page content
     #[serde(default)]
     pub with_content: bool,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Results per page (max 100)
+    #[serde(default)]
+    pub per_page: Option<u32>,
 }
 
 #[async_trait]
 impl ToolExecutor for ListWikiPages {
     async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
         let project = GitLabClient::encode_project(&self.project);
-        let mut endpoint = format!("/projects/{}/wikis", project);
-
-        if self.with_content {
-            endpoint.push_str("?with_content=1");
-        }
+        let query = QueryBuilder::new()
+            .optional("with_content", self.with_content.then_some("1"))
+            .pagination(self.page, self.per_page)
+            .build();
+        let endpoint = format!("/projects/{}/wikis{}", project, query);
 
         let result: serde_json::Value = ctx.gitlab.get(&endpoint).await?;
         ToolOutput::json_value(result)
@@ -200,3 +208,68 @@ impl ToolExecutor for DeleteWikiPage {
         )))
     }
 }
+
+/// Maximum size of an uploaded wiki attachment (10 MiB)
+const MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Upload an attachment to a wiki
+#[gitlab_tool(
+    name = "upload_wiki_attachment",
+    description = "Upload a file (base64-encoded) to a project wiki and return the markdown link to embed it in a page",
+    category = "wiki",
+    operation = "write",
+    project_field = "project"
+)]
+pub struct UploadWikiAttachment {
+    /// Project path or ID
+    pub project: String,
+    /// File name, including extension (e.g., "diagram.png")
+    pub file_name: String,
+    /// File content, base64-encoded (max 10 MiB decoded)
+    pub content: String,
+    /// Wiki branch to commit the attachment to (default: the wiki's default branch)
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+#[async_trait]
+impl ToolExecutor for UploadWikiAttachment {
+    async fn execute(&self, ctx: &ToolContext) -> Result<ToolOutput, ToolError> {
+        if self.file_name.trim().is_empty() {
+            return Err(ToolError::InvalidArguments(
+                "file_name must not be empty".to_string(),
+            ));
+        }
+
+        // Reject oversized input before decoding (base64 expands data by 4/3)
+        if self.content.trim().len() > MAX_ATTACHMENT_SIZE.div_ceil(3) * 4 {
+            return Err(ToolError::InvalidArguments(format!(
+                "Attachment exceeds the size limit of {} bytes",
+                MAX_ATTACHMENT_SIZE
+            )));
+        }
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(self.content.trim())
+            .map_err(|e| ToolError::InvalidArguments(format!("Invalid base64 content: {}", e)))?;
+
+        if bytes.len() > MAX_ATTACHMENT_SIZE {
+            return Err(ToolError::InvalidArguments(format!(
+                "Attachment exceeds the size limit of {} bytes",
+                MAX_ATTACHMENT_SIZE
+            )));
+        }
+
+        let project = GitLabClient::encode_project(&self.project);
+        let mut endpoint = format!("/projects/{}/wikis/attachments", project);
+        if let Some(ref branch) = self.branch {
+            endpoint.push_str(&format!("?branch={}", urlencoding::encode(branch)));
+        }
+
+        let result: serde_json::Value = ctx
+            .gitlab
+            .post_multipart_file(&endpoint, "file", &self.file_name, &bytes)
+            .await?;
+        ToolOutput::json_value(result)
+    }
+}
@@ -2,14 +2,37 @@
 //!
 //! Defines the interface for executing GitLab MCP tools.
 
-use crate::access_control::AccessResolver;
+use crate::access_control::{AccessResolver, ProjectAllowlist};
+use crate::config::OutputFormat;
 use crate::dashboard::DashboardMetrics;
-use crate::error::ToolError;
-use crate::gitlab::GitLabClient;
+use crate::error::{GitLabResult, ToolError};
+use crate::gitlab::{GitLabClient, Pagination};
+use crate::tools::idempotency::IdempotencyCache;
 // async_trait required for Send guarantee when used in async contexts
 use async_trait::async_trait;
 use serde_json::Value;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// How tools wait for asynchronous GitLab operations to finish
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollConfig {
+    /// Number of re-fetches before giving up
+    pub max_attempts: u32,
+    /// Delay before each re-fetch
+    pub delay: Duration,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 30,
+            delay: Duration::from_secs(2),
+        }
+    }
+}
 
 /// Context provided to tools during execution
 pub struct ToolContext {
@@ -21,6 +44,24 @@ pub struct ToolContext {
     pub request_id: String,
     /// Dashboard metrics collector (optional)
     pub metrics: Option<Arc<DashboardMetrics>>,
+    /// Format used to render JSON results
+    pub output_format: OutputFormat,
+    /// Preview mutating tools instead of calling GitLab
+    pub dry_run: bool,
+    /// Project used when a tool's `project` argument is omitted
+    pub default_project: Option<String>,
+    /// Results of create calls made with an idempotency key (optional)
+    pub idempotency: Option<Arc<IdempotencyCache>>,
+    /// Wrap list results with GitLab pagination metadata
+    pub pagination_meta: bool,
+    /// Reject calls whose serialized arguments exceed this many bytes
+    pub max_request_bytes: Option<usize>,
+    /// Truncate list results to this many items
+    pub max_items_per_list: Option<usize>,
+    /// Polling used by tools called with `wait: true`
+    pub poll: PollConfig,
+    /// Projects tools may act on (None allows every project)
+    pub allowed_projects: Option<Arc<ProjectAllowlist>>,
 }
 
 impl ToolContext {
@@ -35,6 +76,15 @@ impl ToolContext {
             access,
             request_id: request_id.into(),
             metrics: None,
+            output_format: OutputFormat::default(),
+            dry_run: false,
+            default_project: None,
+            idempotency: None,
+            pagination_meta: false,
+            max_request_bytes: None,
+            max_items_per_list: None,
+            poll: PollConfig::default(),
+            allowed_projects: None,
         }
     }
 
@@ -50,8 +100,130 @@ impl ToolContext {
             access,
             request_id: request_id.into(),
             metrics: Some(metrics),
+            output_format: OutputFormat::default(),
+            dry_run: false,
+            default_project: None,
+            idempotency: None,
+            pagination_meta: false,
+            max_request_bytes: None,
+            max_items_per_list: None,
+            poll: PollConfig::default(),
+            allowed_projects: None,
         }
     }
+
+    /// Set the format used to render JSON results
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Enable or disable dry-run mode for mutating tools
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the project used when a tool's `project` argument is omitted
+    pub fn with_default_project(mut self, default_project: Option<String>) -> Self {
+        self.default_project = default_project;
+        self
+    }
+
+    /// Set the cache used to deduplicate create calls with an idempotency key
+    pub fn with_idempotency(mut self, cache: Option<Arc<IdempotencyCache>>) -> Self {
+        self.idempotency = cache;
+        self
+    }
+
+    /// Enable or disable wrapping list results with pagination metadata
+    pub fn with_pagination_meta(mut self, pagination_meta: bool) -> Self {
+        self.pagination_meta = pagination_meta;
+        self
+    }
+
+    /// Set the largest accepted size of a call's serialized arguments
+    pub fn with_max_request_bytes(mut self, max_request_bytes: Option<usize>) -> Self {
+        self.max_request_bytes = max_request_bytes;
+        self
+    }
+
+    /// Set the largest number of items a list result may contain
+    pub fn with_max_items_per_list(mut self, max_items_per_list: Option<usize>) -> Self {
+        self.max_items_per_list = max_items_per_list;
+        self
+    }
+
+    /// Restrict tools to projects on an allowlist
+    pub fn with_allowed_projects(mut self, allowed: Option<Arc<ProjectAllowlist>>) -> Self {
+        self.allowed_projects = allowed;
+        self
+    }
+
+    /// Set how tools wait for asynchronous operations
+    pub fn with_poll_config(mut self, poll: PollConfig) -> Self {
+        self.poll = poll;
+        self
+    }
+
+    /// Path of a project as matched by project rules and the allowlist
+    ///
+    /// Both are keyed by path, so numeric IDs are looked up when either needs
+    /// them; anything else is returned unchanged.
+    pub async fn project_path_for_access(&self, project: &str) -> GitLabResult<String> {
+        let numeric = !project.is_empty() && project.bytes().all(|b| b.is_ascii_digit());
+        if numeric && (self.access.resolves_numeric_ids() || self.allowed_projects.is_some()) {
+            let path = self.gitlab.project_path(project).await?;
+            debug!(id = %project, path = %path, "Resolved numeric project ID");
+            return Ok(path);
+        }
+        Ok(project.to_string())
+    }
+
+    /// Fail with [`ToolError::UnsupportedEdition`] if GitLab runs CE
+    ///
+    /// Used by tools for Premium and Ultimate features. When the edition
+    /// cannot be determined the call proceeds and GitLab has the final say.
+    pub async fn require_enterprise(&self, feature: &str) -> Result<(), ToolError> {
+        match self.gitlab.metadata().await {
+            Ok(metadata) if !metadata.enterprise => Err(ToolError::UnsupportedEdition {
+                feature: feature.to_string(),
+            }),
+            Ok(_) => Ok(()),
+            Err(e) => {
+                debug!(error = %e, "Could not determine GitLab edition");
+                Ok(())
+            }
+        }
+    }
+
+    /// Re-fetch a resource until `done` accepts it.
+    ///
+    /// Waits [`PollConfig::delay`] before each call to `fetch` and fails once
+    /// [`PollConfig::max_attempts`] fetches have not produced a finished state.
+    pub async fn poll_until<F, Fut, E>(
+        &self,
+        what: &str,
+        mut fetch: F,
+        done: impl Fn(&Value) -> bool,
+    ) -> Result<Value, ToolError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Value, E>>,
+        ToolError: From<E>,
+    {
+        for _ in 0..self.poll.max_attempts {
+            tokio::time::sleep(self.poll.delay).await;
+            let value = fetch().await?;
+            if done(&value) {
+                return Ok(value);
+            }
+        }
+        Err(ToolError::ExecutionFailed(format!(
+            "{} did not finish after {} checks",
+            what, self.poll.max_attempts
+        )))
+    }
 }
 
 /// Result of tool execution
@@ -61,6 +233,8 @@ pub struct ToolOutput {
     pub content: Vec<ContentBlock>,
     /// Whether this result represents an error
     pub is_error: bool,
+    /// Structured JSON payload the text content was rendered from, if any
+    pub data: Option<Value>,
 }
 
 impl ToolOutput {
@@ -69,6 +243,7 @@ impl ToolOutput {
         Self {
             content: vec![ContentBlock::Text { text: text.into() }],
             is_error: false,
+            data: None,
         }
     }
 
@@ -78,6 +253,7 @@ impl ToolOutput {
         Ok(Self {
             content: vec![ContentBlock::Text { text: json }],
             is_error: false,
+            data: Some(serde_json::to_value(&value)?),
         })
     }
 
@@ -87,6 +263,7 @@ impl ToolOutput {
         Ok(Self {
             content: vec![ContentBlock::Text { text: json }],
             is_error: false,
+            data: Some(value),
         })
     }
 
@@ -97,12 +274,140 @@ impl ToolOutput {
                 text: message.into(),
             }],
             is_error: true,
+            data: None,
         }
     }
 
     /// Create a result with multiple content blocks
     pub fn multi(content: Vec<ContentBlock>, is_error: bool) -> Self {
-        Self { content, is_error }
+        Self {
+            content,
+            is_error,
+            data: None,
+        }
+    }
+
+    /// Re-render JSON results in the given output format
+    ///
+    /// Outputs without a structured payload (plain text, images, errors) are
+    /// returned unchanged.
+    pub fn formatted(mut self, format: OutputFormat) -> Result<Self, ToolError> {
+        if format == OutputFormat::Pretty {
+            return Ok(self);
+        }
+        if let Some(data) = &self.data {
+            let text = crate::tools::format::render(data, format)?;
+            self.set_rendered_text(text);
+        }
+        Ok(self)
+    }
+
+    /// Project a JSON result down to the requested top-level fields
+    ///
+    /// Objects keep only the listed keys; arrays of objects are projected
+    /// element-wise. Field names not present in the result are ignored and
+    /// reported in a trailing note. `None` or an empty list leaves the output
+    /// unchanged.
+    pub fn select_fields(mut self, fields: Option<&[String]>) -> Result<Self, ToolError> {
+        let (Some(fields), Some(data)) = (fields, self.data.as_mut()) else {
+            return Ok(self);
+        };
+        if fields.is_empty() {
+            return Ok(self);
+        }
+
+        let mut found = vec![false; fields.len()];
+        let mut project = |value: &mut Value| {
+            if let Value::Object(map) = value {
+                for (i, field) in fields.iter().enumerate() {
+                    found[i] |= map.contains_key(field);
+                }
+                map.retain(|key, _| fields.contains(key));
+            }
+        };
+        match &mut *data {
+            Value::Array(items) => items.iter_mut().for_each(&mut project),
+            other => project(other),
+        }
+
+        let text = serde_json::to_string_pretty(data)?;
+        self.set_rendered_text(text);
+
+        let unknown: Vec<&str> = fields
+            .iter()
+            .zip(&found)
+            .filter(|(_, found)| !**found)
+            .map(|(field, _)| field.as_str())
+            .collect();
+        if !unknown.is_empty() {
+            self.content.push(ContentBlock::Text {
+                text: format!("Note: ignored unknown fields: {}", unknown.join(", ")),
+            });
+        }
+        Ok(self)
+    }
+
+    /// Wrap a JSON array result as `{items, total, next_page}`
+    ///
+    /// Results that are not arrays are returned unchanged.
+    pub fn with_pagination(mut self, pagination: Pagination) -> Result<Self, ToolError> {
+        let Some(Value::Array(items)) = self.data.take() else {
+            return Ok(self);
+        };
+
+        let data = serde_json::json!({
+            "items": items,
+            "total": pagination.total,
+            "next_page": pagination.next_page,
+        });
+        let text = serde_json::to_string_pretty(&data)?;
+        self.set_rendered_text(text);
+        self.data = Some(data);
+        Ok(self)
+    }
+
+    /// Truncate a list result to at most `max_items` items
+    ///
+    /// Applies to JSON arrays and to the `items` of paginated results. A
+    /// trailing note reports how many items were omitted. `None` leaves the
+    /// output unchanged.
+    pub fn capped(mut self, max_items: Option<usize>) -> Result<Self, ToolError> {
+        let (Some(max_items), Some(data)) = (max_items, self.data.as_mut()) else {
+            return Ok(self);
+        };
+        let items = match data {
+            Value::Array(items) => items,
+            Value::Object(map) => match map.get_mut("items") {
+                Some(Value::Array(items)) => items,
+                _ => return Ok(self),
+            },
+            _ => return Ok(self),
+        };
+        if items.len() <= max_items {
+            return Ok(self);
+        }
+
+        let total = items.len();
+        items.truncate(max_items);
+        let text = serde_json::to_string_pretty(data)?;
+        self.set_rendered_text(text);
+        self.content.push(ContentBlock::Text {
+            text: format!(
+                "Note: results truncated to {} of {} items ({} omitted, server.max_items_per_list)",
+                max_items,
+                total,
+                total - max_items
+            ),
+        });
+        Ok(self)
+    }
+
+    /// Replace the leading text block rendered from `data`
+    fn set_rendered_text(&mut self, text: String) {
+        match self.content.first_mut() {
+            Some(ContentBlock::Text { text: existing }) => *existing = text,
+            _ => self.content.insert(0, ContentBlock::Text { text }),
+        }
     }
 }
 
@@ -151,6 +456,21 @@ pub trait ToolInfo {
 
     /// Get the operation type
     fn operation_type() -> crate::access_control::OperationType;
+
+    /// Name of the required argument holding the project, if any
+    fn project_field() -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the tool returns raw text (e.g., file contents) rather than JSON
+    fn text_output() -> bool {
+        false
+    }
+
+    /// Whether the result holds a secret that must not be cached
+    fn returns_secret() -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +502,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_output_select_fields() {
+        let data = serde_json::json!({"iid": 1, "title": "test", "description": "long"});
+        let fields = vec!["iid".to_string(), "title".to_string(), "bogus".to_string()];
+        let output = ToolOutput::json_value(data)
+            .unwrap()
+            .select_fields(Some(&fields))
+            .unwrap();
+
+        assert_eq!(
+            output.data,
+            Some(serde_json::json!({"iid": 1, "title": "test"}))
+        );
+        assert_eq!(output.content.len(), 2);
+        match &output.content[1] {
+            ContentBlock::Text { text } => assert!(text.contains("bogus")),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[test]
+    fn test_tool_output_select_fields_array() {
+        let data = serde_json::json!([{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]);
+        let fields = vec!["id".to_string()];
+        let output = ToolOutput::json_value(data)
+            .unwrap()
+            .select_fields(Some(&fields))
+            .unwrap();
+
+        assert_eq!(output.data, Some(serde_json::json!([{"id": 1}, {"id": 2}])));
+        assert_eq!(output.content.len(), 1);
+    }
+
     #[test]
     fn test_tool_output_error() {
         let output = ToolOutput::error("Something went wrong");
@@ -0,0 +1,161 @@
+//! Tool output formatting
+//!
+//! Renders JSON tool results according to the configured [`OutputFormat`].
+
+use crate::config::OutputFormat;
+use crate::error::ToolError;
+use serde_json::{Map, Value};
+
+/// Keys shown first in markdown tables, in this order, when present
+const PREFERRED_COLUMNS: &[&str] = &[
+    "iid",
+    "id",
+    "name",
+    "title",
+    "state",
+    "status",
+    "ref",
+    "author",
+    "created_at",
+    "web_url",
+];
+
+/// Maximum number of columns in a markdown table
+const MAX_TABLE_COLUMNS: usize = 6;
+
+/// Render a JSON value in the given output format
+pub fn render(value: &Value, format: OutputFormat) -> Result<String, ToolError> {
+    match format {
+        OutputFormat::Pretty => Ok(serde_json::to_string_pretty(value)?),
+        OutputFormat::Compact => Ok(serde_json::to_string(value)?),
+        OutputFormat::Markdown => Ok(to_markdown(value)),
+    }
+}
+
+/// Render a JSON value as markdown
+///
+/// Arrays of objects become tables, objects become bullet lists, and
+/// everything else is rendered inline.
+fn to_markdown(value: &Value) -> String {
+    match value {
+        Value::Array(items) if items.is_empty() => "_No results_".to_string(),
+        Value::Array(items) if items.iter().all(Value::is_object) => table(items),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| format!("- {}", cell(item)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| format!("- **{}**: {}", key, cell(value)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => cell(other),
+    }
+}
+
+/// Render an array of objects as a markdown table
+fn table(items: &[Value]) -> String {
+    let rows: Vec<&Map<String, Value>> = items.iter().filter_map(Value::as_object).collect();
+    let columns = table_columns(&rows);
+
+    let mut out = format!("| {} |\n", columns.join(" | "));
+    out.push_str(&format!("|{}\n", " --- |".repeat(columns.len())));
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| row.get(*column).map(cell).unwrap_or_default())
+            .collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Choose table columns: preferred keys first, then other scalar keys
+fn table_columns<'a>(rows: &[&'a Map<String, Value>]) -> Vec<&'a str> {
+    let mut columns: Vec<&str> = Vec::new();
+    let first = rows.first().copied();
+
+    for key in PREFERRED_COLUMNS {
+        if let Some((key, _)) = first.and_then(|row| row.get_key_value(*key)) {
+            columns.push(key.as_str());
+        }
+    }
+
+    if let Some(row) = first {
+        for (key, value) in row {
+            if columns.len() >= MAX_TABLE_COLUMNS {
+                break;
+            }
+            let scalar = !matches!(value, Value::Object(_) | Value::Array(_));
+            if scalar && !columns.contains(&key.as_str()) {
+                columns.push(key.as_str());
+            }
+        }
+    }
+
+    columns.truncate(MAX_TABLE_COLUMNS);
+    columns
+}
+
+/// Render a single value for use inside a table cell or list item
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Object(map) => ["username", "name", "title"]
+            .iter()
+            .find_map(|key| map.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        Value::Array(items) if items.iter().all(Value::is_string) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    };
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_compact() {
+        let value = json!({"id": 1, "title": "Test"});
+        let text = render(&value, OutputFormat::Compact).unwrap();
+        assert_eq!(text, r#"{"id":1,"title":"Test"}"#);
+    }
+
+    #[test]
+    fn test_render_markdown_table() {
+        let value = json!([
+            {"iid": 1, "title": "First | issue", "state": "opened", "author": {"username": "alice"}},
+            {"iid": 2, "title": "Second", "state": "closed", "author": {"username": "bob"}}
+        ]);
+        let text = render(&value, OutputFormat::Markdown).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "| iid | title | state | author |");
+        assert_eq!(lines[1], "| --- | --- | --- | --- |");
+        assert_eq!(lines[2], "| 1 | First \\| issue | opened | alice |");
+        assert_eq!(lines[3], "| 2 | Second | closed | bob |");
+    }
+
+    #[test]
+    fn test_render_markdown_object() {
+        let value = json!({"iid": 3, "labels": ["bug", "ui"]});
+        let text = render(&value, OutputFormat::Markdown).unwrap();
+        assert!(text.contains("- **iid**: 3"));
+        assert!(text.contains("- **labels**: bug, ui"));
+    }
+
+    #[test]
+    fn test_render_markdown_empty_list() {
+        let text = render(&json!([]), OutputFormat::Markdown).unwrap();
+        assert_eq!(text, "_No results_");
+    }
+}
@@ -0,0 +1,275 @@
+//! Idempotency cache
+//!
+//! Remembers the results of create tool calls made with an idempotency key,
+//! so that a retried call returns the original result instead of creating a
+//! duplicate in GitLab. Keys are scoped to the caller, so a caller using its
+//! own GitLab token never receives a result created with someone else's, and
+//! bound to the arguments they were first used with: reusing a key for a
+//! different request is rejected rather than answered with the old result.
+
+use crate::error::ToolError;
+use crate::tools::executor::ToolOutput;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Caller, tool, and idempotency key of a cached result
+type EntryKey = (Option<String>, String, String);
+
+/// Short-lived in-memory map of (caller, tool, idempotency key) to tool result
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<EntryKey, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    stored_at: Instant,
+    args: Value,
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    /// A call with the key is running; its result is sent on the channel
+    Pending(watch::Receiver<Option<ToolOutput>>),
+    /// The call finished with this result
+    Done(ToolOutput),
+}
+
+/// Outcome of [`IdempotencyCache::claim`]
+#[derive(Debug)]
+pub enum Claim<'a> {
+    /// Result of an earlier call made with the key
+    Cached(ToolOutput),
+    /// No call has used the key yet; this call owns it
+    Reserved(Reservation<'a>),
+}
+
+/// A key held by a running call
+///
+/// Calls with the same key wait until the result is passed to
+/// [`Reservation::complete`]. Dropping the reservation without completing
+/// it releases the key so the call can be retried.
+#[derive(Debug)]
+pub struct Reservation<'a> {
+    cache: &'a IdempotencyCache,
+    key: EntryKey,
+    sender: watch::Sender<Option<ToolOutput>>,
+}
+
+impl IdempotencyCache {
+    /// Create a cache whose entries expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim a caller's key before making the call
+    ///
+    /// `caller` is `None` for calls made with the server's own token. Returns
+    /// the cached result if the key was already used, waiting for it while an
+    /// earlier call with the key is still running. Fails when the key was
+    /// used with arguments other than `args`.
+    pub async fn claim(
+        &self,
+        caller: Option<&str>,
+        tool: &str,
+        key: &str,
+        args: &Value,
+    ) -> Result<Claim<'_>, ToolError> {
+        let entry_key = (
+            caller.map(str::to_string),
+            tool.to_string(),
+            key.to_string(),
+        );
+
+        loop {
+            let mut pending = {
+                let mut entries = self.lock();
+                let now = Instant::now();
+                entries.retain(|_, entry| {
+                    matches!(entry.state, State::Pending(_))
+                        || now.duration_since(entry.stored_at) < self.ttl
+                });
+                match entries.get(&entry_key) {
+                    Some(entry) if entry.args != *args => {
+                        return Err(ToolError::InvalidArguments(format!(
+                            "idempotency_key '{}' was already used with different arguments",
+                            key
+                        )));
+                    }
+                    Some(Entry {
+                        state: State::Done(output),
+                        ..
+                    }) => return Ok(Claim::Cached(output.clone())),
+                    Some(Entry {
+                        state: State::Pending(receiver),
+                        ..
+                    }) => receiver.clone(),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        entries.insert(
+                            entry_key.clone(),
+                            Entry {
+                                stored_at: now,
+                                args: args.clone(),
+                                state: State::Pending(receiver),
+                            },
+                        );
+                        return Ok(Claim::Reserved(Reservation {
+                            cache: self,
+                            key: entry_key,
+                            sender,
+                        }));
+                    }
+                }
+            };
+
+            if let Ok(output) = pending.wait_for(Option::is_some).await
+                && let Some(output) = output.as_ref()
+            {
+                return Ok(Claim::Cached(output.clone()));
+            }
+            // The earlier call failed and released the key, so claim it again
+        }
+    }
+
+    /// Lock the entries, recovering from a poisoned lock
+    fn lock(&self) -> MutexGuard<'_, HashMap<EntryKey, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Reservation<'_> {
+    /// Remember the result of the call and hand it to calls waiting on the key
+    pub fn complete(self, output: ToolOutput) {
+        if let Some(entry) = self.cache.lock().get_mut(&self.key) {
+            entry.stored_at = Instant::now();
+            entry.state = State::Done(output.clone());
+        }
+        self.sender.send_replace(Some(output));
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.sender.borrow().is_none() {
+            self.cache.lock().remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Value {
+        serde_json::json!({"title": "Issue"})
+    }
+
+    /// Claim a key that must be free and complete it with `output`
+    async fn store(cache: &IdempotencyCache, caller: Option<&str>, key: &str, output: &str) {
+        match cache
+            .claim(caller, "create_issue", key, &args())
+            .await
+            .unwrap()
+        {
+            Claim::Reserved(reservation) => reservation.complete(ToolOutput::text(output)),
+            Claim::Cached(_) => panic!("key {} was already used", key),
+        }
+    }
+
+    async fn is_cached(
+        cache: &IdempotencyCache,
+        caller: Option<&str>,
+        tool: &str,
+        key: &str,
+    ) -> bool {
+        matches!(
+            cache.claim(caller, tool, key, &args()).await.unwrap(),
+            Claim::Cached(_)
+        )
+    }
+
+    #[tokio::test]
+    async fn test_cached_result_is_returned() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        store(&cache, None, "key-1", "created").await;
+
+        assert!(is_cached(&cache, None, "create_issue", "key-1").await);
+        assert!(!is_cached(&cache, None, "create_issue", "key-2").await);
+        assert!(!is_cached(&cache, None, "create_label", "key-1").await);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_to_the_caller() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        store(&cache, Some("a"), "key-1", "created").await;
+
+        assert!(is_cached(&cache, Some("a"), "create_issue", "key-1").await);
+        assert!(!is_cached(&cache, Some("b"), "create_issue", "key-1").await);
+        assert!(!is_cached(&cache, None, "create_issue", "key-1").await);
+    }
+
+    #[tokio::test]
+    async fn test_reused_key_with_different_arguments_is_rejected() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        store(&cache, None, "key-1", "created").await;
+
+        let other = serde_json::json!({"title": "Another issue"});
+        let error = cache
+            .claim(None, "create_issue", "key-1", &other)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("different arguments"));
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire() {
+        let cache = IdempotencyCache::new(Duration::ZERO);
+        store(&cache, None, "key-1", "created").await;
+
+        assert!(!is_cached(&cache, None, "create_issue", "key-1").await);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_waits_for_the_running_call() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let args = args();
+        let Claim::Reserved(reservation) = cache
+            .claim(None, "create_issue", "key-1", &args)
+            .await
+            .unwrap()
+        else {
+            panic!("first claim should reserve the key");
+        };
+
+        let (claim, ()) = tokio::join!(cache.claim(None, "create_issue", "key-1", &args), async {
+            tokio::task::yield_now().await;
+            reservation.complete(ToolOutput::text("created"));
+        });
+        assert!(matches!(claim.unwrap(), Claim::Cached(_)));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_reservation_releases_the_key() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let claim = cache
+            .claim(None, "create_issue", "key-1", &args())
+            .await
+            .unwrap();
+        drop(claim);
+
+        let claim = cache
+            .claim(None, "create_issue", "key-1", &args())
+            .await
+            .unwrap();
+        assert!(matches!(claim, Claim::Reserved(_)));
+    }
+}
@@ -4,9 +4,12 @@
 
 pub mod definitions;
 pub mod executor;
+pub mod format;
+pub mod idempotency;
 pub mod registry;
 
-pub use executor::{ContentBlock, ToolContext, ToolExecutor, ToolInfo, ToolOutput};
+pub use executor::{ContentBlock, PollConfig, ToolContext, ToolExecutor, ToolInfo, ToolOutput};
+pub use idempotency::IdempotencyCache;
 pub use registry::{RegisteredTool, ToolRegistration, ToolRegistry};
 
 // Re-export the macro for convenience
@@ -2,18 +2,21 @@
 //!
 //! Manages the collection of available tools and their metadata.
 
+use crate::access_control::ProjectAllowlist;
 use crate::access_control::{AccessControlled, AccessDecision, OperationType, ToolCategory};
 use crate::error::{AccessDeniedError, ToolError};
+use crate::gitlab::{with_pagination, with_request_id};
 use crate::tools::executor::ToolInfo;
 use crate::tools::executor::{ToolContext, ToolExecutor, ToolOutput};
+use crate::tools::idempotency::Claim;
 // async_trait required for dyn-compatibility with Box<dyn ToolHandler>
 use async_trait::async_trait;
 use schemars::Schema;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::collections::HashMap;
-use std::time::Instant;
-use tracing::{debug, instrument, warn};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, instrument, warn};
 
 /// Compile-time tool registration entry for auto-discovery
 ///
@@ -26,6 +29,9 @@ pub struct ToolRegistration {
 
 inventory::collect!(ToolRegistration);
 
+/// Arguments that name a project, checked against `gitlab.allowed_projects`
+const PROJECT_ARGUMENTS: &[&str] = &["project", "target_project"];
+
 /// A registered tool with all its metadata
 pub struct RegisteredTool {
     /// Tool name
@@ -38,6 +44,19 @@ pub struct RegisteredTool {
     pub operation: OperationType,
     /// JSON Schema for the tool's input
     pub input_schema: Schema,
+    /// Whether the registry handles a per-call `dry_run` argument for this tool
+    ///
+    /// False for read-only tools and for tools that forward their own
+    /// `dry_run` argument to GitLab.
+    dry_run_arg: bool,
+    /// Whether the registry handles a per-call `idempotency_key` argument
+    idempotency_arg: bool,
+    /// Whether the registry handles a per-call `fields` projection argument
+    fields_arg: bool,
+    /// Whether the tool takes a `project` argument
+    takes_project: bool,
+    /// Required argument holding the project, checked before the call
+    project_field: Option<&'static str>,
     /// The tool handler
     handler: Box<dyn ToolHandler>,
 }
@@ -48,10 +67,19 @@ trait ToolHandler: Send + Sync {
     /// Execute the tool with raw JSON arguments
     async fn call(&self, ctx: &ToolContext, args: Value) -> Result<ToolOutput, ToolError>;
 
+    /// Check that the arguments deserialize into the tool without executing it
+    fn validate(&self, args: &Value) -> Result<(), ToolError>;
+
     /// Extract project from arguments (for access control)
     fn extract_project(&self, args: &Value) -> Option<String>;
 }
 
+/// Deserialize raw JSON arguments into a tool struct
+fn parse_arguments<T: DeserializeOwned>(args: Value) -> Result<T, ToolError> {
+    serde_json::from_value(args)
+        .map_err(|e| ToolError::InvalidArguments(format!("Failed to parse arguments: {}", e)))
+}
+
 /// Generic tool handler implementation
 struct TypedToolHandler<T>
 where
@@ -78,14 +106,16 @@ where
 {
     async fn call(&self, ctx: &ToolContext, args: Value) -> Result<ToolOutput, ToolError> {
         // Deserialize arguments into the tool struct
-        let tool: T = serde_json::from_value(args).map_err(|e| {
-            ToolError::InvalidArguments(format!("Failed to parse arguments: {}", e))
-        })?;
+        let tool: T = parse_arguments(args)?;
 
         // Execute the tool
         tool.execute(ctx).await
     }
 
+    fn validate(&self, args: &Value) -> Result<(), ToolError> {
+        parse_arguments::<T>(args.clone()).map(drop)
+    }
+
     fn extract_project(&self, args: &Value) -> Option<String> {
         // Try to deserialize and extract project
         // If deserialization fails, return None
@@ -98,17 +128,22 @@ where
 }
 
 /// Tool registry
+///
+/// Tools are kept sorted by name, so listings are stable across runs no
+/// matter in which order they were registered.
 pub struct ToolRegistry {
-    tools: HashMap<String, RegisteredTool>,
+    tools: BTreeMap<String, RegisteredTool>,
     by_category: HashMap<ToolCategory, Vec<String>>,
+    disabled: HashSet<String>,
 }
 
 impl ToolRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
         Self {
-            tools: HashMap::new(),
+            tools: BTreeMap::new(),
             by_category: HashMap::new(),
+            disabled: HashSet::new(),
         }
     }
 
@@ -130,7 +165,49 @@ impl ToolRegistry {
         let operation = <T as ToolInfo>::operation_type();
 
         // Generate JSON Schema
-        let input_schema = schemars::schema_for!(T);
+        let mut input_schema = schemars::schema_for!(T);
+
+        // Mutating tools accept a per-call `dry_run` argument, unless the tool
+        // already defines one that it forwards to GitLab
+        let dry_run_arg = operation.is_mutating()
+            && add_optional_property(
+                &mut input_schema,
+                "dry_run",
+                serde_json::json!({
+                    "type": "boolean",
+                    "description": "Validate the call and describe what it would do without changing anything in GitLab"
+                }),
+            );
+
+        // Create tools accept an `idempotency_key` so retries do not duplicate,
+        // unless their result is a secret the cache would have to keep
+        let idempotency_arg = operation == OperationType::Write
+            && name.starts_with("create_")
+            && !<T as ToolInfo>::returns_secret()
+            && add_optional_property(
+                &mut input_schema,
+                "idempotency_key",
+                serde_json::json!({
+                    "type": "string",
+                    "description": "Unique key for this creation; retrying with the same key returns the original result instead of creating a duplicate"
+                }),
+            );
+
+        // Read tools accept `fields` to trim large JSON results to what is needed
+        let fields_arg = operation == OperationType::Read
+            && !<T as ToolInfo>::text_output()
+            && add_optional_property(
+                &mut input_schema,
+                "fields",
+                serde_json::json!({
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only return these top-level fields of the result (e.g., [\"id\", \"title\"])"
+                }),
+            );
+        let takes_project = input_schema
+            .get("properties")
+            .is_some_and(|properties| properties.get("project").is_some());
 
         let tool = RegisteredTool {
             name,
@@ -138,6 +215,11 @@ impl ToolRegistry {
             category,
             operation,
             input_schema,
+            dry_run_arg,
+            idempotency_arg,
+            fields_arg,
+            takes_project,
+            project_field: <T as ToolInfo>::project_field(),
             handler: Box::new(TypedToolHandler::<T>::new()),
         };
 
@@ -153,24 +235,55 @@ impl ToolRegistry {
         debug!(name = name, category = %category, "Registered tool");
     }
 
+    /// Remove a tool from the registry
+    ///
+    /// Returns the removed tool, or `None` if no tool with that name was registered.
+    pub fn unregister(&mut self, name: &str) -> Option<RegisteredTool> {
+        let tool = self.tools.remove(name)?;
+        if let Some(names) = self.by_category.get_mut(&tool.category) {
+            names.retain(|n| n != name);
+        }
+        debug!(name = name, "Unregistered tool");
+        Some(tool)
+    }
+
+    /// Disable a tool by configuration
+    ///
+    /// The tool is removed from the registry, and calls to it fail with
+    /// [`ToolError::Disabled`] instead of "not found". Returns false if no
+    /// tool with that name was registered.
+    pub fn disable(&mut self, name: &str) -> bool {
+        let removed = self.unregister(name).is_some();
+        if removed {
+            self.disabled.insert(name.to_string());
+        }
+        removed
+    }
+
+    /// Check whether a tool was disabled by configuration
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.disabled.contains(name)
+    }
+
     /// Get a tool by name
     pub fn get(&self, name: &str) -> Option<&RegisteredTool> {
         self.tools.get(name)
     }
 
-    /// Get all tool names
+    /// Get all tool names, sorted
     pub fn tool_names(&self) -> impl Iterator<Item = &str> {
         self.tools.keys().map(|s| s.as_str())
     }
 
-    /// Get all tools
+    /// Get all tools, sorted by name
     pub fn tools(&self) -> impl Iterator<Item = &RegisteredTool> {
         self.tools.values()
     }
 
-    /// Get tools by category
+    /// Get tools by category, sorted by name
     pub fn tools_in_category(&self, category: ToolCategory) -> Vec<&RegisteredTool> {
-        self.by_category
+        let mut tools: Vec<&RegisteredTool> = self
+            .by_category
             .get(&category)
             .map(|names| {
                 names
@@ -178,7 +291,9 @@ impl ToolRegistry {
                     .filter_map(|name| self.tools.get(name))
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        tools.sort_by_key(|tool| tool.name);
+        tools
     }
 
     /// Get the number of registered tools
@@ -191,6 +306,27 @@ impl ToolRegistry {
         self.tools.is_empty()
     }
 
+    /// Describe every tool as JSON, sorted by name
+    ///
+    /// Each entry holds the tool's name, description, category, operation,
+    /// and input schema, for tooling that needs the catalog without a server.
+    pub fn catalog(&self) -> Value {
+        Value::Array(
+            self.tools
+                .values()
+                .map(|tool| {
+                    serde_json::json!({
+                        "name": tool.name,
+                        "description": tool.description,
+                        "category": tool.category.as_str(),
+                        "operation": tool.operation.as_str(),
+                        "input_schema": tool.input_schema,
+                    })
+                })
+                .collect(),
+        )
+    }
+
     /// Register all tools discovered via `#[gitlab_tool]` macro
     ///
     /// This method iterates over all `ToolRegistration` entries submitted at compile time
@@ -202,6 +338,9 @@ impl ToolRegistry {
     }
 
     /// Execute a tool by name
+    ///
+    /// Logs one "Tool call finished" line per call with the tool, category,
+    /// operation, project, access decision, latency, and outcome.
     #[instrument(skip(self, ctx, args), fields(tool = %name))]
     pub async fn execute(
         &self,
@@ -210,19 +349,126 @@ impl ToolRegistry {
         args: Value,
     ) -> Result<ToolOutput, ToolError> {
         let start = Instant::now();
+        let mut summary = CallSummary::default();
+        let result = self
+            .execute_inner(name, ctx, args, start, &mut summary)
+            .await;
+        summary.log(name, start.elapsed(), &result);
+        result
+    }
 
-        let tool = self
-            .tools
-            .get(name)
-            .ok_or_else(|| ToolError::NotFound(name.to_string()))?;
+    async fn execute_inner(
+        &self,
+        name: &str,
+        ctx: &ToolContext,
+        mut args: Value,
+        start: Instant,
+        summary: &mut CallSummary,
+    ) -> Result<ToolOutput, ToolError> {
+        let tool = self.tools.get(name).ok_or_else(|| {
+            if self.is_disabled(name) {
+                ToolError::Disabled(name.to_string())
+            } else {
+                ToolError::NotFound(name.to_string())
+            }
+        })?;
+        summary.category = Some(tool.category);
+        summary.operation = Some(tool.operation);
+
+        // Reject oversized arguments before any parsing or GitLab call
+        if let Some(limit) = ctx.max_request_bytes {
+            let size = serialized_len(&args);
+            if size > limit {
+                warn!(
+                    tool = %name,
+                    size,
+                    limit,
+                    request_id = %ctx.request_id,
+                    "Tool arguments too large"
+                );
+                let error = ToolError::InvalidArguments(format!(
+                    "Arguments are {} bytes, exceeding the server limit of {} bytes",
+                    size, limit
+                ));
+                return Err(record_rejection(ctx, tool, None, start, error));
+            }
+        }
+
+        // Fill in the default project when the caller omitted a required
+        // one; an optional project (e.g. lint_ci_yaml's) stays omitted
+        if tool.project_field == Some("project")
+            && let Some(default_project) = ctx.default_project.as_deref()
+            && let Some(map) = args.as_object_mut()
+            && map.get("project").is_none_or(Value::is_null)
+        {
+            map.insert("project".to_string(), Value::from(default_project));
+        }
+
+        // A missing project would otherwise surface as a deserialization error
+        if let Some(field) = tool.project_field
+            && args.get(field).is_none_or(|value| {
+                value.is_null() || value.as_str().is_some_and(|s| s.trim().is_empty())
+            })
+        {
+            let error = ToolError::MissingArgument(format!(
+                "{} (tool '{}' requires a project ID or path, e.g. \"group/project\")",
+                field, name
+            ));
+            return Err(record_rejection(ctx, tool, None, start, error));
+        }
 
         // Extract project for access control
-        let project = tool.handler.extract_project(&args);
+        let mut project = tool.handler.extract_project(&args);
+
+        // Project rules and the allowlist are keyed by path, so look up
+        // numeric IDs first
+        if let Some(id) = project.as_deref() {
+            let path = ctx
+                .project_path_for_access(id)
+                .await
+                .map_err(|error| record_rejection(ctx, tool, Some(id), start, error.into()))?;
+            project = Some(path);
+        }
+
+        // Projects outside the allowlist are refused whatever the access rules say
+        if let Some(allowlist) = &ctx.allowed_projects
+            && let Some(error) =
+                Self::check_allowlist(allowlist, tool, &args, project.as_deref(), ctx).await?
+        {
+            warn!(
+                tool = %name,
+                project = ?project,
+                reason = %error.reason,
+                request_id = %ctx.request_id,
+                "Tool call refused by gitlab.allowed_projects"
+            );
+            summary.project = project.clone();
+            summary.decision = Some("denied");
+            if let Some(ref metrics) = ctx.metrics {
+                metrics.record_call_with_audit(
+                    name,
+                    tool.category,
+                    project.as_deref(),
+                    start.elapsed(),
+                    false,
+                    Some(&ctx.request_id),
+                    Some("denied"),
+                    Some(&error.reason),
+                );
+            }
+            return Err(ToolError::AccessDenied(error));
+        }
 
         // Check access control with enhanced error messages
         let decision = ctx
             .access
             .check(name, tool.category, tool.operation, project.as_deref());
+        summary.project = project.clone();
+        summary.decision = Some(if decision.is_allowed() {
+            "allowed"
+        } else {
+            "denied"
+        });
 
         if let AccessDecision::Denied(reason) = decision {
             // Check if tool is globally denied vs project-specific denial
@@ -274,8 +520,82 @@ impl ToolRegistry {
             return Err(ToolError::AccessDenied(error));
         }
 
-        // Execute the tool
-        let result = tool.handler.call(ctx, args).await;
+        // In dry-run mode, mutating tools only validate their arguments
+        let per_call_dry_run = tool.dry_run_arg
+            && args
+                .as_object_mut()
+                .and_then(|map| map.remove("dry_run"))
+                .is_some_and(|value| value == Value::Bool(true));
+        let dry_run = tool.operation.is_mutating() && (ctx.dry_run || per_call_dry_run);
+
+        // A retried create call with the same idempotency key reuses the
+        // result; the key is reserved before the call so that a retry made
+        // while the first call is still running waits for it
+        let idempotency = tool
+            .idempotency_arg
+            .then(|| args.as_object_mut()?.remove("idempotency_key"))
+            .flatten()
+            .and_then(|key| key.as_str().map(str::to_string))
+            .zip(ctx.idempotency.as_deref());
+        let claim = match &idempotency {
+            Some((key, cache)) if !dry_run => {
+                Some(cache.claim(ctx.gitlab.caller(), name, key, &args).await)
+            }
+            _ => None,
+        };
+
+        // Read results are projected to the requested top-level fields before
+        // any pagination wrapper is added
+        let fields: Option<Vec<String>> = tool
+            .fields_arg
+            .then(|| args.as_object_mut()?.remove("fields"))
+            .flatten()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                let error = ToolError::InvalidArguments(format!("Invalid fields argument: {e}"));
+                record_rejection(ctx, tool, project.as_deref(), start, error)
+            })?;
+        let call = |args| {
+            with_request_id(ctx.request_id.clone(), async {
+                tool.handler
+                    .call(ctx, args)
+                    .await
+                    .and_then(|output| output.select_fields(fields.as_deref()))
+            })
+        };
+
+        // Execute the tool, tagging its GitLab calls with the request ID, and
+        // render the result in the configured format
+        let result = match claim {
+            _ if dry_run => tool
+                .handler
+                .validate(&args)
+                .and_then(|()| dry_run_preview(tool, project.as_deref(), args)),
+            Some(Err(error)) => Err(error),
+            Some(Ok(Claim::Cached(output))) => {
+                debug!(tool = %name, "Returning cached result for idempotency key");
+                Ok(output)
+            }
+            Some(Ok(Claim::Reserved(reservation))) => {
+                let result = call(args).await;
+                if let Ok(ref output) = result
+                    && !output.is_error
+                {
+                    reservation.complete(output.clone());
+                }
+                result
+            }
+            None if ctx.pagination_meta && tool.operation == OperationType::Read => {
+                match with_pagination(call(args)).await {
+                    (Ok(output), Some(pagination)) => output.with_pagination(pagination),
+                    (result, _) => result,
+                }
+            }
+            None => call(args).await,
+        }
+        .and_then(|output| output.capped(ctx.max_items_per_list))
+        .and_then(|output| output.formatted(ctx.output_format));
 
         // Record metrics with audit info if available
         if let Some(ref metrics) = ctx.metrics {
@@ -300,6 +620,174 @@ impl ToolRegistry {
 
         result
     }
+
+    /// Check a call against `gitlab.allowed_projects`
+    ///
+    /// Besides the project used for access control (already resolved to a
+    /// path), optional `project` arguments and secondary project arguments
+    /// such as `target_project` must be allowed too. Raw requests can reach
+    /// any endpoint, and tools without a project argument act on groups,
+    /// users, or the whole instance, so they are refused outright.
+    async fn check_allowlist(
+        allowlist: &ProjectAllowlist,
+        tool: &RegisteredTool,
+        args: &Value,
+        project: Option<&str>,
+        ctx: &ToolContext,
+    ) -> Result<Option<AccessDeniedError>, ToolError> {
+        if tool.category == ToolCategory::Raw {
+            return Ok(Some(AccessDeniedError::new(
+                tool.name,
+                "forbidden: raw requests are disabled while gitlab.allowed_projects is set",
+            )));
+        }
+
+        if !tool.takes_project {
+            return Ok(Some(AccessDeniedError::new(
+                tool.name,
+                "forbidden: tools without a project argument are disabled while gitlab.allowed_projects is set",
+            )));
+        }
+
+        if let Some(path) = project.filter(|path| !allowlist.allows(path)) {
+            return Ok(Some(AccessDeniedError::project_not_allowed(
+                tool.name, path,
+            )));
+        }
+
+        for field in PROJECT_ARGUMENTS {
+            let Some(value) = args.get(*field).and_then(Value::as_str) else {
+                continue;
+            };
+            if value.trim().is_empty() || tool.project_field == Some(*field) {
+                continue;
+            }
+            if let Some(path) = allowlist.outside(&ctx.gitlab, value).await? {
+                return Ok(Some(AccessDeniedError::project_not_allowed(
+                    tool.name, path,
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// What is known about a tool call when it finishes, for its log line
+#[derive(Debug, Default)]
+struct CallSummary {
+    category: Option<ToolCategory>,
+    operation: Option<OperationType>,
+    project: Option<String>,
+    decision: Option<&'static str>,
+}
+
+impl CallSummary {
+    fn log(&self, tool: &str, duration: Duration, result: &Result<ToolOutput, ToolError>) {
+        let (outcome, error_code) = match result {
+            Ok(output) if !output.is_error => ("ok", None),
+            Ok(_) => ("error", Some("tool_error")),
+            Err(e) => ("error", Some(e.code())),
+        };
+
+        info!(
+            tool = %tool,
+            category = self.category.map(|c| c.as_str()),
+            operation = self.operation.map(|o| o.as_str()),
+            project = self.project.as_deref(),
+            decision = self.decision,
+            duration_ms = duration.as_millis() as u64,
+            outcome,
+            error_code,
+            "Tool call finished"
+        );
+    }
+}
+
+/// Record a call refused before the access check, returning its error
+fn record_rejection(
+    ctx: &ToolContext,
+    tool: &RegisteredTool,
+    project: Option<&str>,
+    start: Instant,
+    error: ToolError,
+) -> ToolError {
+    if let Some(ref metrics) = ctx.metrics {
+        metrics.record_call_with_audit(
+            tool.name,
+            tool.category,
+            project,
+            start.elapsed(),
+            false,
+            Some(&ctx.request_id),
+            None,
+            Some(&error.to_string()),
+        );
+    }
+    error
+}
+
+/// Add an optional property to a tool's input schema
+///
+/// Returns false, leaving the schema untouched, if the tool already defines
+/// a property with that name.
+fn add_optional_property(schema: &mut Schema, name: &str, property: Value) -> bool {
+    let Some(properties) = schema
+        .as_object_mut()
+        .and_then(|schema| schema.get_mut("properties"))
+        .and_then(Value::as_object_mut)
+    else {
+        return false;
+    };
+    if properties.contains_key(name) {
+        return false;
+    }
+    properties.insert(name.to_string(), property);
+    true
+}
+
+/// Describe what a mutating tool call would do, without calling GitLab
+fn dry_run_preview(
+    tool: &RegisteredTool,
+    project: Option<&str>,
+    arguments: Value,
+) -> Result<ToolOutput, ToolError> {
+    let target = project
+        .map(|project| format!(" on project '{}'", project))
+        .unwrap_or_default();
+    ToolOutput::json_value(serde_json::json!({
+        "dry_run": true,
+        "tool": tool.name,
+        "category": tool.category,
+        "operation": tool.operation,
+        "project": project,
+        "arguments": arguments,
+        "summary": format!(
+            "Would run {} ({}){}; GitLab was not called",
+            tool.name, tool.operation, target
+        ),
+    }))
+}
+
+/// Size of a value's compact JSON serialization, without allocating it
+fn serialized_len(value: &Value) -> usize {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    // Writing a `Value` into an infallible writer cannot fail
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
 }
 
 impl Default for ToolRegistry {
@@ -3,20 +3,70 @@
 //! Runs the MCP server over HTTP using the Streamable HTTP transport.
 
 use crate::config::CorsMode;
-use crate::server::GitLabMcpHandler;
+use crate::error::{ToolError, TransportError, mcp_mapper::map_tool_error};
+use crate::gitlab::GitLabHealth;
+use crate::server::{GITLAB_TOKEN_HEADER, GitLabMcpHandler};
+use crate::transport::webhook::{WEBHOOK_PATH, WebhookConfig, webhook_handler};
 use crate::util::bind_port_strict;
-use axum::{Json, Router, routing::get};
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::{StreamExt, stream};
 use rmcp::transport::streamable_http_server::{
-    StreamableHttpService, session::local::LocalSessionManager,
+    SessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    session::local::LocalSessionManager,
 };
+use std::convert::Infallible;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
 use tower_http::cors::CorsLayer;
-use tracing::{Instrument, info, info_span};
+use tracing::{Instrument, info, info_span, warn};
 
 /// Default port for HTTP transport
 pub const DEFAULT_HTTP_PORT: u16 = 20289;
 
+/// Default interval between SSE keep-alive comments
+pub const DEFAULT_SSE_KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Request correlation ID header, echoed on responses and forwarded to GitLab
+const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Maximum accepted length of a client-supplied request ID
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Default maximum number of MCP requests handled concurrently
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 64;
+
+/// Default maximum number of messages in a JSON-RPC batch
+pub const DEFAULT_MAX_BATCH_LEN: usize = 32;
+
+/// Default maximum size of a single JSON-RPC message in bytes
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Largest request body read, however many messages a batch may hold
+///
+/// Raised to `max_message_bytes` when a single message may be larger.
+pub const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Media type of newline-delimited JSON tool results
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// MCP session header sent by clients after initialization
+const MCP_SESSION_ID: HeaderName = HeaderName::from_static("mcp-session-id");
+
+/// JSON-RPC error code for unparseable JSON
+const PARSE_ERROR: i64 = -32700;
+
+/// JSON-RPC error code for a message that is not a valid request
+const INVALID_REQUEST: i64 = -32600;
+
 /// Configuration for the HTTP server
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
@@ -26,6 +76,18 @@ pub struct HttpConfig {
     pub mcp_path: String,
     /// CORS mode (default: Permissive)
     pub cors: CorsMode,
+    /// Interval between SSE keep-alive comments (`None` disables them)
+    pub sse_keep_alive: Option<Duration>,
+    /// Maximum number of MCP requests handled concurrently (`None` is unbounded)
+    pub max_concurrent_requests: Option<usize>,
+    /// GitLab webhook endpoint settings (`None` disables the endpoint)
+    pub webhook: Option<WebhookConfig>,
+    /// Maximum number of messages in a JSON-RPC batch
+    pub max_batch_len: usize,
+    /// Maximum size of a single JSON-RPC message in bytes
+    pub max_message_bytes: usize,
+    /// GitLab availability reported by `/readyz` (`None` is always ready)
+    pub gitlab_health: Option<Arc<GitLabHealth>>,
 }
 
 impl Default for HttpConfig {
@@ -34,6 +96,12 @@ impl Default for HttpConfig {
             bind: SocketAddr::from(([127, 0, 0, 1], DEFAULT_HTTP_PORT)),
             mcp_path: "/mcp".to_string(),
             cors: CorsMode::default(),
+            sse_keep_alive: Some(DEFAULT_SSE_KEEP_ALIVE),
+            max_concurrent_requests: Some(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            webhook: None,
+            max_batch_len: DEFAULT_MAX_BATCH_LEN,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            gitlab_health: None,
         }
     }
 }
@@ -47,6 +115,37 @@ impl HttpConfig {
         }
     }
 
+    /// Set the SSE keep-alive interval in seconds (0 disables keep-alive)
+    pub fn with_sse_keep_alive_secs(mut self, secs: u64) -> Self {
+        self.sse_keep_alive = (secs > 0).then(|| Duration::from_secs(secs));
+        self
+    }
+
+    /// Set the maximum number of concurrent MCP requests (0 removes the limit)
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = (max > 0).then_some(max);
+        self
+    }
+
+    /// Set the limits on JSON-RPC batches and messages
+    pub fn with_message_limits(mut self, max_batch_len: usize, max_message_bytes: usize) -> Self {
+        self.max_batch_len = max_batch_len;
+        self.max_message_bytes = max_message_bytes;
+        self
+    }
+
+    /// Report not ready on `/readyz` while GitLab is degraded
+    pub fn with_gitlab_health(mut self, health: Arc<GitLabHealth>) -> Self {
+        self.gitlab_health = Some(health);
+        self
+    }
+
+    /// Enable the webhook endpoint
+    pub fn with_webhook(mut self, webhook: WebhookConfig) -> Self {
+        self.webhook = Some(webhook);
+        self
+    }
+
     /// Create config from host and port strings
     pub fn from_host_port(host: &str, port: u16) -> Result<Self, std::net::AddrParseError> {
         let addr: SocketAddr = format!("{}:{}", host, port).parse()?;
@@ -59,6 +158,387 @@ async fn health_handler() -> Json<serde_json::Value> {
     Json(serde_json::json!({"status": "ok"}))
 }
 
+/// Readiness endpoint handler
+///
+/// Answers `503 Service Unavailable` while GitLab is degraded, so load
+/// balancers stop routing to a server that cannot reach its backend.
+async fn readiness_handler(State(health): State<Option<Arc<GitLabHealth>>>) -> Response {
+    match health.map(|health| health.status()) {
+        Some(status) if status.degraded => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"status": "degraded", "gitlab": status})),
+        )
+            .into_response(),
+        _ => Json(serde_json::json!({"status": "ready"})).into_response(),
+    }
+}
+
+/// Ensure every MCP request carries an `X-Request-Id` and echo it back
+///
+/// A well-formed ID supplied by the client is kept; otherwise a new one is
+/// generated. The handler forwards it to GitLab on the resulting API calls.
+async fn propagate_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&X_REQUEST_ID)
+        .filter(|value| {
+            let bytes = value.as_bytes();
+            !bytes.is_empty()
+                && bytes.len() <= MAX_REQUEST_ID_LEN
+                && bytes.iter().all(u8::is_ascii_graphic)
+        })
+        .cloned()
+        .unwrap_or_else(|| {
+            HeaderValue::from_str(&format!("{:x}", rand::random::<u64>()))
+                .expect("hex string is a valid header value")
+        });
+
+    request
+        .headers_mut()
+        .insert(X_REQUEST_ID, request_id.clone());
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(X_REQUEST_ID, request_id);
+    response
+}
+
+/// Reject MCP requests beyond the concurrency limit with 503
+///
+/// Only POST requests carry MCP messages that cause GitLab API work; the
+/// long-lived standalone SSE stream (GET) and session teardown are not
+/// counted. A permit is held until the response body, which may be an SSE
+/// stream, has been fully sent or dropped.
+async fn limit_concurrency(
+    State(semaphore): State<Arc<Semaphore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let Ok(permit) = semaphore.try_acquire_owned() else {
+        warn!("Rejecting MCP request: concurrency limit reached");
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "1")],
+            "Too many concurrent requests",
+        )
+            .into_response();
+    };
+
+    next.run(request).await.map(|body| {
+        Body::from_stream(body.into_data_stream().map(move |chunk| {
+            let _permit = &permit;
+            chunk
+        }))
+    })
+}
+
+/// Limits applied to JSON-RPC bodies before dispatch
+#[derive(Debug, Clone, Copy)]
+struct MessageLimits {
+    max_batch_len: usize,
+    max_message_bytes: usize,
+}
+
+/// Reject malformed or oversized JSON-RPC bodies before they reach the MCP service
+///
+/// Only POST bodies are checked. A body must be a JSON-RPC 2.0 message or a
+/// non-empty batch of at most `max_batch_len` messages, each no larger than
+/// `max_message_bytes`, and the body no larger than [`MAX_BODY_BYTES`].
+/// Violations are answered with a JSON-RPC error (`-32700` for unparseable
+/// JSON, `-32600` otherwise) without reading more of the body than the
+/// limits allow.
+async fn validate_jsonrpc(
+    State(limits): State<MessageLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let max_body = limits
+        .max_message_bytes
+        .saturating_mul(limits.max_batch_len)
+        .min(MAX_BODY_BYTES.max(limits.max_message_bytes));
+    let Ok(bytes) = axum::body::to_bytes(body, max_body).await else {
+        warn!(max_body, "Rejecting MCP request: body too large");
+        return jsonrpc_error(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            INVALID_REQUEST,
+            format!("Request body exceeds {} bytes", max_body),
+        );
+    };
+
+    let message: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(message) => message,
+        Err(e) => {
+            return jsonrpc_error(
+                StatusCode::BAD_REQUEST,
+                PARSE_ERROR,
+                format!("Parse error: {}", e),
+            );
+        }
+    };
+
+    let problem = match &message {
+        serde_json::Value::Array(batch) if batch.is_empty() => Some("Empty batch".to_string()),
+        serde_json::Value::Array(batch) if batch.len() > limits.max_batch_len => Some(format!(
+            "Batch of {} messages exceeds the limit of {}",
+            batch.len(),
+            limits.max_batch_len
+        )),
+        serde_json::Value::Array(batch) => batch
+            .iter()
+            .find_map(|message| invalid_message(message, limits.max_message_bytes)),
+        message => invalid_message(message, limits.max_message_bytes),
+    };
+    if let Some(problem) = problem {
+        warn!(%problem, "Rejecting invalid JSON-RPC request");
+        return jsonrpc_error(StatusCode::BAD_REQUEST, INVALID_REQUEST, problem);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+/// Why a single JSON-RPC message is rejected, if it is
+fn invalid_message(message: &serde_json::Value, max_bytes: usize) -> Option<String> {
+    if message.get("jsonrpc").and_then(|v| v.as_str()) != Some("2.0") {
+        return Some("Invalid Request: not a JSON-RPC 2.0 message".to_string());
+    }
+    let size = serde_json::to_vec(message).map_or(usize::MAX, |bytes| bytes.len());
+    (size > max_bytes).then(|| {
+        format!(
+            "Message of {} bytes exceeds the limit of {} bytes",
+            size, max_bytes
+        )
+    })
+}
+
+/// A JSON-RPC error response without a request ID
+fn jsonrpc_error(status: StatusCode, code: i64, message: String) -> Response {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {"code": code, "message": message},
+    });
+    (status, Json(body)).into_response()
+}
+
+/// What the NDJSON layer needs to run tool calls outside the MCP service
+#[derive(Clone)]
+struct NdjsonState {
+    handler: GitLabMcpHandler,
+    sessions: Arc<LocalSessionManager>,
+}
+
+/// Stream `tools/call` results as NDJSON when the client accepts it
+///
+/// A single `tools/call` request sent with `Accept: application/x-ndjson` on
+/// an initialized session is answered with one JSON line per result item,
+/// written as soon as GitLab returns each page, instead of one complete
+/// result. A final JSON-RPC response line carries the request ID and either
+/// the item count or the error. Other requests pass through unchanged.
+async fn stream_ndjson(State(state): State<NdjsonState>, request: Request, next: Next) -> Response {
+    let accepts_ndjson = request
+        .headers()
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.contains(NDJSON_CONTENT_TYPE));
+    if request.method() != Method::POST || !accepts_ndjson {
+        return next.run(request).await;
+    }
+
+    // The body has already been size-checked by `validate_jsonrpc`
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let message: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_default();
+    if message.get("method").and_then(|m| m.as_str()) != Some("tools/call") {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    }
+
+    let Some(session_id) = parts
+        .headers
+        .get(&MCP_SESSION_ID)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized: Session ID is required",
+        )
+            .into_response();
+    };
+    if !state
+        .sessions
+        .has_session(&Arc::from(session_id))
+        .await
+        .unwrap_or(false)
+    {
+        return (StatusCode::UNAUTHORIZED, "Unauthorized: Session not found").into_response();
+    }
+
+    let id = message.get("id").cloned().unwrap_or_default();
+    let params = message.get("params");
+    let name = params
+        .and_then(|params| params.get("name"))
+        .and_then(|name| name.as_str())
+        .unwrap_or_default();
+    let arguments = params
+        .and_then(|params| params.get("arguments"))
+        .and_then(|arguments| arguments.as_object())
+        .cloned();
+    let request_id = parts
+        .headers
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let handler = &state.handler;
+    let pages = match handler.gitlab_for(Some(&parts)) {
+        Ok(gitlab) => handler.stream_tool(name, arguments, request_id, gitlab),
+        Err(e) => stream::iter([Err(ToolError::InvalidArguments(format!(
+            "invalid {} header: {}",
+            GITLAB_TOKEN_HEADER, e
+        )))])
+        .boxed(),
+    };
+
+    // One line per item, then a JSON-RPC response line ending the stream
+    let lines = stream::unfold(Some((pages, 0usize)), move |state| {
+        let id = id.clone();
+        async move {
+            let (mut pages, count) = state?;
+            let (chunk, state) = match pages.next().await {
+                Some(Ok(items)) => {
+                    let count = count + items.len();
+                    let chunk: String = items.iter().map(|item| format!("{}\n", item)).collect();
+                    (chunk, Some((pages, count)))
+                }
+                Some(Err(e)) => {
+                    warn!(error = %e, "Streamed tool call failed");
+                    let response = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": map_tool_error(&e),
+                    });
+                    (format!("{}\n", response), None)
+                }
+                None => {
+                    let response = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {"items": count},
+                    });
+                    (format!("{}\n", response), None)
+                }
+            };
+            Some((Ok::<_, Infallible>(chunk), state))
+        }
+    });
+
+    (
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Build the HTTP router with the MCP service and health endpoints
+///
+/// SSE streams emit a comment line (`:`) at the configured keep-alive
+/// interval so idle connections are not dropped by intermediaries. Comments
+/// are ignored by SSE parsers and do not affect MCP message framing.
+///
+/// MCP requests beyond `max_concurrent_requests` are rejected with
+/// `503 Service Unavailable`; the health and readiness endpoints are never
+/// limited. Malformed
+/// or oversized JSON-RPC bodies are answered with a JSON-RPC error before
+/// dispatch. MCP responses carry an `X-Request-Id` header that is also sent
+/// to GitLab.
+///
+/// With a webhook configured, `POST /webhook` accepts GitLab events that
+/// invalidate cached project metadata.
+///
+/// Tool calls sent with `Accept: application/x-ndjson` are streamed as
+/// newline-delimited JSON (see [`stream_ndjson`]).
+pub fn build_router<F>(handler_factory: F, config: &HttpConfig) -> Router
+where
+    F: Fn() -> GitLabMcpHandler + Send + Sync + Clone + 'static,
+{
+    let sessions = Arc::new(LocalSessionManager::default());
+    let ndjson = NdjsonState {
+        handler: handler_factory(),
+        sessions: sessions.clone(),
+    };
+    let service = StreamableHttpService::new(
+        move || Ok(handler_factory()),
+        sessions,
+        StreamableHttpServerConfig {
+            sse_keep_alive: config.sse_keep_alive,
+            ..Default::default()
+        },
+    );
+
+    let mut router = Router::new()
+        .nest_service(&config.mcp_path, service)
+        .route_layer(middleware::from_fn_with_state(ndjson, stream_ndjson))
+        .route_layer(middleware::from_fn_with_state(
+            MessageLimits {
+                max_batch_len: config.max_batch_len,
+                max_message_bytes: config.max_message_bytes,
+            },
+            validate_jsonrpc,
+        ));
+    if let Some(max) = config.max_concurrent_requests {
+        router = router.layer(middleware::from_fn_with_state(
+            Arc::new(Semaphore::new(max)),
+            limit_concurrency,
+        ));
+    }
+    let router = router.layer(middleware::from_fn(propagate_request_id));
+    let mut router = router.route("/health", get(health_handler)).route(
+        "/readyz",
+        get(readiness_handler).with_state(config.gitlab_health.clone()),
+    );
+    if let Some(webhook) = &config.webhook {
+        router = router.route(
+            WEBHOOK_PATH,
+            post(webhook_handler).with_state(webhook.clone()),
+        );
+    }
+
+    // Apply CORS layer based on config
+    match config.cors {
+        CorsMode::Permissive => router.layer(CorsLayer::permissive()),
+        CorsMode::Disabled => router,
+    }
+}
+
+/// Check that the configured HTTP port can be bound
+///
+/// Run at startup so a busy port is reported before anything else starts,
+/// instead of surfacing as a bind error once the server is half up.
+pub async fn check_http_port(config: &HttpConfig) -> Result<(), TransportError> {
+    let host = config.bind.ip().to_string();
+    let port = config.bind.port();
+    match bind_port_strict(&host, port).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(TransportError::Http(format!(
+            "port {} on {} is already in use; stop the process using it or choose another port with --http-port",
+            port, host
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Run the MCP server using HTTP transport with Streamable HTTP
 ///
 /// This starts an HTTP server that handles MCP protocol messages using
@@ -94,23 +574,7 @@ where
 
     let ct = CancellationToken::new();
 
-    // Create the Streamable HTTP service
-    let service = StreamableHttpService::new(
-        move || Ok(handler_factory()),
-        LocalSessionManager::default().into(),
-        Default::default(),
-    );
-
-    // Build router with MCP service and health endpoint
-    let router = Router::new()
-        .nest_service(&config.mcp_path, service)
-        .route("/health", get(health_handler));
-
-    // Apply CORS layer based on config
-    let router = match config.cors {
-        CorsMode::Permissive => router.layer(CorsLayer::permissive()),
-        CorsMode::Disabled => router,
-    };
+    let router = build_router(handler_factory, &config);
 
     // Bind and serve the router
     let listener = tokio::net::TcpListener::bind(bind_addr).await?;
@@ -135,6 +599,13 @@ where
     info!("HTTP server listening on http://{}", bind_addr);
     info!("  MCP endpoint: {}", config.mcp_path);
     info!("  Health endpoint: /health");
+    info!("  Readiness endpoint: /readyz");
+    if let Some(max) = config.max_concurrent_requests {
+        info!("  Max concurrent requests: {}", max);
+    }
+    if config.webhook.is_some() {
+        info!("  Webhook endpoint: {}", WEBHOOK_PATH);
+    }
 
     Ok(ct)
 }
@@ -4,6 +4,10 @@
 
 pub mod http;
 pub mod stdio;
+pub mod webhook;
 
-pub use http::{DEFAULT_HTTP_PORT, HttpConfig, run_http_blocking};
+pub use http::{
+    DEFAULT_HTTP_PORT, HttpConfig, MAX_BODY_BYTES, build_router, check_http_port, run_http_blocking,
+};
 pub use stdio::run_stdio;
+pub use webhook::WebhookConfig;
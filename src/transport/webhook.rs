@@ -0,0 +1,110 @@
+//! GitLab webhook receiver
+//!
+//! When `server.http.webhook_secret` is set, the HTTP transport accepts
+//! GitLab webhooks on `POST /webhook`. Push, tag push, issue, and merge
+//! request events drop the cached metadata of the project they concern, so
+//! changes made outside this server are visible before the cache TTL expires.
+
+use crate::gitlab::{GitLabClient, ResponseCache};
+use crate::util::SecretString;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderName, StatusCode};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Path of the webhook endpoint
+pub const WEBHOOK_PATH: &str = "/webhook";
+
+/// Header carrying the secret token configured on the GitLab webhook
+const X_GITLAB_TOKEN: HeaderName = HeaderName::from_static("x-gitlab-token");
+
+/// Event kinds that invalidate the affected project's cache entries
+const INVALIDATING_EVENTS: &[&str] = &["push", "tag_push", "issue", "merge_request"];
+
+/// Webhook endpoint settings
+#[derive(Clone)]
+pub struct WebhookConfig {
+    secret: SecretString,
+    gitlab: Arc<GitLabClient>,
+}
+
+impl WebhookConfig {
+    /// Accept webhooks signed with `secret` and invalidate `gitlab`'s cache
+    pub fn new(secret: impl Into<String>, gitlab: Arc<GitLabClient>) -> Self {
+        Self {
+            secret: SecretString::new(secret),
+            gitlab,
+        }
+    }
+}
+
+impl fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("secret", &self.secret)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle a GitLab webhook delivery
+///
+/// Responds `401` when the secret token is missing or wrong and `400` when the
+/// body is not JSON. Events that do not affect cached data are acknowledged
+/// and ignored.
+pub(crate) async fn webhook_handler(
+    State(config): State<WebhookConfig>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let token = headers
+        .get(&X_GITLAB_TOKEN)
+        .map(|value| value.as_bytes())
+        .unwrap_or_default();
+    if !constant_time_eq(token, config.secret.expose_secret().as_bytes()) {
+        warn!("Rejecting webhook: invalid X-Gitlab-Token");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Ok(event) = serde_json::from_slice::<Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let kind = event
+        .get("object_kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !INVALIDATING_EVENTS.contains(&kind) {
+        debug!(kind, "Ignoring webhook event");
+        return StatusCode::OK;
+    }
+
+    for project in affected_projects(&event) {
+        config.gitlab.cache().invalidate_project(&project);
+        info!(kind, project = %project, "Invalidated project cache from webhook");
+    }
+
+    StatusCode::OK
+}
+
+/// Cache keys of the project an event concerns: its ID and encoded path
+fn affected_projects(event: &Value) -> Vec<String> {
+    let mut keys = event
+        .get("project")
+        .map(ResponseCache::project_keys)
+        .unwrap_or_default();
+    if let Some(id) = event.get("project_id").and_then(Value::as_u64) {
+        let id = id.to_string();
+        if !keys.contains(&id) {
+            keys.push(id);
+        }
+    }
+    keys
+}
+
+/// Compare two byte strings without exiting early on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
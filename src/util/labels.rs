@@ -0,0 +1,87 @@
+//! Scoped label handling.
+//!
+//! GitLab treats labels named `scope::value` as scoped: an issue or merge
+//! request carries at most one label per scope. Grouping them by scope lets
+//! callers read e.g. the priority of an issue without parsing label names.
+
+use serde_json::{Map, Value};
+
+/// Split a scoped label into its scope and value.
+///
+/// Nested labels like `team::backend::api` have the scope `team::backend`, as
+/// in GitLab. Returns `None` for labels without a scope.
+pub fn split_scoped_label(name: &str) -> Option<(&str, &str)> {
+    name.rsplit_once("::")
+        .filter(|(scope, value)| !scope.is_empty() && !value.is_empty())
+}
+
+/// Group the scoped labels of a `labels` array by scope.
+///
+/// Accepts both plain label names and the label objects GitLab returns with
+/// `with_labels_details=true`. Maps each scope to its value.
+pub fn group_scoped_labels(labels: &[Value]) -> Map<String, Value> {
+    labels
+        .iter()
+        .filter_map(|label| match label {
+            Value::String(name) => Some(name.as_str()),
+            Value::Object(details) => details.get("name").and_then(Value::as_str),
+            _ => None,
+        })
+        .filter_map(split_scoped_label)
+        .map(|(scope, value)| (scope.to_string(), Value::String(value.to_string())))
+        .collect()
+}
+
+/// Add a `scoped_labels` object to an issue or merge request, or to each one
+/// in a list, when it has scoped labels.
+pub fn add_scoped_labels(response: &mut Value) {
+    match response {
+        Value::Array(items) => items.iter_mut().for_each(add_scoped_labels),
+        Value::Object(item) => {
+            let scoped = item
+                .get("labels")
+                .and_then(Value::as_array)
+                .map(|labels| group_scoped_labels(labels))
+                .unwrap_or_default();
+            if !scoped.is_empty() {
+                item.insert("scoped_labels".to_string(), Value::Object(scoped));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_split_scoped_label() {
+        assert_eq!(
+            split_scoped_label("priority::high"),
+            Some(("priority", "high"))
+        );
+        assert_eq!(
+            split_scoped_label("team::backend::api"),
+            Some(("team::backend", "api"))
+        );
+        assert_eq!(split_scoped_label("bug"), None);
+        assert_eq!(split_scoped_label("::high"), None);
+    }
+
+    #[test]
+    fn test_add_scoped_labels_to_list() {
+        let mut response = json!([
+            {"iid": 1, "labels": ["bug", "priority::high"]},
+            {"iid": 2, "labels": [{"name": "workflow::review", "color": "#428BCA"}]},
+            {"iid": 3, "labels": ["bug"]}
+        ]);
+
+        add_scoped_labels(&mut response);
+
+        assert_eq!(response[0]["scoped_labels"], json!({"priority": "high"}));
+        assert_eq!(response[1]["scoped_labels"], json!({"workflow": "review"}));
+        assert!(response[2].get("scoped_labels").is_none());
+    }
+}
@@ -1,14 +1,25 @@
 //! Utility functions shared across the application.
 
+mod labels;
 mod secret;
+mod zip_index;
 
-pub use secret::SecretString;
+pub use labels::{add_scoped_labels, group_scoped_labels, split_scoped_label};
+pub use secret::{SecretString, redact_arguments};
+pub use zip_index::{
+    CentralDirectory, ZIP_TAIL_LEN, ZipEntry, find_central_directory, parse_central_directory,
+};
 
+use regex::Regex;
 use std::fmt::Display;
 use std::net::SocketAddr;
+use std::sync::LazyLock;
 use tokio::net::TcpListener;
 use tracing::warn;
 
+/// Largest page size GitLab accepts.
+pub const MAX_PER_PAGE: u32 = 100;
+
 /// Builder for URL query parameters.
 ///
 /// Provides a fluent API for constructing query strings with proper URL encoding.
@@ -61,6 +72,14 @@ impl QueryBuilder {
         self
     }
 
+    /// Add `page` and `per_page`, capping `per_page` at [`MAX_PER_PAGE`].
+    ///
+    /// Missing values are left out so GitLab applies its own defaults.
+    pub fn pagination(self, page: Option<u32>, per_page: Option<u32>) -> Self {
+        self.optional("page", page)
+            .optional("per_page", per_page.map(|p| p.min(MAX_PER_PAGE)))
+    }
+
     /// Build the query string.
     ///
     /// Returns an empty string if no parameters were added,
@@ -81,6 +100,42 @@ impl QueryBuilder {
     }
 }
 
+/// Default maximum length of a single diff before it is truncated
+pub const DEFAULT_MAX_DIFF_LENGTH: usize = 10_000;
+
+/// Serde default for the `max_diff_length` argument of diff tools
+pub fn default_max_diff_length() -> usize {
+    DEFAULT_MAX_DIFF_LENGTH
+}
+
+/// Truncate a diff to at most `max_len` bytes, respecting UTF-8 char boundaries.
+///
+/// Returns the (possibly shortened) diff and whether truncation happened.
+pub fn truncate_diff(diff: &str, max_len: usize) -> (String, bool) {
+    if diff.len() <= max_len {
+        return (diff.to_string(), false);
+    }
+    let mut end = max_len;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    (format!("{}\n... (diff truncated)", &diff[..end]), true)
+}
+
+/// Check that a string is an ISO 8601 date or date-time.
+///
+/// Accepts `YYYY-MM-DD`, optionally followed by `THH:MM[:SS[.fff]]` and a
+/// `Z` or `+HH:MM` offset, which is what GitLab's date filters take.
+pub fn is_iso8601(value: &str) -> bool {
+    static ISO8601: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])(T([01]\d|2[0-3]):[0-5]\d(:[0-5]\d(\.\d+)?)?(Z|[+-]([01]\d|2[0-3]):?[0-5]\d)?)?$",
+        )
+        .expect("valid ISO 8601 pattern")
+    });
+    ISO8601.is_match(value)
+}
+
 /// Verify that a specific port is available, failing if it is not.
 ///
 /// Unlike `find_available_port`, this does not fall back to alternate ports.
@@ -171,6 +226,18 @@ pub async fn find_available_port(host: &str, preferred: u16) -> std::io::Result<
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_iso8601() {
+        assert!(is_iso8601("2024-01-31"));
+        assert!(is_iso8601("2024-01-31T12:30:00Z"));
+        assert!(is_iso8601("2024-01-31T12:30:00.123+02:00"));
+        assert!(is_iso8601("2024-01-31T12:30"));
+        assert!(!is_iso8601("2024-13-01"));
+        assert!(!is_iso8601("31/01/2024"));
+        assert!(!is_iso8601("yesterday"));
+        assert!(!is_iso8601("2024-01-31 12:30:00"));
+    }
+
     #[tokio::test]
     async fn test_bind_port_strict_available() {
         // Use a high port that's likely available
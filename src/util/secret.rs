@@ -3,8 +3,12 @@
 //! Provides a wrapper type that prevents accidental logging of sensitive values.
 
 use serde::Deserialize;
+use serde_json::{Map, Value};
 use std::fmt;
 
+/// Tool argument names whose values are never logged
+const SENSITIVE_ARGUMENTS: &[&str] = &["key", "token", "password", "secret"];
+
 /// A wrapper for secrets that prevents accidental logging.
 ///
 /// `SecretString` ensures that sensitive values like API tokens and passwords
@@ -68,6 +72,21 @@ impl Drop for SecretString {
     }
 }
 
+/// Copy tool arguments for logging, replacing sensitive values with `[REDACTED]`
+pub fn redact_arguments(arguments: &Map<String, Value>) -> Map<String, Value> {
+    arguments
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_ARGUMENTS.contains(&name.as_str()) {
+                Value::from("[REDACTED]")
+            } else {
+                value.clone()
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
 impl<'de> Deserialize<'de> for SecretString {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -89,6 +108,14 @@ mod tests {
         assert!(!debug_output.contains("my-secret-token"));
     }
 
+    #[test]
+    fn test_redact_arguments() {
+        let arguments = serde_json::json!({"project": "a/b", "key": "ssh-ed25519 AAAA"});
+        let redacted = redact_arguments(arguments.as_object().unwrap());
+        assert_eq!(redacted["project"], "a/b");
+        assert_eq!(redacted["key"], "[REDACTED]");
+    }
+
     #[test]
     fn test_display_redacted() {
         let secret = SecretString::new("my-secret-token");
@@ -0,0 +1,260 @@
+//! Zip archive index reading.
+//!
+//! Lists the files of a zip archive from its central directory alone, so an
+//! archive's contents can be shown after fetching only its last few kilobytes
+//! instead of downloading the whole thing.
+
+/// Bytes at the end of an archive that always contain the end of central
+/// directory record: the record itself plus the longest possible comment.
+pub const ZIP_TAIL_LEN: usize = EOCD_LEN + u16::MAX as usize;
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const EOCD_LEN: usize = 22;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const ZIP64_LOCATOR_LEN: usize = 20;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const CENTRAL_HEADER_LEN: usize = 46;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+/// Location of an archive's central directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CentralDirectory {
+    /// Offset of the first central directory record in the archive
+    pub offset: u64,
+    /// Size of the central directory in bytes
+    pub size: u64,
+    /// Number of entries in the archive
+    pub entries: u64,
+}
+
+/// A file or directory stored in an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntry {
+    /// Path inside the archive; directories end with `/`
+    pub path: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    /// Compressed size in bytes
+    pub compressed_size: u64,
+}
+
+impl CentralDirectory {
+    /// Offset just past the central directory, or `None` if it overflows
+    pub fn end(&self) -> Option<u64> {
+        self.offset.checked_add(self.size)
+    }
+}
+
+impl ZipEntry {
+    /// Whether the entry is a directory
+    pub fn is_dir(&self) -> bool {
+        self.path.ends_with('/')
+    }
+}
+
+/// Locate the central directory from the last bytes of an archive.
+///
+/// `tail` must end where the archive ends; [`ZIP_TAIL_LEN`] bytes always
+/// suffice.
+pub fn find_central_directory(tail: &[u8]) -> Result<CentralDirectory, String> {
+    let eocd = (0..=tail.len().saturating_sub(EOCD_LEN))
+        .rev()
+        .find(|&pos| read_u32(tail, pos) == Some(EOCD_SIGNATURE))
+        .ok_or("Not a zip archive: end of central directory record not found")?;
+
+    let entries = read_u16(tail, eocd + 10).ok_or("Truncated zip archive")?;
+    let size = read_u32(tail, eocd + 12).ok_or("Truncated zip archive")?;
+    let offset = read_u32(tail, eocd + 16).ok_or("Truncated zip archive")?;
+
+    if entries != u16::MAX && size != u32::MAX && offset != u32::MAX {
+        return Ok(CentralDirectory {
+            offset: offset.into(),
+            size: size.into(),
+            entries: entries.into(),
+        });
+    }
+
+    // Zip64: the real values live in a record the locator before the EOCD points to
+    let locator = eocd
+        .checked_sub(ZIP64_LOCATOR_LEN)
+        .filter(|&pos| read_u32(tail, pos) == Some(ZIP64_LOCATOR_SIGNATURE))
+        .ok_or("Zip64 archive without a zip64 end of central directory locator")?;
+    let record_offset = read_u64(tail, locator + 8).ok_or("Truncated zip archive")?;
+
+    // The record sits right before the locator in archives written in one pass
+    let record = (0..locator)
+        .rev()
+        .find(|&pos| read_u32(tail, pos) == Some(ZIP64_EOCD_SIGNATURE))
+        .ok_or_else(|| {
+            format!(
+                "Zip64 end of central directory record at offset {} is outside the fetched data",
+                record_offset
+            )
+        })?;
+
+    let directory = CentralDirectory {
+        entries: read_u64(tail, record + 32).ok_or("Truncated zip archive")?,
+        size: read_u64(tail, record + 40).ok_or("Truncated zip archive")?,
+        offset: read_u64(tail, record + 48).ok_or("Truncated zip archive")?,
+    };
+    if directory.end().is_none() {
+        return Err(format!(
+            "Corrupt zip archive: central directory at offset {} with size {} overflows",
+            directory.offset, directory.size
+        ));
+    }
+    Ok(directory)
+}
+
+/// Parse the records of a central directory.
+pub fn parse_central_directory(data: &[u8]) -> Result<Vec<ZipEntry>, String> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+
+    while pos + CENTRAL_HEADER_LEN <= data.len() {
+        if read_u32(data, pos) != Some(CENTRAL_HEADER_SIGNATURE) {
+            break;
+        }
+        let truncated = || format!("Truncated central directory record at offset {}", pos);
+
+        let mut compressed_size = u64::from(read_u32(data, pos + 20).ok_or_else(truncated)?);
+        let mut size = u64::from(read_u32(data, pos + 24).ok_or_else(truncated)?);
+        let name_len = usize::from(read_u16(data, pos + 28).ok_or_else(truncated)?);
+        let extra_len = usize::from(read_u16(data, pos + 30).ok_or_else(truncated)?);
+        let comment_len = usize::from(read_u16(data, pos + 32).ok_or_else(truncated)?);
+
+        let name_start = pos + CENTRAL_HEADER_LEN;
+        let extra_start = name_start + name_len;
+        let next = extra_start + extra_len + comment_len;
+        let name = data.get(name_start..extra_start).ok_or_else(truncated)?;
+        let extra = data
+            .get(extra_start..extra_start + extra_len)
+            .ok_or_else(truncated)?;
+
+        // Zip64 sizes replace the 32-bit fields set to 0xFFFFFFFF, uncompressed first
+        if let Some(zip64) = find_extra_field(extra, ZIP64_EXTRA_ID) {
+            let mut field = 0;
+            if size == u64::from(u32::MAX) {
+                size = read_u64(zip64, field).ok_or_else(truncated)?;
+                field += 8;
+            }
+            if compressed_size == u64::from(u32::MAX) {
+                compressed_size = read_u64(zip64, field).ok_or_else(truncated)?;
+            }
+        }
+
+        entries.push(ZipEntry {
+            path: String::from_utf8_lossy(name).into_owned(),
+            size,
+            compressed_size,
+        });
+        pos = next;
+    }
+
+    Ok(entries)
+}
+
+/// Find the data of an extra field by its header ID
+fn find_extra_field(extra: &[u8], id: u16) -> Option<&[u8]> {
+    let mut pos = 0;
+    while let (Some(field_id), Some(len)) = (read_u16(extra, pos), read_u16(extra, pos + 2)) {
+        let data = extra.get(pos + 4..pos + 4 + usize::from(len))?;
+        if field_id == id {
+            return Some(data);
+        }
+        pos += 4 + usize::from(len);
+    }
+    None
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A central directory record for a stored file
+    fn central_record(name: &str, size: u32) -> Vec<u8> {
+        let mut record = Vec::new();
+        record.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        record.extend_from_slice(&[0; 16]);
+        record.extend_from_slice(&size.to_le_bytes()); // compressed size
+        record.extend_from_slice(&size.to_le_bytes()); // uncompressed size
+        record.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        record.extend_from_slice(&[0; 16]);
+        record.extend_from_slice(name.as_bytes());
+        record
+    }
+
+    #[test]
+    fn test_reads_entries_through_comment() {
+        let directory = [
+            central_record("coverage/", 0),
+            central_record("coverage/report.xml", 1234),
+        ]
+        .concat();
+
+        let mut archive = vec![0; 100]; // local headers and file data
+        let directory_offset = archive.len() as u32;
+        archive.extend_from_slice(&directory);
+        archive.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[0; 4]);
+        archive.extend_from_slice(&2u16.to_le_bytes());
+        archive.extend_from_slice(&2u16.to_le_bytes());
+        archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&directory_offset.to_le_bytes());
+        archive.extend_from_slice(&7u16.to_le_bytes());
+        archive.extend_from_slice(b"comment");
+
+        let location = find_central_directory(&archive).unwrap();
+        assert_eq!(location.offset, 100);
+        assert_eq!(location.entries, 2);
+
+        let start = location.offset as usize;
+        let entries =
+            parse_central_directory(&archive[start..start + location.size as usize]).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].is_dir());
+        assert_eq!(entries[1].path, "coverage/report.xml");
+        assert_eq!(entries[1].size, 1234);
+    }
+
+    #[test]
+    fn test_rejects_overflowing_zip64_directory() {
+        let mut archive = Vec::new();
+        archive.extend_from_slice(&ZIP64_EOCD_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[0; 28]);
+        archive.extend_from_slice(&1u64.to_le_bytes()); // entries
+        archive.extend_from_slice(&u64::MAX.to_le_bytes()); // size
+        archive.extend_from_slice(&16u64.to_le_bytes()); // offset
+        archive.extend_from_slice(&ZIP64_LOCATOR_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[0; 4]);
+        archive.extend_from_slice(&0u64.to_le_bytes());
+        archive.extend_from_slice(&[0; 4]);
+        archive.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[0; 6]);
+        archive.extend_from_slice(&u16::MAX.to_le_bytes());
+        archive.extend_from_slice(&u32::MAX.to_le_bytes());
+        archive.extend_from_slice(&u32::MAX.to_le_bytes());
+        archive.extend_from_slice(&[0; 2]);
+
+        let error = find_central_directory(&archive).unwrap_err();
+        assert!(error.contains("overflows"), "{}", error);
+    }
+
+    #[test]
+    fn test_rejects_non_zip_data() {
+        assert!(find_central_directory(b"definitely not a zip archive").is_err());
+    }
+}
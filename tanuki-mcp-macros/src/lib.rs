@@ -24,6 +24,13 @@ struct GitLabToolArgs {
     /// Optional: field name containing the project identifier
     #[darling(default)]
     project_field: Option<String>,
+    /// Optional: "json" (default) or "text" for tools returning raw text
+    #[darling(default)]
+    output: Option<String>,
+    /// Optional flag for tools whose result holds a secret that must not be
+    /// kept, e.g. a newly created token
+    #[darling(default)]
+    returns_secret: bool,
 }
 
 /// Extract description from doc comments (first paragraph only)
@@ -48,6 +55,43 @@ fn extract_doc_comment(attrs: &[Attribute]) -> Option<String> {
     (!doc_lines.is_empty()).then(|| doc_lines.join(" "))
 }
 
+/// How a tool struct holds its project identifier
+enum ProjectField {
+    /// `String`: the project must be given
+    Required,
+    /// `Option<String>`: the tool also works without a project
+    Optional,
+}
+
+/// Find a `String` or `Option<String>` field with the given name
+fn find_project_field(input: &DeriveInput, name: &str) -> Option<ProjectField> {
+    let syn::Data::Struct(data) = &input.data else {
+        return None;
+    };
+    let field = data
+        .fields
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))?;
+    let syn::Type::Path(ty) = &field.ty else {
+        return None;
+    };
+    if ty.path.is_ident("String") {
+        return Some(ProjectField::Required);
+    }
+    let segment = ty.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    matches!(
+        args.args.first(),
+        Some(syn::GenericArgument::Type(syn::Type::Path(inner))) if inner.path.is_ident("String")
+    )
+    .then_some(ProjectField::Optional)
+}
+
 /// Derive macro for GitLab MCP tools.
 ///
 /// This macro generates:
@@ -139,11 +183,17 @@ fn impl_gitlab_tool(args: &GitLabToolArgs, input: &DeriveInput) -> TokenStream2
         "pipelines" => quote! { crate::access_control::ToolCategory::Pipelines },
         "milestones" => quote! { crate::access_control::ToolCategory::Milestones },
         "releases" => quote! { crate::access_control::ToolCategory::Releases },
+        "environments" => quote! { crate::access_control::ToolCategory::Environments },
+        "runners" => quote! { crate::access_control::ToolCategory::Runners },
+        "deploy_keys" => quote! { crate::access_control::ToolCategory::DeployKeys },
+        "access_tokens" => quote! { crate::access_control::ToolCategory::AccessTokens },
         "users" => quote! { crate::access_control::ToolCategory::Users },
         "groups" => quote! { crate::access_control::ToolCategory::Groups },
         "graphql" => quote! { crate::access_control::ToolCategory::GraphQL },
         "tags" => quote! { crate::access_control::ToolCategory::Tags },
         "search" => quote! { crate::access_control::ToolCategory::Search },
+        "instance" => quote! { crate::access_control::ToolCategory::Instance },
+        "raw" => quote! { crate::access_control::ToolCategory::Raw },
         _ => {
             return syn::Error::new_spanned(input, format!("Unknown category: {}", category))
                 .to_compile_error();
@@ -168,21 +218,63 @@ fn impl_gitlab_tool(args: &GitLabToolArgs, input: &DeriveInput) -> TokenStream2
         }
     };
 
-    // Generate project extraction code
-    let project_extraction = if let Some(field_name) = &args.project_field {
-        let field_ident = Ident::new(field_name, proc_macro2::Span::call_site());
-        quote! {
-            fn extract_project(&self) -> Option<String> {
-                Some(self.#field_ident.clone())
-            }
+    // Raw text output has no fields to project
+    let text_output = match args.output.as_deref() {
+        None | Some("json") => false,
+        Some("text") => true,
+        Some(other) => {
+            return syn::Error::new_spanned(
+                input,
+                format!("Unknown output: {}. Use: json or text", other),
+            )
+            .to_compile_error();
         }
-    } else {
-        // Try to auto-detect a "project" field
-        quote! {
-            fn extract_project(&self) -> Option<String> {
-                None
-            }
+    };
+
+    let returns_secret = args.returns_secret;
+
+    // Generate project extraction code, auto-detecting a `project: String`
+    // or `project: Option<String>` field when no project_field is given.
+    // Only required fields are reported as `project_field`, which the
+    // registry checks for before the call.
+    let project_field = match &args.project_field {
+        Some(name) => Some((
+            name.clone(),
+            find_project_field(input, name).unwrap_or(ProjectField::Required),
+        )),
+        None => find_project_field(input, "project").map(|kind| ("project".to_string(), kind)),
+    };
+    let (project_extraction, project_field_name) = match &project_field {
+        Some((field_name, ProjectField::Required)) => {
+            let field_ident = Ident::new(field_name, proc_macro2::Span::call_site());
+            (
+                quote! {
+                    fn extract_project(&self) -> Option<String> {
+                        Some(self.#field_ident.clone())
+                    }
+                },
+                quote! { Some(#field_name) },
+            )
         }
+        Some((field_name, ProjectField::Optional)) => {
+            let field_ident = Ident::new(field_name, proc_macro2::Span::call_site());
+            (
+                quote! {
+                    fn extract_project(&self) -> Option<String> {
+                        self.#field_ident.clone()
+                    }
+                },
+                quote! { None },
+            )
+        }
+        None => (
+            quote! {
+                fn extract_project(&self) -> Option<String> {
+                    None
+                }
+            },
+            quote! { None },
+        ),
     };
 
     // Get the visibility, attributes (except our own), and struct body
@@ -237,6 +329,18 @@ fn impl_gitlab_tool(args: &GitLabToolArgs, input: &DeriveInput) -> TokenStream2
             fn operation_type() -> crate::access_control::OperationType {
                 #operation_variant
             }
+
+            fn project_field() -> Option<&'static str> {
+                #project_field_name
+            }
+
+            fn text_output() -> bool {
+                #text_output
+            }
+
+            fn returns_secret() -> bool {
+                #returns_secret
+            }
         }
 
         impl crate::access_control::AccessControlled for #struct_name {
@@ -3,7 +3,7 @@
 //! This test suite covers all combinations of:
 //! - Access levels (None, Read, Full)
 //! - Operation types (Read, Write, Delete, Execute)
-//! - Tool categories (21 categories)
+//! - Tool categories (25 categories)
 //! - Pattern matching (allow/deny regex)
 //! - Hierarchical resolution (6 levels)
 //! - Project-specific overrides
@@ -33,6 +33,7 @@ fn config_with_level(level: AccessLevel) -> AccessControlConfig {
         categories: HashMap::new(),
         actions: HashMap::new(),
         projects: HashMap::new(),
+        resolve_numeric_ids: false,
     }
 }
 
@@ -2236,7 +2237,7 @@ mod hierarchy_tests {
 }
 
 // =============================================================================
-// 4. Category Tests (42 tests - 21 categories × 2)
+// 4. Category Tests (50 tests - 25 categories × 2)
 // =============================================================================
 
 mod category_tests {
@@ -2407,6 +2408,34 @@ mod category_tests {
         "list_releases",
         "create_release"
     );
+    category_test!(
+        test_environments_allowed,
+        test_environments_denied,
+        Environments,
+        "list_environments",
+        "stop_environment"
+    );
+    category_test!(
+        test_runners_allowed,
+        test_runners_denied,
+        Runners,
+        "list_project_runners",
+        "get_runner"
+    );
+    category_test!(
+        test_deploy_keys_allowed,
+        test_deploy_keys_denied,
+        DeployKeys,
+        "list_deploy_keys",
+        "add_deploy_key"
+    );
+    category_test!(
+        test_access_tokens_allowed,
+        test_access_tokens_denied,
+        AccessTokens,
+        "list_project_access_tokens",
+        "create_project_access_tokens"
+    );
     category_test!(
         test_users_allowed,
         test_users_denied,
@@ -3005,6 +3034,7 @@ mod complex_scenarios {
             categories: HashMap::new(),
             actions: HashMap::new(),
             projects: HashMap::new(),
+            resolve_numeric_ids: false,
         };
         config.categories.insert(
             "issues".to_string(),
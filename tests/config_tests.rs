@@ -1,6 +1,8 @@
 //! Configuration loading tests
 
-use tanuki_mcp::config::{AccessLevel, TransportMode, load_config_from_str};
+use tanuki_mcp::config::{
+    AccessLevel, AppConfig, OutputFormat, TransportMode, load_config_from_str,
+};
 
 const MINIMAL_CONFIG: &str = r#"
 [server]
@@ -138,6 +140,36 @@ all = "read"
     assert_eq!(config.gitlab.timeout_secs, 30); // Default timeout
     assert_eq!(config.gitlab.max_retries, 3); // Default retries
     assert!(config.gitlab.verify_ssl); // Default verify_ssl
+    assert_eq!(config.server.output_format, OutputFormat::Pretty); // Default output format
+}
+
+#[test]
+fn test_output_format_config() {
+    let config_str = r#"
+[server]
+output_format = "markdown"
+
+[gitlab]
+token = "token"
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert_eq!(config.server.output_format, OutputFormat::Markdown);
+    assert!(!config.server.dry_run);
+}
+
+#[test]
+fn test_dry_run_config() {
+    let config_str = r#"
+[server]
+dry_run = true
+
+[gitlab]
+token = "token"
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert!(config.server.dry_run);
 }
 
 #[test]
@@ -177,6 +209,65 @@ deny = ["[invalid"]
     assert!(result.is_err());
 }
 
+#[test]
+fn test_tools_enabled_disabled_config() {
+    let config_str = r#"
+[gitlab]
+token = "token"
+
+[tools]
+enabled = ["^list_"]
+disabled = ["^list_users$"]
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert_eq!(config.tools.enabled, vec!["^list_"]);
+    assert_eq!(config.tools.disabled, vec!["^list_users$"]);
+
+    let invalid = r#"
+[gitlab]
+token = "token"
+
+[tools]
+disabled = ["[invalid"]
+"#;
+    assert!(load_config_from_str(invalid).is_err());
+}
+
+#[test]
+fn test_ratelimit_config() {
+    let config_str = r#"
+[gitlab]
+token = "token"
+
+[ratelimit.categories]
+pipelines = "10/s"
+search = "30/m"
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert_eq!(config.ratelimit.categories["pipelines"], "10/s");
+    assert_eq!(config.ratelimit.categories["search"], "30/m");
+
+    let invalid_rate = r#"
+[gitlab]
+token = "token"
+
+[ratelimit.categories]
+pipelines = "ten/s"
+"#;
+    assert!(load_config_from_str(invalid_rate).is_err());
+
+    let unknown_category = r#"
+[gitlab]
+token = "token"
+
+[ratelimit.categories]
+pipeline = "10/s"
+"#;
+    assert!(load_config_from_str(unknown_category).is_err());
+}
+
 #[test]
 #[serial_test::serial]
 fn test_env_var_priority_tanuki_mcp_over_gitlab_token() {
@@ -339,3 +430,170 @@ all = "read"
         env::remove_var("GITLAB_URL");
     }
 }
+
+#[test]
+fn test_gitlab_instance_overrides() {
+    let config_str = r#"
+[gitlab]
+token = "token"
+timeout_secs = 30
+instance = "self-hosted"
+
+[gitlab.instances.self-hosted]
+url = "https://gitlab.example.com"
+connect_timeout_secs = 10
+timeout_secs = 120
+max_retries = 5
+
+[gitlab.instances.cloud]
+url = "https://gitlab.com"
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert_eq!(config.gitlab.instances.len(), 2);
+    assert_eq!(config.gitlab.api_url(), "https://gitlab.example.com/api/v4");
+    assert_eq!(config.gitlab.effective_connect_timeout_secs(), 10);
+    assert_eq!(config.gitlab.effective_timeout_secs(), 120);
+    assert_eq!(config.gitlab.effective_max_retries(), 5);
+
+    let unknown = r#"
+[gitlab]
+token = "token"
+instance = "missing"
+"#;
+    let err = load_config_from_str(unknown).unwrap_err();
+    assert!(err.to_string().contains("unknown instance"));
+
+    let invalid = r#"
+[gitlab]
+token = "token"
+
+[gitlab.instances.broken]
+url = "https://gitlab.example.com"
+timeout_secs = 0
+"#;
+    assert!(load_config_from_str(invalid).is_err());
+}
+
+#[test]
+fn test_gitlab_connect_and_request_timeouts() {
+    let config_str = r#"
+[gitlab]
+token = "token"
+timeout_secs = 30
+connect_timeout_secs = 5
+"#;
+
+    let config = load_config_from_str(config_str).unwrap();
+    assert_eq!(config.gitlab.effective_connect_timeout_secs(), 5);
+    assert_eq!(config.gitlab.effective_timeout_secs(), 30);
+
+    let invalid = r#"
+[gitlab]
+token = "token"
+request_timeout_secs = 0
+"#;
+    let err = load_config_from_str(invalid).unwrap_err();
+    assert!(err.to_string().contains("request_timeout_secs"));
+}
+
+#[test]
+fn test_redacted_toml_round_trips_without_token() {
+    let config = load_config_from_str(FULL_CONFIG).unwrap();
+    let rendered = config.to_redacted_toml().unwrap();
+
+    assert!(!rendered.contains("glpat-test"));
+    assert!(rendered.contains(AppConfig::REDACTED));
+
+    let reparsed = load_config_from_str(&rendered).unwrap();
+    assert_eq!(reparsed.server.transport, TransportMode::Http);
+    assert_eq!(reparsed.server.port, 9000);
+    assert_eq!(reparsed.gitlab.url, "https://gitlab.company.com");
+    assert_eq!(reparsed.access_control.all, config.access_control.all);
+}
+
+#[test]
+fn test_warm_projects_require_cache() {
+    let config = r#"
+[gitlab]
+url = "https://gitlab.example.com"
+warm_projects = ["group/app"]
+"#;
+    let err = load_config_from_str(config).unwrap_err();
+    assert!(err.to_string().contains("gitlab.cache_ttl_secs"));
+
+    let config = r#"
+[gitlab]
+url = "https://gitlab.example.com"
+cache_ttl_secs = 300
+warm_projects = ["group/app"]
+"#;
+    let config = load_config_from_str(config).unwrap();
+    assert_eq!(config.gitlab.warm_projects, vec!["group/app"]);
+}
+
+#[test]
+fn test_zero_max_concurrent_requests_rejected() {
+    let config = r#"
+[gitlab]
+url = "https://gitlab.example.com"
+max_concurrent_requests = 0
+"#;
+    let err = load_config_from_str(config).unwrap_err();
+    assert!(err.to_string().contains("gitlab.max_concurrent_requests"));
+}
+
+#[test]
+fn test_poll_settings_loaded_and_validated() {
+    let config = r#"
+[gitlab]
+url = "https://gitlab.example.com"
+
+[server]
+poll_max_attempts = 5
+poll_interval_secs = 10
+"#;
+    let config = load_config_from_str(config).unwrap();
+    assert_eq!(config.server.poll_max_attempts, 5);
+    assert_eq!(config.server.poll_interval_secs, 10);
+
+    let config = r#"
+[gitlab]
+url = "https://gitlab.example.com"
+
+[server]
+poll_interval_secs = 0
+"#;
+    let err = load_config_from_str(config).unwrap_err();
+    assert!(err.to_string().contains("server.poll_interval_secs"));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_starter_config_loads() {
+    use std::env;
+    use tanuki_mcp::config::{load_config, write_starter_config};
+    use tempfile::tempdir;
+
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("tanuki-mcp.toml");
+    write_starter_config(&config_path, false).unwrap();
+
+    // The starter config leaves the token to the environment
+    unsafe {
+        env::set_var("GITLAB_TOKEN", "starter-token");
+    }
+    let config = load_config(Some(config_path.to_str().unwrap()));
+    unsafe {
+        env::remove_var("GITLAB_TOKEN");
+    }
+
+    let config = config.unwrap();
+    assert_eq!(config.access_control.all, AccessLevel::Read);
+    assert_eq!(config.access_control.deny, vec!["^delete_".to_string()]);
+    assert_eq!(config.server.transport, TransportMode::Stdio);
+
+    // An existing file is only replaced with force
+    assert!(write_starter_config(&config_path, false).is_err());
+    write_starter_config(&config_path, true).unwrap();
+}
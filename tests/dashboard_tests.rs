@@ -366,6 +366,19 @@ fn test_dashboard_config_custom() {
     assert_eq!(config.bind.ip().to_string(), "0.0.0.0");
 }
 
+#[test]
+fn test_dashboard_config_conflicts_compare_host_and_port() {
+    let config = DashboardConfig::new("127.0.0.1", 8080).unwrap();
+
+    assert!(config.conflicts_with("127.0.0.1:8080".parse().unwrap()));
+    assert!(config.conflicts_with("0.0.0.0:8080".parse().unwrap()));
+    assert!(!config.conflicts_with("127.0.0.1:8081".parse().unwrap()));
+    assert!(!config.conflicts_with("192.168.1.10:8080".parse().unwrap()));
+
+    let all = DashboardConfig::new("0.0.0.0", 8080).unwrap();
+    assert!(all.conflicts_with("192.168.1.10:8080".parse().unwrap()));
+}
+
 #[test]
 fn test_metrics_uptime() {
     let metrics = DashboardMetrics::new();
@@ -397,3 +410,174 @@ fn test_metrics_snapshot_requests_per_minute() {
     // Should have a positive requests_per_minute
     assert!(snapshot.requests_per_minute >= 0.0);
 }
+
+#[tokio::test]
+async fn test_dashboard_recent_endpoint_newest_first() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tanuki_mcp::access_control::AccessResolver;
+    use tanuki_mcp::config::AppConfig;
+    use tanuki_mcp::dashboard::{DashboardState, dashboard_router};
+    use tower::ServiceExt;
+
+    let metrics = Arc::new(DashboardMetrics::with_capacity(3));
+    for i in 0..5 {
+        metrics.record_call_with_audit(
+            &format!("tool_{}", i),
+            ToolCategory::Issues,
+            Some("group/project"),
+            Duration::from_millis(10),
+            i != 4,
+            None,
+            Some("allowed"),
+            (i == 4).then_some("boom"),
+        );
+    }
+
+    let state = DashboardState::new(
+        metrics,
+        Arc::new(AppConfig::default()),
+        Arc::new(AccessResolver::allow_all()),
+    );
+    let app = dashboard_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/recent")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["capacity"], 3);
+    let calls = json["calls"].as_array().unwrap();
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[0]["tool"], "tool_4");
+    assert_eq!(calls[0]["success"], false);
+    assert_eq!(calls[0]["error_details"], "boom");
+    assert_eq!(calls[0]["access_decision"], "allowed");
+    assert_eq!(calls[2]["tool"], "tool_2");
+}
+
+#[tokio::test]
+async fn test_dashboard_project_access_read_only() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tanuki_mcp::access_control::AccessResolver;
+    use tanuki_mcp::config::{AccessLevel, AppConfig, ProjectAccessConfig};
+    use tanuki_mcp::dashboard::{DashboardState, dashboard_router};
+    use tower::ServiceExt;
+
+    let mut config = AppConfig::default();
+    config.access_control.all = AccessLevel::Full;
+    config.access_control.projects.insert(
+        "group/readonly".to_string(),
+        ProjectAccessConfig {
+            all: Some(AccessLevel::Read),
+            ..Default::default()
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&config.access_control).unwrap());
+    let state = DashboardState::new(Arc::new(DashboardMetrics::new()), Arc::new(config), access);
+    let app = dashboard_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/projects/group%2Freadonly/access")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["project"], "group/readonly");
+    assert!(json["allowed_count"].as_u64().unwrap() > 0);
+    assert!(json["denied_count"].as_u64().unwrap() > 0);
+
+    let issues = json["categories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["category"] == "issues")
+        .unwrap();
+    let allowed: Vec<&str> = issues["allowed"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t.as_str().unwrap())
+        .collect();
+    let denied: Vec<&str> = issues["denied"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["tool"].as_str().unwrap())
+        .collect();
+
+    assert!(allowed.contains(&"list_issues"));
+    assert!(allowed.contains(&"get_issue"));
+    assert!(denied.contains(&"create_issue"));
+    assert!(denied.contains(&"delete_issue"));
+}
+
+#[tokio::test]
+async fn test_dashboard_tools_endpoint() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tanuki_mcp::access_control::AccessResolver;
+    use tanuki_mcp::config::AppConfig;
+    use tanuki_mcp::dashboard::{DashboardState, dashboard_router};
+    use tower::ServiceExt;
+
+    let state = DashboardState::new(
+        Arc::new(DashboardMetrics::new()),
+        Arc::new(AppConfig::default()),
+        Arc::new(AccessResolver::allow_all()),
+    );
+    let tool_count = state.registry.len();
+    let app = dashboard_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/tools")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["total"], tool_count);
+    let tools = json["tools"].as_array().unwrap();
+    assert_eq!(tools.len(), tool_count);
+
+    let create_issue = tools.iter().find(|t| t["name"] == "create_issue").unwrap();
+    assert_eq!(create_issue["category"], "issues");
+    assert_eq!(create_issue["operation"], "write");
+    assert!(!create_issue["description"].as_str().unwrap().is_empty());
+
+    // The raw request tool is not registered by default
+    assert!(tools.iter().all(|t| t["name"] != "gitlab_request"));
+
+    let categories = json["categories"].as_array().unwrap();
+    let summed: u64 = categories
+        .iter()
+        .map(|c| c["count"].as_u64().unwrap())
+        .sum();
+    assert_eq!(summed as usize, tool_count);
+    assert!(categories.iter().all(|c| c["category"] != "raw"));
+}
@@ -0,0 +1,146 @@
+//! Doctor subcommand tests with mock server
+
+use serde_json::json;
+use tanuki_mcp::access_control::{AccessResolver, ToolCategory};
+use tanuki_mcp::auth::PatProvider;
+use tanuki_mcp::config::{
+    AccessControlConfig, AccessLevel, CategoryAccessConfig, GitLabConfig, ToolsConfig,
+};
+use tanuki_mcp::doctor::{self, CheckStatus};
+use tanuki_mcp::gitlab::GitLabClient;
+use tanuki_mcp::tools::{ToolRegistry, definitions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn create_test_client(mock_server: &MockServer) -> GitLabClient {
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        token: Some("test-token".to_string()),
+        api_version: "v4".to_string(),
+        timeout_secs: 30,
+        connect_timeout_secs: None,
+        request_timeout_secs: None,
+        max_retries: 0,
+        max_concurrent_requests: None,
+        verify_ssl: true,
+        follow_redirects: false,
+        user_agent: None,
+        default_project: None,
+        instance: None,
+        instances: Default::default(),
+        cache_ttl_secs: 0,
+        warm_projects: vec![],
+        allowed_projects: vec![],
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    GitLabClient::new(&config, Box::new(auth)).unwrap()
+}
+
+#[tokio::test]
+async fn test_doctor_aggregates_mixed_results() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 1})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/issues"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({"message": "403 Forbidden"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server);
+    let report = doctor::run_checks(
+        &client,
+        &[
+            ToolCategory::Users,
+            ToolCategory::Issues,
+            ToolCategory::Groups,
+        ],
+        None,
+    )
+    .await;
+
+    assert_eq!(report.checks.len(), 3);
+    assert_eq!(report.checks[0].status, CheckStatus::Ok);
+    assert!(matches!(report.checks[1].status, CheckStatus::Denied(_)));
+    assert!(matches!(report.checks[2].status, CheckStatus::Error(_)));
+    assert_eq!(report.ok_count(), 1);
+    assert_eq!(report.denied_count(), 1);
+    assert_eq!(report.error_count(), 1);
+    assert!(report.has_denied());
+
+    let table = report.to_string();
+    assert!(table.starts_with("CATEGORY"));
+    assert!(table.contains("users"));
+    assert!(table.contains("DENIED"));
+    assert!(table.contains("1 ok, 1 denied, 1 error"));
+}
+
+#[tokio::test]
+async fn test_doctor_project_scoped_probes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/group%2Fapp/pipelines"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/group%2Fapp/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server);
+    let report = doctor::run_checks(
+        &client,
+        &[ToolCategory::Pipelines, ToolCategory::Labels],
+        Some("group/app"),
+    )
+    .await;
+
+    assert_eq!(report.ok_count(), 2);
+    assert!(!report.has_denied());
+    assert_eq!(
+        report.checks[0].endpoint,
+        "/projects/group%2Fapp/pipelines?per_page=1"
+    );
+}
+
+#[test]
+fn test_doctor_allowed_categories_follow_config() {
+    let mut config = AccessControlConfig {
+        all: AccessLevel::None,
+        ..Default::default()
+    };
+    config.categories.insert(
+        "issues".to_string(),
+        CategoryAccessConfig {
+            level: AccessLevel::Read,
+            deny: vec![],
+            allow: vec![],
+        },
+    );
+    let access = AccessResolver::new(&config).unwrap();
+
+    let mut registry = ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &ToolsConfig::default());
+
+    assert_eq!(
+        doctor::allowed_categories(&registry, &access),
+        vec![ToolCategory::Issues]
+    );
+
+    // Categories without registered tools (raw by default) are never probed
+    let all = doctor::allowed_categories(&registry, &AccessResolver::allow_all());
+    assert!(all.contains(&ToolCategory::Projects));
+    assert!(!all.contains(&ToolCategory::Raw));
+}
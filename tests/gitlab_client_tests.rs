@@ -2,7 +2,7 @@
 
 use serde_json::json;
 use tanuki_mcp::auth::PatProvider;
-use tanuki_mcp::config::GitLabConfig;
+use tanuki_mcp::config::{GitLabConfig, TransportMode};
 use tanuki_mcp::error::GitLabError;
 use tanuki_mcp::gitlab::GitLabClient;
 use wiremock::matchers::{header, method, path};
@@ -15,9 +15,19 @@ fn create_test_client(mock_server: &MockServer, token: &str) -> GitLabClient {
         token: Some(token.to_string()),
         api_version: "v4".to_string(),
         timeout_secs: 30,
+        connect_timeout_secs: None,
+        request_timeout_secs: None,
         max_retries: 0, // No retries for tests
+        max_concurrent_requests: None,
         verify_ssl: true,
+        follow_redirects: false,
         user_agent: None,
+        default_project: None,
+        instance: None,
+        instances: Default::default(),
+        cache_ttl_secs: 0,
+        warm_projects: vec![],
+        allowed_projects: vec![],
     };
     let auth = PatProvider::new(token.to_string()).unwrap();
     GitLabClient::new(&config, Box::new(auth)).unwrap()
@@ -306,3 +316,448 @@ async fn test_api_error_with_detailed_message() {
         _ => panic!("Expected Api error with status 400"),
     }
 }
+
+#[test]
+fn test_instance_overrides_select_timeouts() {
+    use std::time::Duration;
+    use tanuki_mcp::config::GitLabInstanceConfig;
+
+    let mut config = GitLabConfig {
+        url: "https://gitlab.com".to_string(),
+        timeout_secs: 30,
+        max_retries: 3,
+        ..Default::default()
+    };
+    config.instances.insert(
+        "self-hosted".to_string(),
+        GitLabInstanceConfig {
+            url: "https://gitlab.example.com/".to_string(),
+            connect_timeout_secs: Some(10),
+            timeout_secs: Some(120),
+            max_retries: Some(5),
+        },
+    );
+
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let cloud = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    config.instance = Some("self-hosted".to_string());
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let self_hosted = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    assert_eq!(cloud.timeout(), Duration::from_secs(30));
+    assert_eq!(cloud.connect_timeout(), Duration::from_secs(30));
+    assert_eq!(cloud.max_retries(), 3);
+    assert_eq!(self_hosted.timeout(), Duration::from_secs(120));
+    assert_eq!(self_hosted.connect_timeout(), Duration::from_secs(10));
+    assert_eq!(self_hosted.max_retries(), 5);
+}
+
+#[test]
+fn test_connect_and_request_timeouts_are_distinct() {
+    use std::time::Duration;
+
+    let config = GitLabConfig {
+        timeout_secs: 30,
+        connect_timeout_secs: Some(5),
+        request_timeout_secs: Some(90),
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+    assert_eq!(client.connect_timeout(), Duration::from_secs(5));
+    assert_eq!(client.timeout(), Duration::from_secs(90));
+
+    // timeout_secs remains the default for both
+    let config = GitLabConfig {
+        timeout_secs: 45,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+    assert_eq!(client.connect_timeout(), Duration::from_secs(45));
+    assert_eq!(client.timeout(), Duration::from_secs(45));
+}
+
+#[tokio::test]
+async fn test_unreachable_host_fails_within_connect_timeout() {
+    use std::time::{Duration, Instant};
+
+    // 10.255.255.1 is non-routable: the connect either hangs until the
+    // connect timeout or fails immediately, never waiting for the request
+    // timeout
+    let config = GitLabConfig {
+        url: "http://10.255.255.1".to_string(),
+        timeout_secs: 60,
+        connect_timeout_secs: Some(1),
+        max_retries: 0,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    let started = Instant::now();
+    let result = client.get_json("/version").await;
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_instance_timeout_applies_to_requests() {
+    use tanuki_mcp::config::GitLabInstanceConfig;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/123"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"id": 123}))
+                .set_delay(std::time::Duration::from_secs(3)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut config = GitLabConfig {
+        url: mock_server.uri(),
+        token: Some("test-token".to_string()),
+        timeout_secs: 30,
+        connect_timeout_secs: None,
+        request_timeout_secs: None,
+        max_retries: 0,
+        instance: Some("mock".to_string()),
+        ..Default::default()
+    };
+    config.instances.insert(
+        "mock".to_string(),
+        GitLabInstanceConfig {
+            url: mock_server.uri(),
+            timeout_secs: Some(1),
+            ..Default::default()
+        },
+    );
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    let result: Result<serde_json::Value, _> = client.get("/projects/123").await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_default_user_agent_contains_version() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/version"))
+        .and(header(
+            "user-agent",
+            format!("tanuki-mcp/{}", env!("CARGO_PKG_VERSION")).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"version": "17.0.0"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server, "test-token");
+    let result: serde_json::Value = client.get("/version").await.unwrap();
+
+    assert_eq!(result["version"], "17.0.0");
+}
+
+#[tokio::test]
+async fn test_user_agent_override_with_transport_suffix() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/version"))
+        .and(header("user-agent", "acme-bot/2.1 (http)"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"version": "17.0.0"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        token: Some("test-token".to_string()),
+        api_version: "v4".to_string(),
+        timeout_secs: 30,
+        connect_timeout_secs: None,
+        request_timeout_secs: None,
+        max_retries: 0,
+        max_concurrent_requests: None,
+        verify_ssl: true,
+        follow_redirects: false,
+        user_agent: Some("acme-bot/2.1".to_string()),
+        default_project: None,
+        instance: None,
+        instances: Default::default(),
+        cache_ttl_secs: 0,
+        warm_projects: vec![],
+        allowed_projects: vec![],
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::for_transport(&config, Box::new(auth), TransportMode::Http).unwrap();
+
+    client.get_json("/version").await.unwrap();
+}
+
+/// Mount a 301 from `old%2Fname` to `new%2Fname` and the renamed project
+async fn mount_renamed_project(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/old%2Fname"))
+        .respond_with(ResponseTemplate::new(301).insert_header(
+            "location",
+            format!("{}/api/v4/projects/new%2Fname", mock_server.uri()),
+        ))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/new%2Fname"))
+        .and(header("PRIVATE-TOKEN", "test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7,
+            "path_with_namespace": "new/name"
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_follows_project_redirect_when_enabled() {
+    let mock_server = MockServer::start().await;
+    mount_renamed_project(&mock_server).await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        max_retries: 0,
+        follow_redirects: true,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    let project = client.get_json("/projects/old%2Fname").await.unwrap();
+    assert_eq!(project["path_with_namespace"], "new/name");
+}
+
+#[tokio::test]
+async fn test_redirects_not_followed_by_default() {
+    let mock_server = MockServer::start().await;
+    mount_renamed_project(&mock_server).await;
+
+    let client = create_test_client(&mock_server, "test-token");
+    let result = client.get_json("/projects/old%2Fname").await;
+
+    assert!(matches!(result, Err(GitLabError::Api { status: 301, .. })));
+}
+
+#[tokio::test]
+async fn test_warm_projects_fetches_once_and_serves_from_cache() {
+    let mock_server = MockServer::start().await;
+
+    for endpoint in ["", "/labels", "/milestones"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v4/projects/group%2Fapp{}", endpoint)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/group%2Fgone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"message": "404 Not found"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        max_retries: 0,
+        cache_ttl_secs: 60,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    // A failing project is skipped without affecting the others
+    client
+        .warm_projects(&["group/gone".to_string(), "group/app".to_string()])
+        .await;
+    assert_eq!(client.cache().len(), 3);
+
+    client.get_json("/projects/group%2Fapp").await.unwrap();
+    client
+        .get_json("/projects/group%2Fapp/labels")
+        .await
+        .unwrap();
+    client
+        .get_json("/projects/group%2Fapp/milestones")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_write_invalidates_cached_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/group%2Fapp/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/group%2Fapp/labels"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1})))
+        .mount(&mock_server)
+        .await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        max_retries: 0,
+        cache_ttl_secs: 60,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    client
+        .get_json("/projects/group%2Fapp/labels")
+        .await
+        .unwrap();
+    client
+        .get_json("/projects/group%2Fapp/labels")
+        .await
+        .unwrap();
+    client
+        .post_json("/projects/group%2Fapp/labels", &json!({"name": "bug"}))
+        .await
+        .unwrap();
+    client
+        .get_json("/projects/group%2Fapp/labels")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests_serializes_calls() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/version"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"version": "17.0.0"}))
+                .set_delay(std::time::Duration::from_millis(300)),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        max_retries: 0,
+        max_concurrent_requests: Some(1),
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    let start = std::time::Instant::now();
+    let (first, second) = tokio::join!(client.get_json("/version"), client.get_json("/version"));
+    first.unwrap();
+    second.unwrap();
+
+    assert!(start.elapsed() >= std::time::Duration::from_millis(600));
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests_covers_body_download() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Send the headers at once but the body only after a delay, which a mock
+    // server's response delay cannot do
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 4\r\nconnection: close\r\n\r\n")
+                    .await;
+                tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                let _ = socket.write_all(b"body").await;
+            });
+        }
+    });
+
+    let config = GitLabConfig {
+        url,
+        max_retries: 0,
+        max_concurrent_requests: Some(1),
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+
+    let start = std::time::Instant::now();
+    let (first, second) = tokio::join!(
+        client.get_bytes("/file", 1024),
+        client.get_bytes("/file", 1024)
+    );
+    assert_eq!(first.unwrap(), b"body");
+    assert_eq!(second.unwrap(), b"body");
+
+    assert!(start.elapsed() >= std::time::Duration::from_millis(600));
+}
+
+#[tokio::test]
+async fn test_caller_client_shares_cache_for_invalidation_only() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/a%2Fb"))
+        .and(header("PRIVATE-TOKEN", "caller-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 2})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/a%2Fb/labels/3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 3})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = GitLabConfig {
+        url: mock_server.uri(),
+        max_retries: 0,
+        cache_ttl_secs: 60,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("server-token".to_string()).unwrap();
+    let client = GitLabClient::new(&config, Box::new(auth)).unwrap();
+    client.cache().insert("/projects/a%2Fb", json!({"id": 1}));
+
+    let auth = PatProvider::new("caller-token".to_string()).unwrap();
+    let caller = client.with_auth(Box::new(auth), "caller");
+    assert_eq!(caller.caller(), Some("caller"));
+
+    // Reads with the caller's token never see the shared cache
+    let project: serde_json::Value = caller.get("/projects/a%2Fb").await.unwrap();
+    assert_eq!(project["id"], 2);
+    assert_eq!(
+        client.cache().get("/projects/a%2Fb"),
+        Some(json!({"id": 1}))
+    );
+
+    // Writes with the caller's token invalidate it
+    let _: serde_json::Value = caller
+        .put("/projects/a%2Fb/labels/3", &json!({"name": "bug"}))
+        .await
+        .unwrap();
+    assert!(client.cache().get("/projects/a%2Fb").is_none());
+}
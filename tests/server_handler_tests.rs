@@ -1,14 +1,16 @@
 //! Server handler integration tests
 
 use rmcp::handler::server::ServerHandler;
-use tanuki_mcp::access_control::AccessResolver;
+use tanuki_mcp::access_control::{AccessResolver, ToolCategory};
 use tanuki_mcp::auth::PatProvider;
 use tanuki_mcp::config::{
     AccessControlConfig, AccessLevel, AppConfig, CorsMode, DashboardConfigToml, GitLabConfig,
-    LoggingConfig, ServerConfig, TransportMode,
+    HttpServerConfig, LoggingConfig, OutputFormat, RateLimitConfig, ServerConfig, ToolsConfig,
+    TransportMode,
 };
+use tanuki_mcp::error::ToolError;
 use tanuki_mcp::gitlab::GitLabClient;
-use tanuki_mcp::server::GitLabMcpHandler;
+use tanuki_mcp::server::{GitLabMcpHandler, RateLimiter};
 use tanuki_mcp::update::UpdateConfig;
 
 use serde_json::json;
@@ -26,20 +28,43 @@ fn create_test_config(gitlab_url: &str) -> AppConfig {
             host: "127.0.0.1".to_string(),
             port: 3000,
             cors: CorsMode::default(),
+            output_format: OutputFormat::default(),
+            http: HttpServerConfig::default(),
+            dry_run: false,
+            idempotency_ttl_secs: 600,
+            include_pagination_meta: false,
+            max_request_bytes: 1024 * 1024,
+            max_items_per_list: None,
+            soft_errors: false,
+            tool_prefix: String::new(),
+            poll_max_attempts: 30,
+            poll_interval_secs: 2,
         },
         gitlab: GitLabConfig {
             url: gitlab_url.to_string(),
             token: Some("test-token".to_string()),
             api_version: "v4".to_string(),
             timeout_secs: 30,
+            connect_timeout_secs: None,
+            request_timeout_secs: None,
             max_retries: 0,
+            max_concurrent_requests: None,
             verify_ssl: true,
+            follow_redirects: false,
             user_agent: None,
+            default_project: None,
+            instance: None,
+            instances: Default::default(),
+            cache_ttl_secs: 0,
+            warm_projects: vec![],
+            allowed_projects: vec![],
         },
         access_control: AccessControlConfig::default(),
         logging: LoggingConfig::default(),
         dashboard: DashboardConfigToml::default(),
         updates: UpdateConfig::default(),
+        tools: ToolsConfig::default(),
+        ratelimit: RateLimitConfig::default(),
     }
 }
 
@@ -54,7 +79,7 @@ async fn create_test_handler(mock_server: &MockServer) -> GitLabMcpHandler {
     policy.all = AccessLevel::Full;
     let access = AccessResolver::new(&policy).unwrap();
 
-    GitLabMcpHandler::new(&config, gitlab, access)
+    GitLabMcpHandler::new(&config, gitlab, access).unwrap()
 }
 
 #[tokio::test]
@@ -120,7 +145,7 @@ async fn test_handler_with_access_control_denied() {
     policy.all = AccessLevel::None;
     let access = AccessResolver::new(&policy).unwrap();
 
-    let _handler = GitLabMcpHandler::new(&config, gitlab, access);
+    let _handler = GitLabMcpHandler::new(&config, gitlab, access).unwrap();
 
     // Handler with deny-all access control created successfully
 }
@@ -145,7 +170,7 @@ async fn test_handler_with_read_only_access() {
     policy.all = AccessLevel::Read;
     let access = AccessResolver::new(&policy).unwrap();
 
-    let _handler = GitLabMcpHandler::new(&config, gitlab, access);
+    let _handler = GitLabMcpHandler::new(&config, gitlab, access).unwrap();
 
     // Handler with read-only access control created successfully
 }
@@ -162,8 +187,10 @@ async fn test_handler_shared_resources() {
     let access = Arc::new(AccessResolver::new(&policy).unwrap());
 
     // Create multiple handlers sharing the same resources
-    let handler1 = GitLabMcpHandler::new_with_shared(&config, gitlab.clone(), access.clone());
-    let handler2 = GitLabMcpHandler::new_with_shared(&config, gitlab.clone(), access.clone());
+    let handler1 =
+        GitLabMcpHandler::new_with_shared(&config, gitlab.clone(), access.clone()).unwrap();
+    let handler2 =
+        GitLabMcpHandler::new_with_shared(&config, gitlab.clone(), access.clone()).unwrap();
 
     // Both handlers should have the same info
     assert_eq!(
@@ -187,7 +214,7 @@ async fn test_handler_initialization_with_full_config() {
     policy.all = AccessLevel::Full;
     let access = AccessResolver::new(&policy).unwrap();
 
-    let handler = GitLabMcpHandler::new(&config, gitlab, access);
+    let handler = GitLabMcpHandler::new(&config, gitlab, access).unwrap();
 
     let info = handler.get_info();
     assert_eq!(info.server_info.name, "custom-tanuki-mcp");
@@ -227,3 +254,143 @@ async fn test_handler_instructions() {
     assert!(instructions.contains("GitLab"));
     assert!(instructions.contains("MCP"));
 }
+
+#[test]
+fn test_rate_limiter_rejects_eleventh_pipeline_call() {
+    let mut config = RateLimitConfig::default();
+    config
+        .categories
+        .insert("pipelines".to_string(), "10/s".to_string());
+    let limiter = RateLimiter::new(config.limits().unwrap());
+
+    for _ in 0..10 {
+        assert!(limiter.check(ToolCategory::Pipelines).is_ok());
+    }
+    let err = limiter.check(ToolCategory::Pipelines).unwrap_err();
+    assert!(matches!(
+        err,
+        ToolError::RateLimited { ref category, ref limit } if category == "pipelines" && limit == "10/s"
+    ));
+
+    // Other categories are unlimited
+    for _ in 0..20 {
+        assert!(limiter.check(ToolCategory::Issues).is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_call_is_recorded_in_metrics() {
+    use futures::StreamExt;
+    use tanuki_mcp::dashboard::DashboardMetrics;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut config = create_test_config(&mock_server.uri());
+    config
+        .ratelimit
+        .categories
+        .insert("issues".to_string(), "1/h".to_string());
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let metrics = Arc::new(DashboardMetrics::new());
+    let handler =
+        GitLabMcpHandler::new_with_metrics(&config, gitlab.clone(), access, metrics.clone())
+            .unwrap();
+
+    let args = json!({"project": "test/project"}).as_object().cloned();
+    for _ in 0..2 {
+        let _: Vec<_> = handler
+            .stream_tool("list_issues", args.clone(), None, gitlab.clone())
+            .collect()
+            .await;
+    }
+
+    // The refused call never reached the registry but is on the dashboard
+    let calls = metrics.recent_calls(None);
+    assert_eq!(calls.len(), 2);
+    assert!(calls.iter().any(|call| {
+        !call.success
+            && call.project.as_deref() == Some("test/project")
+            && call
+                .error_details
+                .as_deref()
+                .is_some_and(|details| details.contains("Rate limited"))
+    }));
+}
+
+#[tokio::test]
+async fn test_configured_poll_attempts_are_honored() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/rebase",
+        ))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({"rebase_in_progress": true})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"rebase_in_progress": true})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut config = create_test_config(&mock_server.uri());
+    config.server.poll_max_attempts = 1;
+    config.server.poll_interval_secs = 1;
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let handler = GitLabMcpHandler::new_with_shared(&config, gitlab.clone(), access).unwrap();
+
+    let args = json!({"project": "test/project", "merge_request_iid": 5, "wait": true})
+        .as_object()
+        .cloned();
+    let results: Vec<_> = handler
+        .stream_tool("rebase_merge_request", args, None, gitlab)
+        .collect()
+        .await;
+
+    let error = results[0].as_ref().unwrap_err();
+    assert!(error.to_string().contains("did not finish after 1 checks"));
+}
+
+#[test]
+fn test_rate_limiter_rejects_invalid_config() {
+    let mut config = RateLimitConfig::default();
+    config
+        .categories
+        .insert("pipelines".to_string(), "10 per second".to_string());
+    assert!(config.limits().is_err());
+
+    let mut config = RateLimitConfig::default();
+    config
+        .categories
+        .insert("not_a_category".to_string(), "10/s".to_string());
+    assert!(config.limits().is_err());
+
+    assert!(RateLimitConfig::default().limits().unwrap().is_empty());
+}
+
+#[test]
+fn test_handler_rejects_invalid_rate_limit() {
+    let mut config = create_test_config("https://gitlab.example.com");
+    config
+        .ratelimit
+        .categories
+        .insert("issues".to_string(), "often".to_string());
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+
+    assert!(GitLabMcpHandler::new_with_shared(&config, gitlab, access).is_err());
+}
@@ -6,22 +6,41 @@ use serde_json::json;
 use std::sync::Arc;
 use tanuki_mcp::access_control::AccessResolver;
 use tanuki_mcp::auth::PatProvider;
-use tanuki_mcp::config::{AccessControlConfig, AccessLevel, GitLabConfig};
+use tanuki_mcp::config::{
+    AccessControlConfig, AccessLevel, CategoryAccessConfig, GitLabConfig, OutputFormat,
+    ProjectAccessConfig,
+};
+use tanuki_mcp::error::{GitLabError, ToolError};
 use tanuki_mcp::gitlab::GitLabClient;
-use tanuki_mcp::tools::{ToolContext, ToolRegistry, definitions};
-use wiremock::matchers::{body_json, header, method, path, query_param};
+use tanuki_mcp::tools::{PollConfig, ToolContext, ToolRegistry, definitions};
+use wiremock::matchers::{body_json, header, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Create a test GitLab client
 fn create_test_gitlab(mock_server: &MockServer) -> Arc<GitLabClient> {
+    create_cached_test_gitlab(mock_server, 0)
+}
+
+/// Create a test GitLab client whose project response cache keeps entries for `ttl_secs`
+fn create_cached_test_gitlab(mock_server: &MockServer, ttl_secs: u64) -> Arc<GitLabClient> {
     let config = GitLabConfig {
         url: mock_server.uri(),
         token: Some("test-token".to_string()),
         api_version: "v4".to_string(),
         timeout_secs: 30,
+        connect_timeout_secs: None,
+        request_timeout_secs: None,
         max_retries: 0,
+        max_concurrent_requests: None,
         verify_ssl: true,
+        follow_redirects: false,
         user_agent: None,
+        default_project: None,
+        instance: None,
+        instances: Default::default(),
+        cache_ttl_secs: ttl_secs,
+        warm_projects: vec![],
+        allowed_projects: vec![],
     };
     let auth = PatProvider::new("test-token".to_string()).unwrap();
     Arc::new(GitLabClient::new(&config, Box::new(auth)).unwrap())
@@ -121,6 +140,41 @@ async fn test_list_issues_with_filters() {
     assert!(!result.is_error);
 }
 
+#[tokio::test]
+async fn test_list_issues_with_labels_details() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .and(query_param("with_labels_details", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "iid": 1,
+                "title": "Slow search",
+                "labels": [
+                    {"name": "bug", "color": "#d9534f", "description": "Something is broken"},
+                    {"name": "priority::high", "color": "#ff0000", "description": "Fix this sprint"}
+                ]
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "with_labels_details": true});
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    let issues = result.data.unwrap();
+    assert_eq!(issues[0]["labels"][1]["color"], "#ff0000");
+    assert_eq!(issues[0]["labels"][1]["description"], "Fix this sprint");
+    assert_eq!(issues[0]["scoped_labels"], json!({"priority": "high"}));
+}
+
 #[tokio::test]
 async fn test_get_issue() {
     let mock_server = MockServer::start().await;
@@ -191,24 +245,32 @@ async fn test_create_issue() {
     assert!(!result.is_error);
 }
 
-// ============================================================================
-// Merge Request Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_list_merge_requests() {
+async fn test_list_issue_label_events() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/issues/42/resource_label_events",
+        ))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
             {
-                "id": 1,
-                "iid": 1,
-                "title": "Feature MR",
-                "state": "opened",
-                "source_branch": "feature",
-                "target_branch": "main"
+                "id": 142,
+                "user": {"id": 1, "username": "alice", "name": "Alice"},
+                "created_at": "2024-01-01T10:00:00.000Z",
+                "resource_type": "Issue",
+                "resource_id": 100,
+                "label": {"id": 73, "name": "bug", "color": "#FF0000"},
+                "action": "add"
+            },
+            {
+                "id": 143,
+                "user": {"id": 2, "username": "bob", "name": "Bob"},
+                "created_at": "2024-01-02T10:00:00.000Z",
+                "resource_type": "Issue",
+                "resource_id": 100,
+                "label": {"id": 73, "name": "bug", "color": "#FF0000"},
+                "action": "remove"
             }
         ])))
         .mount(&mock_server)
@@ -219,78 +281,84 @@ async fn test_list_merge_requests() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
+    let args = json!({"project": "test/project", "issue_iid": 42});
     let result = registry
-        .execute("list_merge_requests", &ctx, args)
+        .execute("list_issue_label_events", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("Feature MR"));
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            let events = value.as_array().unwrap();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0]["action"], "add");
+            assert_eq!(events[0]["label"]["name"], "bug");
+            assert_eq!(events[0]["user"]["username"], "alice");
+            assert_eq!(events[1]["action"], "remove");
+            assert_eq!(events[1]["created_at"], "2024-01-02T10:00:00.000Z");
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_get_merge_request() {
+async fn test_list_issues_output_formats() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/merge_requests/10"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "id": 50,
-            "iid": 10,
-            "title": "Big Feature",
-            "description": "Adds new feature",
-            "state": "merged",
-            "source_branch": "feature-branch",
-            "target_branch": "main"
-        })))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 10, "iid": 1, "title": "First Issue", "state": "opened"}
+        ])))
         .mount(&mock_server)
         .await;
 
-    let gitlab = create_test_gitlab(&mock_server);
-    let access = create_full_access();
-    let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
+    let args = json!({"project": "test/project"});
 
-    let args = json!({"project": "test/project", "merge_request_iid": 10});
-    let result = registry
-        .execute("get_merge_request", &ctx, args)
-        .await
-        .unwrap();
+    let mut outputs = Vec::new();
+    for format in [
+        OutputFormat::Pretty,
+        OutputFormat::Compact,
+        OutputFormat::Markdown,
+    ] {
+        let gitlab = create_test_gitlab(&mock_server);
+        let ctx = create_test_context(gitlab, create_full_access()).with_output_format(format);
+        let result = registry
+            .execute("list_issues", &ctx, args.clone())
+            .await
+            .unwrap();
+        match &result.content[0] {
+            tanuki_mcp::tools::ContentBlock::Text { text } => outputs.push(text.clone()),
+            _ => panic!("Expected text content"),
+        }
+    }
 
-    assert!(!result.is_error);
+    assert!(outputs[0].contains("\"title\": \"First Issue\""));
+    assert!(outputs[0].contains('\n'));
+    assert!(outputs[1].contains("\"title\":\"First Issue\""));
+    assert!(!outputs[1].contains('\n'));
+    assert!(outputs[2].starts_with("| iid | id | title | state |"));
+    assert!(outputs[2].contains("| 1 | 10 | First Issue | opened |"));
 }
 
-// ============================================================================
-// Repository Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_get_repository_file() {
+async fn test_set_issue_confidential() {
     let mock_server = MockServer::start().await;
 
-    // GitLab returns base64 encoded content
-    use base64::Engine;
-    let content = base64::engine::general_purpose::STANDARD.encode("Hello, World!");
-
-    Mock::given(method("GET"))
-        .and(path(
-            "/api/v4/projects/test%2Fproject/repository/files/README.md",
-        ))
-        .and(query_param("ref", "main"))
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/5"))
+        .and(body_json(json!({"confidential": true})))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "file_name": "README.md",
-            "file_path": "README.md",
-            "size": 13,
-            "encoding": "base64",
-            "content": content,
-            "ref": "main"
+            "id": 105,
+            "iid": 5,
+            "title": "Security report",
+            "confidential": true,
+            "discussion_locked": null
         })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -299,31 +367,37 @@ async fn test_get_repository_file() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    // Use ref_name, not ref (as per tool definition)
-    let args = json!({
-        "project": "test/project",
-        "file_path": "README.md",
-        "ref_name": "main"
-    });
+    let args = json!({"project": "test/project", "issue_iid": 5, "confidential": true});
     let result = registry
-        .execute("get_repository_file", &ctx, args)
+        .execute("set_issue_confidential", &ctx, args)
         .await
         .unwrap();
 
-    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["iid"], 5);
+            assert_eq!(value["confidential"], true);
+            assert_eq!(value["discussion_locked"], false);
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_get_repository_tree() {
+async fn test_set_issue_discussion_locked() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/repository/tree"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": "abc123", "name": "src", "type": "tree", "path": "src"},
-            {"id": "def456", "name": "README.md", "type": "blob", "path": "README.md"},
-            {"id": "ghi789", "name": "Cargo.toml", "type": "blob", "path": "Cargo.toml"}
-        ])))
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/5"))
+        .and(body_json(json!({"discussion_locked": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 105,
+            "iid": 5,
+            "confidential": false,
+            "discussion_locked": true
+        })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -332,40 +406,33 @@ async fn test_get_repository_tree() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
+    let args = json!({"project": "test/project", "issue_iid": 5, "discussion_locked": true});
     let result = registry
-        .execute("get_repository_tree", &ctx, args)
+        .execute("set_issue_discussion_locked", &ctx, args)
         .await
         .unwrap();
 
-    assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("src"));
-            assert!(text.contains("README.md"));
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["discussion_locked"], true);
+            assert_eq!(value["confidential"], false);
         }
         _ => panic!("Expected text content"),
     }
 }
 
-// ============================================================================
-// Project Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_get_project() {
+async fn test_list_group_issues_with_state_filter() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "id": 123,
-            "name": "project",
-            "path_with_namespace": "test/project",
-            "description": "Test project",
-            "default_branch": "main",
-            "visibility": "private"
-        })))
+        .and(path("/api/v4/groups/my-group%2Fsub/issues"))
+        .and(query_param("state", "opened"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 10, "iid": 3, "title": "Group Issue", "state": "opened", "project_id": 7}
+        ])))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -374,27 +441,43 @@ async fn test_get_project() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
-    let result = registry.execute("get_project", &ctx, args).await.unwrap();
+    let args = json!({"group": "my-group/sub", "state": "opened"});
+    let result = registry
+        .execute("list_group_issues", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("test/project"));
+            assert!(text.contains("Group Issue"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_list_projects() {
+async fn test_create_blocking_issue_link() {
     let mock_server = MockServer::start().await;
 
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/1/links"))
+        .and(body_json(json!({
+            "target_project_id": "test/project",
+            "target_issue_iid": 2,
+            "link_type": "blocks"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "source_issue": {"iid": 1},
+            "target_issue": {"iid": 2},
+            "link_type": "blocks"
+        })))
+        .mount(&mock_server)
+        .await;
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/1/links"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 1, "name": "project1", "path_with_namespace": "group/project1"},
-            {"id": 2, "name": "project2", "path_with_namespace": "group/project2"}
+            {"iid": 2, "issue_link_id": 7, "link_type": "blocks"}
         ])))
         .mount(&mock_server)
         .await;
@@ -404,26 +487,55 @@ async fn test_list_projects() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({});
-    let result = registry.execute("list_projects", &ctx, args).await.unwrap();
-
+    let args = json!({
+        "project": "test/project",
+        "issue_iid": 1,
+        "target_project": "test/project",
+        "target_issue_iid": 2,
+        "link_type": "blocks"
+    });
+    let result = registry
+        .execute("create_issue_link", &ctx, args)
+        .await
+        .unwrap();
     assert!(!result.is_error);
-}
+    assert_eq!(result.data.unwrap()["link_type"], "blocks");
 
-// ============================================================================
-// Pipeline Tools Tests
-// ============================================================================
+    let args = json!({"project": "test/project", "issue_iid": 1});
+    let result = registry
+        .execute("list_issue_links", &ctx, args)
+        .await
+        .unwrap();
+    assert_eq!(result.data.unwrap()[0]["link_type"], "blocks");
+
+    // Unknown link types are rejected before reaching GitLab
+    let args = json!({
+        "project": "test/project",
+        "issue_iid": 1,
+        "target_project": "test/project",
+        "target_issue_iid": 2,
+        "link_type": "duplicates"
+    });
+    assert!(
+        registry
+            .execute("create_issue_link", &ctx, args)
+            .await
+            .is_err()
+    );
+}
 
 #[tokio::test]
-async fn test_list_pipelines() {
+async fn test_create_issue_validation_error_lists_fields() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/pipelines"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 100, "status": "success", "ref": "main", "sha": "abc123"},
-            {"id": 99, "status": "failed", "ref": "feature", "sha": "def456"}
-        ])))
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "message": {
+                "title": ["can't be blank"],
+                "due_date": ["must be a valid date", "is in the past"]
+            }
+        })))
         .mount(&mock_server)
         .await;
 
@@ -432,29 +544,48 @@ async fn test_list_pipelines() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
-    let result = registry
-        .execute("list_pipelines", &ctx, args)
+    let args = json!({"project": "test/project", "title": " ", "due_date": "yesterday"});
+    let error = registry
+        .execute("create_issue", &ctx, args)
         .await
-        .unwrap();
+        .unwrap_err();
 
-    assert!(!result.is_error);
+    let ToolError::GitLab(GitLabError::Validation { fields }) = &error else {
+        panic!("expected a validation error, got {:?}", error);
+    };
+    assert_eq!(fields["title"], vec!["can't be blank"]);
+    assert_eq!(
+        fields["due_date"],
+        vec!["must be a valid date", "is in the past"]
+    );
+    assert_eq!(error.code(), "gitlab_validation");
+    assert!(error.to_string().contains("title: can't be blank"));
 }
 
 #[tokio::test]
-async fn test_get_pipeline() {
+async fn test_get_issue_render_markdown() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/pipelines/100"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/42"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "id": 100,
-            "status": "success",
-            "ref": "main",
-            "sha": "abc123def456",
-            "created_at": "2024-01-15T10:00:00Z",
-            "finished_at": "2024-01-15T10:05:00Z"
+            "iid": 42,
+            "title": "Broken build",
+            "description": "Fails since **#41**"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/markdown"))
+        .and(body_json(json!({
+            "text": "Fails since **#41**",
+            "gfm": true,
+            "project": "test/project"
         })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "html": "<p>Fails since <strong><a href=\"/test/project/-/issues/41\">#41</a></strong></p>"
+        })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -463,117 +594,150 @@ async fn test_get_pipeline() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project", "pipeline_id": 100});
-    let result = registry.execute("get_pipeline", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "issue_iid": 42, "render_markdown": true});
+    let result = registry.execute("get_issue", &ctx, args).await.unwrap();
 
-    assert!(!result.is_error);
+    let issue = result.data.unwrap();
+    assert_eq!(issue["description"], "Fails since **#41**");
+    assert!(
+        issue["description_html"]
+            .as_str()
+            .unwrap()
+            .contains("<strong>")
+    );
 }
 
-// ============================================================================
-// Access Control Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_tool_access_denied() {
+async fn test_close_and_reopen_issue() {
     let mock_server = MockServer::start().await;
 
-    let gitlab = create_test_gitlab(&mock_server);
-
-    // Create read-only access
-    let mut policy = AccessControlConfig::default();
-    policy.all = AccessLevel::Read;
-    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/7"))
+        .and(body_json(json!({"state_event": "close"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 7,
+            "state": "closed",
+            "closed_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/7"))
+        .and(body_json(json!({"state_event": "reopen"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 7,
+            "state": "opened",
+            "closed_at": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
 
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    // Write operation should be denied
-    let args = json!({
-        "project": "test/project",
-        "title": "New Issue"
-    });
-    let result = registry.execute("create_issue", &ctx, args).await;
+    let args = json!({"project": "test/project", "issue_iid": 7});
+    let result = registry
+        .execute("close_issue", &ctx, args.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.data.unwrap()["state"], "closed");
 
-    assert!(result.is_err());
-    let err = result.unwrap_err();
-    assert!(err.to_string().contains("denied") || err.to_string().contains("Access"));
+    let result = registry.execute("reopen_issue", &ctx, args).await.unwrap();
+    let issue = result.data.unwrap();
+    assert_eq!(issue["state"], "opened");
+    assert!(issue["closed_at"].is_null());
 }
 
 #[tokio::test]
-async fn test_tool_not_found_error() {
+async fn test_list_issues_without_project_is_rejected() {
+    use tanuki_mcp::dashboard::DashboardMetrics;
+
     let mock_server = MockServer::start().await;
 
     let gitlab = create_test_gitlab(&mock_server);
     let access = create_full_access();
-    let ctx = create_test_context(gitlab, access);
+    let metrics = Arc::new(DashboardMetrics::new());
+    let ctx = ToolContext::with_metrics(gitlab, access, "test-request-123", metrics.clone());
     let registry = create_registry();
 
-    let args = json!({});
-    let result = registry.execute("nonexistent_tool", &ctx, args).await;
+    for args in [json!({}), json!({"project": ""}), json!({"project": null})] {
+        let error = registry
+            .execute("list_issues", &ctx, args)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ToolError::MissingArgument(_)));
+        assert!(
+            error
+                .to_string()
+                .contains("Missing required argument: project (tool 'list_issues' requires")
+        );
+    }
 
-    assert!(result.is_err());
-    let err = result.unwrap_err();
-    assert!(err.to_string().contains("not found") || err.to_string().contains("Not Found"));
-}
-
-#[tokio::test]
-async fn test_tool_invalid_arguments() {
-    let mock_server = MockServer::start().await;
-
-    let gitlab = create_test_gitlab(&mock_server);
-    let access = create_full_access();
-    let ctx = create_test_context(gitlab, access);
-    let registry = create_registry();
-
-    // Missing required 'project' field
-    let args = json!({"invalid_field": "value"});
-    let result = registry.execute("list_issues", &ctx, args).await;
-
-    assert!(result.is_err());
+    // Nothing reached GitLab
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+    assert_eq!(metrics.total_errors(), 3);
 }
 
-// ============================================================================
-// Label Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_list_labels() {
+async fn test_project_override_applies_to_detected_project_field() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/labels"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 1, "name": "bug", "color": "#FF0000"},
-            {"id": 2, "name": "feature", "color": "#00FF00"}
-        ])))
+        .and(path("/api/v4/projects/public%2Fapp/pipelines"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
+    // list_pipelines declares no project_field; its `project` is detected
+    let mut config = AccessControlConfig {
+        all: AccessLevel::Read,
+        ..Default::default()
+    };
+    config.projects.insert(
+        "secret/app".to_string(),
+        ProjectAccessConfig {
+            all: Some(AccessLevel::Deny),
+            ..Default::default()
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&config).unwrap());
+
     let gitlab = create_test_gitlab(&mock_server);
-    let access = create_full_access();
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
-    let result = registry.execute("list_labels", &ctx, args).await.unwrap();
+    let result = registry
+        .execute("list_pipelines", &ctx, json!({"project": "secret/app"}))
+        .await;
+    assert!(matches!(result, Err(ToolError::AccessDenied(_))));
 
+    let result = registry
+        .execute("list_pipelines", &ctx, json!({"project": "public/app"}))
+        .await
+        .unwrap();
     assert!(!result.is_error);
 }
 
-// ============================================================================
-// Wiki Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_list_wiki_pages() {
+async fn test_set_issue_weight() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/wikis"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"slug": "home", "title": "Home", "format": "markdown"},
-            {"slug": "getting-started", "title": "Getting Started", "format": "markdown"}
-        ])))
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/5"))
+        .and(body_json(json!({"weight": 3})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 105,
+            "iid": 5,
+            "web_url": "https://gitlab.example.com/test/project/-/issues/5",
+            "weight": 3,
+            "iteration": null
+        })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -582,28 +746,42 @@ async fn test_list_wiki_pages() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
+    let args = json!({"project": "test/project", "issue_iid": 5, "weight": 3});
     let result = registry
-        .execute("list_wiki_pages", &ctx, args)
+        .execute("set_issue_weight", &ctx, args)
         .await
         .unwrap();
 
-    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["iid"], 5);
+            assert_eq!(value["weight"], 3);
+            assert!(value.get("notice").is_none());
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 // ============================================================================
-// Milestone Tools Tests
+// Merge Request Tools Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_list_milestones() {
+async fn test_list_merge_requests() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/milestones"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 1, "iid": 1, "title": "v1.0", "state": "active"},
-            {"id": 2, "iid": 2, "title": "v2.0", "state": "active"}
+            {
+                "id": 1,
+                "iid": 1,
+                "title": "Feature MR",
+                "state": "opened",
+                "source_branch": "feature",
+                "target_branch": "main"
+            }
         ])))
         .mount(&mock_server)
         .await;
@@ -615,37 +793,34 @@ async fn test_list_milestones() {
 
     let args = json!({"project": "test/project"});
     let result = registry
-        .execute("list_milestones", &ctx, args)
+        .execute("list_merge_requests", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("Feature MR"));
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
-// ============================================================================
-// Tag Tools Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_list_tags() {
+async fn test_get_merge_request() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {
-                "name": "v1.0.0",
-                "message": "Release v1.0.0",
-                "target": "abc123",
-                "commit": {"id": "abc123", "message": "Initial release"}
-            },
-            {
-                "name": "v1.1.0",
-                "message": "Release v1.1.0",
-                "target": "def456",
-                "commit": {"id": "def456", "message": "Minor update"}
-            }
-        ])))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "iid": 10,
+            "title": "Big Feature",
+            "description": "Adds new feature",
+            "state": "merged",
+            "source_branch": "feature-branch",
+            "target_branch": "main"
+        })))
         .mount(&mock_server)
         .await;
 
@@ -654,29 +829,28 @@ async fn test_list_tags() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
-    let result = registry.execute("list_tags", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "merge_request_iid": 10});
+    let result = registry
+        .execute("get_merge_request", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
-    match &result.content[0] {
-        tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("v1.0.0"));
-            assert!(text.contains("v1.1.0"));
-        }
-        _ => panic!("Expected text content"),
-    }
 }
 
 #[tokio::test]
-async fn test_list_tags_with_filters() {
+async fn test_get_merge_request_with_fields() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
-        .and(query_param("order_by", "updated"))
-        .and(query_param("sort", "desc"))
-        .and(query_param("search", "v1"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "iid": 10,
+            "title": "Big Feature",
+            "description": "Adds new feature",
+            "state": "merged"
+        })))
         .mount(&mock_server)
         .await;
 
@@ -687,35 +861,66 @@ async fn test_list_tags_with_filters() {
 
     let args = json!({
         "project": "test/project",
-        "order_by": "updated",
-        "sort": "desc",
-        "search": "v1"
+        "merge_request_iid": 10,
+        "fields": ["iid", "title", "nonexistent"]
     });
-    let result = registry.execute("list_tags", &ctx, args).await.unwrap();
+    let result = registry
+        .execute("get_merge_request", &ctx, args)
+        .await
+        .unwrap();
+
     assert!(!result.is_error);
+    assert_eq!(
+        result.data,
+        Some(json!({"iid": 10, "title": "Big Feature"}))
+    );
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("Big Feature"));
+            assert!(!text.contains("description"));
+            assert!(!text.contains("merged"));
+        }
+        _ => panic!("Expected text content"),
+    }
+    match &result.content[1] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("nonexistent"));
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_get_tag() {
+async fn test_get_merge_request_changes() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
         .and(path(
-            "/api/v4/projects/test%2Fproject/repository/tags/v1.0.0",
+            "/api/v4/projects/test%2Fproject/merge_requests/10/changes",
         ))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "name": "v1.0.0",
-            "message": "Release v1.0.0",
-            "target": "abc123def456",
-            "commit": {
-                "id": "abc123def456",
-                "message": "Initial release",
-                "author_name": "Developer"
-            },
-            "release": {
-                "tag_name": "v1.0.0",
-                "description": "First stable release"
-            }
+            "iid": 10,
+            "title": "Big Feature",
+            "source_branch": "feature-branch",
+            "target_branch": "main",
+            "changes": [
+                {
+                    "old_path": "src/lib.rs",
+                    "new_path": "src/lib.rs",
+                    "new_file": false,
+                    "renamed_file": false,
+                    "deleted_file": false,
+                    "diff": "@@ -1 +1 @@\n-old line\n+new line\n"
+                },
+                {
+                    "old_path": "README.md",
+                    "new_path": "README.md",
+                    "new_file": false,
+                    "renamed_file": false,
+                    "deleted_file": false,
+                    "diff": "@@ -1 +1 @@\n-# Title\n+# New Title\n"
+                }
+            ]
         })))
         .mount(&mock_server)
         .await;
@@ -725,33 +930,44 @@ async fn test_get_tag() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project", "tag_name": "v1.0.0"});
-    let result = registry.execute("get_tag", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "merge_request_iid": 10});
+    let result = registry
+        .execute("get_merge_request_changes", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("v1.0.0"));
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["changes_count"], 2);
+            assert_eq!(value["changes"][0]["new_path"], "src/lib.rs");
+            assert!(
+                value["changes"][0]["diff"]
+                    .as_str()
+                    .unwrap()
+                    .contains("+new line")
+            );
+            assert_eq!(value["changes"][0]["truncated"], false);
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_create_tag() {
+async fn test_get_merge_request_changes_single_file_truncated() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("POST"))
-        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
-        .and(body_json(json!({
-            "tag_name": "v2.0.0",
-            "ref": "main",
-            "message": "Release v2.0.0"
-        })))
-        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
-            "name": "v2.0.0",
-            "message": "Release v2.0.0",
-            "target": "abc123"
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/10/changes",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 10,
+            "changes": [
+                {"old_path": "a.txt", "new_path": "a.txt", "diff": "+aaaaaaaaaaaaaaaaaaaa"},
+                {"old_path": "b.txt", "new_path": "b.txt", "diff": "+bbbbbbbbbbbbbbbbbbbb"}
+            ]
         })))
         .mount(&mock_server)
         .await;
@@ -763,24 +979,43 @@ async fn test_create_tag() {
 
     let args = json!({
         "project": "test/project",
-        "tag_name": "v2.0.0",
-        "ref_name": "main",
-        "message": "Release v2.0.0"
+        "merge_request_iid": 10,
+        "file_path": "b.txt",
+        "max_diff_length": 5
     });
-    let result = registry.execute("create_tag", &ctx, args).await.unwrap();
+    let result = registry
+        .execute("get_merge_request_changes", &ctx, args)
+        .await
+        .unwrap();
 
-    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["changes_count"], 1);
+            assert_eq!(value["changes"][0]["new_path"], "b.txt");
+            assert_eq!(value["changes"][0]["truncated"], true);
+            assert!(
+                value["changes"][0]["diff"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("+bbbb")
+            );
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_delete_tag() {
+async fn test_rebase_merge_request() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("DELETE"))
+    Mock::given(method("PUT"))
         .and(path(
-            "/api/v4/projects/test%2Fproject/repository/tags/v1.0.0",
+            "/api/v4/projects/test%2Fproject/merge_requests/10/rebase",
         ))
-        .respond_with(ResponseTemplate::new(204))
+        .and(body_json(json!({"skip_ci": true})))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({"rebase_in_progress": true})))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -789,28 +1024,42 @@ async fn test_delete_tag() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project", "tag_name": "v1.0.0"});
-    let result = registry.execute("delete_tag", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "merge_request_iid": 10, "skip_ci": true});
+    let result = registry
+        .execute("rebase_merge_request", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("deleted"));
-            assert!(text.contains("v1.0.0"));
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["rebase_in_progress"], true);
+            assert_eq!(value["merge_request_iid"], 10);
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_list_protected_tags() {
+async fn test_list_merge_request_label_events() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/protected_tags"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/10/resource_label_events",
+        ))
+        .and(query_param("per_page", "50"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"name": "v*", "create_access_levels": [{"access_level": 40}]},
-            {"name": "release-*", "create_access_levels": [{"access_level": 30}]}
+            {
+                "id": 200,
+                "user": {"id": 1, "username": "alice", "name": "Alice"},
+                "created_at": "2024-01-01T10:00:00.000Z",
+                "resource_type": "MergeRequest",
+                "resource_id": 50,
+                "label": null,
+                "action": "add"
+            }
         ])))
         .mount(&mock_server)
         .await;
@@ -820,24 +1069,40 @@ async fn test_list_protected_tags() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project"});
+    let args = json!({"project": "test/project", "merge_request_iid": 10, "per_page": 50});
     let result = registry
-        .execute("list_protected_tags", &ctx, args)
+        .execute("list_merge_request_label_events", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value[0]["resource_type"], "MergeRequest");
+            assert!(value[0]["label"].is_null());
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_get_protected_tag() {
+async fn test_merge_merge_request_immediate() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/protected_tags/v%2A"))
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/merge",
+        ))
+        .and(body_json(json!({
+            "squash": true,
+            "should_remove_source_branch": true
+        })))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "name": "v*",
-            "create_access_levels": [{"access_level": 40, "access_level_description": "Maintainers"}]
+            "id": 50,
+            "iid": 5,
+            "state": "merged",
+            "merge_when_pipeline_succeeds": false
         })))
         .mount(&mock_server)
         .await;
@@ -847,28 +1112,40 @@ async fn test_get_protected_tag() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project", "name": "v*"});
+    let args = json!({
+        "project": "test/project",
+        "merge_request_iid": 5,
+        "squash": true,
+        "should_remove_source_branch": true
+    });
     let result = registry
-        .execute("get_protected_tag", &ctx, args)
+        .execute("merge_merge_request", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("\"merged\""));
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_protect_tag() {
+async fn test_merge_merge_request_when_pipeline_succeeds() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("POST"))
-        .and(path("/api/v4/projects/test%2Fproject/protected_tags"))
-        .and(body_json(json!({
-            "name": "release-*",
-            "create_access_level": 40
-        })))
-        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
-            "name": "release-*",
-            "create_access_levels": [{"access_level": 40}]
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/merge",
+        ))
+        .and(body_json(json!({"merge_when_pipeline_succeeds": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "iid": 5,
+            "state": "opened",
+            "merge_when_pipeline_succeeds": true
         })))
         .mount(&mock_server)
         .await;
@@ -880,21 +1157,33 @@ async fn test_protect_tag() {
 
     let args = json!({
         "project": "test/project",
-        "name": "release-*",
-        "create_access_level": 40
+        "merge_request_iid": 5,
+        "merge_when_pipeline_succeeds": true
     });
-    let result = registry.execute("protect_tag", &ctx, args).await.unwrap();
+    let result = registry
+        .execute("merge_merge_request", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("\"merge_when_pipeline_succeeds\": true"));
+            assert!(text.contains("\"opened\""));
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_unprotect_tag() {
+async fn test_merge_merge_request_accepted_empty_body() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("DELETE"))
-        .and(path("/api/v4/projects/test%2Fproject/protected_tags/v%2A"))
-        .respond_with(ResponseTemplate::new(204))
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/merge",
+        ))
+        .respond_with(ResponseTemplate::new(202))
         .mount(&mock_server)
         .await;
 
@@ -903,34 +1192,70 @@ async fn test_unprotect_tag() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({"project": "test/project", "name": "v*"});
-    let result = registry.execute("unprotect_tag", &ctx, args).await.unwrap();
+    let args = json!({
+        "project": "test/project",
+        "merge_request_iid": 5,
+        "merge_when_pipeline_succeeds": true
+    });
+    let result = registry
+        .execute("merge_merge_request", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("unprotected"));
+            assert!(text.contains("accepted"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
-// ============================================================================
-// Search Tools Tests
-// ============================================================================
+#[tokio::test]
+async fn test_merge_merge_request_not_mergeable() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/merge",
+        ))
+        .respond_with(
+            ResponseTemplate::new(405).set_body_json(json!({"message": "405 Method Not Allowed"})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "merge_request_iid": 5
+    });
+    let err = registry
+        .execute("merge_merge_request", &ctx, args)
+        .await
+        .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("HTTP 405"));
+    assert!(message.contains("merge_when_pipeline_succeeds"));
+}
 
 #[tokio::test]
-async fn test_search_global() {
+async fn test_list_group_merge_requests_with_state_filter() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/search"))
-        .and(query_param("scope", "projects"))
-        .and(query_param("search", "gitlab"))
+        .and(path("/api/v4/groups/42/merge_requests"))
+        .and(query_param("state", "merged"))
+        .and(query_param("target_branch", "main"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 1, "name": "tanuki-mcp", "path_with_namespace": "user/tanuki-mcp"},
-            {"id": 2, "name": "gitlab-runner", "path_with_namespace": "org/gitlab-runner"}
+            {"id": 5, "iid": 2, "title": "Group MR", "state": "merged"}
         ])))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -939,35 +1264,37 @@ async fn test_search_global() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({
-        "scope": "projects",
-        "search": "gitlab"
-    });
-    let result = registry.execute("search_global", &ctx, args).await.unwrap();
+    let args = json!({"group": "42", "state": "merged", "target_branch": "main"});
+    let result = registry
+        .execute("list_group_merge_requests", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("tanuki-mcp"));
-            assert!(text.contains("gitlab-runner"));
+            assert!(text.contains("Group MR"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_search_global_issues() {
+async fn test_set_merge_request_reviewers() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/search"))
-        .and(query_param("scope", "issues"))
-        .and(query_param("search", "bug"))
-        .and(query_param("state", "opened"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": 1, "iid": 10, "title": "Critical bug", "state": "opened"},
-            {"id": 2, "iid": 20, "title": "Minor bug fix", "state": "opened"}
-        ])))
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/7"))
+        .and(body_json(json!({"reviewer_ids": [11, 12]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 7,
+            "title": "Feature",
+            "reviewers": [
+                {"id": 11, "username": "alice"},
+                {"id": 12, "username": "bob"}
+            ]
+        })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -976,40 +1303,34 @@ async fn test_search_global_issues() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({
-        "scope": "issues",
-        "search": "bug",
-        "state": "opened"
-    });
-    let result = registry.execute("search_global", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "merge_request_iid": 7, "user_ids": [11, 12]});
+    let result = registry
+        .execute("set_merge_request_reviewers", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("alice"));
+            assert!(text.contains("bob"));
+        }
+        _ => panic!("Expected text content"),
+    }
 }
 
 #[tokio::test]
-async fn test_search_project() {
+async fn test_set_merge_request_assignees() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/search"))
-        .and(query_param("scope", "blobs"))
-        .and(query_param("search", "TODO"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {
-                "basename": "main.rs",
-                "data": "// TODO: implement this",
-                "path": "src/main.rs",
-                "filename": "main.rs",
-                "ref": "main"
-            },
-            {
-                "basename": "lib.rs",
-                "data": "// TODO: add tests",
-                "path": "src/lib.rs",
-                "filename": "lib.rs",
-                "ref": "main"
-            }
-        ])))
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/7"))
+        .and(body_json(json!({"assignee_ids": [21]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 7,
+            "assignees": [{"id": 21, "username": "carol"}]
+        })))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -1018,38 +1339,35 @@ async fn test_search_project() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({
-        "project": "test/project",
-        "scope": "blobs",
-        "search": "TODO"
-    });
+    let args = json!({"project": "test/project", "merge_request_iid": 7, "user_ids": [21]});
     let result = registry
-        .execute("search_project", &ctx, args)
+        .execute("set_merge_request_assignees", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("main.rs"));
-            assert!(text.contains("lib.rs"));
+            assert!(text.contains("carol"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_search_project_with_ref() {
+async fn test_get_merge_request_merge_status() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/projects/test%2Fproject/search"))
-        .and(query_param("scope", "commits"))
-        .and(query_param("search", "fix"))
-        .and(query_param("ref", "develop"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {"id": "abc123", "title": "Fix bug", "author_name": "Developer"}
-        ])))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "iid": 10,
+            "title": "Big Feature",
+            "merge_status": "cannot_be_merged",
+            "detailed_merge_status": "ci_still_running",
+            "has_conflicts": false,
+            "head_pipeline": {"id": 300, "status": "running", "ref": "feature-branch"}
+        })))
         .mount(&mock_server)
         .await;
 
@@ -1060,42 +1378,36 @@ async fn test_search_project_with_ref() {
 
     let args = json!({
         "project": "test/project",
-        "scope": "commits",
-        "search": "fix",
-        "ref_name": "develop"
+        "merge_request_iid": 10,
+        "fields": ["merge_status", "detailed_merge_status", "has_conflicts", "head_pipeline"]
     });
     let result = registry
-        .execute("search_project", &ctx, args)
+        .execute("get_merge_request", &ctx, args)
         .await
         .unwrap();
 
     assert!(!result.is_error);
+    let data = result.data.unwrap();
+    assert_eq!(data["merge_status"], "cannot_be_merged");
+    assert_eq!(data["detailed_merge_status"], "ci_still_running");
+    assert_eq!(data["has_conflicts"], false);
+    assert_eq!(data["head_pipeline"]["status"], "running");
 }
 
 #[tokio::test]
-async fn test_search_group() {
+async fn test_list_merge_request_pipelines() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v4/groups/mygroup/search"))
-        .and(query_param("scope", "merge_requests"))
-        .and(query_param("search", "feature"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/10/pipelines",
+        ))
+        .and(query_param("per_page", "20"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!([
-            {
-                "id": 1,
-                "iid": 5,
-                "title": "Add new feature",
-                "state": "merged",
-                "source_branch": "feature-x"
-            },
-            {
-                "id": 2,
-                "iid": 10,
-                "title": "Feature improvement",
-                "state": "opened",
-                "source_branch": "feature-y"
-            }
+            {"id": 301, "sha": "abc123", "ref": "refs/merge-requests/10/head", "status": "success"},
+            {"id": 300, "sha": "def456", "ref": "refs/merge-requests/10/head", "status": "failed"}
         ])))
+        .expect(1)
         .mount(&mock_server)
         .await;
 
@@ -1104,34 +1416,41 @@ async fn test_search_group() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({
-        "group": "mygroup",
-        "scope": "merge_requests",
-        "search": "feature"
-    });
-    let result = registry.execute("search_group", &ctx, args).await.unwrap();
+    let args = json!({"project": "test/project", "merge_request_iid": 10});
+    let result = registry
+        .execute("list_merge_request_pipelines", &ctx, args)
+        .await
+        .unwrap();
 
     assert!(!result.is_error);
     match &result.content[0] {
         tanuki_mcp::tools::ContentBlock::Text { text } => {
-            assert!(text.contains("Add new feature"));
-            assert!(text.contains("Feature improvement"));
+            assert!(text.contains("\"success\""));
+            assert!(text.contains("301"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
 #[tokio::test]
-async fn test_search_group_with_state_filter() {
+async fn test_create_merge_request_with_labels_and_people() {
     let mock_server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/v4/groups/mygroup/search"))
-        .and(query_param("scope", "issues"))
-        .and(query_param("search", "critical"))
-        .and(query_param("state", "opened"))
-        .and(query_param("confidential", "true"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
+        .and(body_json(json!({
+            "source_branch": "feature",
+            "target_branch": "main",
+            "title": "Add feature",
+            "assignee_ids": [5],
+            "reviewer_ids": [7, 8],
+            "labels": "bug,backend"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "iid": 11,
+            "labels": ["bug", "backend"]
+        })))
+        .expect(2)
         .mount(&mock_server)
         .await;
 
@@ -1140,14 +1459,5235 @@ async fn test_search_group_with_state_filter() {
     let ctx = create_test_context(gitlab, access);
     let registry = create_registry();
 
-    let args = json!({
-        "group": "mygroup",
-        "scope": "issues",
-        "search": "critical",
-        "state": "opened",
-        "confidential": true
-    });
-    let result = registry.execute("search_group", &ctx, args).await.unwrap();
+    for labels in [json!(["bug", "backend"]), json!("bug,backend")] {
+        let args = json!({
+            "project": "test/project",
+            "source_branch": "feature",
+            "target_branch": "main",
+            "title": "Add feature",
+            "labels": labels,
+            "assignee_ids": [5],
+            "reviewer_ids": [7, 8]
+        });
+        let result = registry
+            .execute("create_merge_request", &ctx, args)
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+    }
+}
+
+#[tokio::test]
+async fn test_get_merge_request_diff_patch() {
+    let mock_server = MockServer::start().await;
+
+    let patch = "diff --git a/src/main.rs b/src/main.rs\n\
+                 index 1111111..2222222 100644\n\
+                 --- a/src/main.rs\n\
+                 +++ b/src/main.rs\n\
+                 @@ -1 +1 @@\n\
+                 -fn main() {}\n\
+                 +fn main() { println!(\"hi\"); }\n";
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/10/raw_diffs",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string(patch))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
 
+    let args = json!({"project": "test/project", "merge_request_iid": 10});
+    let result = registry
+        .execute("get_merge_request_diff_patch", &ctx, args)
+        .await
+        .unwrap();
     assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => assert_eq!(text, patch),
+        _ => panic!("Expected text content"),
+    }
+
+    let args = json!({"project": "test/project", "merge_request_iid": 10, "max_length": 20});
+    let result = registry
+        .execute("get_merge_request_diff_patch", &ctx, args)
+        .await
+        .unwrap();
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.starts_with("diff --git a/src/ma"));
+            assert!(text.contains("Patch truncated to 20 bytes"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_merge_request_diff_patch_size_is_capped() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/10/raw_diffs",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![b'+'; 16 * 1024 * 1024 + 1]))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // The body is not buffered past the server's ceiling, whatever max_length says
+    let args = json!({"project": "test/project", "merge_request_iid": 10, "max_length": u64::MAX});
+    let err = registry
+        .execute("get_merge_request_diff_patch", &ctx, args)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("size limit of 16777216 bytes"));
+}
+
+#[tokio::test]
+async fn test_list_merge_requests_only_conflicting() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
+        .and(query_param("with_merge_status_recheck", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"iid": 1, "has_conflicts": true, "detailed_merge_status": "conflict"},
+            {"iid": 2, "has_conflicts": false, "detailed_merge_status": "mergeable"},
+            {"iid": 3, "detailed_merge_status": "conflict"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "with_merge_status_recheck": true,
+        "has_conflicts": true
+    });
+    let result = registry
+        .execute("list_merge_requests", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    assert_eq!(
+        result.data,
+        Some(json!([
+            {"iid": 1, "has_conflicts": true, "detailed_merge_status": "conflict"},
+            {"iid": 3, "detailed_merge_status": "conflict"}
+        ]))
+    );
+}
+
+#[tokio::test]
+async fn test_list_mr_discussions_all_pages() {
+    let mock_server = MockServer::start().await;
+
+    let thread = |id: &str, resolved: bool| {
+        json!({
+            "id": id,
+            "individual_note": false,
+            "notes": [
+                {"id": 1, "body": "first", "resolvable": true, "resolved": resolved},
+                {"id": 2, "body": "reply", "resolvable": true, "resolved": resolved}
+            ]
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/1/discussions",
+        ))
+        .and(query_param("page", "1"))
+        .and(query_param("per_page", "100"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([thread("a", true), thread("b", false)]))
+                .insert_header("X-Total", "3")
+                .insert_header("X-Next-Page", "2"),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/1/discussions",
+        ))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([thread("c", false)]))
+                .insert_header("X-Total", "3")
+                .insert_header("X-Next-Page", ""),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "merge_request_iid": 1, "all_pages": true});
+    let result = registry
+        .execute("list_mr_discussions", &ctx, args)
+        .await
+        .unwrap();
+    let discussions = result.data.unwrap();
+    assert_eq!(discussions.as_array().unwrap().len(), 3);
+    assert_eq!(discussions[2]["id"], "c");
+    assert_eq!(discussions[0]["notes"][1]["body"], "reply");
+
+    let args = json!({
+        "project": "test/project",
+        "merge_request_iid": 1,
+        "all_pages": true,
+        "only_unresolved": true
+    });
+    let result = registry
+        .execute("list_mr_discussions", &ctx, args)
+        .await
+        .unwrap();
+    let ids: Vec<_> = result
+        .data
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["id"].clone())
+        .collect();
+    assert_eq!(ids, vec![json!("b"), json!("c")]);
+}
+
+#[tokio::test]
+async fn test_list_mr_discussions_all_pages_reports_truncation() {
+    use tanuki_mcp::gitlab::client::MAX_ALL_PAGES;
+
+    let mock_server = MockServer::start().await;
+
+    // Every page points at another one
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/1/discussions",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([{"id": "a", "notes": []}]))
+                .insert_header("X-Next-Page", "99"),
+        )
+        .expect(u64::from(MAX_ALL_PAGES))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "merge_request_iid": 1, "all_pages": true});
+    let result = registry
+        .execute("list_mr_discussions", &ctx, args)
+        .await
+        .unwrap();
+    let data = result.data.unwrap();
+    assert_eq!(data["truncated"], true);
+    assert_eq!(data["next_page"], 99);
+    assert_eq!(
+        data["items"].as_array().unwrap().len(),
+        MAX_ALL_PAGES as usize
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_and_unresolve_mr_discussion() {
+    let mock_server = MockServer::start().await;
+
+    let discussion = |resolved: bool| {
+        json!({
+            "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+            "notes": [
+                {"id": 1, "body": "Please rename", "resolvable": true, "resolved": resolved},
+                {"id": 2, "body": "Done", "resolvable": true, "resolved": resolved}
+            ]
+        })
+    };
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/discussions/6a9c1750b37d513a43987b574953fceb50b03ce7",
+        ))
+        .and(query_param("resolved", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discussion(true)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/merge_requests/5/discussions/6a9c1750b37d513a43987b574953fceb50b03ce7",
+        ))
+        .and(query_param("resolved", "false"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discussion(false)))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "merge_request_iid": 5,
+        "discussion_id": "6a9c1750b37d513a43987b574953fceb50b03ce7"
+    });
+
+    let result = registry
+        .execute("resolve_mr_discussion", &ctx, args.clone())
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    let data = result.data.unwrap();
+    assert_eq!(data["resolved"], true);
+    assert_eq!(data["resolvable"], true);
+
+    let result = registry
+        .execute("unresolve_mr_discussion", &ctx, args.clone())
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert_eq!(result.data.unwrap()["resolved"], false);
+
+    // The deprecated `resolved: false` form still reopens the thread
+    let mut legacy = args;
+    legacy["resolved"] = json!(false);
+    let result = registry
+        .execute("resolve_mr_discussion", &ctx, legacy)
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert_eq!(result.data.unwrap()["resolved"], false);
+}
+
+/// Mount the branch and commit endpoints used by `propose_change`
+async fn mount_propose_change_steps(mock_server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/branches"))
+        .and(body_json(json!({"branch": "fix-typo", "ref": "main"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"name": "fix-typo"})))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/commits"))
+        .and(body_json(json!({
+            "branch": "fix-typo",
+            "commit_message": "Fix typo in README",
+            "actions": [{"action": "update", "file_path": "README.md", "content": "Hello"}]
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "ed899a2f4b50b4370feeea94676502b42383c746",
+            "short_id": "ed899a2f",
+            "title": "Fix typo in README"
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+fn propose_change_args() -> serde_json::Value {
+    json!({
+        "project": "test/project",
+        "base_branch": "main",
+        "branch": "fix-typo",
+        "actions": [{"action": "update", "file_path": "README.md", "content": "Hello"}],
+        "title": "Fix typo in README"
+    })
+}
+
+#[tokio::test]
+async fn test_propose_change_creates_branch_commit_and_mr() {
+    let mock_server = MockServer::start().await;
+    mount_propose_change_steps(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
+        .and(body_json(json!({
+            "source_branch": "fix-typo",
+            "target_branch": "main",
+            "title": "Fix typo in README"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "iid": 12,
+            "title": "Fix typo in README",
+            "web_url": "https://gitlab.example.com/test/project/-/merge_requests/12"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let result = registry
+        .execute("propose_change", &ctx, propose_change_args())
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let data = result.data.unwrap();
+    assert_eq!(data["branch"], "fix-typo");
+    assert_eq!(data["commit"]["short_id"], "ed899a2f");
+    assert_eq!(data["merge_request"]["iid"], 12);
+}
+
+#[tokio::test]
+async fn test_propose_change_deletes_branch_when_mr_fails() {
+    let mock_server = MockServer::start().await;
+    mount_propose_change_steps(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/merge_requests"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+            "message": ["Another open merge request already exists for this source branch"]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/branches/fix-typo",
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let result = registry
+        .execute("propose_change", &ctx, propose_change_args())
+        .await;
+
+    match result {
+        Err(tanuki_mcp::error::ToolError::ExecutionFailed(message)) => {
+            assert!(message.contains("create the merge request"));
+            assert!(message.contains("Branch 'fix-typo' was deleted again"));
+        }
+        other => panic!(
+            "Expected execution failure, got {:?}",
+            other.map(|r| r.data)
+        ),
+    }
+}
+
+// ============================================================================
+// Repository Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_repository_file() {
+    let mock_server = MockServer::start().await;
+
+    // GitLab returns base64 encoded content
+    use base64::Engine;
+    let content = base64::engine::general_purpose::STANDARD.encode("Hello, World!");
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/README.md",
+        ))
+        .and(query_param("ref", "main"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "file_name": "README.md",
+            "file_path": "README.md",
+            "size": 13,
+            "encoding": "base64",
+            "content": content,
+            "ref": "main"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // Use ref_name, not ref (as per tool definition)
+    let args = json!({
+        "project": "test/project",
+        "file_path": "README.md",
+        "ref_name": "main"
+    });
+    let result = registry
+        .execute("get_repository_file", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_repository_tree() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tree"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": "abc123", "name": "src", "type": "tree", "path": "src"},
+            {"id": "def456", "name": "README.md", "type": "blob", "path": "README.md"},
+            {"id": "ghi789", "name": "Cargo.toml", "type": "blob", "path": "Cargo.toml"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("get_repository_tree", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("src"));
+            assert!(text.contains("README.md"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_contributors_sorted_by_commits() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/contributors"))
+        .and(query_param("order_by", "commits"))
+        .and(query_param("sort", "desc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"name": "Alice", "email": "alice@example.com", "commits": 120, "additions": 0, "deletions": 0},
+            {"name": "Bob", "email": "bob@example.com", "commits": 15, "additions": 0, "deletions": 0}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let result = registry
+        .execute(
+            "list_contributors",
+            &ctx,
+            json!({"project": "test/project"}),
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let contributors = result.data.unwrap();
+    assert_eq!(contributors[0]["name"], "Alice");
+    assert_eq!(contributors[0]["commits"], 120);
+    assert_eq!(contributors[1]["name"], "Bob");
+}
+
+#[tokio::test]
+async fn test_diff_file_between_refs() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/src%2Flib.rs/raw",
+        ))
+        .and(query_param("ref", "v1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("fn a() {}\nfn b() {}\n"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/src%2Flib.rs/raw",
+        ))
+        .and(query_param("ref", "main"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("fn a() {}\nfn b() -> u8 { 1 }\n"))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_path": "src/lib.rs",
+        "from": "v1.0",
+        "to": "main"
+    });
+    let result = registry
+        .execute("diff_file_between_refs", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let data = result.data.unwrap();
+    let diff = data["diff"].as_str().unwrap();
+    assert!(diff.contains("--- a/src/lib.rs"));
+    assert!(diff.contains("-fn b() {}"));
+    assert!(diff.contains("+fn b() -> u8 { 1 }"));
+    assert!(!diff.contains("-fn a() {}"));
+    assert_eq!(data["from_size"], 20);
+    assert_eq!(data["to_size"], 29);
+    assert_eq!(data["identical"], false);
+}
+
+#[tokio::test]
+async fn test_diff_file_between_refs_rejects_large_file() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/big.txt/raw",
+        ))
+        .and(query_param("ref", "v1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("small\n"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/big.txt/raw",
+        ))
+        .and(query_param("ref", "main"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(1024 * 1024 + 1)))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_path": "big.txt",
+        "from": "v1.0",
+        "to": "main"
+    });
+    let err = registry
+        .execute("diff_file_between_refs", &ctx, args)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ToolError::InvalidArguments(_)));
+    assert!(
+        err.to_string()
+            .contains("'big.txt' at 'main' is too large to diff")
+    );
+}
+
+#[tokio::test]
+async fn test_get_file_blame_line_range() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/src%2Fmain.rs/blame",
+        ))
+        .and(query_param("ref", "main"))
+        .and(query_param("range[start]", "11"))
+        .and(query_param("range[end]", "20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"commit": {"id": "abc123"}, "lines": ["fn main() {", "    run();"]},
+            {"commit": {"id": "def456"}, "lines": ["}", "", "fn run() {}", "", "", "", "", ""]}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_path": "src/main.rs",
+        "ref_name": "main",
+        "start_line": 11,
+        "end_line": 20
+    });
+    let result = registry
+        .execute("get_file_blame", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let ranges = result.data.unwrap();
+    let lines: usize = ranges
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|range| range["lines"].as_array().unwrap().len())
+        .sum();
+    assert_eq!(lines, 10);
+
+    let args = json!({
+        "project": "test/project",
+        "file_path": "src/main.rs",
+        "start_line": 20,
+        "end_line": 11
+    });
+    let result = registry.execute("get_file_blame", &ctx, args).await;
+    assert!(matches!(
+        result,
+        Err(tanuki_mcp::error::ToolError::InvalidArguments(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_get_file_raw_returns_plain_text() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/docs%2FREADME.md/raw",
+        ))
+        .and(query_param("ref", "main"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("# Project\n\nHello\n"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_path": "docs/README.md",
+        "ref_name": "main"
+    });
+    let result = registry.execute("get_file_raw", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    assert!(result.data.is_none());
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert_eq!(text, "# Project\n\nHello\n");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_file_raw_size_is_capped() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/files/big.bin/raw",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 16 * 1024 * 1024 + 1]))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // A larger limit from the caller does not raise the server's ceiling
+    let args = json!({
+        "project": "test/project",
+        "file_path": "big.bin",
+        "max_size_bytes": u64::MAX
+    });
+    let err = registry
+        .execute("get_file_raw", &ctx, args)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("size limit of 16777216 bytes"));
+}
+
+// ============================================================================
+// Commit Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_commit_with_stats() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/commits/abc123",
+        ))
+        .and(query_param("stats", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "abc123",
+            "title": "Fix bug",
+            "stats": {"additions": 12, "deletions": 3, "total": 15}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "sha": "abc123", "with_stats": true});
+    let result = registry.execute("get_commit", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let commit: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(commit["stats"]["additions"], 12);
+            assert_eq!(commit["stats"]["deletions"], 3);
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_commit_diff() {
+    let mock_server = MockServer::start().await;
+
+    let large_diff = format!("@@ -1 +1 @@\n{}", "+x\n".repeat(100));
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/commits/abc123/diff",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "old_path": "src/main.rs",
+                "new_path": "src/main.rs",
+                "new_file": false,
+                "renamed_file": false,
+                "deleted_file": false,
+                "diff": "@@ -1 +1 @@\n-old\n+new\n"
+            },
+            {
+                "old_path": "data.txt",
+                "new_path": "data.txt",
+                "new_file": true,
+                "renamed_file": false,
+                "deleted_file": false,
+                "diff": large_diff
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "sha": "abc123", "max_diff_length": 50});
+    let result = registry
+        .execute("get_commit_diff", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let diff: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(diff["files_count"], 2);
+            assert_eq!(diff["files"][0]["new_path"], "src/main.rs");
+            assert_eq!(diff["files"][0]["truncated"], false);
+            assert_eq!(diff["files"][1]["truncated"], true);
+            assert!(
+                diff["files"][1]["diff"]
+                    .as_str()
+                    .unwrap()
+                    .ends_with("... (diff truncated)")
+            );
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_commit_with_multiple_actions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/commits"))
+        .and(body_json(json!({
+            "branch": "main",
+            "commit_message": "Add docs and config",
+            "actions": [
+                {"action": "create", "file_path": "docs/README.md", "content": "# Docs"},
+                {"action": "create", "file_path": "config.toml", "content": "key = 1"}
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "def456",
+            "short_id": "def456",
+            "title": "Add docs and config"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "branch": "main",
+        "commit_message": "Add docs and config",
+        "actions": [
+            {"action": "create", "file_path": "docs/README.md", "content": "# Docs"},
+            {"action": "create", "file_path": "config.toml", "content": "key = 1"}
+        ]
+    });
+    let result = registry.execute("create_commit", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("def456"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_commit_requires_content_for_create() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "branch": "main",
+        "commit_message": "Broken",
+        "actions": [{"action": "create", "file_path": "empty.txt"}]
+    });
+    let result = registry.execute("create_commit", &ctx, args).await;
+
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("content is required")
+    );
+}
+
+#[tokio::test]
+async fn test_list_commits_with_window_and_author() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/commits"))
+        .and(query_param("since", "2024-01-01T00:00:00Z"))
+        .and(query_param("until", "2024-01-31"))
+        .and(query_param("author", "alice@example.com"))
+        .and(query_param("path", "src/main.rs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": "abc123",
+                "short_id": "abc123",
+                "title": "Fix startup crash",
+                "author_email": "alice@example.com",
+                "committed_date": "2024-01-15T10:00:00Z"
+            }
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "since": "2024-01-01T00:00:00Z",
+        "until": "2024-01-31",
+        "author": "alice@example.com",
+        "path": "src/main.rs"
+    });
+    let result = registry.execute("list_commits", &ctx, args).await.unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            let commits = value.as_array().unwrap();
+            assert_eq!(commits.len(), 1);
+            assert_eq!(commits[0]["author_email"], "alice@example.com");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_commits_rejects_invalid_date() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "since": "last tuesday"});
+    let err = registry
+        .execute("list_commits", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, tanuki_mcp::error::ToolError::InvalidArguments(ref msg) if msg.contains("since"))
+    );
+}
+
+// ============================================================================
+// Project Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "name": "project",
+            "path_with_namespace": "test/project",
+            "description": "Test project",
+            "default_branch": "main",
+            "visibility": "private"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("get_project", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("test/project"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_projects() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "project1", "path_with_namespace": "group/project1"},
+            {"id": 2, "name": "project2", "path_with_namespace": "group/project2"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({});
+    let result = registry.execute("list_projects", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_project_languages() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/languages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Rust": 80.2,
+            "Shell": 19.8
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("get_project_languages", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["Rust"], 80.2);
+            assert_eq!(value["Shell"], 19.8);
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_project_statistics() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(query_param("statistics", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "project",
+            "path_with_namespace": "test/project",
+            "statistics": {
+                "commit_count": 37,
+                "storage_size": 1038090,
+                "repository_size": 1038090
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("get_project_statistics", &ctx, args)
+        .await
+        .unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["statistics"]["commit_count"], 37);
+            assert_eq!(value["path_with_namespace"], "test/project");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_add_project_badge() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/badges"))
+        .and(body_json(json!({
+            "link_url": "https://gitlab.example.com/%{project_path}/-/pipelines",
+            "image_url": "https://gitlab.example.com/%{project_path}/badges/%{default_branch}/pipeline.svg",
+            "name": "pipeline"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 7,
+            "name": "pipeline",
+            "link_url": "https://gitlab.example.com/%{project_path}/-/pipelines",
+            "image_url": "https://gitlab.example.com/%{project_path}/badges/%{default_branch}/pipeline.svg",
+            "rendered_link_url": "https://gitlab.example.com/test/project/-/pipelines",
+            "rendered_image_url": "https://gitlab.example.com/test/project/badges/main/pipeline.svg",
+            "kind": "project"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "link_url": "https://gitlab.example.com/%{project_path}/-/pipelines",
+        "image_url": "https://gitlab.example.com/%{project_path}/badges/%{default_branch}/pipeline.svg",
+        "name": "pipeline"
+    });
+    let result = registry
+        .execute("add_project_badge", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["id"], 7);
+            assert_eq!(value["kind"], "project");
+            assert_eq!(
+                value["rendered_image_url"],
+                "https://gitlab.example.com/test/project/badges/main/pipeline.svg"
+            );
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_project_badges() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/badges"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": 7,
+                "name": "pipeline",
+                "link_url": "https://example.com/pipelines",
+                "image_url": "https://example.com/pipeline.svg",
+                "kind": "project"
+            },
+            {
+                "id": 3,
+                "name": "coverage",
+                "link_url": "https://example.com/coverage",
+                "image_url": "https://example.com/coverage.svg",
+                "kind": "group"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_project_badges", &ctx, args)
+        .await
+        .unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            let badges = value.as_array().unwrap();
+            assert_eq!(badges.len(), 2);
+            assert_eq!(badges[0]["name"], "pipeline");
+            assert_eq!(badges[1]["kind"], "group");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_edit_project_visibility() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(body_json(json!({"visibility": "internal"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "path_with_namespace": "test/project",
+            "visibility": "internal"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "visibility": "internal"});
+    let result = registry.execute("edit_project", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("internal"));
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Unknown visibility levels are rejected before calling GitLab
+    let args = json!({"project": "test/project", "visibility": "secret"});
+    let result = registry.execute("edit_project", &ctx, args).await;
+    assert!(matches!(
+        result,
+        Err(tanuki_mcp::error::ToolError::InvalidArguments(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_edit_project_toggle_issues() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/42"))
+        .and(body_json(json!({"issues_enabled": false})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "issues_enabled": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "42", "issues_enabled": false});
+    let result = registry.execute("edit_project", &ctx, args).await.unwrap();
+    assert!(!result.is_error);
+
+    // An empty edit is rejected
+    let result = registry
+        .execute("edit_project", &ctx, json!({"project": "42"}))
+        .await;
+    assert!(matches!(
+        result,
+        Err(tanuki_mcp::error::ToolError::InvalidArguments(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_update_project_renames_and_edits_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(body_json(json!({
+            "name": "Renamed",
+            "visibility": "private",
+            "wiki_enabled": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "Renamed",
+            "visibility": "private"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let schema =
+        serde_json::to_value(&registry.get("update_project").unwrap().input_schema).unwrap();
+    assert!(schema["properties"]["visibility"].is_object());
+
+    let args = json!({
+        "project": "test/project",
+        "name": "Renamed",
+        "visibility": "private",
+        "wiki_enabled": true
+    });
+    let result = registry
+        .execute("update_project", &ctx, args)
+        .await
+        .unwrap();
+    assert_eq!(result.data.unwrap()["name"], "Renamed");
+}
+
+#[tokio::test]
+async fn test_get_project_ci_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "path_with_namespace": "test/project",
+            "description": "not a CI setting",
+            "auto_devops_enabled": false,
+            "ci_config_path": "ci/pipeline.yml",
+            "build_timeout": 3600,
+            "shared_runners_enabled": true
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("get_project_ci_settings", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let settings: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(settings["ci_config_path"], "ci/pipeline.yml");
+            assert_eq!(settings["build_timeout"], 3600);
+            assert!(settings.get("description").is_none());
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_update_project_ci_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(body_json(json!({"ci_config_path": "ci/pipeline.yml"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "path_with_namespace": "test/project",
+            "auto_devops_enabled": false,
+            "ci_config_path": "ci/pipeline.yml",
+            "build_timeout": 3600,
+            "shared_runners_enabled": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "ci_config_path": "ci/pipeline.yml"});
+    let result = registry
+        .execute("update_project_ci_settings", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let settings: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(settings["ci_config_path"], "ci/pipeline.yml");
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Nothing to change is rejected before GitLab is contacted
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("update_project_ci_settings", &ctx, args)
+        .await;
+    assert!(matches!(
+        result,
+        Err(tanuki_mcp::error::ToolError::InvalidArguments(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_get_merge_request_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "path_with_namespace": "test/project",
+            "ci_config_path": "not a merge setting",
+            "merge_method": "merge",
+            "squash_option": "default_off",
+            "only_allow_merge_if_pipeline_succeeds": false,
+            "remove_source_branch_after_merge": true
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("get_merge_request_settings", &ctx, args)
+        .await
+        .unwrap();
+
+    let settings = result.data.unwrap();
+    assert_eq!(settings["merge_method"], "merge");
+    assert_eq!(settings["squash_option"], "default_off");
+    assert!(settings.get("ci_config_path").is_none());
+}
+
+#[tokio::test]
+async fn test_update_merge_request_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(body_json(
+            json!({"merge_method": "ff", "squash_option": "always"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "path_with_namespace": "test/project",
+            "merge_method": "ff",
+            "squash_option": "always",
+            "only_allow_merge_if_pipeline_succeeds": false,
+            "remove_source_branch_after_merge": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "merge_method": "ff", "squash_option": "always"});
+    let result = registry
+        .execute("update_merge_request_settings", &ctx, args)
+        .await
+        .unwrap();
+
+    let settings = result.data.unwrap();
+    assert_eq!(settings["merge_method"], "ff");
+    assert_eq!(settings["squash_option"], "always");
+
+    // Unknown merge methods are rejected before GitLab is contacted
+    let args = json!({"project": "test/project", "merge_method": "octopus"});
+    assert!(
+        registry
+            .execute("update_merge_request_settings", &ctx, args)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_set_project_topics() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(body_json(json!({"topics": ["rust", "mcp"]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "path_with_namespace": "test/project",
+            "description": "not a topic",
+            "topics": ["rust", "mcp"]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "topics": ["rust", "mcp"]});
+    let result = registry
+        .execute("set_project_topics", &ctx, args)
+        .await
+        .unwrap();
+
+    let topics = result.data.unwrap();
+    assert_eq!(topics["topics"], json!(["rust", "mcp"]));
+    assert!(topics.get("description").is_none());
+}
+
+#[tokio::test]
+async fn test_fork_project_waits_for_import() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/fork"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 99,
+            "path_with_namespace": "alice/project",
+            "import_status": "scheduled"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/99"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"id": 99, "import_status": "started"})),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/99"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"id": 99, "import_status": "finished"})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Polls must not be answered from the project cache
+    let gitlab = create_cached_test_gitlab(&mock_server, 300);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_poll_config(PollConfig {
+        max_attempts: 5,
+        delay: std::time::Duration::from_millis(10),
+    });
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "wait": true});
+    let result = registry.execute("fork_project", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    assert_eq!(result.data.unwrap()["import_status"], "finished");
+    let polls = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.method.as_str() == "GET")
+        .count();
+    assert_eq!(polls, 2);
+}
+
+// ============================================================================
+// Pipeline Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_pipelines() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/pipelines"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 100, "status": "success", "ref": "main", "sha": "abc123"},
+            {"id": 99, "status": "failed", "ref": "feature", "sha": "def456"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_pipelines", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_pipeline() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/pipelines/100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 100,
+            "status": "success",
+            "ref": "main",
+            "sha": "abc123def456",
+            "created_at": "2024-01-15T10:00:00Z",
+            "finished_at": "2024-01-15T10:05:00Z"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "pipeline_id": 100});
+    let result = registry.execute("get_pipeline", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_pipeline_test_report() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/pipelines/42/test_report"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_time": 12.5,
+            "total_count": 4,
+            "success_count": 1,
+            "failed_count": 2,
+            "skipped_count": 0,
+            "error_count": 1,
+            "test_suites": [{
+                "name": "rspec",
+                "total_count": 4,
+                "success_count": 1,
+                "failed_count": 2,
+                "skipped_count": 0,
+                "error_count": 1,
+                "test_cases": [
+                    {"status": "success", "name": "passes", "classname": "spec.a"},
+                    {"status": "failed", "name": "rejects empty input", "classname": "spec.b", "system_output": "expected error"},
+                    {"status": "failed", "name": "parses dates", "classname": "spec.c", "system_output": "bad date"},
+                    {"status": "error", "name": "loads fixture", "classname": "spec.d", "system_output": "missing file"}
+                ]
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "pipeline_id": 42, "max_failed_tests": 2});
+    let result = registry
+        .execute("get_pipeline_test_report", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("rejects empty input"));
+            assert!(text.contains("expected error"));
+            assert!(text.contains("parses dates"));
+            assert!(!text.contains("loads fixture"));
+            assert!(!text.contains("\"passes\""));
+            assert!(text.contains("\"failed_tests_truncated\": true"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+/// Build a zip archive storing `files` uncompressed
+fn stored_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut archive = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in files {
+        let offset = archive.len() as u32;
+        let size = (data.len() as u32).to_le_bytes();
+        let name_len = (name.len() as u16).to_le_bytes();
+
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&[0; 14]);
+        archive.extend_from_slice(&size);
+        archive.extend_from_slice(&size);
+        archive.extend_from_slice(&name_len);
+        archive.extend_from_slice(&[0; 2]);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(data);
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&[0; 16]);
+        directory.extend_from_slice(&size);
+        directory.extend_from_slice(&size);
+        directory.extend_from_slice(&name_len);
+        directory.extend_from_slice(&[0; 12]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = archive.len() as u32;
+    let count = (files.len() as u16).to_le_bytes();
+    archive.extend_from_slice(&directory);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]);
+    archive.extend_from_slice(&count);
+    archive.extend_from_slice(&count);
+    archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&directory_offset.to_le_bytes());
+    archive.extend_from_slice(&[0; 2]);
+    archive
+}
+
+#[tokio::test]
+async fn test_list_job_artifacts() {
+    let mock_server = MockServer::start().await;
+
+    let archive = stored_zip(&[
+        ("coverage/", b""),
+        ("coverage/summary.txt", b"Lines: 87.5%\n"),
+        ("bin/app", &[0x7f, b'E', 0x00, 0xff]),
+    ]);
+    let len = archive.len();
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/jobs/7/artifacts"))
+        .and(header("range", "bytes=-65557"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-range", format!("bytes 0-{}/{}", len - 1, len))
+                .set_body_bytes(archive),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "job_id": 7});
+    let result = registry
+        .execute("list_job_artifacts", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let data = result.data.unwrap();
+    assert_eq!(data["archive_size"], len);
+    assert_eq!(data["file_count"], 2);
+    assert_eq!(
+        data["files"],
+        json!([
+            {"path": "coverage/summary.txt", "size": 13, "compressed_size": 13},
+            {"path": "bin/app", "size": 4, "compressed_size": 4}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_list_job_artifacts_rejects_directory_outside_archive() {
+    let mock_server = MockServer::start().await;
+
+    // Point the central directory far past the end of the archive
+    let mut archive = stored_zip(&[("notes.txt", b"hello")]);
+    let len = archive.len();
+    archive[len - 6..len - 2].copy_from_slice(&u32::MAX.wrapping_sub(1).to_le_bytes());
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/jobs/7/artifacts"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-range", format!("bytes 0-{}/{}", len - 1, len))
+                .set_body_bytes(archive),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "job_id": 7});
+    let error = registry
+        .execute("list_job_artifacts", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("outside the"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_get_job_artifact_file_text() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/jobs/7/artifacts/coverage/summary.txt",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Lines: 87.5%\n"))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "job_id": 7,
+        "artifact_path": "coverage/summary.txt"
+    });
+    let result = registry
+        .execute("get_job_artifact_file", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert_eq!(text, "Lines: 87.5%\n");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_job_artifact_file_binary() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/jobs/7/artifacts/bin/app",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x7f, b'E', 0x00, 0xff]))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "job_id": 7,
+        "artifact_path": "bin/app"
+    });
+    let result = registry
+        .execute("get_job_artifact_file", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("\"encoding\": \"base64\""));
+            assert!(text.contains("\"content\": \"f0UA/w==\""));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_job_artifact_file_too_large() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/jobs/7/artifacts/big.log",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(100)))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "job_id": 7,
+        "artifact_path": "big.log",
+        "max_size_bytes": 10
+    });
+    let err = registry
+        .execute("get_job_artifact_file", &ctx, args)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("size limit"));
+}
+
+#[tokio::test]
+async fn test_get_job_artifacts_size_is_capped() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/jobs/7/artifacts"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 16 * 1024 * 1024 + 1]))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // A larger limit from the caller does not raise the server's ceiling
+    let args = json!({"project": "test/project", "job_id": 7, "max_size_bytes": u64::MAX});
+    let err = registry
+        .execute("get_job_artifacts", &ctx, args)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("size limit of 16777216 bytes"));
+}
+
+#[tokio::test]
+async fn test_lint_ci_yaml_valid_project_config() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/ci/lint"))
+        .and(body_json(json!({"content": "test:\n  script: echo ok\n"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "valid": true,
+            "errors": [],
+            "warnings": [],
+            "merged_yaml": "---\ntest:\n  script: echo ok\n"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "content": "test:\n  script: echo ok\n"});
+    let result = registry.execute("lint_ci_yaml", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["valid"], true);
+            assert_eq!(value["errors"], json!([]));
+            assert_eq!(value["warnings"], json!([]));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_lint_ci_yaml_project_is_access_controlled() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/public%2Fapp/ci/lint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "valid": true,
+            "errors": [],
+            "warnings": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // The optional `project` is checked against project rules when given
+    let mut config = AccessControlConfig {
+        all: AccessLevel::Read,
+        ..Default::default()
+    };
+    config.projects.insert(
+        "secret/app".to_string(),
+        ProjectAccessConfig {
+            all: Some(AccessLevel::Deny),
+            ..Default::default()
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&config).unwrap());
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let content = "test:\n  script: echo ok\n";
+    let result = registry
+        .execute(
+            "lint_ci_yaml",
+            &ctx,
+            json!({"project": "secret/app", "content": content}),
+        )
+        .await;
+    assert!(matches!(result, Err(ToolError::AccessDenied(_))));
+
+    let result = registry
+        .execute(
+            "lint_ci_yaml",
+            &ctx,
+            json!({"project": "public/app", "content": content}),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_lint_ci_yaml_standalone_syntax_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/ci/lint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "invalid",
+            "errors": ["(<unknown>): did not find expected key while parsing a block mapping at line 1 column 1"],
+            "warnings": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"content": "test:\n  script: [echo\n"});
+    let result = registry.execute("lint_ci_yaml", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["valid"], false);
+            assert!(
+                value["errors"][0]
+                    .as_str()
+                    .unwrap()
+                    .contains("did not find expected key")
+            );
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_pipeline_bridges() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/pipelines/100/bridges",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 501,
+            "name": "trigger-child",
+            "status": "success",
+            "pipeline": {"id": 100},
+            "downstream_pipeline": {
+                "id": 200,
+                "project_id": 7,
+                "status": "success",
+                "web_url": "https://gitlab.example.com/test/project/-/pipelines/200"
+            }
+        }])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "pipeline_id": 100});
+    let result = registry
+        .execute("list_pipeline_bridges", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let bridges = result.data.unwrap();
+    assert_eq!(bridges[0]["name"], "trigger-child");
+    assert_eq!(bridges[0]["downstream_pipeline"]["id"], 200);
+}
+
+#[tokio::test]
+async fn test_list_pipeline_bridges_encodes_scopes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/pipelines/100/bridges",
+        ))
+        .and(query_param("scope[]", "failed&per_page=1"))
+        .and(query_param("per_page", "20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "pipeline_id": 100,
+        "scope": ["failed&per_page=1"],
+        "per_page": 20
+    });
+    let result = registry
+        .execute("list_pipeline_bridges", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+// ============================================================================
+// Access Control Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_tool_access_denied() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+
+    // Create read-only access
+    let mut policy = AccessControlConfig::default();
+    policy.all = AccessLevel::Read;
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // Write operation should be denied
+    let args = json!({
+        "project": "test/project",
+        "title": "New Issue"
+    });
+    let result = registry.execute("create_issue", &ctx, args).await;
+
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("denied") || err.to_string().contains("Access"));
+}
+
+#[tokio::test]
+async fn test_tool_not_found_error() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({});
+    let result = registry.execute("nonexistent_tool", &ctx, args).await;
+
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("not found") || err.to_string().contains("Not Found"));
+}
+
+#[tokio::test]
+async fn test_tool_invalid_arguments() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // Missing required 'project' field
+    let args = json!({"invalid_field": "value"});
+    let result = registry.execute("list_issues", &ctx, args).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_numeric_project_id_resolved_for_project_rules() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "path_with_namespace": "prod/app"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/42/issues"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"iid": 1})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut config = AccessControlConfig {
+        all: AccessLevel::Full,
+        resolve_numeric_ids: true,
+        ..Default::default()
+    };
+    config.projects.insert(
+        "prod/app".to_string(),
+        ProjectAccessConfig {
+            all: Some(AccessLevel::Read),
+            ..Default::default()
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&config).unwrap());
+
+    // The response cache serves the second lookup
+    let gitlab = create_cached_test_gitlab(&mock_server, 60);
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    for _ in 0..2 {
+        let result = registry
+            .execute(
+                "create_issue",
+                &ctx,
+                json!({"project": "42", "title": "Should be denied"}),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(tanuki_mcp::error::ToolError::AccessDenied(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_renamed_project_id_resolves_to_new_path() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "path_with_namespace": "a/app"
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "path_with_namespace": "other/app"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/42/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_cached_test_gitlab(&mock_server, 60);
+    let access = create_full_access();
+    let ctx =
+        create_test_context(gitlab.clone(), access).with_allowed_projects(allowlist(&["a/*"]));
+    let registry = create_registry();
+
+    let args = json!({"project": "42"});
+    registry
+        .execute("list_issues", &ctx, args.clone())
+        .await
+        .unwrap();
+
+    // The project is transferred, and a webhook drops its cached entries
+    gitlab.cache().invalidate_project("42");
+    let error = registry
+        .execute("list_issues", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ToolError::AccessDenied(_)));
+    assert!(error.to_string().contains("other/app"));
+}
+
+// ============================================================================
+// Label Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_labels() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "bug", "color": "#FF0000"},
+            {"id": 2, "name": "feature", "color": "#00FF00"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("list_labels", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_promote_project_label() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/labels/needs%20review/promote",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "name": "needs review",
+            "color": "#FF0000",
+            "group_id": 7
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "label_id": "needs review"});
+    let result = registry
+        .execute("promote_project_label", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let label: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(label["id"], 42);
+            assert_eq!(label["group_id"], 7);
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_labels_forwards_pagination() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/labels"))
+        .and(query_param("page", "3"))
+        .and(query_param("per_page", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    // per_page above GitLab's maximum is capped at 100
+    let args = json!({"project": "test/project", "page": 3, "per_page": 500});
+    let result = registry.execute("list_labels", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_create_prioritized_label() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/labels"))
+        .and(body_json(json!({
+            "name": "incident",
+            "color": "crimson",
+            "description": "Production incidents",
+            "priority": 1
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 9,
+            "name": "incident",
+            "color": "#DC143C",
+            "description": "Production incidents",
+            "priority": 1
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "name": "incident",
+        "color": "crimson",
+        "description": "Production incidents",
+        "priority": 1
+    });
+    let result = registry.execute("create_label", &ctx, args).await.unwrap();
+    assert_eq!(result.data.unwrap()["priority"], 1);
+
+    // Malformed colors are rejected before calling GitLab
+    for color in ["#FF00", "FF0000", "not-a-color"] {
+        let args = json!({"project": "test/project", "name": "bad", "color": color});
+        let error = registry
+            .execute("create_label", &ctx, args)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ToolError::InvalidArguments(_)), "{}", color);
+    }
+}
+
+#[tokio::test]
+async fn test_list_labels_with_counts() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/labels"))
+        .and(query_param("with_counts", "true"))
+        .and(query_param("include_ancestor_groups", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "bug", "open_issues_count": 4, "closed_issues_count": 10, "open_merge_requests_count": 1}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "with_counts": true,
+        "include_ancestor_groups": true
+    });
+    let result = registry.execute("list_labels", &ctx, args).await.unwrap();
+    assert_eq!(result.data.unwrap()[0]["open_issues_count"], 4);
+}
+
+// ============================================================================
+// Wiki Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_wiki_pages() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/wikis"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"slug": "home", "title": "Home", "format": "markdown"},
+            {"slug": "getting-started", "title": "Getting Started", "format": "markdown"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_wiki_pages", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_upload_wiki_attachment() {
+    use base64::Engine;
+    use wiremock::matchers::{body_string_contains, header_regex};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/wikis/attachments"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=.+$",
+        ))
+        .and(body_string_contains(
+            "Content-Disposition: form-data; name=\"file\"; filename=\"diagram.png\"",
+        ))
+        .and(body_string_contains("PNGDATA"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "file_name": "diagram.png",
+            "file_path": "uploads/abc123/diagram.png",
+            "branch": "main",
+            "link": {
+                "url": "uploads/abc123/diagram.png",
+                "markdown": "![diagram](uploads/abc123/diagram.png)"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_name": "diagram.png",
+        "content": base64::engine::general_purpose::STANDARD.encode(b"PNGDATA")
+    });
+    let result = registry
+        .execute("upload_wiki_attachment", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("![diagram](uploads/abc123/diagram.png)"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_upload_wiki_attachment_rejects_invalid_base64() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "file_name": "diagram.png",
+        "content": "not base64!"
+    });
+    let result = registry.execute("upload_wiki_attachment", &ctx, args).await;
+
+    assert!(result.unwrap_err().to_string().contains("base64"));
+}
+
+#[tokio::test]
+async fn test_list_wiki_pages_forwards_pagination() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/wikis"))
+        .and(query_param("page", "2"))
+        .and(query_param("per_page", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/wikis"))
+        .and(query_param_is_missing("page"))
+        .and(query_param_is_missing("per_page"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "page": 2, "per_page": 10});
+    let result = registry
+        .execute("list_wiki_pages", &ctx, args)
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+
+    // Without pagination arguments GitLab's defaults apply
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_wiki_pages", &ctx, args)
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+}
+
+// ============================================================================
+// Milestone Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_milestones() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/milestones"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "iid": 1, "title": "v1.0", "state": "active"},
+            {"id": 2, "iid": 2, "title": "v2.0", "state": "active"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_milestones", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_create_group_milestone() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/groups/my-group/milestones"))
+        .and(body_json(
+            json!({"title": "Q3 Release", "due_date": "2024-09-30"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 12,
+            "iid": 3,
+            "group_id": 5,
+            "title": "Q3 Release",
+            "due_date": "2024-09-30",
+            "state": "active"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"group": "my-group", "title": "Q3 Release", "due_date": "2024-09-30"});
+    let result = registry
+        .execute("create_group_milestone", &ctx, args)
+        .await
+        .unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("\"group_id\": 5"));
+            assert!(text.contains("Q3 Release"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_group_milestones() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups/parent%2Fchild/milestones"))
+        .and(query_param("state", "active"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 12, "group_id": 5, "title": "Q3 Release", "state": "active"},
+            {"id": 13, "group_id": 5, "title": "Q4 Release", "state": "active"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"group": "parent/child", "state": "active"});
+    let result = registry
+        .execute("list_group_milestones", &ctx, args)
+        .await
+        .unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("Q3 Release"));
+            assert!(text.contains("Q4 Release"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+// ============================================================================
+// Tag Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_tags() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "name": "v1.0.0",
+                "message": "Release v1.0.0",
+                "target": "abc123",
+                "commit": {"id": "abc123", "message": "Initial release"}
+            },
+            {
+                "name": "v1.1.0",
+                "message": "Release v1.1.0",
+                "target": "def456",
+                "commit": {"id": "def456", "message": "Minor update"}
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("list_tags", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("v1.0.0"));
+            assert!(text.contains("v1.1.0"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_tags_with_filters() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
+        .and(query_param("order_by", "updated"))
+        .and(query_param("sort", "desc"))
+        .and(query_param("search", "v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "order_by": "updated",
+        "sort": "desc",
+        "search": "v1"
+    });
+    let result = registry.execute("list_tags", &ctx, args).await.unwrap();
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/tags/v1.0.0",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "v1.0.0",
+            "message": "Release v1.0.0",
+            "target": "abc123def456",
+            "commit": {
+                "id": "abc123def456",
+                "message": "Initial release",
+                "author_name": "Developer"
+            },
+            "release": {
+                "tag_name": "v1.0.0",
+                "description": "First stable release"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "tag_name": "v1.0.0"});
+    let result = registry.execute("get_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("v1.0.0"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
+        .and(body_json(json!({
+            "tag_name": "v2.0.0",
+            "ref": "main",
+            "message": "Release v2.0.0"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "v2.0.0",
+            "message": "Release v2.0.0",
+            "target": "abc123"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "tag_name": "v2.0.0",
+        "ref_name": "main",
+        "message": "Release v2.0.0"
+    });
+    let result = registry.execute("create_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_create_lightweight_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
+        .and(body_json(json!({
+            "tag_name": "v2.0.1",
+            "ref": "main"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "v2.0.1",
+            "message": null,
+            "target": "abc123"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/releases"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "tag_name": "v2.0.1",
+        "ref_name": "main"
+    });
+    let result = registry.execute("create_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_create_tag_with_release() {
+    let mock_server = MockServer::start().await;
+
+    // Tag and release are created in one call
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/repository/tags"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/releases"))
+        .and(body_json(json!({
+            "tag_name": "v3.0.0",
+            "ref": "main",
+            "tag_message": "Version 3",
+            "description": "## Changes\n- Everything"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "tag_name": "v3.0.0",
+            "name": "v3.0.0",
+            "description": "## Changes\n- Everything",
+            "commit": {"id": "def456"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "tag_name": "v3.0.0",
+        "ref_name": "main",
+        "message": "Version 3",
+        "release_description": "## Changes\n- Everything"
+    });
+    let result = registry.execute("create_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let tag: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(tag["name"], "v3.0.0");
+            assert_eq!(tag["commit"]["id"], "def456");
+            assert_eq!(tag["release"]["tag_name"], "v3.0.0");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_tag_with_release_requires_release_access() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut policy = AccessControlConfig {
+        all: AccessLevel::Full,
+        ..Default::default()
+    };
+    policy.categories.insert(
+        "releases".to_string(),
+        CategoryAccessConfig {
+            level: AccessLevel::Read,
+            deny: vec![],
+            allow: vec![],
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "tag_name": "v3.0.0",
+        "ref_name": "main",
+        "release_description": "Notes"
+    });
+    let result = registry.execute("create_tag", &ctx, args).await;
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("cannot create release"));
+}
+
+#[tokio::test]
+async fn test_create_tag_with_release_checks_resolved_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "path_with_namespace": "prod/app"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    // Releases are read-only in prod/app, which the caller names by ID
+    let mut policy = AccessControlConfig {
+        all: AccessLevel::Full,
+        resolve_numeric_ids: true,
+        ..Default::default()
+    };
+    let mut project = ProjectAccessConfig::default();
+    project.categories.insert(
+        "releases".to_string(),
+        CategoryAccessConfig {
+            level: AccessLevel::Read,
+            deny: vec![],
+            allow: vec![],
+        },
+    );
+    policy.projects.insert("prod/app".to_string(), project);
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "42",
+        "tag_name": "v3.0.0",
+        "ref_name": "main",
+        "release_description": "Notes"
+    });
+    let err = registry
+        .execute("create_tag", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot create release"));
+}
+
+#[tokio::test]
+async fn test_delete_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/tags/v1.0.0",
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "tag_name": "v1.0.0"});
+    let result = registry.execute("delete_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("deleted"));
+            assert!(text.contains("v1.0.0"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_protected_tags() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/protected_tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"name": "v*", "create_access_levels": [{"access_level": 40}]},
+            {"name": "release-*", "create_access_levels": [{"access_level": 30}]}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry
+        .execute("list_protected_tags", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_get_protected_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/protected_tags/v%2A"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "v*",
+            "create_access_levels": [{"access_level": 40, "access_level_description": "Maintainers"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "name": "v*"});
+    let result = registry
+        .execute("get_protected_tag", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_protect_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/protected_tags"))
+        .and(body_json(json!({
+            "name": "release-*",
+            "create_access_level": 40
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "release-*",
+            "create_access_levels": [{"access_level": 40}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "name": "release-*",
+        "create_access_level": 40
+    });
+    let result = registry.execute("protect_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_unprotect_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/projects/test%2Fproject/protected_tags/v%2A"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "name": "v*"});
+    let result = registry.execute("unprotect_tag", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("unprotected"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_release_link() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/releases/v1.0.0/assets/links",
+        ))
+        .and(body_json(json!({
+            "name": "linux-amd64",
+            "url": "https://example.com/app-linux-amd64.tar.gz",
+            "link_type": "package"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 7,
+            "name": "linux-amd64",
+            "url": "https://example.com/app-linux-amd64.tar.gz",
+            "direct_asset_url": "https://gitlab.com/test/project/-/releases/v1.0.0/downloads/app",
+            "link_type": "package",
+            "external": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "tag_name": "v1.0.0",
+        "name": "linux-amd64",
+        "url": "https://example.com/app-linux-amd64.tar.gz",
+        "link_type": "package"
+    });
+    let result = registry
+        .execute("create_release_link", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let link: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(link["id"], 7);
+            assert_eq!(link["link_type"], "package");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_release_links() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/releases/v1.0.0/assets/links",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": 1,
+                "name": "docs",
+                "url": "https://example.com/docs",
+                "link_type": "runbook"
+            },
+            {
+                "id": 2,
+                "name": "binary",
+                "url": "https://example.com/bin",
+                "link_type": "package"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "tag_name": "v1.0.0"});
+    let result = registry
+        .execute("list_release_links", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let links: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(links.as_array().unwrap().len(), 2);
+            assert_eq!(links[1]["name"], "binary");
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+// ============================================================================
+// Search Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_search_global() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/search"))
+        .and(query_param("scope", "projects"))
+        .and(query_param("search", "gitlab"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "tanuki-mcp", "path_with_namespace": "user/tanuki-mcp"},
+            {"id": 2, "name": "gitlab-runner", "path_with_namespace": "org/gitlab-runner"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "scope": "projects",
+        "search": "gitlab"
+    });
+    let result = registry.execute("search_global", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("tanuki-mcp"));
+            assert!(text.contains("gitlab-runner"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_search_global_issues() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/search"))
+        .and(query_param("scope", "issues"))
+        .and(query_param("search", "bug"))
+        .and(query_param("state", "opened"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "iid": 10, "title": "Critical bug", "state": "opened"},
+            {"id": 2, "iid": 20, "title": "Minor bug fix", "state": "opened"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "scope": "issues",
+        "search": "bug",
+        "state": "opened"
+    });
+    let result = registry.execute("search_global", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_search_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/search"))
+        .and(query_param("scope", "blobs"))
+        .and(query_param("search", "TODO"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "basename": "main.rs",
+                "data": "// TODO: implement this",
+                "path": "src/main.rs",
+                "filename": "main.rs",
+                "ref": "main"
+            },
+            {
+                "basename": "lib.rs",
+                "data": "// TODO: add tests",
+                "path": "src/lib.rs",
+                "filename": "lib.rs",
+                "ref": "main"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "scope": "blobs",
+        "search": "TODO"
+    });
+    let result = registry
+        .execute("search_project", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("main.rs"));
+            assert!(text.contains("lib.rs"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_search_project_with_ref() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/search"))
+        .and(query_param("scope", "commits"))
+        .and(query_param("search", "fix"))
+        .and(query_param("ref", "develop"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": "abc123", "title": "Fix bug", "author_name": "Developer"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "scope": "commits",
+        "search": "fix",
+        "ref_name": "develop"
+    });
+    let result = registry
+        .execute("search_project", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_search_group() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups/mygroup/search"))
+        .and(query_param("scope", "merge_requests"))
+        .and(query_param("search", "feature"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": 1,
+                "iid": 5,
+                "title": "Add new feature",
+                "state": "merged",
+                "source_branch": "feature-x"
+            },
+            {
+                "id": 2,
+                "iid": 10,
+                "title": "Feature improvement",
+                "state": "opened",
+                "source_branch": "feature-y"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "group": "mygroup",
+        "scope": "merge_requests",
+        "search": "feature"
+    });
+    let result = registry.execute("search_group", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("Add new feature"));
+            assert!(text.contains("Feature improvement"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_search_group_with_state_filter() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups/mygroup/search"))
+        .and(query_param("scope", "issues"))
+        .and(query_param("search", "critical"))
+        .and(query_param("state", "opened"))
+        .and(query_param("confidential", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "group": "mygroup",
+        "scope": "issues",
+        "search": "critical",
+        "state": "opened",
+        "confidential": true
+    });
+    let result = registry.execute("search_group", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+// ============================================================================
+// User Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_user_projects() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/users/7/projects"))
+        .and(query_param("archived", "false"))
+        .and(query_param("per_page", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "path_with_namespace": "alice/app"},
+            {"id": 2, "path_with_namespace": "alice/lib"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"user_id": 7, "archived": false, "per_page": 50});
+    let result = registry
+        .execute("list_user_projects", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let projects = result.data.unwrap();
+    assert_eq!(projects.as_array().unwrap().len(), 2);
+    assert_eq!(projects[1]["path_with_namespace"], "alice/lib");
+}
+
+#[tokio::test]
+async fn test_list_user_memberships_requires_admin() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/users/7/memberships"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({"message": "403 Forbidden"})))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let error = registry
+        .execute("list_user_memberships", &ctx, json!({"user_id": 7}))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("administrator"));
+}
+
+#[tokio::test]
+async fn test_get_gitlab_metadata() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/metadata"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "version": "17.5.1",
+            "revision": "abc123",
+            "kas": {"enabled": false},
+            "enterprise": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    for _ in 0..2 {
+        let result = registry
+            .execute("get_gitlab_metadata", &ctx, json!({}))
+            .await
+            .unwrap();
+        let metadata = result.data.unwrap();
+        assert_eq!(metadata["version"], "17.5.1");
+        assert_eq!(metadata["edition"], "EE");
+        assert_eq!(metadata["enterprise"], true);
+    }
+}
+
+#[tokio::test]
+async fn test_gitlab_metadata_failure_is_cached() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/metadata"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    for _ in 0..2 {
+        let result = registry
+            .execute("get_gitlab_metadata", &ctx, json!({}))
+            .await;
+        assert!(result.is_err());
+    }
+}
+
+// ============================================================================
+// Group Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_add_group_member() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/groups/my-group%2Fsub/members"))
+        .and(body_json(json!({"user_id": 7, "access_level": 30})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 7,
+            "username": "alice",
+            "access_level": 30
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"group": "my-group/sub", "user_id": 7, "access_level": "developer"});
+    let result = registry
+        .execute("add_group_member", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    assert_eq!(result.data.unwrap()["username"], "alice");
+}
+
+#[tokio::test]
+async fn test_list_group_members() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups/my-group/members"))
+        .and(query_param("query", "ali"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 7, "username": "alice", "access_level": 30}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"group": "my-group", "query": "ali"});
+    let result = registry
+        .execute("list_group_members", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    assert_eq!(result.data.unwrap()[0]["access_level"], 30);
+}
+
+#[tokio::test]
+async fn test_list_group_epics_rejected_on_ce() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/metadata"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "version": "17.5.1",
+            "revision": "abc123",
+            "enterprise": false
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/groups/acme/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let error = registry
+        .execute("list_group_epics", &ctx, json!({"group": "acme"}))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ToolError::UnsupportedEdition { .. }));
+    assert!(
+        error
+            .to_string()
+            .contains("Unsupported on this GitLab edition")
+    );
+}
+
+// ============================================================================
+// Environment Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_stop_environment() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/environments/12/stop"))
+        .and(body_json(json!({})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 12,
+            "name": "review/feature-x",
+            "state": "stopping"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "environment_id": 12});
+    let result = registry
+        .execute("stop_environment", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    let environment = result.data.unwrap();
+    assert_eq!(environment["name"], "review/feature-x");
+    assert_eq!(environment["state"], "stopping");
+}
+
+#[tokio::test]
+async fn test_delete_environment() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/projects/test%2Fproject/environments/12"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "environment_id": 12});
+    let result = registry
+        .execute("delete_environment", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+// ============================================================================
+// Runner Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_project_runners() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/runners"))
+        .and(query_param("status", "online"))
+        .and(query_param("tag_list", "docker,linux"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 6,
+            "description": "docker runner",
+            "runner_type": "instance_type",
+            "status": "online",
+            "online": true,
+            "paused": false
+        }])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/runners/6"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 6,
+            "status": "online",
+            "online": true,
+            "tag_list": ["docker", "linux"],
+            "run_untagged": false
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "status": "online",
+        "tag_list": ["docker", "linux"]
+    });
+    let result = registry
+        .execute("list_project_runners", &ctx, args)
+        .await
+        .unwrap();
+    let runners = result.data.unwrap();
+    assert_eq!(runners[0]["id"], 6);
+    assert_eq!(runners[0]["online"], true);
+
+    let result = registry
+        .execute("get_runner", &ctx, json!({"runner_id": 6}))
+        .await
+        .unwrap();
+    assert_eq!(result.data.unwrap()["tag_list"], json!(["docker", "linux"]));
+}
+
+// ============================================================================
+// Deploy Key Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_add_deploy_key() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/deploy_keys"))
+        .and(body_json(json!({
+            "title": "CI",
+            "key": "ssh-ed25519 AAAAC3Nza deploy@ci",
+            "can_push": true
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 12,
+            "title": "CI",
+            "key": "ssh-ed25519 AAAAC3Nza deploy@ci",
+            "fingerprint_sha256": "SHA256:abc",
+            "can_push": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "title": "CI",
+        "key": "ssh-ed25519 AAAAC3Nza deploy@ci",
+        "can_push": true
+    });
+    let result = registry
+        .execute("add_deploy_key", &ctx, args)
+        .await
+        .unwrap();
+
+    let key = result.data.unwrap();
+    assert_eq!(key["id"], 12);
+    assert_eq!(key["fingerprint_sha256"], "SHA256:abc");
+    assert!(key.get("key").is_none());
+}
+
+#[tokio::test]
+async fn test_list_deploy_keys_omits_key_material() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/deploy_keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 12, "title": "CI", "key": "ssh-ed25519 AAAA1", "can_push": true},
+            {"id": 13, "title": "Deploy", "key": "ssh-ed25519 AAAA2", "can_push": false}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let result = registry
+        .execute("list_deploy_keys", &ctx, json!({"project": "test/project"}))
+        .await
+        .unwrap();
+
+    let keys = result.data.unwrap();
+    assert_eq!(keys.as_array().unwrap().len(), 2);
+    assert_eq!(keys[1]["title"], "Deploy");
+    assert!(
+        keys.as_array()
+            .unwrap()
+            .iter()
+            .all(|key| key.get("key").is_none())
+    );
+    assert!(
+        !result
+            .content
+            .iter()
+            .any(|block| format!("{:?}", block).contains("AAAA"))
+    );
+}
+
+// ============================================================================
+// Access Token Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_create_project_access_tokens() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/access_tokens"))
+        .and(body_json(json!({
+            "name": "ci-bot",
+            "scopes": ["read_api", "read_repository"],
+            "expires_at": "2026-12-31",
+            "access_level": 30
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 42,
+            "name": "ci-bot",
+            "scopes": ["read_api", "read_repository"],
+            "access_level": 30,
+            "expires_at": "2026-12-31",
+            "token": "glpat-secret"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "name": "ci-bot",
+        "scopes": ["read_api", "read_repository"],
+        "expires_at": "2026-12-31",
+        "access_level": "developer"
+    });
+    let result = registry
+        .execute("create_project_access_tokens", &ctx, args)
+        .await
+        .unwrap();
+
+    let token = result.data.unwrap();
+    assert_eq!(token["id"], 42);
+    assert_eq!(token["token"], "glpat-secret");
+    assert!(token["notice"].as_str().unwrap().contains("not show"));
+}
+
+#[tokio::test]
+async fn test_create_project_access_tokens_requires_scopes() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "name": "ci-bot",
+        "scopes": [],
+        "expires_at": "2026-12-31"
+    });
+    let error = registry
+        .execute("create_project_access_tokens", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ToolError::InvalidArguments(_)));
+}
+
+#[tokio::test]
+async fn test_revoke_project_access_token() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/projects/test%2Fproject/access_tokens/42"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "token_id": 42});
+    let result = registry
+        .execute("revoke_project_access_token", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => assert!(text.contains("revoked")),
+        _ => panic!("Expected text content"),
+    }
+}
+
+// ============================================================================
+// Raw Request Tools Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_gitlab_request_arbitrary_path() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/123/badges"))
+        .and(query_param("name", "coverage"))
+        .and(query_param("page", "2"))
+        .and(query_param("per_page", "100"))
+        .and(header("PRIVATE-TOKEN", "test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "coverage", "kind": "project"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "method": "get",
+        "path": "/projects/123/badges",
+        "query": {"name": "coverage"},
+        "page": 2,
+        "per_page": 500
+    });
+    let result = registry
+        .execute("gitlab_request", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            assert!(text.contains("coverage"));
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_gitlab_request_encodes_query_keys() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/123/badges"))
+        .and(query_param("name&sudo=root", "x"))
+        .and(query_param_is_missing("sudo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "method": "GET",
+        "path": "/projects/123/badges",
+        "query": {"name&sudo=root": "x"}
+    });
+    let result = registry
+        .execute("gitlab_request", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_gitlab_request_with_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/123/badges"))
+        .and(body_json(json!({"link_url": "https://example.com"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 7})))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "method": "POST",
+        "path": "/projects/123/badges",
+        "body": {"link_url": "https://example.com"}
+    });
+    let result = registry
+        .execute("gitlab_request", &ctx, args)
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_gitlab_request_rejects_invalid_path() {
+    let mock_server = MockServer::start().await;
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    for bad_path in [
+        "projects/1",
+        "/projects/../users",
+        "/%2e%2e/%2e%2e/admin",
+        "/projects/.%2e/users",
+        "/projects/%2E./users",
+        "/projects/%2e/1",
+        "https://evil.example/api",
+    ] {
+        let args = json!({"method": "GET", "path": bad_path});
+        let result = registry.execute("gitlab_request", &ctx, args).await;
+        assert!(result.is_err(), "path {} should be rejected", bad_path);
+    }
+}
+
+// ============================================================================
+// Request Correlation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_gitlab_requests_carry_request_id() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .and(header("x-request-id", "test-request-123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "path_with_namespace": "test/project"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("get_project", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers["x-request-id"], ctx.request_id.as_str());
+}
+
+// ============================================================================
+// Dry-Run Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_dry_run_create_issue_does_not_call_gitlab() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_dry_run(true);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "title": "Planned issue",
+        "labels": "bug"
+    });
+    let result = registry.execute("create_issue", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let preview: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(preview["dry_run"], true);
+            assert_eq!(preview["tool"], "create_issue");
+            assert_eq!(preview["operation"], "write");
+            assert_eq!(preview["project"], "test/project");
+            assert_eq!(preview["arguments"]["title"], "Planned issue");
+            assert!(
+                preview["summary"]
+                    .as_str()
+                    .unwrap()
+                    .contains("Would run create_issue")
+            );
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_still_validates_arguments() {
+    let mock_server = MockServer::start().await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_dry_run(true);
+    let registry = create_registry();
+
+    // Missing required 'title'
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("create_issue", &ctx, args).await;
+
+    assert!(result.unwrap_err().to_string().contains("title"));
+}
+
+#[tokio::test]
+async fn test_per_call_dry_run_argument() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "issue_iid": 5, "dry_run": true});
+    let result = registry.execute("delete_issue", &ctx, args).await.unwrap();
+
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let preview: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(preview["operation"], "delete");
+            assert!(preview["arguments"].get("dry_run").is_none());
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    let schema = serde_json::to_value(&registry.get("delete_issue").unwrap().input_schema).unwrap();
+    assert_eq!(schema["properties"]["dry_run"]["type"], "boolean");
+    let schema = serde_json::to_value(&registry.get("get_issue").unwrap().input_schema).unwrap();
+    assert!(schema["properties"].get("dry_run").is_none());
+}
+
+#[tokio::test]
+async fn test_dry_run_mode_runs_read_tools() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "path_with_namespace": "test/project"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_dry_run(true);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("get_project", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_native_dry_run_argument_is_forwarded_to_gitlab() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/api/v4/projects/test%2Fproject/repository/commits/abc123/revert",
+        ))
+        .and(body_json(json!({"branch": "main", "dry_run": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"dry_run": "success"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "sha": "abc123",
+        "branch": "main",
+        "dry_run": true
+    });
+    let result = registry.execute("revert_commit", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+// ============================================================================
+// Default Project Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_default_project_used_when_omitted() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/default%2Frepo/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"iid": 1, "title": "Default project issue", "state": "opened"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx =
+        create_test_context(gitlab, access).with_default_project(Some("default/repo".to_string()));
+    let registry = create_registry();
+
+    let result = registry
+        .execute("list_issues", &ctx, json!({}))
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_default_project_not_used_for_optional_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/ci/lint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "valid",
+            "errors": [],
+            "warnings": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx =
+        create_test_context(gitlab, access).with_default_project(Some("default/repo".to_string()));
+    let registry = create_registry();
+
+    // Standalone lint stays reachable with a default project configured
+    let args = json!({"content": "test:\n  script: echo ok\n"});
+    let result = registry.execute("lint_ci_yaml", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_explicit_project_overrides_default() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/other%2Frepo/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx =
+        create_test_context(gitlab, access).with_default_project(Some("default/repo".to_string()));
+    let registry = create_registry();
+
+    let args = json!({"project": "other/repo"});
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+}
+
+#[tokio::test]
+async fn test_default_project_access_is_resolved() {
+    use tanuki_mcp::config::ProjectAccessConfig;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    // Full access overall, but the default project is locked down
+    let mut policy = AccessControlConfig {
+        all: AccessLevel::Full,
+        ..Default::default()
+    };
+    policy.projects.insert(
+        "default/repo".to_string(),
+        ProjectAccessConfig {
+            all: Some(AccessLevel::None),
+            ..Default::default()
+        },
+    );
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let ctx =
+        create_test_context(gitlab, access).with_default_project(Some("default/repo".to_string()));
+    let registry = create_registry();
+
+    let result = registry.execute("list_issues", &ctx, json!({})).await;
+
+    let err = result.unwrap_err();
+    assert!(matches!(err, tanuki_mcp::error::ToolError::AccessDenied(_)));
+    assert!(err.to_string().contains("default/repo"));
+}
+
+// ============================================================================
+// Project Allowlist Tests
+// ============================================================================
+
+fn allowlist(globs: &[&str]) -> Option<Arc<tanuki_mcp::access_control::ProjectAllowlist>> {
+    let globs: Vec<String> = globs.iter().map(|glob| glob.to_string()).collect();
+    Some(Arc::new(
+        tanuki_mcp::access_control::ProjectAllowlist::new(&globs).unwrap(),
+    ))
+}
+
+#[tokio::test]
+async fn test_allowed_project_is_called() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/a%2Fapp"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 1})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_allowed_projects(allowlist(&["a/*", "b/c"]));
+    let registry = create_registry();
+
+    let result = registry
+        .execute("get_project", &ctx, json!({"project": "a/app"}))
+        .await
+        .unwrap();
+    assert_eq!(result.data.unwrap()["id"], 1);
+}
+
+#[tokio::test]
+async fn test_project_outside_allowlist_is_forbidden() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/other%2Fapp"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 5})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    // Numeric IDs are resolved to their path before the check
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/5"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"id": 5, "path_with_namespace": "other/app"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/5/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_allowed_projects(allowlist(&["a/*", "b/c"]));
+    let registry = create_registry();
+
+    for (tool, project) in [("get_project", "other/app"), ("list_issues", "5")] {
+        let args = json!({"project": project});
+        let error = registry.execute(tool, &ctx, args).await.unwrap_err();
+        assert!(matches!(error, ToolError::AccessDenied(_)));
+        assert!(error.to_string().contains("forbidden"));
+        assert!(error.to_string().contains("other/app"));
+    }
+}
+
+#[tokio::test]
+async fn test_failed_project_id_lookup_is_recorded() {
+    use tanuki_mcp::dashboard::DashboardMetrics;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/404"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"message": "404 Not Found"})))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let metrics = Arc::new(DashboardMetrics::new());
+    let ctx = ToolContext::with_metrics(gitlab, access, "test-request-123", metrics.clone())
+        .with_allowed_projects(allowlist(&["a/*"]));
+    let registry = create_registry();
+
+    let args = json!({"project": "404"});
+    registry
+        .execute("list_issues", &ctx, args)
+        .await
+        .unwrap_err();
+
+    let calls = metrics.recent_calls(None);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].project.as_deref(), Some("404"));
+    assert!(!calls[0].success);
+}
+
+#[tokio::test]
+async fn test_allowlist_checks_secondary_and_optional_projects() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_allowed_projects(allowlist(&["a/*"]));
+    let registry = create_registry();
+
+    let link = json!({
+        "project": "a/app",
+        "issue_iid": 1,
+        "target_project": "other/app",
+        "target_issue_iid": 2
+    });
+    let lint = json!({"project": "other/app", "content": "test:\n  script: true\n"});
+    for (tool, args) in [("create_issue_link", link), ("lint_ci_yaml", lint)] {
+        let error = registry.execute(tool, &ctx, args).await.unwrap_err();
+        assert!(matches!(error, ToolError::AccessDenied(_)));
+        assert!(error.to_string().contains("other/app"));
+    }
+}
+
+#[tokio::test]
+async fn test_allowlist_refuses_tools_without_project() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(wiremock::matchers::any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_allowed_projects(allowlist(&["a/*"]));
+    let registry = create_registry();
+
+    // Group, user, and instance-wide tools can reach any project
+    let mut refused = Vec::new();
+    for tool in registry.tools() {
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        if schema["properties"].get("project").is_some() {
+            continue;
+        }
+        let error = registry
+            .execute(tool.name, &ctx, json!({}))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, ToolError::AccessDenied(_)),
+            "{} should be refused, got {error}",
+            tool.name
+        );
+        refused.push(tool.name);
+    }
+    for name in [
+        "add_group_member",
+        "list_groups",
+        "list_users",
+        "list_projects",
+        "search_global",
+    ] {
+        assert!(refused.contains(&name), "{name} should be refused");
+    }
+}
+
+#[tokio::test]
+async fn test_allowlist_refuses_raw_requests() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_allowed_projects(allowlist(&["a/*"]));
+    let registry = create_registry();
+
+    // Even a path inside an allowed project is refused
+    let args = json!({"method": "GET", "path": "/projects/a%2Fapp/badges"});
+    let error = registry
+        .execute("gitlab_request", &ctx, args)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ToolError::AccessDenied(_)));
+    assert!(error.to_string().contains("raw requests"));
+}
+
+// ============================================================================
+// Idempotency Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_idempotency_key_deduplicates_create() {
+    use std::time::Duration;
+    use tanuki_mcp::tools::IdempotencyCache;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .and(body_json(json!({"title": "Flaky network issue"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 1,
+            "iid": 17,
+            "title": "Flaky network issue"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60)));
+    let ctx = create_test_context(gitlab, access).with_idempotency(Some(cache));
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "title": "Flaky network issue",
+        "idempotency_key": "retry-abc"
+    });
+    let first = registry
+        .execute("create_issue", &ctx, args.clone())
+        .await
+        .unwrap();
+    let second = registry.execute("create_issue", &ctx, args).await.unwrap();
+
+    match (&first.content[0], &second.content[0]) {
+        (
+            tanuki_mcp::tools::ContentBlock::Text { text: first },
+            tanuki_mcp::tools::ContentBlock::Text { text: second },
+        ) => {
+            assert!(first.contains("\"iid\": 17"));
+            assert_eq!(first, second);
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_retry_with_idempotency_key_waits_for_first_call() {
+    use std::time::Duration;
+    use tanuki_mcp::tools::IdempotencyCache;
+
+    let mock_server = MockServer::start().await;
+
+    // The first call is still running when the retry arrives
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(json!({"id": 1, "iid": 17}))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60)));
+    let ctx = create_test_context(gitlab, access).with_idempotency(Some(cache));
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "title": "Issue", "idempotency_key": "k"});
+    let (first, second) = tokio::join!(
+        registry.execute("create_issue", &ctx, args.clone()),
+        registry.execute("create_issue", &ctx, args),
+    );
+
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert_eq!(first.data, second.data);
+}
+
+#[tokio::test]
+async fn test_different_idempotency_keys_create_separately() {
+    use std::time::Duration;
+    use tanuki_mcp::tools::IdempotencyCache;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1, "iid": 17})))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60)));
+    let ctx = create_test_context(gitlab, access).with_idempotency(Some(cache));
+    let registry = create_registry();
+
+    for key in ["key-1", "key-2"] {
+        let args = json!({"project": "test/project", "title": "Issue", "idempotency_key": key});
+        registry.execute("create_issue", &ctx, args).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_idempotency_key_reused_with_different_arguments_is_rejected() {
+    use std::time::Duration;
+    use tanuki_mcp::dashboard::DashboardMetrics;
+    use tanuki_mcp::tools::IdempotencyCache;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1, "iid": 17})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60)));
+    let metrics = Arc::new(DashboardMetrics::new());
+    let ctx = ToolContext::with_metrics(gitlab, access, "test-request-123", metrics.clone())
+        .with_idempotency(Some(cache));
+    let registry = create_registry();
+
+    let args = |title| json!({"project": "test/project", "title": title, "idempotency_key": "k"});
+    registry
+        .execute("create_issue", &ctx, args("First issue"))
+        .await
+        .unwrap();
+    let error = registry
+        .execute("create_issue", &ctx, args("Second issue"))
+        .await
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("already used with different arguments")
+    );
+
+    // The rejected call is recorded like any other failure
+    assert_eq!(metrics.total_requests(), 2);
+    assert_eq!(metrics.total_errors(), 1);
+}
+
+// ============================================================================
+// Request Size Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_oversized_arguments_are_rejected() {
+    use tanuki_mcp::dashboard::DashboardMetrics;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"iid": 1})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let metrics = Arc::new(DashboardMetrics::new());
+    let ctx = ToolContext::with_metrics(gitlab, access, "test-request-123", metrics.clone())
+        .with_max_request_bytes(Some(1024));
+    let registry = create_registry();
+
+    let args = json!({
+        "project": "test/project",
+        "title": "Huge",
+        "description": "x".repeat(2048)
+    });
+    let result = registry.execute("create_issue", &ctx, args).await;
+
+    match result {
+        Err(tanuki_mcp::error::ToolError::InvalidArguments(message)) => {
+            assert!(message.contains("1024 bytes"));
+        }
+        other => panic!("Expected InvalidArguments, got {:?}", other.map(|_| ())),
+    }
+
+    // The rejection still shows up on the dashboard
+    let calls = metrics.recent_calls(None);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool, "create_issue");
+    assert!(!calls[0].success);
+}
+
+// ============================================================================
+// Pagination Metadata Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_results_wrapped_with_pagination_meta() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([{"id": 1, "iid": 1, "title": "First Issue"}]))
+                .insert_header("X-Total", "42")
+                .insert_header("X-Next-Page", "2"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let registry = create_registry();
+    let args = json!({"project": "test/project"});
+
+    let ctx = create_test_context(gitlab.clone(), access.clone()).with_pagination_meta(true);
+    let result = registry
+        .execute("list_issues", &ctx, args.clone())
+        .await
+        .unwrap();
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["total"], 42);
+            assert_eq!(value["next_page"], 2);
+            assert_eq!(value["items"][0]["title"], "First Issue");
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Off by default: the bare array is kept
+    let ctx = create_test_context(gitlab, access);
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert!(value.is_array());
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_results_projected_before_pagination_meta() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([
+                    {"id": 1, "iid": 1, "title": "First Issue", "description": "Long text"}
+                ]))
+                .insert_header("X-Total", "1"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_pagination_meta(true);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "fields": ["iid", "title"]});
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+    assert_eq!(
+        result.data,
+        Some(json!({
+            "items": [{"iid": 1, "title": "First Issue"}],
+            "total": 1,
+            "next_page": null
+        }))
+    );
+}
+
+#[tokio::test]
+async fn test_pagination_meta_last_page_has_no_next_page() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([]))
+                .insert_header("X-Total", "0")
+                .insert_header("X-Next-Page", ""),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_pagination_meta(true);
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project"});
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+    match &result.content[0] {
+        tanuki_mcp::tools::ContentBlock::Text { text } => {
+            let value: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["total"], 0);
+            assert!(value["next_page"].is_null());
+        }
+        _ => panic!("Expected text content"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_results_truncated_to_max_items() {
+    let mock_server = MockServer::start().await;
+
+    let issues: Vec<_> = (1..=200)
+        .map(|iid| json!({"iid": iid, "title": format!("Issue {}", iid)}))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access).with_max_items_per_list(Some(50));
+    let registry = create_registry();
+
+    let args = json!({"project": "test/project", "per_page": 100});
+    let result = registry.execute("list_issues", &ctx, args).await.unwrap();
+
+    assert!(!result.is_error);
+    let items = result.data.as_ref().unwrap().as_array().unwrap();
+    assert_eq!(items.len(), 50);
+    assert_eq!(items[49]["iid"], 50);
+    match result.content.last() {
+        Some(tanuki_mcp::tools::ContentBlock::Text { text }) => {
+            assert!(text.contains("truncated to 50 of 200 items"));
+            assert!(text.contains("150 omitted"));
+        }
+        _ => panic!("Expected truncation note"),
+    }
+}
+
+// ============================================================================
+// Tool Call Logging Tests
+// ============================================================================
+
+/// Writer collecting formatted log output in memory
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CapturedLogs {
+    /// Fields of every "Tool call finished" line
+    fn tool_calls(&self) -> Vec<serde_json::Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .map(|line| line["fields"].clone())
+            .filter(|fields| fields["message"] == "Tool call finished")
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn test_tool_call_log_line_fields() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"iid": 1})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues/2"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"message": "404 Not found"})))
+        .mount(&mock_server)
+        .await;
+
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_max_level(tracing::Level::INFO)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let gitlab = create_test_gitlab(&mock_server);
+    let access = create_full_access();
+    let ctx = create_test_context(gitlab, access);
+    let registry = create_registry();
+
+    registry
+        .execute(
+            "get_issue",
+            &ctx,
+            json!({"project": "test/project", "issue_iid": 1}),
+        )
+        .await
+        .unwrap();
+    let _ = registry
+        .execute(
+            "get_issue",
+            &ctx,
+            json!({"project": "test/project", "issue_iid": 2}),
+        )
+        .await;
+
+    let calls = logs.tool_calls();
+    assert_eq!(calls.len(), 2);
+
+    let ok = &calls[0];
+    assert_eq!(ok["tool"], "get_issue");
+    assert_eq!(ok["category"], "issues");
+    assert_eq!(ok["operation"], "read");
+    assert_eq!(ok["project"], "test/project");
+    assert_eq!(ok["decision"], "allowed");
+    assert_eq!(ok["outcome"], "ok");
+    assert!(ok["duration_ms"].is_u64());
+    assert!(ok.get("error_code").is_none());
+
+    let failed = &calls[1];
+    assert_eq!(failed["outcome"], "error");
+    assert_eq!(failed["error_code"], "gitlab_not_found");
 }
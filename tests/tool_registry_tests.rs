@@ -1,6 +1,12 @@
 //! Tool registry integration tests
 
-use tanuki_mcp::tools::definitions;
+use std::sync::Arc;
+use tanuki_mcp::access_control::{AccessResolver, ToolCategory};
+use tanuki_mcp::auth::PatProvider;
+use tanuki_mcp::config::{AccessControlConfig, AccessLevel, GitLabConfig, ToolsConfig};
+use tanuki_mcp::error::ToolError;
+use tanuki_mcp::gitlab::GitLabClient;
+use tanuki_mcp::tools::{ToolContext, definitions};
 
 #[test]
 fn test_all_tools_registered() {
@@ -113,3 +119,191 @@ fn test_tool_schemas_valid() {
         );
     }
 }
+
+#[test]
+fn test_tool_order_is_stable() {
+    let build = || {
+        let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+        definitions::register_all_tools(&mut registry);
+        registry
+            .tools()
+            .map(|t| t.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let first = build();
+    assert_eq!(first, build());
+
+    let mut sorted = first.clone();
+    sorted.sort_unstable();
+    assert_eq!(first, sorted, "Tools should be listed by name");
+}
+
+#[test]
+fn test_catalog_describes_tools() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &ToolsConfig::default());
+
+    let catalog = registry.catalog();
+    let tools = catalog.as_array().expect("Catalog should be an array");
+    assert_eq!(tools.len(), registry.len());
+
+    let names: Vec<&str> = tools.iter().filter_map(|t| t["name"].as_str()).collect();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted, "Catalog should be sorted by name");
+
+    let get_issue = tools
+        .iter()
+        .find(|t| t["name"] == "get_issue")
+        .expect("get_issue should be in the catalog");
+    assert_eq!(get_issue["category"], "issues");
+    assert_eq!(get_issue["operation"], "read");
+    assert!(!get_issue["description"].as_str().unwrap().is_empty());
+    assert!(get_issue["input_schema"]["properties"]["project"].is_object());
+    assert!(get_issue["input_schema"]["properties"]["issue_iid"].is_object());
+}
+
+#[test]
+fn test_fields_argument_offered_on_read_tools() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    definitions::register_all_tools(&mut registry);
+
+    for name in ["get_issue", "list_issues", "get_label", "list_pipelines"] {
+        let schema = serde_json::to_value(&registry.get(name).unwrap().input_schema).unwrap();
+        assert!(
+            schema["properties"]["fields"].is_object(),
+            "{name} should accept fields"
+        );
+    }
+    for name in [
+        "create_issue",
+        "get_file_raw",
+        "get_job_log",
+        "get_job_artifact_file",
+        "get_merge_request_diff_patch",
+    ] {
+        let schema = serde_json::to_value(&registry.get(name).unwrap().input_schema).unwrap();
+        assert!(
+            schema["properties"].get("fields").is_none(),
+            "{name} should not accept fields"
+        );
+    }
+}
+
+#[test]
+fn test_idempotency_key_not_offered_on_secret_results() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    definitions::register_all_tools(&mut registry);
+
+    let schema = serde_json::to_value(&registry.get("create_issue").unwrap().input_schema).unwrap();
+    assert!(schema["properties"]["idempotency_key"].is_object());
+
+    // A cached result would keep the plaintext token around
+    let tool = registry.get("create_project_access_tokens").unwrap();
+    let schema = serde_json::to_value(&tool.input_schema).unwrap();
+    assert!(schema["properties"].get("idempotency_key").is_none());
+}
+
+#[test]
+fn test_raw_request_disabled_by_default() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &ToolsConfig::default());
+
+    assert!(registry.get("gitlab_request").is_none());
+    assert!(registry.tools_in_category(ToolCategory::Raw).is_empty());
+}
+
+#[test]
+fn test_raw_request_enabled() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    let config = ToolsConfig {
+        enable_raw_request: true,
+        ..Default::default()
+    };
+    definitions::register_enabled_tools(&mut registry, &config);
+
+    assert!(registry.get("gitlab_request").is_some());
+}
+
+#[tokio::test]
+async fn test_raw_request_rejected_when_disabled() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    definitions::register_enabled_tools(&mut registry, &ToolsConfig::default());
+
+    let config = GitLabConfig {
+        token: Some("test-token".to_string()),
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&config, Box::new(auth)).unwrap());
+    let policy = AccessControlConfig {
+        all: AccessLevel::Full,
+        ..Default::default()
+    };
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+    let ctx = ToolContext::new(gitlab, access, "test-request");
+
+    let args = serde_json::json!({"method": "GET", "path": "/version"});
+    let result = registry.execute("gitlab_request", &ctx, args).await;
+
+    assert!(matches!(result, Err(ToolError::NotFound(_))));
+}
+
+fn create_test_context() -> ToolContext {
+    let config = GitLabConfig {
+        token: Some("test-token".to_string()),
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&config, Box::new(auth)).unwrap());
+    let policy = AccessControlConfig {
+        all: AccessLevel::Full,
+        ..Default::default()
+    };
+    let access = Arc::new(AccessResolver::new(&policy).unwrap());
+    ToolContext::new(gitlab, access, "test-request")
+}
+
+#[tokio::test]
+async fn test_disabled_tool_not_listed_and_rejected() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    let config = ToolsConfig {
+        disabled: vec!["^delete_".to_string()],
+        ..Default::default()
+    };
+    definitions::register_enabled_tools(&mut registry, &config);
+
+    assert!(
+        registry
+            .tool_names()
+            .all(|name| !name.starts_with("delete_"))
+    );
+    assert!(registry.get("delete_issue").is_none());
+    assert!(registry.get("create_issue").is_some());
+
+    let ctx = create_test_context();
+    let args = serde_json::json!({"project": "test/project", "issue_iid": 1});
+    let result = registry.execute("delete_issue", &ctx, args).await;
+
+    let err = result.unwrap_err();
+    assert!(matches!(err, ToolError::Disabled(ref name) if name == "delete_issue"));
+    assert!(err.to_string().contains("disabled"));
+}
+
+#[test]
+fn test_enabled_patterns_restrict_tools() {
+    let mut registry = tanuki_mcp::tools::ToolRegistry::new();
+    let config = ToolsConfig {
+        enabled: vec!["^(list|get)_issues?$".to_string()],
+        disabled: vec!["^get_issue$".to_string()],
+        ..Default::default()
+    };
+    definitions::register_enabled_tools(&mut registry, &config);
+
+    let mut names: Vec<&str> = registry.tool_names().collect();
+    names.sort();
+    assert_eq!(names, vec!["list_issues"]);
+    assert!(registry.is_disabled("get_issue"));
+    assert!(registry.is_disabled("create_issue"));
+}
@@ -2,9 +2,22 @@
 //!
 //! Tests for HTTP configuration and basic functionality.
 
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use serde_json::json;
 use std::net::SocketAddr;
-use tanuki_mcp::config::CorsMode;
-use tanuki_mcp::transport::HttpConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tanuki_mcp::access_control::AccessResolver;
+use tanuki_mcp::auth::PatProvider;
+use tanuki_mcp::config::{AppConfig, CorsMode, GitLabConfig};
+use tanuki_mcp::gitlab::GitLabClient;
+use tanuki_mcp::server::{GITLAB_TOKEN_HEADER, GitLabMcpHandler};
+use tanuki_mcp::transport::{
+    HttpConfig, MAX_BODY_BYTES, WebhookConfig, build_router, check_http_port,
+};
+use tower::ServiceExt;
 
 #[test]
 fn test_http_config_default() {
@@ -46,6 +59,21 @@ fn test_http_config_from_host_port_invalid() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_check_http_port_detects_busy_port() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = HttpConfig::from_host_port("127.0.0.1", port).unwrap();
+
+    let error = check_http_port(&config).await.unwrap_err();
+    assert!(error.to_string().contains("already in use"));
+    assert!(error.to_string().contains(&port.to_string()));
+
+    // Once released, the same port passes the check
+    drop(listener);
+    assert!(check_http_port(&config).await.is_ok());
+}
+
 #[test]
 fn test_http_config_clone() {
     let config1 = HttpConfig::new(SocketAddr::from(([127, 0, 0, 1], 3000)));
@@ -111,3 +139,1383 @@ fn test_cors_mode_deserialization() {
     let disabled: CorsMode = serde_json::from_str(r#""disabled""#).unwrap();
     assert_eq!(disabled, CorsMode::Disabled);
 }
+
+// ============================================================================
+// SSE Keep-Alive Tests
+// ============================================================================
+
+#[test]
+fn test_http_config_sse_keep_alive() {
+    let config = HttpConfig::default();
+    assert_eq!(config.sse_keep_alive, Some(Duration::from_secs(30)));
+
+    let config = HttpConfig::default().with_sse_keep_alive_secs(45);
+    assert_eq!(config.sse_keep_alive, Some(Duration::from_secs(45)));
+
+    let config = HttpConfig::default().with_sse_keep_alive_secs(0);
+    assert_eq!(config.sse_keep_alive, None);
+}
+
+/// Send a JSON-RPC message to the MCP endpoint
+async fn post_mcp(
+    router: &axum::Router,
+    session_id: Option<&str>,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream");
+    if let Some(id) = session_id {
+        request = request.header("mcp-session-id", id);
+    }
+    router
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_sse_keep_alive_on_idle_stream() {
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+
+    let config = HttpConfig {
+        sse_keep_alive: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &config,
+    );
+
+    // Initialize a session
+    let response = post_mcp(
+        &router,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.0.0"}
+            }
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let response = post_mcp(
+        &router,
+        Some(&session_id),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    )
+    .await;
+    assert!(response.status().is_success());
+
+    // Open the standalone SSE stream; nothing is sent on it besides keep-alives
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/mcp")
+                .header("accept", "text/event-stream")
+                .header("mcp-session-id", &session_id)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let mut stream = response.into_body().into_data_stream();
+    let frame = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("keep-alive frame should arrive on an idle stream")
+        .unwrap()
+        .unwrap();
+
+    // A bare SSE comment, which clients ignore
+    assert!(frame.starts_with(b":"));
+    assert!(!String::from_utf8_lossy(&frame).contains("jsonrpc"));
+}
+
+// ============================================================================
+// Concurrency Limit Tests
+// ============================================================================
+
+#[test]
+fn test_http_config_max_concurrent_requests() {
+    let config = HttpConfig::default();
+    assert_eq!(config.max_concurrent_requests, Some(64));
+
+    let config = HttpConfig::default().with_max_concurrent_requests(8);
+    assert_eq!(config.max_concurrent_requests, Some(8));
+
+    let config = HttpConfig::default().with_max_concurrent_requests(0);
+    assert_eq!(config.max_concurrent_requests, None);
+}
+
+#[tokio::test]
+async fn test_concurrency_limit_rejects_excess_requests() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // A slow GitLab keeps the first tool call in flight
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"id": 1, "name": "project"}))
+                .set_delay(Duration::from_secs(10)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+
+    let config = HttpConfig::default().with_max_concurrent_requests(1);
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &config,
+    );
+
+    let response = post_mcp(
+        &router,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.0.0"}
+            }
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    drop(response);
+
+    let response = post_mcp(
+        &router,
+        Some(&session_id),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    )
+    .await;
+    assert!(response.status().is_success());
+    drop(response);
+
+    // Occupy the only slot with a tool call waiting on GitLab
+    let in_flight = post_mcp(
+        &router,
+        Some(&session_id),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "get_project", "arguments": {"project": "test/project"}}
+        }),
+    )
+    .await;
+    assert_eq!(in_flight.status(), 200);
+
+    let ping = json!({"jsonrpc": "2.0", "id": 3, "method": "ping"});
+    let rejected = post_mcp(&router, Some(&session_id), ping.clone()).await;
+    assert_eq!(rejected.status(), 503);
+    assert!(rejected.headers().contains_key("retry-after"));
+
+    // The health endpoint is never limited
+    let health = router
+        .clone()
+        .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(health.status(), 200);
+
+    // Finishing the in-flight request frees its slot
+    drop(in_flight);
+    let accepted = post_mcp(&router, Some(&session_id), ping).await;
+    assert_eq!(accepted.status(), 200);
+}
+
+// ============================================================================
+// Request ID Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_request_id_echoed_and_forwarded_to_gitlab() {
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(header("x-request-id", "client-corr-42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 1})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+
+    // Without a client-supplied ID, one is generated and echoed
+    let response = post_mcp(
+        &router,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.0.0"}
+            }
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    assert!(!response.headers()["x-request-id"].is_empty());
+    let session_id = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    drop(response);
+
+    let response = post_mcp(
+        &router,
+        Some(&session_id),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    )
+    .await;
+    assert!(response.status().is_success());
+    drop(response);
+
+    // A client-supplied ID is echoed and sent on the GitLab call
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .header("mcp-session-id", &session_id)
+        .header("x-request-id", "client-corr-42")
+        .body(Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "get_project", "arguments": {"project": "test/project"}}
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-request-id"], "client-corr-42");
+
+    // Drain the response stream so the tool call completes
+    let mut stream = response.into_body().into_data_stream();
+    let mut body = String::new();
+    while let Ok(Some(Ok(chunk))) =
+        tokio::time::timeout(Duration::from_secs(5), stream.next()).await
+    {
+        body.push_str(&String::from_utf8_lossy(&chunk));
+        if body.contains("\"id\":2") {
+            break;
+        }
+    }
+    assert!(body.contains("\"id\":2"));
+    assert!(!body.contains("\"isError\":true"));
+}
+
+// ============================================================================
+// Rate Limit Tests
+// ============================================================================
+
+/// Initialize an MCP session over HTTP and return its session ID
+async fn initialize_session(router: &axum::Router) -> String {
+    let response = post_mcp(
+        router,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.0.0"}
+            }
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let session_id = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    drop(response);
+
+    let response = post_mcp(
+        router,
+        Some(&session_id),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    )
+    .await;
+    assert!(response.status().is_success());
+    drop(response);
+
+    session_id
+}
+
+/// Call a tool over HTTP and return the streamed response body
+async fn call_tool_over_http(
+    router: &axum::Router,
+    session_id: &str,
+    id: u64,
+    name: &str,
+    arguments: serde_json::Value,
+) -> String {
+    request_over_http(
+        router,
+        session_id,
+        id,
+        "tools/call",
+        json!({"name": name, "arguments": arguments}),
+    )
+    .await
+}
+
+async fn request_over_http(
+    router: &axum::Router,
+    session_id: &str,
+    id: u64,
+    method: &str,
+    params: serde_json::Value,
+) -> String {
+    let response = post_mcp(
+        router,
+        Some(session_id),
+        json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let marker = format!("\"id\":{}", id);
+    let mut stream = response.into_body().into_data_stream();
+    let mut body = String::new();
+    while let Ok(Some(Ok(chunk))) =
+        tokio::time::timeout(Duration::from_secs(5), stream.next()).await
+    {
+        body.push_str(&String::from_utf8_lossy(&chunk));
+        if body.contains(&marker) {
+            break;
+        }
+    }
+    assert!(body.contains(&marker));
+    body
+}
+
+#[tokio::test]
+async fn test_rate_limit_rejects_over_limit_tool_calls() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/pipelines"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let mut app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    app_config
+        .ratelimit
+        .categories
+        .insert("pipelines".to_string(), "1/m".to_string());
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+
+    let session_id = initialize_session(&router).await;
+
+    let args = json!({"project": "test/project"});
+    let first = call_tool_over_http(&router, &session_id, 2, "list_pipelines", args.clone()).await;
+    assert!(!first.contains("\"isError\":true"));
+
+    let second = call_tool_over_http(&router, &session_id, 3, "list_pipelines", args.clone()).await;
+    assert!(second.contains("\"isError\":true"));
+    assert!(second.contains("Rate limited: pipelines"));
+
+    // Other categories are unaffected
+    let issues = call_tool_over_http(&router, &session_id, 4, "list_issues", args).await;
+    assert!(!issues.contains("\"isError\":true"));
+}
+
+#[tokio::test]
+async fn test_soft_errors_report_not_found_as_result() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fmissing"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(json!({"message": "404 Project Not Found"})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let router_with = |soft_errors: bool| {
+        let mut app_config = AppConfig {
+            gitlab: GitLabConfig {
+                url: mock_server.uri(),
+                token: Some("test-token".to_string()),
+                max_retries: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app_config.server.soft_errors = soft_errors;
+        let auth = PatProvider::new("test-token".to_string()).unwrap();
+        let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+        let access = Arc::new(AccessResolver::allow_all());
+        build_router(
+            move || {
+                GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone())
+                    .unwrap()
+            },
+            &HttpConfig::default(),
+        )
+    };
+    let args = json!({"project": "test/missing"});
+
+    // Hard errors (default): the 404 is a failed tool call
+    let router = router_with(false);
+    let session_id = initialize_session(&router).await;
+    let body = call_tool_over_http(&router, &session_id, 2, "get_project", args.clone()).await;
+    assert!(body.contains("\"isError\":true"));
+    assert!(!body.contains("gitlab_not_found"));
+
+    // Soft errors: the same 404 is a regular result with an error field
+    let router = router_with(true);
+    let session_id = initialize_session(&router).await;
+    let body = call_tool_over_http(&router, &session_id, 2, "get_project", args).await;
+    assert!(body.contains("\"isError\":false"));
+    assert!(body.contains("gitlab_not_found"));
+
+    // Validation errors stay hard
+    let body = call_tool_over_http(&router, &session_id, 3, "get_project", json!({})).await;
+    assert!(body.contains("\"isError\":true"));
+}
+
+#[tokio::test]
+async fn test_tool_prefix_applied_to_listing_and_calls() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 7})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    app_config.server.tool_prefix = "gl_".to_string();
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+
+    let session_id = initialize_session(&router).await;
+
+    let listing = request_over_http(&router, &session_id, 2, "tools/list", json!({})).await;
+    assert!(listing.contains("\"name\":\"gl_get_project\""));
+    assert!(!listing.contains("\"name\":\"get_project\""));
+
+    let args = json!({"project": "test/project"});
+    let body = call_tool_over_http(&router, &session_id, 3, "gl_get_project", args.clone()).await;
+    assert!(!body.contains("\"isError\":true"));
+
+    // The plain name is no longer advertised and is not dispatched
+    let body = call_tool_over_http(&router, &session_id, 4, "get_project", args).await;
+    assert!(body.contains("\"isError\":true"));
+
+    // Completions match and return the advertised names
+    let params = json!({
+        "ref": {"type": "ref/prompt", "name": "tools"},
+        "argument": {"name": "name", "value": "gl_get_proj"}
+    });
+    let body = request_over_http(&router, &session_id, 5, "completion/complete", params).await;
+    assert!(body.contains("\"gl_get_project\""), "{body}");
+    let params = json!({
+        "ref": {"type": "ref/prompt", "name": "tools"},
+        "argument": {"name": "name", "value": "get_proj"}
+    });
+    let body = request_over_http(&router, &session_id, 6, "completion/complete", params).await;
+    assert!(!body.contains("get_project"), "{body}");
+}
+
+#[tokio::test]
+async fn test_ndjson_streams_pages_incrementally() {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .and(query_param("page", "1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-total", "3")
+                .insert_header("x-next-page", "2")
+                .set_body_json(json!([{"iid": 1}, {"iid": 2}])),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/test%2Fproject/issues"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-total", "3")
+                .insert_header("x-next-page", "")
+                .set_body_json(json!([{"iid": 3}]))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            max_retries: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+    let session_id = initialize_session(&router).await;
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {"name": "list_issues", "arguments": {"project": "test/project"}}
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/x-ndjson")
+        .header("mcp-session-id", &session_id)
+        .body(Body::from(call.to_string()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/x-ndjson"
+    );
+
+    // The first page arrives while the second is still being fetched
+    let mut stream = response.into_body().into_data_stream();
+    let first = tokio::time::timeout(Duration::from_millis(400), stream.next())
+        .await
+        .expect("first page was not streamed before the second was fetched")
+        .unwrap()
+        .unwrap();
+    let first = String::from_utf8_lossy(&first).to_string();
+    assert_eq!(first.lines().count(), 2);
+    assert!(!first.contains("\"iid\":3"));
+
+    let mut rest = String::new();
+    while let Some(Ok(chunk)) = stream.next().await {
+        rest.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    let lines: Vec<serde_json::Value> = rest
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["iid"], 3);
+    assert_eq!(lines[1]["id"], 7);
+    assert_eq!(lines[1]["result"]["items"], 3);
+}
+
+/// Mount three pages of two issues each, the last of which must not be fetched
+async fn mount_issue_pages(mock_server: &wiremock::MockServer) {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, ResponseTemplate};
+
+    for page in 1..=3u64 {
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/test%2Fproject/issues"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-next-page", (page + 1).to_string())
+                    .set_body_json(json!([{"iid": page * 2 - 1}, {"iid": page * 2}])),
+            )
+            .expect(if page < 3 { 1 } else { 0 })
+            .mount(mock_server)
+            .await;
+    }
+}
+
+/// Stream `list_issues` over NDJSON and collect every line
+async fn stream_issues_over_http(app_config: AppConfig) -> Vec<serde_json::Value> {
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+    let session_id = initialize_session(&router).await;
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {"name": "list_issues", "arguments": {"project": "test/project"}}
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/x-ndjson")
+        .header("mcp-session-id", &session_id)
+        .body(Body::from(call.to_string()))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8_lossy(&body)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_ndjson_stream_rate_limits_each_page() {
+    let mock_server = wiremock::MockServer::start().await;
+    mount_issue_pages(&mock_server).await;
+
+    let mut app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    app_config
+        .ratelimit
+        .categories
+        .insert("issues".to_string(), "2/m".to_string());
+
+    let lines = stream_issues_over_http(app_config).await;
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[3]["iid"], 4);
+    assert_eq!(lines[4]["id"], 7);
+    assert!(
+        lines[4]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Rate limited: issues")
+    );
+}
+
+#[tokio::test]
+async fn test_ndjson_stream_stops_at_max_items_per_list() {
+    let mock_server = wiremock::MockServer::start().await;
+    mount_issue_pages(&mock_server).await;
+
+    let mut app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    app_config.server.max_items_per_list = Some(3);
+
+    let lines = stream_issues_over_http(app_config).await;
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[2]["iid"], 3);
+    assert_eq!(lines[3]["result"]["items"], 3);
+}
+
+#[tokio::test]
+async fn test_oversized_tool_arguments_rejected_over_http() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"iid": 1})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    app_config.server.max_request_bytes = 256;
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+
+    let session_id = initialize_session(&router).await;
+
+    let args = json!({
+        "project": "test/project",
+        "title": "Huge",
+        "description": "x".repeat(1024)
+    });
+    let body = call_tool_over_http(&router, &session_id, 2, "create_issue", args).await;
+    assert!(body.contains("\"isError\":true"));
+    assert!(body.contains("exceeding the server limit of 256 bytes"));
+}
+
+/// Router with default handlers and the given JSON-RPC limits
+fn limited_router(max_batch_len: usize, max_message_bytes: usize) -> axum::Router {
+    let app_config = AppConfig::default();
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default().with_message_limits(max_batch_len, max_message_bytes),
+    )
+}
+
+/// Read a response body as JSON
+async fn json_body(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_over_limit_batch_rejected() {
+    let router = limited_router(2, 1024);
+
+    let ping = |id: u64| json!({"jsonrpc": "2.0", "id": id, "method": "ping"});
+    let response = post_mcp(&router, None, json!([ping(1), ping(2), ping(3)])).await;
+
+    assert_eq!(response.status(), 400);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], -32600);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Batch of 3 messages exceeds the limit of 2")
+    );
+
+    // A message over the size limit is rejected as well
+    let big =
+        json!({"jsonrpc": "2.0", "id": 1, "method": "ping", "params": {"pad": "x".repeat(1500)}});
+    let response = post_mcp(&router, None, big).await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(json_body(response).await["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_body_over_total_cap_rejected() {
+    // 32 messages of up to 1 MiB would allow 32 MiB; the total cap is lower
+    let router = limited_router(32, 1024 * 1024);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .body(Body::from(vec![b' '; MAX_BODY_BYTES + 1]))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 413);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], -32600);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains(&format!("exceeds {} bytes", MAX_BODY_BYTES))
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_message_rejected() {
+    let router = limited_router(2, 1024);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .body(Body::from("{\"jsonrpc\": \"2.0\", \"method\": "))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 400);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], -32700);
+    assert!(body["id"].is_null());
+
+    // Valid JSON that is not a JSON-RPC 2.0 message
+    let response = post_mcp(&router, None, json!({"method": "ping"})).await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(json_body(response).await["error"]["code"], -32600);
+}
+
+// ============================================================================
+// Per-request Token Tests
+// ============================================================================
+
+/// Call a tool over HTTP with a caller-supplied GitLab token
+async fn call_tool_with_token(
+    router: &axum::Router,
+    session_id: &str,
+    id: u64,
+    token: &str,
+) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .header("mcp-session-id", session_id)
+        .header(GITLAB_TOKEN_HEADER, token)
+        .body(Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {"name": "get_project", "arguments": {"project": "test/project"}}
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let marker = format!("\"id\":{}", id);
+    let mut stream = response.into_body().into_data_stream();
+    let mut body = String::new();
+    while let Ok(Some(Ok(chunk))) =
+        tokio::time::timeout(Duration::from_secs(5), stream.next()).await
+    {
+        body.push_str(&String::from_utf8_lossy(&chunk));
+        if body.contains(&marker) {
+            break;
+        }
+    }
+    assert!(body.contains(&marker));
+    body
+}
+
+#[tokio::test]
+async fn test_per_request_token_sent_to_gitlab() {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    for (token, id) in [("alice-token", 1), ("bob-token", 2), ("server-token", 3)] {
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/test%2Fproject"))
+            .and(header("PRIVATE-TOKEN", token))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": id})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("server-token".to_string()),
+            max_retries: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("server-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+    let session_id = initialize_session(&router).await;
+
+    let alice = call_tool_with_token(&router, &session_id, 2, "alice-token").await;
+    assert!(alice.contains(r#"\"id\": 1"#));
+    let bob = call_tool_with_token(&router, &session_id, 3, "bob-token").await;
+    assert!(bob.contains(r#"\"id\": 2"#));
+
+    // Without the header, the configured token is used
+    let body = call_tool_over_http(
+        &router,
+        &session_id,
+        4,
+        "get_project",
+        json!({"project": "test/project"}),
+    )
+    .await;
+    assert!(body.contains(r#"\"id\": 3"#));
+}
+
+// ============================================================================
+// Webhook Tests
+// ============================================================================
+
+/// POST a webhook delivery with an optional secret token
+async fn post_webhook(
+    router: &axum::Router,
+    token: Option<&str>,
+    event: serde_json::Value,
+) -> axum::response::Response {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/webhook")
+        .header("content-type", "application/json")
+        .header("x-gitlab-event", "Push Hook");
+    if let Some(token) = token {
+        request = request.header("x-gitlab-token", token);
+    }
+    router
+        .clone()
+        .oneshot(request.body(Body::from(event.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_push_webhook_invalidates_project_cache() {
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: "https://gitlab.example.com".to_string(),
+            token: Some("test-token".to_string()),
+            cache_ttl_secs: 300,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    gitlab
+        .cache()
+        .insert("/projects/group%2Fapp", json!({"id": 7}));
+    gitlab
+        .cache()
+        .insert("/projects/group%2Fapp/labels", json!([]));
+    gitlab.cache().insert("/projects/7/milestones", json!([]));
+    gitlab
+        .cache()
+        .insert("/projects/group%2Fother", json!({"id": 8}));
+
+    let access = Arc::new(AccessResolver::allow_all());
+    let config = HttpConfig::default().with_webhook(WebhookConfig::new("s3cret", gitlab.clone()));
+    let handler_gitlab = gitlab.clone();
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, handler_gitlab.clone(), access.clone())
+                .unwrap()
+        },
+        &config,
+    );
+
+    let event = json!({
+        "object_kind": "push",
+        "ref": "refs/heads/main",
+        "project_id": 7,
+        "project": {"id": 7, "path_with_namespace": "group/app"}
+    });
+
+    // A wrong secret is rejected and leaves the cache alone
+    let response = post_webhook(&router, Some("wrong"), event.clone()).await;
+    assert_eq!(response.status(), 401);
+    assert_eq!(gitlab.cache().len(), 4);
+
+    let response = post_webhook(&router, Some("s3cret"), event).await;
+    assert_eq!(response.status(), 200);
+    assert!(gitlab.cache().get("/projects/group%2Fapp").is_none());
+    assert!(gitlab.cache().get("/projects/group%2Fapp/labels").is_none());
+    assert!(gitlab.cache().get("/projects/7/milestones").is_none());
+    assert_eq!(
+        gitlab.cache().get("/projects/group%2Fother"),
+        Some(json!({"id": 8}))
+    );
+}
+
+#[tokio::test]
+async fn test_webhook_endpoint_disabled_by_default() {
+    let app_config = AppConfig::default();
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+
+    let response = post_webhook(&router, Some("s3cret"), json!({"object_kind": "push"})).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_readyz_tracks_gitlab_health() {
+    use tanuki_mcp::dashboard::DashboardMetrics;
+    use tanuki_mcp::gitlab::health::DEFAULT_FAILURE_THRESHOLD;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(502))
+        .mount(&mock_server)
+        .await;
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let metrics = DashboardMetrics::new().with_gitlab_health(gitlab.health().clone());
+    let config = HttpConfig::default().with_gitlab_health(gitlab.health().clone());
+    let access = Arc::new(AccessResolver::allow_all());
+    let handler_gitlab = gitlab.clone();
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, handler_gitlab.clone(), access.clone())
+                .unwrap()
+        },
+        &config,
+    );
+    let readyz = || async {
+        router
+            .clone()
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    };
+
+    assert_eq!(readyz().await.status(), 200);
+
+    // Consecutive server errors mark GitLab as degraded
+    for _ in 0..DEFAULT_FAILURE_THRESHOLD {
+        let _ = gitlab.get_json("/projects/1").await;
+    }
+    assert!(metrics.gitlab_degraded());
+    let status = metrics.snapshot().gitlab.unwrap();
+    assert_eq!(status.consecutive_failures, DEFAULT_FAILURE_THRESHOLD);
+    assert!(status.last_error.unwrap().contains("502"));
+
+    let response = readyz().await;
+    assert_eq!(response.status(), 503);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["status"], "degraded");
+
+    // A single successful call resets it
+    mock_server.reset().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 1})))
+        .mount(&mock_server)
+        .await;
+    gitlab.get_json("/projects/1").await.unwrap();
+
+    assert!(!metrics.gitlab_degraded());
+    assert_eq!(readyz().await.status(), 200);
+}
+
+#[tokio::test]
+async fn test_readyz_recovers_through_health_probe() {
+    use tanuki_mcp::gitlab::health::DEFAULT_FAILURE_THRESHOLD;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    let gitlab_config = GitLabConfig {
+        url: mock_server.uri(),
+        token: Some("test-token".to_string()),
+        max_retries: 0,
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&gitlab_config, Box::new(auth)).unwrap());
+    let config = HttpConfig::default().with_gitlab_health(gitlab.health().clone());
+    let router = build_router(
+        {
+            let app_config = AppConfig {
+                gitlab: gitlab_config.clone(),
+                ..Default::default()
+            };
+            let gitlab = gitlab.clone();
+            let access = Arc::new(AccessResolver::allow_all());
+            move || {
+                GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone())
+                    .unwrap()
+            }
+        },
+        &config,
+    );
+    let readyz = || async {
+        router
+            .clone()
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    };
+
+    for _ in 0..DEFAULT_FAILURE_THRESHOLD {
+        let _ = gitlab.get_json("/projects/1").await;
+    }
+    assert_eq!(readyz().await, 503);
+
+    // GitLab comes back; only the background probe talks to it from here on
+    mock_server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"version": "17.0.0"})))
+        .mount(&mock_server)
+        .await;
+    let probe = gitlab.spawn_health_probe(Duration::from_millis(10));
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while readyz().await != 200 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "health probe did not clear the degraded state"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    probe.abort();
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|r| r.url.path() == "/api/v4/version"));
+}
+
+#[tokio::test]
+async fn test_allowlist_applies_to_resources_and_prompts() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/a%2Fapp/repository/files/README.md"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"content": "hello", "encoding": "text"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v4/projects/other%2Fapp/repository/files/README.md",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"content": "secret", "encoding": "text"})),
+        )
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/projects/other%2Fapp/issues/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"title": "secret"})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            url: mock_server.uri(),
+            token: Some("test-token".to_string()),
+            allowed_projects: vec!["a/*".to_string()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+    let router = build_router(
+        move || {
+            GitLabMcpHandler::new_with_shared(&app_config, gitlab.clone(), access.clone()).unwrap()
+        },
+        &HttpConfig::default(),
+    );
+    let session_id = initialize_session(&router).await;
+
+    let params = json!({"uri": "gitlab://a%2Fapp/README.md"});
+    let body = request_over_http(&router, &session_id, 2, "resources/read", params).await;
+    assert!(body.contains("hello"));
+
+    let params = json!({"uri": "gitlab://other%2Fapp/README.md"});
+    let body = request_over_http(&router, &session_id, 3, "resources/read", params).await;
+    assert!(body.contains("\"error\""));
+    assert!(body.contains("outside the server's allowed projects"));
+    assert!(!body.contains("secret"));
+
+    let params = json!({
+        "name": "analyze_issue",
+        "arguments": {"project": "other/app", "issue_iid": "1"}
+    });
+    let body = request_over_http(&router, &session_id, 4, "prompts/get", params).await;
+    assert!(body.contains("outside the server's allowed projects"));
+}
+
+#[test]
+fn test_invalid_allowlist_fails_handler_creation() {
+    let app_config = AppConfig {
+        gitlab: GitLabConfig {
+            allowed_projects: vec![" ".to_string()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let auth = PatProvider::new("test-token".to_string()).unwrap();
+    let gitlab = Arc::new(GitLabClient::new(&app_config.gitlab, Box::new(auth)).unwrap());
+    let access = Arc::new(AccessResolver::allow_all());
+
+    assert!(GitLabMcpHandler::new_with_shared(&app_config, gitlab, access).is_err());
+}